@@ -13,12 +13,12 @@ pub enum BuidlCommand {
         /// This parameter is required by the Circle API.
         #[arg(long, required = true)]
         wallet_id: String,
-        /// Start of date range (ISO-8601).
+        /// Start of date range (RFC 3339, e.g. `2024-01-01T00:00:00Z`).
         #[arg(long)]
-        from: Option<String>,
-        /// End of date range (ISO-8601).
+        from: Option<chrono::DateTime<chrono::Utc>>,
+        /// End of date range (RFC 3339, e.g. `2024-01-31T23:59:59Z`).
         #[arg(long)]
-        to: Option<String>,
+        to: Option<chrono::DateTime<chrono::Utc>>,
         /// Maximum number of items per page (1–50).
         #[arg(long)]
         page_size: Option<u32>,
@@ -26,7 +26,7 @@ pub enum BuidlCommand {
     /// Get a single transfer by ID.
     GetTransfer {
         /// Transfer UUID.
-        id: String,
+        id: circle_buidl_wallets::ids::TransferId,
     },
     /// List user operations (ERC-4337).
     ListUserOps {
@@ -40,13 +40,13 @@ pub enum BuidlCommand {
     /// Get a single user operation by ID.
     GetUserOp {
         /// User operation UUID.
-        id: String,
+        id: circle_buidl_wallets::ids::UserOpId,
     },
     /// List token balances for a wallet.
     ListWalletBalances {
         /// Wallet UUID.
         #[arg(long)]
-        wallet_id: String,
+        wallet_id: circle_buidl_wallets::ids::WalletId,
         /// Maximum number of items per page (1–50).
         #[arg(long)]
         page_size: Option<u32>,
@@ -55,7 +55,7 @@ pub enum BuidlCommand {
     ListWalletNfts {
         /// Wallet UUID.
         #[arg(long)]
-        wallet_id: String,
+        wallet_id: circle_buidl_wallets::ids::WalletId,
         /// Maximum number of items per page (1–50).
         #[arg(long)]
         page_size: Option<u32>,
@@ -105,10 +105,10 @@ pub(crate) async fn run(
         }
         BuidlCommand::ListWalletBalances { wallet_id, page_size } => {
             use circle_buidl_wallets::models::{
-                common::PageParams, wallet::ListWalletBalancesParams,
+                common::CursorParams, wallet::ListWalletBalancesParams,
             };
             let params = ListWalletBalancesParams {
-                page: PageParams { page_size, ..Default::default() },
+                page: CursorParams { page_size, ..Default::default() },
                 ..Default::default()
             };
             let result = client
@@ -119,9 +119,11 @@ pub(crate) async fn run(
             Ok(())
         }
         BuidlCommand::ListWalletNfts { wallet_id, page_size } => {
-            use circle_buidl_wallets::models::{common::PageParams, wallet::ListWalletNftsParams};
+            use circle_buidl_wallets::models::{
+                common::CursorParams, wallet::ListWalletNftsParams,
+            };
             let params = ListWalletNftsParams {
-                page: PageParams { page_size, ..Default::default() },
+                page: CursorParams { page_size, ..Default::default() },
                 ..Default::default()
             };
             let result = client
@@ -34,9 +34,9 @@ pub(crate) async fn run(
     let client = circle_compliance::ComplianceClient::with_base_url(api_key, base_url);
     match cmd {
         ComplianceCommand::ScreenAddress { chain, address } => {
-            use circle_compliance::models::screening::ScreenAddressRequest;
+            use circle_compliance::{ids::IdempotencyKey, models::screening::ScreenAddressRequest};
             let req = ScreenAddressRequest {
-                idempotency_key: uuid::Uuid::new_v4().to_string(),
+                idempotency_key: IdempotencyKey::random(),
                 address,
                 chain: parse_chain(&chain)?,
             };
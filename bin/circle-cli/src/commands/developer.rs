@@ -36,7 +36,7 @@ pub enum DeveloperCommand {
     /// Get a wallet set by ID.
     GetWalletSet {
         /// Wallet set UUID.
-        id: String,
+        id: circle_developer_controlled_wallets::ids::WalletSetId,
     },
     /// List wallets.
     ListWallets {
@@ -50,7 +50,7 @@ pub enum DeveloperCommand {
     /// Get a wallet by ID.
     GetWallet {
         /// Wallet UUID.
-        id: String,
+        id: circle_developer_controlled_wallets::ids::WalletId,
     },
     /// List transactions.
     ListTransactions {
@@ -67,12 +67,12 @@ pub enum DeveloperCommand {
     /// Get a transaction by ID.
     GetTransaction {
         /// Transaction UUID.
-        id: String,
+        id: circle_developer_controlled_wallets::ids::TransactionId,
     },
     /// Get a token definition by ID.
     GetToken {
         /// Token UUID.
-        id: String,
+        id: circle_developer_controlled_wallets::ids::TokenId,
     },
     /// Validate a blockchain address.
     ValidateAddress {
@@ -100,8 +100,10 @@ pub(crate) async fn run(
             use circle_developer_controlled_wallets::models::{
                 common::PageParams, wallet_set::ListWalletSetsParams,
             };
-            let params =
-                ListWalletSetsParams { page: PageParams { page_size, ..Default::default() } };
+            let params = ListWalletSetsParams {
+                page: PageParams { page_size, ..Default::default() },
+                ..Default::default()
+            };
             let result = client.list_wallet_sets(&params).await.map_err(|e| eyre::eyre!("{e}"))?;
             crate::output::print_result(&result, output);
             Ok(())
@@ -20,12 +20,12 @@ pub enum UserCommand {
     CreateUser {
         /// Application-defined user identifier.
         #[arg(long)]
-        user_id: String,
+        user_id: circle_user_controlled_wallets::ids::UserId,
     },
     /// Get an end-user by ID.
     GetUser {
-        /// User UUID.
-        id: String,
+        /// Application-defined user identifier.
+        id: circle_user_controlled_wallets::ids::UserId,
     },
     /// List end-users.
     ListUsers {
@@ -37,7 +37,7 @@ pub enum UserCommand {
     GetUserToken {
         /// Application-defined user identifier.
         #[arg(long)]
-        user_id: String,
+        user_id: circle_user_controlled_wallets::ids::UserId,
     },
     /// List wallets for an authenticated user.
     ListWallets {
@@ -57,7 +57,7 @@ pub enum UserCommand {
         #[arg(long, env = "CIRCLE_USER_TOKEN")]
         user_token: String,
         /// Wallet UUID.
-        id: String,
+        id: circle_user_controlled_wallets::ids::WalletId,
     },
     /// List transactions for an authenticated user.
     ListTransactions {
@@ -74,7 +74,7 @@ pub enum UserCommand {
         #[arg(long, env = "CIRCLE_USER_TOKEN")]
         user_token: String,
         /// Transaction UUID.
-        id: String,
+        id: circle_user_controlled_wallets::ids::TransactionId,
     },
     /// Validate a blockchain address.
     ValidateAddress {
@@ -114,8 +114,8 @@ pub(crate) async fn run(
                 common::PageParams, user::ListUsersParams,
             };
             let params = ListUsersParams {
-                pin_status: None,
                 page: PageParams { page_size, ..Default::default() },
+                ..Default::default()
             };
             let result = client.list_users(&params).await.map_err(|e| eyre::eyre!("{e}"))?;
             crate::output::print_result(&result, output);
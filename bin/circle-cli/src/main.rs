@@ -17,7 +17,7 @@ pub struct Cli {
     #[arg(long, env = "CIRCLE_API_KEY", global = true)]
     pub api_key: Option<String>,
 
-    /// Override the Circle API base URL (useful for Prism mock servers).
+    /// Override the Circle API base URL (useful for the `circle-mock` server).
     #[arg(long, env = "CIRCLE_BASE_URL", global = true, default_value = "https://api.circle.com")]
     pub base_url: String,
 
@@ -1,65 +1,76 @@
 //! HTTP client for the Buidl Wallets API.
 
+use circle_core::{
+    ApiResponse, ClientBuilder, DEFAULT_BASE_URL, RequestOptions, RequestPath, RestClient,
+    api_path,
+    ids::{TransferId, UserOpId, WalletId},
+    pagination::{self, Stream, StreamOptions},
+};
+
 use crate::{
     error::Error,
     models::{
-        common::ApiErrorBody,
-        transfer::{ListTransfersParams, TransferId, Transfers},
-        user_op::{ListUserOpsParams, UserOpId, UserOps},
-        wallet::{Balances, ListWalletBalancesParams, ListWalletNftsParams, Nfts},
+        transfer::{ListTransfersParams, Transfer, TransferResponse, Transfers},
+        user_op::{ListUserOpsParams, UserOp, UserOpResponse, UserOps},
+        wallet::{Balance, Balances, ListWalletBalancesParams, ListWalletNftsParams, Nft, Nfts},
     },
 };
 
 /// Async HTTP client for the Circle W3S Buidl Wallets API.
 pub struct BuidlWalletsClient {
-    base_url: String,
-    api_key: String,
-    http: hpx::Client,
+    inner: RestClient,
 }
 
 impl std::fmt::Debug for BuidlWalletsClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BuidlWalletsClient")
-            .field("base_url", &self.base_url)
+            .field("base_url", &self.inner.base_url())
             .field("api_key", &"<redacted>")
             .finish_non_exhaustive()
     }
 }
 
+impl From<RestClient> for BuidlWalletsClient {
+    fn from(inner: RestClient) -> Self {
+        Self { inner }
+    }
+}
+
 impl BuidlWalletsClient {
     /// Creates a new client using the Circle production base URL.
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self::with_base_url(api_key, "https://api.circle.com")
+        Self::with_base_url(api_key, DEFAULT_BASE_URL)
     }
 
-    /// Creates a new client with a custom base URL (useful for Prism mock servers).
+    /// Creates a new client with a custom base URL (useful for the `circle-mock` server).
     pub fn with_base_url(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
-        Self { base_url: base_url.into(), api_key: api_key.into(), http: hpx::Client::new() }
+        Self { inner: RestClient::new(api_key, base_url) }
+    }
+
+    /// Starts a [`ClientBuilder`] for timeouts, proxy, TLS roots, default
+    /// headers or a shared `hpx::Client`.
+    pub fn builder(api_key: impl Into<String>) -> ClientBuilder<Self> {
+        ClientBuilder::new(api_key)
+    }
+
+    /// Returns a copy of this client whose requests use `options`: a caller
+    /// request id, a per-call timeout, extra headers or a retry override.
+    #[must_use]
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self { inner: self.inner.with_options(options) }
     }
 
     /// Dispatch a GET request and decode the JSON response.
-    async fn get<T, P>(&self, path: &str, params: &P) -> Result<T, Error>
+    async fn get<T, P>(
+        &self,
+        path: impl Into<RequestPath>,
+        params: &P,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         P: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .query(params)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        if resp.status().is_success() {
-            resp.json::<T>().await.map_err(|e| Error::Http(e.to_string()))
-        } else {
-            let err: ApiErrorBody = resp.json().await.map_err(|e| Error::Http(e.to_string()))?;
-            Err(Error::Api { code: err.code, message: err.message })
-        }
+        self.inner.get(path).query(params).send_with_meta().await
     }
 
     // ── Transfers ──────────────────────────────────────────────────────────
@@ -68,26 +79,80 @@ impl BuidlWalletsClient {
     ///
     /// `params.wallet_addresses` is required by the Circle API.
     pub async fn list_transfers(&self, params: &ListTransfersParams) -> Result<Transfers, Error> {
+        self.list_transfers_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_transfers`], but also returns the response metadata.
+    pub async fn list_transfers_with_meta(
+        &self,
+        params: &ListTransfersParams,
+    ) -> Result<ApiResponse<Transfers>, Error> {
         self.get("/v1/w3s/buidl/transfers", params).await
     }
 
+    /// Stream transfers matching the given filters, [page by page](circle_core::pagination).
+    pub fn list_transfers_stream<'a>(
+        &'a self,
+        params: &ListTransfersParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Transfer, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_transfers(&params).await.map(|page| page.data.transfers)
+        })
+    }
+
     /// Retrieve a single transfer by its UUID.
-    pub async fn get_transfer(&self, id: &str) -> Result<TransferId, Error> {
-        let path = format!("/v1/w3s/buidl/transfers/{}", id);
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_transfer(&self, id: &TransferId) -> Result<TransferResponse, Error> {
+        self.get_transfer_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_transfer`], but also returns the response metadata.
+    pub async fn get_transfer_with_meta(
+        &self,
+        id: &TransferId,
+    ) -> Result<ApiResponse<TransferResponse>, Error> {
+        let path = api_path!("/v1/w3s/buidl/transfers/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     // ── UserOps ────────────────────────────────────────────────────────────
 
     /// List user operations matching the given filters.
     pub async fn list_user_ops(&self, params: &ListUserOpsParams) -> Result<UserOps, Error> {
+        self.list_user_ops_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_user_ops`], but also returns the response metadata.
+    pub async fn list_user_ops_with_meta(
+        &self,
+        params: &ListUserOpsParams,
+    ) -> Result<ApiResponse<UserOps>, Error> {
         self.get("/v1/w3s/buidl/userOps", params).await
     }
 
+    /// Stream user operations matching the given filters, [page by page](circle_core::pagination).
+    pub fn list_user_ops_stream<'a>(
+        &'a self,
+        params: &ListUserOpsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<UserOp, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_user_ops(&params).await.map(|page| page.data.user_operations)
+        })
+    }
+
     /// Retrieve a single user operation by its UUID.
-    pub async fn get_user_op(&self, id: &str) -> Result<UserOpId, Error> {
-        let path = format!("/v1/w3s/buidl/userOps/{}", id);
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_user_op(&self, id: &UserOpId) -> Result<UserOpResponse, Error> {
+        self.get_user_op_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_user_op`], but also returns the response metadata.
+    pub async fn get_user_op_with_meta(
+        &self,
+        id: &UserOpId,
+    ) -> Result<ApiResponse<UserOpResponse>, Error> {
+        let path = api_path!("/v1/w3s/buidl/userOps/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     // ── Wallets ────────────────────────────────────────────────────────────
@@ -95,21 +160,69 @@ impl BuidlWalletsClient {
     /// Retrieve token balances for a wallet by its UUID.
     pub async fn list_wallet_balances_by_id(
         &self,
-        wallet_id: &str,
+        wallet_id: &WalletId,
         params: &ListWalletBalancesParams,
     ) -> Result<Balances, Error> {
-        let path = format!("/v1/w3s/buidl/wallets/{}/balances", wallet_id);
-        self.get(&path, params).await
+        self.list_wallet_balances_by_id_with_meta(wallet_id, params)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_balances_by_id`], but also returns the response metadata.
+    pub async fn list_wallet_balances_by_id_with_meta(
+        &self,
+        wallet_id: &WalletId,
+        params: &ListWalletBalancesParams,
+    ) -> Result<ApiResponse<Balances>, Error> {
+        let path = api_path!("/v1/w3s/buidl/wallets/{wallet_id}/balances");
+        self.get(path, params).await
+    }
+
+    /// Stream token balances for a wallet by its UUID, [page by page](circle_core::pagination).
+    pub fn list_wallet_balances_by_id_stream<'a>(
+        &'a self,
+        wallet_id: &WalletId,
+        params: &ListWalletBalancesParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Balance, Error>> + use<'a> {
+        let wallet_id = *wallet_id;
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_balances_by_id(&wallet_id, &params)
+                .await
+                .map(|page| page.data.token_balances)
+        })
     }
 
     /// Retrieve NFTs held by a wallet by its UUID.
     pub async fn list_wallet_nfts_by_id(
         &self,
-        wallet_id: &str,
+        wallet_id: &WalletId,
         params: &ListWalletNftsParams,
     ) -> Result<Nfts, Error> {
-        let path = format!("/v1/w3s/buidl/wallets/{}/nfts", wallet_id);
-        self.get(&path, params).await
+        self.list_wallet_nfts_by_id_with_meta(wallet_id, params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_nfts_by_id`], but also returns the response metadata.
+    pub async fn list_wallet_nfts_by_id_with_meta(
+        &self,
+        wallet_id: &WalletId,
+        params: &ListWalletNftsParams,
+    ) -> Result<ApiResponse<Nfts>, Error> {
+        let path = api_path!("/v1/w3s/buidl/wallets/{wallet_id}/nfts");
+        self.get(path, params).await
+    }
+
+    /// Stream NFTs held by a wallet by its UUID, [page by page](circle_core::pagination).
+    pub fn list_wallet_nfts_by_id_stream<'a>(
+        &'a self,
+        wallet_id: &WalletId,
+        params: &ListWalletNftsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Nft, Error>> + use<'a> {
+        let wallet_id = *wallet_id;
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_nfts_by_id(&wallet_id, &params).await.map(|page| page.data.nfts)
+        })
     }
 
     /// Retrieve token balances for a wallet by blockchain + address.
@@ -119,8 +232,35 @@ impl BuidlWalletsClient {
         address: &str,
         params: &ListWalletBalancesParams,
     ) -> Result<Balances, Error> {
-        let path = format!("/v1/w3s/buidl/wallets/{}/{}/balances", blockchain, address);
-        self.get(&path, params).await
+        self.list_wallet_balances_by_address_with_meta(blockchain, address, params)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_balances_by_address`], but also returns the response metadata.
+    pub async fn list_wallet_balances_by_address_with_meta(
+        &self,
+        blockchain: &str,
+        address: &str,
+        params: &ListWalletBalancesParams,
+    ) -> Result<ApiResponse<Balances>, Error> {
+        let path = api_path!("/v1/w3s/buidl/wallets/{blockchain}/{address}/balances");
+        self.get(path, params).await
+    }
+
+    /// Stream token balances for a blockchain + address, [page by page](circle_core::pagination).
+    pub fn list_wallet_balances_by_address_stream<'a>(
+        &'a self,
+        blockchain: &'a str,
+        address: &'a str,
+        params: &ListWalletBalancesParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Balance, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_balances_by_address(blockchain, address, &params)
+                .await
+                .map(|page| page.data.token_balances)
+        })
     }
 
     /// Retrieve NFTs for a wallet by blockchain + address.
@@ -130,7 +270,34 @@ impl BuidlWalletsClient {
         address: &str,
         params: &ListWalletNftsParams,
     ) -> Result<Nfts, Error> {
-        let path = format!("/v1/w3s/buidl/wallets/{}/{}/nfts", blockchain, address);
-        self.get(&path, params).await
+        self.list_wallet_nfts_by_address_with_meta(blockchain, address, params)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_nfts_by_address`], but also returns the response metadata.
+    pub async fn list_wallet_nfts_by_address_with_meta(
+        &self,
+        blockchain: &str,
+        address: &str,
+        params: &ListWalletNftsParams,
+    ) -> Result<ApiResponse<Nfts>, Error> {
+        let path = api_path!("/v1/w3s/buidl/wallets/{blockchain}/{address}/nfts");
+        self.get(path, params).await
+    }
+
+    /// Stream NFTs for a blockchain + address, [page by page](circle_core::pagination).
+    pub fn list_wallet_nfts_by_address_stream<'a>(
+        &'a self,
+        blockchain: &'a str,
+        address: &'a str,
+        params: &ListWalletNftsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Nft, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_nfts_by_address(blockchain, address, &params)
+                .await
+                .map(|page| page.data.nfts)
+        })
     }
 }
@@ -50,5 +50,9 @@ pub mod client;
 pub mod error;
 pub mod models;
 
+pub use circle_core::{
+    ApiEnum, ApiResponse, ClientBuilder, RequestOptions, ResponseMeta, RetryPolicy, address,
+    amount, ids, middleware, network, pagination, transport, unknown_fields,
+};
 pub use client::BuidlWalletsClient;
 pub use error::Error;
@@ -3,65 +3,112 @@
 //! Includes the [`Blockchain`] enum, pagination parameters, and the API error
 //! response type.
 
-/// Blockchain network identifier.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
-pub enum Blockchain {
-    /// Ethereum mainnet.
+use chrono::{DateTime, Utc};
+use circle_core::api_enum;
+
+api_enum! {
+    /// Blockchain network identifier.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    pub enum Blockchain {
+        /// Ethereum mainnet.
+        Eth,
+        /// Ethereum Sepolia testnet.
+        #[serde(rename = "ETH-SEPOLIA")]
+        EthSepolia,
+        /// Polygon PoS mainnet.
+        Matic,
+        /// Polygon Amoy testnet.
+        #[serde(rename = "MATIC-AMOY")]
+        MaticAmoy,
+        /// Arbitrum One mainnet.
+        Arb,
+        /// Arbitrum Sepolia testnet.
+        #[serde(rename = "ARB-SEPOLIA")]
+        ArbSepolia,
+        /// Unichain mainnet.
+        Uni,
+        /// Unichain Sepolia testnet.
+        #[serde(rename = "UNI-SEPOLIA")]
+        UniSepolia,
+        /// Base mainnet.
+        Base,
+        /// Base Sepolia testnet.
+        #[serde(rename = "BASE-SEPOLIA")]
+        BaseSepolia,
+        /// Optimism mainnet.
+        Op,
+        /// Optimism Sepolia testnet.
+        #[serde(rename = "OP-SEPOLIA")]
+        OpSepolia,
+        /// Avalanche C-Chain mainnet.
+        Avax,
+        /// Avalanche Fuji testnet.
+        #[serde(rename = "AVAX-FUJI")]
+        AvaxFuji,
+        /// ARC testnet.
+        #[serde(rename = "ARC-TESTNET")]
+        ArcTestnet,
+        /// Monad mainnet.
+        Monad,
+        /// Monad testnet.
+        #[serde(rename = "MONAD-TESTNET")]
+        MonadTestnet,
+    }
+}
+
+circle_core::network_conversions!(Blockchain {
     Eth,
-    /// Ethereum Sepolia testnet.
-    #[serde(rename = "ETH-SEPOLIA")]
     EthSepolia,
-    /// Polygon PoS mainnet.
     Matic,
-    /// Polygon Amoy testnet.
-    #[serde(rename = "MATIC-AMOY")]
     MaticAmoy,
-    /// Arbitrum One mainnet.
     Arb,
-    /// Arbitrum Sepolia testnet.
-    #[serde(rename = "ARB-SEPOLIA")]
     ArbSepolia,
-    /// Unichain mainnet.
     Uni,
-    /// Unichain Sepolia testnet.
-    #[serde(rename = "UNI-SEPOLIA")]
     UniSepolia,
-    /// Base mainnet.
     Base,
-    /// Base Sepolia testnet.
-    #[serde(rename = "BASE-SEPOLIA")]
     BaseSepolia,
-    /// Optimism mainnet.
     Op,
-    /// Optimism Sepolia testnet.
-    #[serde(rename = "OP-SEPOLIA")]
     OpSepolia,
-    /// Avalanche C-Chain mainnet.
     Avax,
-    /// Avalanche Fuji testnet.
-    #[serde(rename = "AVAX-FUJI")]
     AvaxFuji,
-    /// ARC testnet.
-    #[serde(rename = "ARC-TESTNET")]
     ArcTestnet,
-    /// Monad mainnet.
     Monad,
-    /// Monad testnet.
-    #[serde(rename = "MONAD-TESTNET")]
     MonadTestnet,
-}
+});
 
 /// Pagination cursor parameters shared across list endpoints.
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageParams {
     /// Start of date-time range (ISO-8601, inclusive).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub from: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "circle_core::timestamp::serialize_option"
+    )]
+    pub from: Option<DateTime<Utc>>,
     /// End of date-time range (ISO-8601, inclusive).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "circle_core::timestamp::serialize_option"
+    )]
+    pub to: Option<DateTime<Utc>>,
+    /// Cursor for the previous page (exclusive end).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_before: Option<String>,
+    /// Cursor for the next page (exclusive start).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub to: Option<String>,
+    pub page_after: Option<String>,
+    /// Maximum number of items to return (1–50, default 10).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+}
+
+/// Cursor-only pagination parameters, for list endpoints without a
+/// date-time range.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorParams {
     /// Cursor for the previous page (exclusive end).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_before: Option<String>,
@@ -74,13 +121,7 @@ pub struct PageParams {
 }
 
 /// Error response body returned by the Circle API on non-2xx status codes.
-#[derive(Debug, Clone, serde::Deserialize)]
-pub struct ApiErrorBody {
-    /// Numeric error code.
-    pub code: i32,
-    /// Human-readable error message.
-    pub message: String,
-}
+pub use circle_core::ApiErrorBody;
 
 #[cfg(test)]
 mod tests {
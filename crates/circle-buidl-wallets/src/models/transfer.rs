@@ -1,37 +1,50 @@
 //! Transfer request and response types for the Buidl Wallets API.
 //!
-//! Covers [`ListTransfersParams`], [`Transfer`], [`Transfers`], and [`TransferId`].
+//! Covers [`ListTransfersParams`], [`Transfer`], [`Transfers`], and [`TransferResponse`].
+
+use chrono::{DateTime, Utc};
+use circle_core::{
+    amount::TokenAmount,
+    api_enum,
+    ids::{TokenId, TransferId, WalletId},
+};
 
 use super::common::{Blockchain, PageParams};
 
-/// State of a transfer.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransferState {
-    /// Transfer is confirmed on-chain but not yet finalized.
-    Confirmed,
-    /// Transfer has reached finality.
-    Complete,
-    /// Transfer failed.
-    Failed,
+api_enum! {
+    /// State of a transfer.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransferState {
+        /// Transfer is confirmed on-chain but not yet finalized.
+        Confirmed,
+        /// Transfer has reached finality.
+        Complete,
+        /// Transfer failed.
+        Failed,
+    }
 }
 
-/// Direction of a transfer relative to the queried wallet.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransferType {
-    /// Tokens received by the wallet.
-    InboundTransfer,
-    /// Tokens sent from the wallet.
-    OutboundTransfer,
+api_enum! {
+    /// Direction of a transfer relative to the queried wallet.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransferType {
+        /// Tokens received by the wallet.
+        InboundTransfer,
+        /// Tokens sent from the wallet.
+        OutboundTransfer,
+    }
 }
 
-/// Error reason for a failed transfer.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransferErrorReason {
-    /// Transfer failed due to a chain reorganization.
-    FailedReorg,
+api_enum! {
+    /// Error reason for a failed transfer.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransferErrorReason {
+        /// Transfer failed due to a chain reorganization.
+        FailedReorg,
+    }
 }
 
 /// NFT metadata bundled inside a transfer.
@@ -51,14 +64,14 @@ pub struct NftIdMetadata {
 #[serde(rename_all = "camelCase")]
 pub struct Transfer {
     /// System-generated unique identifier (UUID).
-    pub id: String,
+    pub id: TransferId,
     /// Unique identifier of the wallet related to this transfer.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Amount of tokens transferred.
-    pub amount: String,
+    pub amount: TokenAmount,
     /// Date and time the block was mined (ISO-8601).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub block_date: Option<String>,
+    pub block_date: Option<DateTime<Utc>>,
     /// Block hash.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_hash: Option<String>,
@@ -83,7 +96,7 @@ pub struct Transfer {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_address: Option<String>,
     /// Unique identifier of the token.
-    pub token_id: String,
+    pub token_id: TokenId,
     /// Direction of the transfer.
     pub transfer_type: TransferType,
     /// Transaction hash.
@@ -95,10 +108,19 @@ pub struct Transfer {
     pub wallet_address: String,
     /// Creation timestamp (ISO-8601).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub create_date: Option<String>,
+    pub create_date: Option<DateTime<Utc>>,
     /// Last-update timestamp (ISO-8601).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub update_date: Option<String>,
+    pub update_date: Option<DateTime<Utc>>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<Transfer, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for Transfer {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
 }
 
 /// Inner data object of the `listTransfers` response.
@@ -117,16 +139,16 @@ pub struct Transfers {
 
 /// Inner data object of the `getTransfer` response.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct TransferIdData {
+pub struct TransferData {
     /// The retrieved transfer.
     pub transfer: Transfer,
 }
 
 /// Response wrapper for the `getTransfer` endpoint.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct TransferId {
+pub struct TransferResponse {
     /// Response data.
-    pub data: TransferIdData,
+    pub data: TransferData,
 }
 
 /// Query parameters for [`crate::BuidlWalletsClient::list_transfers`].
@@ -160,6 +182,8 @@ pub struct ListTransfersParams {
     pub page: PageParams,
 }
 
+circle_core::paginated!(ListTransfersParams => page);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,7 +227,7 @@ mod tests {
         }"#;
         let resp: Transfers = serde_json::from_str(json)?;
         assert_eq!(resp.data.transfers.len(), 1);
-        assert_eq!(resp.data.transfers[0].id, "c4d1da72-111e-4d52-bdbf-2e74a2d803d5");
+        assert_eq!(resp.data.transfers[0].id.to_string(), "c4d1da72-111e-4d52-bdbf-2e74a2d803d5");
         assert_eq!(resp.data.transfers[0].state, TransferState::Complete);
         Ok(())
     }
@@ -1,29 +1,36 @@
 //! UserOp (ERC-4337 user operation) request and response types for the Buidl Wallets API.
 
+use chrono::{DateTime, Utc};
+use circle_core::{api_enum, ids::UserOpId};
+
 use super::common::{Blockchain, PageParams};
 
-/// State of a user operation.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum UserOpState {
-    /// User operation has been submitted to the bundler.
-    Sent,
-    /// User operation is confirmed on-chain but not finalized.
-    Confirmed,
-    /// User operation has reached finality.
-    Complete,
-    /// User operation failed.
-    Failed,
+api_enum! {
+    /// State of a user operation.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum UserOpState {
+        /// User operation has been submitted to the bundler.
+        Sent,
+        /// User operation is confirmed on-chain but not finalized.
+        Confirmed,
+        /// User operation has reached finality.
+        Complete,
+        /// User operation failed.
+        Failed,
+    }
 }
 
-/// Error reason for a failed user operation.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum UserOpErrorReason {
-    /// User operation failed on-chain.
-    FailedOnChain,
-    /// User operation was replaced by another.
-    FailedReplaced,
+api_enum! {
+    /// Error reason for a failed user operation.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum UserOpErrorReason {
+        /// User operation failed on-chain.
+        FailedOnChain,
+        /// User operation was replaced by another.
+        FailedReplaced,
+    }
 }
 
 /// Raw ERC-4337 user operation fields.
@@ -69,7 +76,7 @@ pub struct UserOperation {
 #[serde(rename_all = "camelCase")]
 pub struct UserOp {
     /// System-generated unique identifier (UUID).
-    pub id: String,
+    pub id: UserOpId,
     /// Blockchain network.
     pub blockchain: Blockchain,
     /// Current state.
@@ -85,7 +92,7 @@ pub struct UserOp {
     /// Actual gas used on-chain.
     pub actual_gas_used: Option<String>,
     /// Date the block was mined (ISO-8601).
-    pub block_date: Option<String>,
+    pub block_date: Option<DateTime<Utc>>,
     /// Block hash.
     pub block_hash: Option<String>,
     /// Block height.
@@ -99,9 +106,18 @@ pub struct UserOp {
     /// Transaction hash.
     pub tx_hash: Option<String>,
     /// Creation timestamp (ISO-8601).
-    pub create_date: Option<String>,
+    pub create_date: Option<DateTime<Utc>>,
     /// Last-update timestamp (ISO-8601).
-    pub update_date: Option<String>,
+    pub update_date: Option<DateTime<Utc>>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<UserOp, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for UserOp {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
 }
 
 /// Inner data of the `listUserOps` response.
@@ -122,16 +138,16 @@ pub struct UserOps {
 /// Inner data of the `getUserOp` response.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct UserOpIdData {
+pub struct UserOpData {
     /// The retrieved user operation.
     pub user_operation: UserOp,
 }
 
 /// Response wrapper for the `getUserOp` endpoint.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct UserOpId {
+pub struct UserOpResponse {
     /// Response data.
-    pub data: UserOpIdData,
+    pub data: UserOpData,
 }
 
 /// Query parameters for [`crate::BuidlWalletsClient::list_user_ops`].
@@ -161,10 +177,21 @@ pub struct ListUserOpsParams {
     pub page: PageParams,
 }
 
+circle_core::paginated!(ListUserOpsParams => page);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn unknown_user_op_state_roundtrips() -> Result<(), Box<dyn std::error::Error>> {
+        let parsed: UserOpState = serde_json::from_str("\"BUNDLED\"")?;
+        assert_eq!(parsed, UserOpState::Unknown("BUNDLED".to_string()));
+        assert_eq!(serde_json::to_string(&parsed)?, "\"BUNDLED\"");
+        Ok(())
+    }
+
     #[test]
     fn user_op_state_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
         let s = serde_json::to_string(&UserOpState::Complete)?;
@@ -2,39 +2,51 @@
 //!
 //! Covers token, balance, NFT, and wallet balance/NFT list-endpoint types.
 
-use super::common::{Blockchain, PageParams};
-
-/// Token standard (fungible tokens).
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum FtStandard {
-    /// Native coin (no ERC standard).
-    #[serde(rename = "")]
-    Native,
-    /// ERC-20 fungible token.
-    Erc20,
-}
-
-/// Token standard (used in combined ERC-20/721/1155 contexts).
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TokenStandard {
-    /// ERC-20 fungible token.
-    Erc20,
-    /// ERC-721 non-fungible token.
-    Erc721,
-    /// ERC-1155 multi-token.
-    Erc1155,
-}
-
-/// NFT token standard.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum NftStandard {
-    /// ERC-721 non-fungible token.
-    Erc721,
-    /// ERC-1155 multi-token.
-    Erc1155,
+use chrono::{DateTime, Utc};
+use circle_core::{
+    amount::{self, AmountError, BigUint, TokenAmount},
+    api_enum,
+};
+
+use super::common::{Blockchain, CursorParams};
+
+api_enum! {
+    /// Token standard (fungible tokens).
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum FtStandard {
+        /// Native coin (no ERC standard).
+        #[serde(rename = "")]
+        Native,
+        /// ERC-20 fungible token.
+        Erc20,
+    }
+}
+
+api_enum! {
+    /// Token standard (used in combined ERC-20/721/1155 contexts).
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TokenStandard {
+        /// ERC-20 fungible token.
+        Erc20,
+        /// ERC-721 non-fungible token.
+        Erc721,
+        /// ERC-1155 multi-token.
+        Erc1155,
+    }
+}
+
+api_enum! {
+    /// NFT token standard.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum NftStandard {
+        /// ERC-721 non-fungible token.
+        Erc721,
+        /// ERC-1155 multi-token.
+        Erc1155,
+    }
 }
 
 /// A blockchain token definition.
@@ -57,16 +69,38 @@ pub struct Token {
     pub token_address: Option<String>,
 }
 
+impl Token {
+    /// The human amount of `units` base units of this token.
+    pub fn amount_from_base_units(
+        &self,
+        units: impl Into<BigUint>,
+    ) -> Result<TokenAmount, AmountError> {
+        Ok(TokenAmount::from_base_units(units, amount::decimals(self.decimals)?))
+    }
+
+    /// `amount` in base units of this token.
+    pub fn to_base_units(&self, amount: &TokenAmount) -> Result<BigUint, AmountError> {
+        amount.to_base_units(amount::decimals(self.decimals)?)
+    }
+}
+
 /// A single fungible token balance entry.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Balance {
-    /// Token amount as a decimal string.
-    pub amount: String,
+    /// Token amount held.
+    pub amount: TokenAmount,
     /// Token definition.
     pub token: Token,
     /// Last-update timestamp (ISO-8601).
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
+}
+
+/// BUIDL balances carry no id to page by, so a stream ends after the first page.
+impl circle_core::pagination::PageItem for Balance {
+    fn cursor(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Inner data of a `listWalletBalancesBy*` response.
@@ -93,13 +127,20 @@ pub struct Nft {
     /// Token definition.
     pub token: Token,
     /// Last-update timestamp (ISO-8601).
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// On-chain token ID.
     pub nft_token_id: Option<String>,
     /// IPFS or HTTP URI of the NFT metadata.
     pub metadata: Option<String>,
 }
 
+/// BUIDL NFTs carry no id to page by, so a stream ends after the first page.
+impl circle_core::pagination::PageItem for Nft {
+    fn cursor(&self) -> Option<String> {
+        None
+    }
+}
+
 /// Inner data of a `listWalletNFTsBy*` response.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NftsData {
@@ -129,7 +170,7 @@ pub struct ListWalletBalancesParams {
     pub token_address: Option<String>,
     /// Pagination parameters.
     #[serde(flatten)]
-    pub page: PageParams,
+    pub page: CursorParams,
 }
 
 /// Query parameters for NFT list endpoints that use a wallet UUID.
@@ -147,9 +188,11 @@ pub struct ListWalletNftsParams {
     pub token_address: Option<String>,
     /// Pagination parameters.
     #[serde(flatten)]
-    pub page: PageParams,
+    pub page: CursorParams,
 }
 
+circle_core::paginated!(ListWalletBalancesParams => page, ListWalletNftsParams => page);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,90 @@
 #![allow(missing_docs)]
 // Integration tests for circle-buidl-wallets.
-// Requires Prism mock server on port 4010.
+// Each test runs against its own in-process circle-mock server.
 // Run via: just integration-test
+
+use circle_buidl_wallets::{
+    BuidlWalletsClient,
+    models::{
+        common::Blockchain,
+        transfer::{ListTransfersParams, TransferState, TransferType},
+        wallet::ListWalletBalancesParams,
+    },
+};
+use circle_mock::MockServer;
+
+async fn start() -> Result<(MockServer, BuidlWalletsClient), Box<dyn std::error::Error>> {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let server = MockServer::start().await?;
+    let client = BuidlWalletsClient::with_base_url("TEST_API_KEY:id:secret", server.base_url());
+    Ok((server, client))
+}
+
+#[tokio::test]
+async fn completed_transfer_is_indexed_for_both_wallets() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (server, client) = start().await?;
+    let source = server.state().create_wallet("ETH-SEPOLIA")?;
+    let destination = server.state().create_wallet("ETH-SEPOLIA")?;
+    let source_address = source["address"].as_str().unwrap_or_default();
+    let destination_address = destination["address"].as_str().unwrap_or_default();
+    let usdc = server.state().token_id("ETH-SEPOLIA", "USDC").unwrap_or_default();
+    server.state().set_balance(source["id"].as_str().unwrap_or_default(), &usdc, "5")?;
+
+    let params = ListTransfersParams {
+        wallet_addresses: Some(format!("{source_address},{destination_address}")),
+        ..Default::default()
+    };
+    assert!(client.list_transfers(&params).await?.data.transfers.is_empty());
+
+    let transaction = server.state().create_transfer(
+        source["id"].as_str().unwrap_or_default(),
+        destination_address,
+        &usdc,
+        "1.5",
+    )?;
+    let state =
+        server.state().settle_transaction(transaction["id"].as_str().unwrap_or_default())?;
+    assert_eq!(state, "COMPLETE");
+
+    let transfers = client.list_transfers(&params).await?.data.transfers;
+    assert_eq!(transfers.len(), 2);
+    for transfer in &transfers {
+        assert_eq!(transfer.state, TransferState::Complete);
+        assert_eq!(transfer.blockchain, Blockchain::EthSepolia);
+        assert_eq!(transfer.amount.to_string(), "1.5");
+    }
+    let outbound = transfers
+        .iter()
+        .find(|transfer| transfer.transfer_type == TransferType::OutboundTransfer)
+        .ok_or("no outbound transfer")?;
+    let fetched = client.get_transfer(&outbound.id).await?.data.transfer;
+    assert_eq!(fetched.to.to_lowercase(), destination_address.to_lowercase());
+    Ok(())
+}
+
+#[tokio::test]
+async fn balances_are_served_by_id_and_address() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let wallet = server.state().create_wallet("ETH-SEPOLIA")?;
+    let wallet_id = wallet["id"].as_str().unwrap_or_default();
+    let usdc = server.state().token_id("ETH-SEPOLIA", "USDC").unwrap_or_default();
+    server.state().set_balance(wallet_id, &usdc, "12.25")?;
+
+    let params = ListWalletBalancesParams::default();
+    let by_id = client.list_wallet_balances_by_id(&wallet_id.parse()?, &params).await?;
+    let by_address = client
+        .list_wallet_balances_by_address(
+            "ETH-SEPOLIA",
+            wallet["address"].as_str().unwrap_or_default(),
+            &params,
+        )
+        .await?;
+    for balances in [by_id, by_address] {
+        let balances = balances.data.token_balances;
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances[0].amount.to_string(), "12.25");
+        assert_eq!(balances[0].token.symbol.as_deref(), Some("USDC"));
+    }
+    Ok(())
+}
@@ -1,65 +1,71 @@
 //! HTTP client for the Compliance Engine API.
 
+use circle_core::{
+    ApiResponse, ClientBuilder, DEFAULT_BASE_URL, RequestOptions, RequestPath, RestClient,
+};
+
 use crate::{
     error::Error,
-    models::{
-        common::ApiErrorBody,
-        screening::{
-            BlockchainAddressScreeningResponse, ScreenAddressEnvelope, ScreenAddressRequest,
-        },
+    models::screening::{
+        BlockchainAddressScreeningResponse, ScreenAddressEnvelope, ScreenAddressRequest,
     },
 };
 
 /// Async HTTP client for the Circle W3S Compliance Engine API.
 pub struct ComplianceClient {
-    base_url: String,
-    api_key: String,
-    http: hpx::Client,
+    inner: RestClient,
 }
 
 impl std::fmt::Debug for ComplianceClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ComplianceClient")
-            .field("base_url", &self.base_url)
+            .field("base_url", &self.inner.base_url())
             .field("api_key", &"<redacted>")
             .finish_non_exhaustive()
     }
 }
 
+impl From<RestClient> for ComplianceClient {
+    fn from(inner: RestClient) -> Self {
+        Self { inner }
+    }
+}
+
 impl ComplianceClient {
     /// Creates a new client using the Circle production base URL.
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self::with_base_url(api_key, "https://api.circle.com")
+        Self::with_base_url(api_key, DEFAULT_BASE_URL)
     }
 
-    /// Creates a new client with a custom base URL (useful for Prism mock servers).
+    /// Creates a new client with a custom base URL (useful for the `circle-mock` server).
     pub fn with_base_url(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
-        Self { base_url: base_url.into(), api_key: api_key.into(), http: hpx::Client::new() }
+        Self { inner: RestClient::new(api_key, base_url) }
+    }
+
+    /// Starts a [`ClientBuilder`] for timeouts, proxy, TLS roots, default
+    /// headers or a shared `hpx::Client`.
+    pub fn builder(api_key: impl Into<String>) -> ClientBuilder<Self> {
+        ClientBuilder::new(api_key)
+    }
+
+    /// Returns a copy of this client whose requests use `options`: a caller
+    /// request id, a per-call timeout, extra headers or a retry override.
+    #[must_use]
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self { inner: self.inner.with_options(options) }
     }
 
     /// Send an authenticated POST request and decode the JSON response.
-    async fn post<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+    async fn post<T, B>(
+        &self,
+        path: impl Into<RequestPath>,
+        body: &B,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        if resp.status().is_success() {
-            resp.json::<T>().await.map_err(|e| Error::Http(e.to_string()))
-        } else {
-            let err: ApiErrorBody = resp.json().await.map_err(|e| Error::Http(e.to_string()))?;
-            Err(Error::Api { code: err.code, message: err.message })
-        }
+        self.inner.post(path).json(body).send_with_meta().await
     }
 
     // ── Address Screening ─────────────────────────────────────────────────
@@ -68,12 +74,86 @@ impl ComplianceClient {
     ///
     /// This is an idempotent operation: repeating the same `idempotency_key`
     /// returns the original response without re-running the screening.
+    /// The address is checked offline first, see
+    /// [`ScreenAddressRequest::validate`].
     pub async fn screen_address(
         &self,
         req: &ScreenAddressRequest,
     ) -> Result<BlockchainAddressScreeningResponse, Error> {
-        let envelope: ScreenAddressEnvelope =
+        self.screen_address_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::screen_address`], but also returns the response metadata.
+    pub async fn screen_address_with_meta(
+        &self,
+        req: &ScreenAddressRequest,
+    ) -> Result<ApiResponse<BlockchainAddressScreeningResponse>, Error> {
+        req.validate()?;
+        let envelope: ApiResponse<ScreenAddressEnvelope> =
             self.post("/v1/w3s/compliance/screening/addresses", req).await?;
-        Ok(envelope.data)
+        Ok(envelope.map(|envelope| envelope.data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use circle_core::{Expectation, Method, MockTransport};
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::screening::{Chain, ScreeningResult};
+
+    #[tokio::test]
+    async fn screen_address_unwraps_data_envelope() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::POST, "/v1/w3s/compliance/screening/addresses")
+                .json_body(json!({
+                    "idempotencyKey": "6f1c2b3a-0d4e-4f5a-8b6c-7d8e9f0a1b2c",
+                    "address": "0x1bf9ad0cc2ad298c69a2995aa806ee832788218c",
+                    "chain": "ETH-SEPOLIA",
+                }))
+                .respond(
+                    200,
+                    json!({ "data": {
+                        "result": "APPROVED",
+                        "decision": { "screeningDate": "2024-01-01T00:00:00Z" },
+                        "id": "6f1c2b3a-0d4e-4f5a-8b6c-7d8e9f0a1b2c",
+                        "address": "0x1bf9ad0cc2ad298c69a2995aa806ee832788218c",
+                        "chain": "ETH-SEPOLIA",
+                        "details": [],
+                    }}),
+                ),
+        );
+        let client = ComplianceClient::builder("key").transport(mock.clone()).build()?;
+
+        let response = client
+            .screen_address(&ScreenAddressRequest {
+                idempotency_key: "6f1c2b3a-0d4e-4f5a-8b6c-7d8e9f0a1b2c".parse()?,
+                address: "0x1bf9ad0cc2ad298c69a2995aa806ee832788218c".to_string(),
+                chain: Chain::EthSepolia,
+            })
+            .await?;
+        assert_eq!(response.result, ScreeningResult::Approved);
+        assert_eq!(mock.pending(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn screen_address_rejects_malformed_addresses_offline()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        let client = ComplianceClient::builder("key").transport(mock.clone()).build()?;
+
+        let result = client
+            .screen_address(&ScreenAddressRequest {
+                idempotency_key: "6f1c2b3a-0d4e-4f5a-8b6c-7d8e9f0a1b2c".parse()?,
+                address: "0x1bf9ad0cc2ad298c69a2995aa806ee83278821".to_string(),
+                chain: Chain::EthSepolia,
+            })
+            .await;
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+        assert!(mock.last_request().is_none());
+        Ok(())
     }
 }
@@ -16,6 +16,7 @@
 //! ```no_run
 //! use circle_compliance::{
 //!     ComplianceClient,
+//!     ids::IdempotencyKey,
 //!     models::screening::{Chain, ScreenAddressRequest},
 //! };
 //!
@@ -23,7 +24,7 @@
 //! async fn main() -> Result<(), circle_compliance::Error> {
 //!     let client = ComplianceClient::new("your_api_key");
 //!     let request = ScreenAddressRequest {
-//!         idempotency_key: "550e8400-e29b-41d4-a716-446655440000".to_string(),
+//!         idempotency_key: IdempotencyKey::random(),
 //!         address: "0xTargetAddress".to_string(),
 //!         chain: Chain::Eth,
 //!     };
@@ -49,5 +50,9 @@ pub mod client;
 pub mod error;
 pub mod models;
 
+pub use circle_core::{
+    ApiEnum, ApiResponse, ClientBuilder, RequestOptions, ResponseMeta, RetryPolicy, address, ids,
+    middleware, network, transport, unknown_fields,
+};
 pub use client::ComplianceClient;
 pub use error::Error;
@@ -1,159 +1,238 @@
 //! Screening resource models for the Circle Compliance Engine API.
 
-/// Supported blockchain networks for address screening.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING-KEBAB-CASE")]
-pub enum Chain {
-    /// Ethereum mainnet.
+use chrono::{DateTime, Utc};
+use circle_core::{
+    address::{self, AddressError},
+    api_enum,
+    ids::IdempotencyKey,
+    network::Network,
+};
+
+api_enum! {
+    /// Supported blockchain networks for address screening.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    pub enum Chain {
+        /// Ethereum mainnet.
+        Eth,
+        /// Ethereum Sepolia testnet.
+        #[serde(rename = "ETH-SEPOLIA")]
+        EthSepolia,
+        /// Avalanche C-Chain mainnet.
+        Avax,
+        /// Avalanche Fuji testnet.
+        #[serde(rename = "AVAX-FUJI")]
+        AvaxFuji,
+        /// Polygon PoS mainnet.
+        Matic,
+        /// Polygon Amoy testnet.
+        #[serde(rename = "MATIC-AMOY")]
+        MaticAmoy,
+        /// Algorand mainnet.
+        Algo,
+        /// Cosmos Hub mainnet.
+        Atom,
+        /// Arbitrum One mainnet.
+        Arb,
+        /// Arbitrum Sepolia testnet.
+        #[serde(rename = "ARB-SEPOLIA")]
+        ArbSepolia,
+        /// Hedera mainnet.
+        Hbar,
+        /// Solana mainnet.
+        Sol,
+        /// Solana devnet.
+        #[serde(rename = "SOL-DEVNET")]
+        SolDevnet,
+        /// Unichain mainnet.
+        Uni,
+        /// Unichain Sepolia testnet.
+        #[serde(rename = "UNI-SEPOLIA")]
+        UniSepolia,
+        /// TRON mainnet.
+        Trx,
+        /// Stellar mainnet.
+        Xlm,
+        /// Bitcoin Cash mainnet.
+        Bch,
+        /// Bitcoin mainnet.
+        Btc,
+        /// Bitcoin SV mainnet.
+        Bsv,
+        /// Ethereum Classic mainnet.
+        Etc,
+        /// Litecoin mainnet.
+        Ltc,
+        /// Monero mainnet.
+        Xmr,
+        /// XRP Ledger mainnet.
+        Xrp,
+        /// 0x / ZRX.
+        Zrx,
+        /// Optimism mainnet.
+        Op,
+        /// Polkadot mainnet.
+        Dot,
+    }
+}
+
+circle_core::network_conversions!(Chain {
     Eth,
-    /// Ethereum Sepolia testnet.
-    #[serde(rename = "ETH-SEPOLIA")]
     EthSepolia,
-    /// Avalanche C-Chain mainnet.
     Avax,
-    /// Avalanche Fuji testnet.
-    #[serde(rename = "AVAX-FUJI")]
     AvaxFuji,
-    /// Polygon PoS mainnet.
     Matic,
-    /// Polygon Amoy testnet.
-    #[serde(rename = "MATIC-AMOY")]
     MaticAmoy,
-    /// Algorand mainnet.
     Algo,
-    /// Cosmos Hub mainnet.
     Atom,
-    /// Arbitrum One mainnet.
     Arb,
-    /// Arbitrum Sepolia testnet.
-    #[serde(rename = "ARB-SEPOLIA")]
     ArbSepolia,
-    /// Hedera mainnet.
     Hbar,
-    /// Solana mainnet.
     Sol,
-    /// Solana devnet.
-    #[serde(rename = "SOL-DEVNET")]
     SolDevnet,
-    /// Unichain mainnet.
     Uni,
-    /// Unichain Sepolia testnet.
-    #[serde(rename = "UNI-SEPOLIA")]
     UniSepolia,
-    /// TRON mainnet.
     Trx,
-    /// Stellar mainnet.
     Xlm,
-    /// Bitcoin Cash mainnet.
     Bch,
-    /// Bitcoin mainnet.
     Btc,
-    /// Bitcoin SV mainnet.
     Bsv,
-    /// Ethereum Classic mainnet.
     Etc,
-    /// Litecoin mainnet.
     Ltc,
-    /// Monero mainnet.
     Xmr,
-    /// XRP Ledger mainnet.
     Xrp,
-    /// 0x / ZRX.
     Zrx,
-    /// Optimism mainnet.
     Op,
-    /// Polkadot mainnet.
     Dot,
-}
+});
 
 /// Request body for the `screenAddress` endpoint.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScreenAddressRequest {
-    /// UUID v4 idempotency key.
-    pub idempotency_key: String,
+    /// Idempotency key.
+    pub idempotency_key: IdempotencyKey,
     /// Blockchain address to screen.
     pub address: String,
     /// Blockchain network.
     pub chain: Chain,
 }
 
-/// Action to take based on a screening decision.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskAction {
-    /// Address is safe to proceed.
-    Approve,
-    /// Address requires manual review.
-    Review,
-    /// The wallet associated with the address should be frozen.
-    FreezeWallet,
-    /// Transaction/interaction should be denied.
-    Deny,
+impl ScreenAddressRequest {
+    /// Checks `address` offline against `chain`, so a malformed address
+    /// fails before the request is sent.
+    ///
+    /// Unknown chains and chains without offline rules, such as `BTC`, pass
+    /// unchecked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn validate(&self) -> Result<(), AddressError> {
+        Network::try_from(self.chain.clone())
+            .map_or(Ok(()), |network| address::validate(network, &self.address))
+    }
+}
+
+api_enum! {
+    /// Action to take based on a screening decision.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskAction {
+        /// Address is safe to proceed.
+        Approve,
+        /// Address requires manual review.
+        Review,
+        /// The wallet associated with the address should be frozen.
+        FreezeWallet,
+        /// Transaction/interaction should be denied.
+        Deny,
+    }
+}
+
+api_enum! {
+    /// Risk severity score.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskScore {
+        /// Risk could not be determined (`UNKNOWN` on the wire).
+        #[serde(rename = "UNKNOWN")]
+        Undetermined,
+        /// Low risk.
+        Low,
+        /// Medium risk.
+        Medium,
+        /// High risk.
+        High,
+        /// Severe risk.
+        Severe,
+        /// Address is on a blocklist.
+        Blocklist,
+    }
 }
 
-/// Risk severity score.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskScore {
-    /// Risk cannot be determined.
-    Unknown,
-    /// Low risk.
-    Low,
-    /// Medium risk.
-    Medium,
-    /// High risk.
-    High,
-    /// Severe risk.
-    Severe,
-    /// Address is on a blocklist.
-    Blocklist,
+api_enum! {
+    /// Risk category of a signal.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskCategory {
+        /// Government/international sanctions.
+        Sanctions,
+        /// Child sexual abuse material.
+        Csam,
+        /// General illicit behavior.
+        IllicitBehavior,
+        /// Gambling-related.
+        Gambling,
+        /// Terrorist financing.
+        TerroristFinancing,
+        /// Unsupported category.
+        Unsupported,
+        /// Frozen address.
+        Frozen,
+        /// Other risk.
+        Other,
+        /// Industry considered high-risk.
+        HighRiskIndustry,
+        /// Politically exposed person.
+        Pep,
+        /// Trusted entity.
+        Trusted,
+        /// Hacking-related.
+        Hacking,
+        /// Human trafficking.
+        HumanTrafficking,
+        /// Subject to special regulatory measures.
+        SpecialMeasures,
+    }
 }
 
-/// Risk category of a signal.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskCategory {
-    /// Government/international sanctions.
-    Sanctions,
-    /// Child sexual abuse material.
-    Csam,
-    /// General illicit behavior.
-    IllicitBehavior,
-    /// Gambling-related.
-    Gambling,
-    /// Terrorist financing.
-    TerroristFinancing,
-    /// Unsupported category.
-    Unsupported,
-    /// Frozen address.
-    Frozen,
-    /// Other risk.
-    Other,
-    /// Industry considered high-risk.
-    HighRiskIndustry,
-    /// Politically exposed person.
-    Pep,
-    /// Trusted entity.
-    Trusted,
-    /// Hacking-related.
-    Hacking,
-    /// Human trafficking.
-    HumanTrafficking,
-    /// Subject to special regulatory measures.
-    SpecialMeasures,
+api_enum! {
+    /// Relationship type of a risk signal to the screened address.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskType {
+        /// Direct ownership risk.
+        Ownership,
+        /// Risk from a counterparty.
+        Counterparty,
+        /// Indirect exposure.
+        Indirect,
+    }
 }
 
-/// Relationship type of a risk signal to the screened address.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskType {
-    /// Direct ownership risk.
-    Ownership,
-    /// Risk from a counterparty.
-    Counterparty,
-    /// Indirect exposure.
-    Indirect,
+api_enum! {
+    /// What a risk signal was derived from.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskSignalSource {
+        /// The screened address itself.
+        Address,
+        /// The blockchain network.
+        Blockchain,
+        /// An asset held or transferred.
+        Asset,
+    }
 }
 
 /// Risk signal source identifier and location.
@@ -170,8 +249,8 @@ pub struct SignalSource {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RiskSignal {
-    /// Signal data source (`ADDRESS`, `BLOCKCHAIN`, or `ASSET`).
-    pub source: String,
+    /// Signal data source.
+    pub source: RiskSignalSource,
     /// Value of the source (e.g. a blockchain address).
     pub source_value: String,
     /// Risk severity.
@@ -190,7 +269,7 @@ pub struct RiskSignal {
 #[serde(rename_all = "camelCase")]
 pub struct AddressScreeningDecision {
     /// ISO-8601 date the screening was run.
-    pub screening_date: String,
+    pub screening_date: DateTime<Utc>,
     /// Matched rule name (if any).
     pub rule_name: Option<String>,
     /// Actions to take.
@@ -210,7 +289,7 @@ pub struct ScreeningVendorDetail {
     /// Free-form vendor response payload.
     pub response: serde_json::Value,
     /// Creation timestamp (ISO-8601).
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
 }
 
 /// Outer `{ "data": … }` envelope returned by the compliance API.
@@ -241,22 +320,87 @@ pub struct BlockchainAddressScreeningResponse {
     pub details: Vec<ScreeningVendorDetail>,
     /// UUID of any generated compliance alert.
     pub alert_id: Option<String>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(
+        flatten,
+        deserialize_with = "circle_core::unknown_fields::capture::<BlockchainAddressScreeningResponse, _>"
+    )]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-/// Top-level outcome of a screening request.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ScreeningResult {
-    /// Address is approved.
-    Approved,
-    /// Address is denied.
-    Denied,
+api_enum! {
+    /// Top-level outcome of a screening request.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum ScreeningResult {
+        /// Address is approved.
+        Approved,
+        /// Address is denied.
+        Denied,
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use circle_core::ApiEnum;
+
     use super::*;
 
+    #[test]
+    fn chains_convert_through_network() -> Result<(), Box<dyn std::error::Error>> {
+        let network = circle_core::Network::try_from(Chain::Btc)?;
+        assert!(!network.is_evm());
+        assert_eq!(Chain::try_from(circle_core::Network::UniSepolia)?, Chain::UniSepolia);
+        let unsupported = Chain::try_from(circle_core::Network::BaseSepolia).err();
+        assert_eq!(
+            unsupported.map(|error| error.to_string()).as_deref(),
+            Some("BASE-SEPOLIA is not supported by Chain")
+        );
+        Ok(())
+    }
+
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn unknown_chain_converts_if_the_network_is_known() -> Result<(), Box<dyn std::error::Error>> {
+        let parsed: Chain = serde_json::from_str("\"BASE\"")?;
+        assert_eq!(circle_core::Network::try_from(parsed)?, circle_core::Network::Base);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn unknown_chain_roundtrips() -> Result<(), Box<dyn std::error::Error>> {
+        let parsed: Chain = serde_json::from_str("\"TEMPO\"")?;
+        assert_eq!(parsed, Chain::Unknown("TEMPO".to_string()));
+        assert_eq!(parsed.unknown_value(), Some("TEMPO"));
+        assert_eq!(serde_json::to_string(&parsed)?, "\"TEMPO\"");
+        Ok(())
+    }
+
+    #[cfg(feature = "strict")]
+    #[test]
+    fn strict_rejects_unknown_chain() {
+        let error = serde_json::from_str::<Chain>("\"TEMPO\"").err().map(|e| e.to_string());
+        assert_eq!(error.as_deref(), Some("unknown variant `TEMPO` of Chain"));
+    }
+
+    #[test]
+    fn unknown_variant_exists_in_every_build() -> Result<(), Box<dyn std::error::Error>> {
+        let chain = Chain::Unknown("TEMPO".to_string());
+        assert_eq!(serde_json::to_string(&chain)?, "\"TEMPO\"");
+        assert!(!chain.is_known());
+        assert_eq!(serde_json::from_str::<Chain>("\"ETH-SEPOLIA\"")?, Chain::EthSepolia);
+        Ok(())
+    }
+
+    #[test]
+    fn risk_score_unknown_is_a_known_variant() -> Result<(), Box<dyn std::error::Error>> {
+        let parsed: RiskScore = serde_json::from_str("\"UNKNOWN\"")?;
+        assert_eq!(parsed, RiskScore::Undetermined);
+        assert!(parsed.is_known());
+        Ok(())
+    }
+
     #[test]
     fn chain_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
         let s = serde_json::to_string(&Chain::EthSepolia)?;
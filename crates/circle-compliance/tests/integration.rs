@@ -1,4 +1,51 @@
 #![allow(missing_docs)]
 // Integration tests for circle-compliance.
-// Requires Prism mock server on port 4011.
+// Each test runs against its own in-process circle-mock server.
 // Run via: just integration-test
+
+use circle_compliance::{
+    ComplianceClient,
+    ids::IdempotencyKey,
+    models::screening::{Chain, ScreenAddressRequest, ScreeningResult},
+};
+use circle_mock::MockServer;
+
+async fn start() -> Result<(MockServer, ComplianceClient), Box<dyn std::error::Error>> {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let server = MockServer::start().await?;
+    let client = ComplianceClient::with_base_url("TEST_API_KEY:id:secret", server.base_url());
+    Ok((server, client))
+}
+
+fn request(idempotency_key: IdempotencyKey, address: &str) -> ScreenAddressRequest {
+    ScreenAddressRequest { idempotency_key, address: address.to_string(), chain: Chain::EthSepolia }
+}
+
+#[tokio::test]
+async fn unlisted_address_is_approved() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let address = "0x1bf9ad0cc2ad298c69a2995aa806ee832788218c";
+
+    let screening = client
+        .screen_address(&request("0e1d2c3b-4a59-4687-9a6b-5c4d3e2f1a0b".parse()?, address))
+        .await?;
+    assert_eq!(screening.result, ScreeningResult::Approved);
+    assert_eq!(screening.address, address);
+    assert_eq!(screening.chain, Chain::EthSepolia);
+    assert_eq!(server.state().screenings().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn denied_address_is_denied_with_reasons() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let address = "0x8589427373d6d84e98730d7795d8f6f8731fda16";
+    server.state().deny_address(address);
+
+    let screening = client
+        .screen_address(&request("5f4e3d2c-1b0a-4987-8a6b-7c6d5e4f3a2b".parse()?, address))
+        .await?;
+    assert_eq!(screening.result, ScreeningResult::Denied);
+    assert!(screening.decision.reasons.is_some_and(|reasons| !reasons.is_empty()));
+    Ok(())
+}
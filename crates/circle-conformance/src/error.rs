@@ -0,0 +1,38 @@
+//! Error type of the conformance harness.
+
+use crate::report::Report;
+
+/// Errors raised while loading a spec or when a run finds gaps.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The spec or the report file could not be read or written.
+    #[error("I/O error on {path}: {source}")]
+    Io {
+        /// File being read or written.
+        path: String,
+        /// Underlying error.
+        source: std::io::Error,
+    },
+
+    /// The spec is not valid YAML.
+    #[error("{path} is not valid YAML: {source}")]
+    Yaml {
+        /// Spec file.
+        path: String,
+        /// Underlying parser error.
+        source: serde_yaml_ng::Error,
+    },
+
+    /// The spec parsed but is not a usable OpenAPI document.
+    #[error("malformed spec {spec}: {message}")]
+    Spec {
+        /// Spec file name.
+        spec: String,
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// The run found operations or enums that do not conform.
+    #[error("{0}")]
+    Gaps(Box<Report>),
+}
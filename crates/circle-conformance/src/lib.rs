@@ -0,0 +1,62 @@
+//! Conformance of the Circle SDK crates to the OpenAPI specs in
+//! `docs/wallets`.
+//!
+//! The clients and models are written by hand, so nothing stops them from
+//! drifting away from Circle's contracts: an operation without a client
+//! method, a renamed or newly required field, a new `Blockchain` variant. This
+//! crate loads a spec and checks a client against it:
+//!
+//! - every `operationId` is backed by a client method, which sends the spec's method, path, query
+//!   parameters and body fields;
+//! - every documented response example decodes into the method's return type, both with all fields
+//!   present and with only the required ones;
+//! - every `enum` in the spec has exactly the values of a Rust enum.
+//!
+//! The outcome is a [`Report`] with a line per operation and enum. Anything
+//! unchecked is a gap, and a report with gaps fails the run.
+//!
+//! ## Contents
+//!
+//! | Module | Functionality |
+//! |--------|---------------|
+//! | [`spec`] | [`Spec`]: loading and `$ref` resolution, operations, enums |
+//! | [`example`] | Example payloads assembled from field-level `example:`s |
+//! | [`suite`] | [`Suite`]: running client methods and enums against a spec |
+//! | [`report`] | The [`Report`] of a run |
+//! | [`error`] | The [`Error`] type |
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use circle_conformance::Suite;
+//! use circle_core::RestClient;
+//!
+//! let mut suite = Suite::wallets("compliance.yaml")?;
+//! let client: RestClient = suite.client()?;
+//! suite
+//!     .operation("screenAddress", || {
+//!         client
+//!             .post("/v1/w3s/compliance/screening/addresses")
+//!             .json(&serde_json::json!({ "idempotencyKey": "k", "address": "0x1", "chain": "ETH" }))
+//!             .send::<serde_json::Value>()
+//!     })
+//!     .await;
+//! suite.finish().into_result()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The suites for the four SDK crates live in this crate's `tests/`; run them
+//! with `just conformance`.
+
+pub mod error;
+pub mod example;
+pub mod report;
+pub mod spec;
+pub mod suite;
+
+pub use error::Error;
+pub use report::{Line, Outcome, Report, Subject};
+pub use spec::{Operation, Parameter, Spec, SpecEnum, SuccessResponse};
+pub use suite::{ExampleTransport, Suite};
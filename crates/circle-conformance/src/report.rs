@@ -0,0 +1,125 @@
+//! The coverage report of a run.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use crate::error::Error;
+
+/// What a line of the report is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subject {
+    /// An `operationId`.
+    Operation,
+    /// An `enum` schema.
+    Enum,
+}
+
+/// Result of checking one operation or enum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Everything checked conforms; the text says what was checked.
+    Conforms(String),
+    /// Knowingly not covered yet, for the given reason.
+    Pending(String),
+    /// Does not conform, for each of the listed reasons.
+    Gap(Vec<String>),
+}
+
+/// One line of the report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Operation or enum.
+    pub subject: Subject,
+    /// The `operationId` or the enum's JSON pointer.
+    pub name: String,
+    /// What was found.
+    pub outcome: Outcome,
+}
+
+/// Coverage of one spec: a line per operation and per enum, in spec order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    spec: String,
+    lines: Vec<Line>,
+}
+
+impl Report {
+    pub(crate) const fn new(spec: String, lines: Vec<Line>) -> Self {
+        Self { spec, lines }
+    }
+
+    /// The spec the report is about.
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    /// All lines, operations first.
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// The lines that are gaps.
+    pub fn gaps(&self) -> impl Iterator<Item = &Line> {
+        self.lines.iter().filter(|line| matches!(line.outcome, Outcome::Gap(_)))
+    }
+
+    /// Whether nothing is a gap. Pending lines do not count as gaps.
+    pub fn is_clean(&self) -> bool {
+        self.gaps().next().is_none()
+    }
+
+    /// Writes the report to `<dir>/<spec>.txt` and returns the file's path.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        let dir = dir.as_ref();
+        let path = dir.join(format!("{}.txt", self.spec.trim_end_matches(".yaml")));
+        std::fs::create_dir_all(dir)
+            .and_then(|()| std::fs::write(&path, self.to_string()))
+            .map_err(|source| Error::Io { path: path.display().to_string(), source })?;
+        Ok(path)
+    }
+
+    /// Fails with [`Error::Gaps`] unless the report [`is_clean`](Self::is_clean).
+    pub fn into_result(self) -> Result<Self, Error> {
+        if self.is_clean() { Ok(self) } else { Err(Error::Gaps(Box::new(self))) }
+    }
+
+    fn summary(&self, subject: Subject) -> String {
+        let lines = || self.lines.iter().filter(move |line| line.subject == subject);
+        let count = |pick: fn(&Outcome) -> bool| lines().filter(|line| pick(&line.outcome)).count();
+        format!(
+            "{} ({} conform, {} pending, {} gaps)",
+            lines().count(),
+            count(|o| matches!(o, Outcome::Conforms(_))),
+            count(|o| matches!(o, Outcome::Pending(_))),
+            count(|o| matches!(o, Outcome::Gap(_))),
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Conformance of {}", self.spec)?;
+        writeln!(f, "  operations: {}", self.summary(Subject::Operation))?;
+        writeln!(f, "  enums:      {}", self.summary(Subject::Enum))?;
+        for (subject, title) in [(Subject::Operation, "Operations"), (Subject::Enum, "Enums")] {
+            writeln!(f, "\n{title}")?;
+            for line in self.lines.iter().filter(|line| line.subject == subject) {
+                match &line.outcome {
+                    Outcome::Conforms(detail) => writeln!(f, "  ok       {}: {detail}", line.name)?,
+                    Outcome::Pending(reason) => {
+                        writeln!(f, "  pending  {}: {reason}", line.name)?;
+                    }
+                    Outcome::Gap(problems) => {
+                        writeln!(f, "  GAP      {}", line.name)?;
+                        for problem in problems {
+                            writeln!(f, "             - {problem}")?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
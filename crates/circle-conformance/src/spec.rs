@@ -0,0 +1,243 @@
+//! Reading operations, schemas and enums out of an OpenAPI document.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use circle_core::Method;
+use serde_json::Value;
+
+use crate::error::Error;
+
+/// Directory holding the Circle W3S specs.
+const WALLETS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../docs/wallets");
+
+/// Keys of a path item that are operations.
+const HTTP_METHODS: &[&str] =
+    &["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// A parsed OpenAPI 3 document.
+#[derive(Debug, Clone)]
+pub struct Spec {
+    name: String,
+    document: Value,
+    pub(crate) field_examples: BTreeMap<String, Value>,
+}
+
+/// One `operationId` with what the harness checks about it.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    /// The `operationId`.
+    pub id: String,
+    /// HTTP method.
+    pub method: Method,
+    /// Path template, e.g. `/v1/w3s/wallets/{id}`.
+    pub path: String,
+    /// Path, query and header parameters, with `$ref`s resolved.
+    pub parameters: Vec<Parameter>,
+    /// Schema of the JSON request body, if the operation takes one.
+    pub request_body: Option<Value>,
+    /// The documented 2xx responses.
+    pub responses: Vec<SuccessResponse>,
+}
+
+/// A declared operation parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    /// Parameter name.
+    pub name: String,
+    /// `path`, `query` or `header`.
+    pub location: String,
+    /// Whether the spec marks it required.
+    pub required: bool,
+}
+
+/// A documented 2xx response.
+#[derive(Debug, Clone)]
+pub struct SuccessResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Schema of the JSON body; `None` for an empty response such as `204`.
+    pub schema: Option<Value>,
+}
+
+/// An `enum` found anywhere in the document.
+#[derive(Debug, Clone)]
+pub struct SpecEnum {
+    /// JSON pointer to the schema, e.g. `/components/schemas/Blockchain`.
+    pub pointer: String,
+    /// The allowed values, in spec order.
+    pub values: Vec<Value>,
+}
+
+impl Spec {
+    /// Loads the spec at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let text = std::fs::read_to_string(path)
+            .map_err(|source| Error::Io { path: display.clone(), source })?;
+        let document = serde_yaml_ng::from_str(&text)
+            .map_err(|source| Error::Yaml { path: display.clone(), source })?;
+        let name = path.file_name().map_or(display, |name| name.to_string_lossy().into_owned());
+        Ok(Self::from_document(name, document))
+    }
+
+    /// Wraps an already parsed document.
+    pub fn from_document(name: impl Into<String>, document: Value) -> Self {
+        Self { name: name.into(), document, field_examples: BTreeMap::new() }
+    }
+
+    /// Loads `file_name` from the repository's `docs/wallets` directory.
+    pub fn wallets(file_name: &str) -> Result<Self, Error> {
+        Self::load(PathBuf::from(WALLETS_DIR).join(file_name))
+    }
+
+    /// The spec's file name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The whole document.
+    pub const fn document(&self) -> &Value {
+        &self.document
+    }
+
+    /// Follows `$ref`s until `schema` is an inline schema.
+    ///
+    /// Only local references (`#/...`) are supported; an unresolvable
+    /// reference resolves to itself.
+    pub fn resolve<'a>(&'a self, mut schema: &'a Value) -> &'a Value {
+        for _ in 0..32 {
+            let Some(target) = schema
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+                .and_then(|pointer| self.document.pointer(pointer))
+            else {
+                break;
+            };
+            schema = target;
+        }
+        schema
+    }
+
+    /// Every operation, in document order.
+    pub fn operations(&self) -> Result<Vec<Operation>, Error> {
+        let paths = self
+            .document
+            .get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| self.malformed("no `paths` object"))?;
+        let mut operations = Vec::new();
+        for (path, item) in paths {
+            let shared = item.get("parameters");
+            for (method, operation) in item.as_object().into_iter().flatten() {
+                if !HTTP_METHODS.contains(&method.as_str()) {
+                    continue;
+                }
+                let Ok(method) = Method::from_bytes(method.to_ascii_uppercase().as_bytes()) else {
+                    continue;
+                };
+                let id = operation
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| self.malformed(format!("{method} {path} has no operationId")))?;
+                let mut parameters = self.parameters(shared);
+                for parameter in self.parameters(operation.get("parameters")) {
+                    parameters
+                        .retain(|p| p.name != parameter.name || p.location != parameter.location);
+                    parameters.push(parameter);
+                }
+                operations.push(Operation {
+                    id: id.to_string(),
+                    method,
+                    path: path.clone(),
+                    parameters,
+                    request_body: operation
+                        .get("requestBody")
+                        .map(|body| self.resolve(body))
+                        .and_then(|body| body.pointer("/content/application~1json/schema"))
+                        .cloned(),
+                    responses: self.success_responses(operation.get("responses")),
+                });
+            }
+        }
+        Ok(operations)
+    }
+
+    /// Every `enum` in the document, in document order.
+    pub fn enums(&self) -> Vec<SpecEnum> {
+        let mut found = Vec::new();
+        collect_enums(&self.document, &mut String::new(), &mut found);
+        found
+    }
+
+    fn parameters(&self, list: Option<&Value>) -> Vec<Parameter> {
+        list.and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|parameter| self.resolve(parameter))
+            .filter_map(|parameter| {
+                Some(Parameter {
+                    name: parameter.get("name")?.as_str()?.to_string(),
+                    location: parameter.get("in")?.as_str()?.to_string(),
+                    required: parameter.get("required").and_then(Value::as_bool).unwrap_or(false),
+                })
+            })
+            .collect()
+    }
+
+    fn success_responses(&self, responses: Option<&Value>) -> Vec<SuccessResponse> {
+        responses
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(status, response)| {
+                let status = status.parse::<u16>().ok().filter(|s| (200..300).contains(s))?;
+                let schema =
+                    self.resolve(response).pointer("/content/application~1json/schema").cloned();
+                Some(SuccessResponse { status, schema })
+            })
+            .collect()
+    }
+
+    fn malformed(&self, message: impl Into<String>) -> Error {
+        Error::Spec { spec: self.name.clone(), message: message.into() }
+    }
+}
+
+impl Operation {
+    /// The parameters declared `in` the given location.
+    pub fn parameters_in<'a>(&'a self, location: &'a str) -> impl Iterator<Item = &'a Parameter> {
+        self.parameters.iter().filter(move |parameter| parameter.location == location)
+    }
+}
+
+fn collect_enums(node: &Value, pointer: &mut String, found: &mut Vec<SpecEnum>) {
+    match node {
+        Value::Object(map) => {
+            if let Some(values) = map.get("enum").and_then(Value::as_array) {
+                found.push(SpecEnum { pointer: pointer.clone(), values: values.clone() });
+            }
+            for (key, child) in map {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                collect_enums(child, pointer, found);
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&index.to_string());
+                collect_enums(child, pointer, found);
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
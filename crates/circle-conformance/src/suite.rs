@@ -0,0 +1,574 @@
+//! Running client methods and Rust enums against a spec.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use circle_core::{
+    ApiEnum, BoxFuture, ClientBuilder, HttpRequest, HttpResponse, HttpTransport, RestClient,
+    RetryPolicy,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+use crate::{
+    error::Error,
+    report::{Line, Outcome, Report, Subject},
+    spec::{Operation, Spec, SpecEnum},
+};
+
+/// [`HttpTransport`] answering each call with the example queued for it and
+/// recording what the client sent.
+#[derive(Debug, Clone, Default)]
+pub struct ExampleTransport {
+    state: Arc<Mutex<ExampleState>>,
+}
+
+#[derive(Debug, Default)]
+struct ExampleState {
+    next: Option<HttpResponse>,
+    requests: Vec<HttpRequest>,
+}
+
+impl ExampleTransport {
+    fn answer_with(&self, response: HttpResponse) {
+        let mut state = self.lock();
+        state.next = Some(response);
+        state.requests.clear();
+    }
+
+    fn take_requests(&self) -> Vec<HttpRequest> {
+        let mut state = self.lock();
+        state.next = None;
+        std::mem::take(&mut state.requests)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ExampleState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl HttpTransport for ExampleTransport {
+    fn send(
+        &self,
+        request: HttpRequest,
+    ) -> BoxFuture<'_, Result<HttpResponse, circle_core::Error>> {
+        let mut state = self.lock();
+        let response = state.next.take().ok_or_else(|| {
+            circle_core::Error::Middleware(format!(
+                "conformance: second request {} {} in one call",
+                request.method,
+                request.path.as_str()
+            ))
+        });
+        state.requests.push(request);
+        Box::pin(std::future::ready(response))
+    }
+}
+
+/// Checks a client crate against one spec and collects the [`Report`].
+///
+/// Register every client method with [`operation`](Self::operation) and
+/// every Rust enum with [`enumeration`](Self::enumeration), then call
+/// [`finish`](Self::finish). Operations and enums of the spec that were
+/// never registered, and registrations the spec does not know, are gaps.
+#[derive(Debug)]
+pub struct Suite {
+    spec: Spec,
+    operations: Vec<Operation>,
+    enums: Vec<SpecEnum>,
+    transport: ExampleTransport,
+    checked_operations: BTreeMap<String, Outcome>,
+    checked_enums: BTreeMap<String, Outcome>,
+    pending: BTreeMap<String, String>,
+    envelopes: BTreeMap<String, String>,
+    always_present: BTreeMap<String, Vec<String>>,
+    stray: Vec<Line>,
+}
+
+impl Suite {
+    /// Starts a suite for `file_name` in `docs/wallets`.
+    pub fn wallets(file_name: &str) -> Result<Self, Error> {
+        Self::new(Spec::wallets(file_name)?)
+    }
+
+    /// Starts a suite for an already loaded spec.
+    pub fn new(spec: Spec) -> Result<Self, Error> {
+        Ok(Self {
+            operations: spec.operations()?,
+            enums: spec.enums(),
+            spec,
+            transport: ExampleTransport::default(),
+            checked_operations: BTreeMap::new(),
+            checked_enums: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            envelopes: BTreeMap::new(),
+            always_present: BTreeMap::new(),
+            stray: Vec::new(),
+        })
+    }
+
+    /// A client wired to this suite's transport, without retries.
+    pub fn client<C: From<RestClient>>(&self) -> Result<C, circle_core::Error> {
+        ClientBuilder::<C>::new("conformance")
+            .transport(self.transport.clone())
+            .retry_policy(RetryPolicy::none())
+            .build()
+    }
+
+    /// Records that `operation_id` has no client method yet, and why.
+    ///
+    /// A pending operation that turns out to be covered, or that is not in
+    /// the spec, is reported as a gap so the list cannot go stale.
+    pub fn pending(&mut self, operation_id: &str, reason: &str) -> &mut Self {
+        self.pending.insert(operation_id.to_string(), reason.to_string());
+        self
+    }
+
+    /// Wraps the response examples of `operation_id` in `{"<field>": …}`.
+    ///
+    /// For operations whose spec documents the bare payload although the
+    /// live API wraps it, like every other endpoint, in an envelope. Call
+    /// before [`operation`](Self::operation).
+    pub fn envelope(&mut self, operation_id: &str, field: &str) -> &mut Self {
+        self.envelopes.insert(operation_id.to_string(), field.to_string());
+        self
+    }
+
+    /// Uses `value` as the example of every property called `name` that the
+    /// spec documents without one. See [`Spec::field_example`]. Call before
+    /// [`operation`](Self::operation).
+    pub fn field_example(&mut self, name: &str, value: Value) -> &mut Self {
+        self.spec.field_example(name, value);
+        self
+    }
+
+    /// Treats the response field at `pointer` (a JSON pointer into the body,
+    /// inside any [`envelope`](Self::envelope)) as required for
+    /// `operation_id`.
+    ///
+    /// For fields the spec marks optional although the live API always
+    /// sends them, such as the `data` of most responses. The required-only
+    /// example then carries the field from the full one. Call before
+    /// [`operation`](Self::operation).
+    pub fn always_present(&mut self, operation_id: &str, pointer: &str) -> &mut Self {
+        self.always_present.entry(operation_id.to_string()).or_default().push(pointer.to_string());
+        self
+    }
+
+    /// Checks the client method behind `operation_id`.
+    ///
+    /// `call` is invoked once per example of every documented 2xx response
+    /// (an empty body where the spec documents none) and must succeed on
+    /// each. The request it sends must use the spec's method and path, send
+    /// only declared query parameters and body fields, and include every
+    /// required one.
+    pub async fn operation<F, Fut, T>(&mut self, operation_id: &str, call: F)
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, circle_core::Error>>,
+    {
+        let Some(operation) = self.operations.iter().find(|op| op.id == operation_id).cloned()
+        else {
+            self.stray(Subject::Operation, operation_id, "operationId is not in the spec");
+            return;
+        };
+        let envelope = self.envelopes.get(operation_id).cloned();
+        let always_present = self.always_present.get(operation_id).cloned().unwrap_or_default();
+        let mut problems = Vec::new();
+        let mut request = None;
+        let mut examples = 0;
+        for response in &operation.responses {
+            let bodies = response.schema.as_ref().map_or_else(
+                || vec![None],
+                |schema| {
+                    with_fields(self.spec.examples(schema), &always_present)
+                        .into_iter()
+                        .map(Some)
+                        .collect()
+                },
+            );
+            for (index, mut body) in bodies.into_iter().enumerate() {
+                if let Some(field) = &envelope {
+                    body = body.map(|payload| serde_json::json!({ field.as_str(): payload }));
+                }
+                self.transport.answer_with(HttpResponse {
+                    status: response.status,
+                    headers: Default::default(),
+                    body: body.as_ref().map(Value::to_string).unwrap_or_default().into_bytes(),
+                });
+                let result = call().await;
+                let mut sent = self.transport.take_requests();
+                match result {
+                    Ok(_) => examples += 1,
+                    Err(error) => {
+                        let which = if body.is_some() {
+                            format!("example {}", index + 1)
+                        } else {
+                            "empty body".to_string()
+                        };
+                        problems.push(format!(
+                            "{} response {which} rejected: {error}",
+                            response.status
+                        ));
+                    }
+                }
+                if request.is_none() && !sent.is_empty() {
+                    request = Some(sent.swap_remove(0));
+                }
+            }
+        }
+        match &request {
+            Some(request) => problems.extend(self.request_problems(&operation, request)),
+            None => problems.push("the client method sent no request".to_string()),
+        }
+        let outcome = if problems.is_empty() {
+            let wrapped =
+                envelope.map(|field| format!(" wrapped in `{field}`")).unwrap_or_default();
+            Outcome::Conforms(format!(
+                "{} {}, {examples} response example(s){wrapped}",
+                operation.method, operation.path
+            ))
+        } else {
+            Outcome::Gap(problems)
+        };
+        self.checked_operations.insert(operation.id, outcome);
+    }
+
+    /// Checks that the Rust enum `E` has exactly the values of the spec enum
+    /// at `pointer`.
+    ///
+    /// `pointer` is a JSON pointer into the spec, or the name of a schema
+    /// under `components/schemas`. A spec value only counts as accepted if it
+    /// parses to one of [`ApiEnum::KNOWN`], not to the `Unknown` fallback.
+    pub fn enumeration<E: ApiEnum + Serialize + DeserializeOwned>(
+        &mut self,
+        pointer: &str,
+    ) -> &mut Self {
+        self.check_enum::<E>(pointer, true)
+    }
+
+    /// Checks that the Rust enum `E` accepts every value of the spec enum at
+    /// `pointer`, for schemas that narrow a shared enum to a few values
+    /// (e.g. a `custodyType` fixed to `DEVELOPER`).
+    pub fn narrowed_enumeration<E: ApiEnum + Serialize + DeserializeOwned>(
+        &mut self,
+        pointer: &str,
+    ) -> &mut Self {
+        self.check_enum::<E>(pointer, false)
+    }
+
+    /// Builds the report; every spec operation and enum gets a line.
+    pub fn finish(self) -> Report {
+        let mut lines = Vec::new();
+        for operation in &self.operations {
+            let outcome =
+                match (self.checked_operations.get(&operation.id), self.pending.get(&operation.id))
+                {
+                    (Some(_), Some(_)) => Outcome::Gap(vec![
+                        "listed as pending but checked; drop it from the pending list".to_string(),
+                    ]),
+                    (Some(outcome), None) => outcome.clone(),
+                    (None, Some(reason)) => Outcome::Pending(reason.clone()),
+                    (None, None) => Outcome::Gap(vec![format!(
+                        "no client method for {} {}",
+                        operation.method, operation.path
+                    )]),
+                };
+            lines.push(Line { subject: Subject::Operation, name: operation.id.clone(), outcome });
+        }
+        for id in self.pending.keys() {
+            if !self.operations.iter().any(|operation| &operation.id == id) {
+                lines.push(Line {
+                    subject: Subject::Operation,
+                    name: id.clone(),
+                    outcome: Outcome::Gap(vec![
+                        "pending operationId is not in the spec".to_string(),
+                    ]),
+                });
+            }
+        }
+        for spec_enum in &self.enums {
+            let outcome =
+                self.checked_enums.get(&spec_enum.pointer).cloned().unwrap_or_else(|| {
+                    Outcome::Gap(vec!["no Rust enum is checked against it".to_string()])
+                });
+            lines.push(Line { subject: Subject::Enum, name: spec_enum.pointer.clone(), outcome });
+        }
+        lines.extend(self.stray);
+        Report::new(self.spec.name().to_string(), lines)
+    }
+
+    fn check_enum<E: ApiEnum + Serialize + DeserializeOwned>(
+        &mut self,
+        pointer: &str,
+        exact: bool,
+    ) -> &mut Self {
+        let pointer = if pointer.starts_with('/') {
+            pointer.to_string()
+        } else {
+            format!("/components/schemas/{pointer}")
+        };
+        let Some(spec_enum) = self.enums.iter().find(|e| e.pointer == pointer) else {
+            self.stray(Subject::Enum, &pointer, "no enum at this pointer in the spec");
+            return self;
+        };
+        let rust = std::any::type_name::<E>();
+        let mut problems: Vec<String> = spec_enum
+            .values
+            .iter()
+            .filter(|value| !accepts::<E>(value))
+            .map(|value| format!("{rust} does not accept spec value {value}"))
+            .collect();
+        if exact {
+            match variant_names::<E>() {
+                Some(names) => problems.extend(
+                    names
+                        .into_iter()
+                        .filter(|name| !spec_enum.values.iter().any(|value| value == name))
+                        .map(|name| {
+                            format!("{rust} has variant \"{name}\" the spec does not list")
+                        }),
+                ),
+                None => problems.push(format!("cannot list the variants of {rust}")),
+            }
+        }
+        let outcome = if problems.is_empty() {
+            Outcome::Conforms(format!("{rust}, {} value(s)", spec_enum.values.len()))
+        } else {
+            Outcome::Gap(problems)
+        };
+        self.checked_enums.insert(pointer, outcome);
+        self
+    }
+
+    fn request_problems(&self, operation: &Operation, request: &HttpRequest) -> Vec<String> {
+        let mut problems = Vec::new();
+        if request.method != operation.method {
+            problems.push(format!("sent {} instead of {}", request.method, operation.method));
+        }
+        if normalize(request.path.template()) != normalize(&operation.path) {
+            problems.push(format!(
+                "sent to {} instead of {}",
+                request.path.template(),
+                operation.path
+            ));
+        }
+        let query = request.query_pairs();
+        for (name, _) in &query {
+            if !operation.parameters_in("query").any(|parameter| &parameter.name == name) {
+                problems.push(format!("query parameter `{name}` is not in the spec"));
+            }
+        }
+        for parameter in operation.parameters_in("query").filter(|p| p.required) {
+            if !query.iter().any(|(name, _)| name == &parameter.name) {
+                problems.push(format!("required query parameter `{}` is missing", parameter.name));
+            }
+        }
+        for parameter in operation.parameters_in("header").filter(|p| p.required) {
+            if !request.headers.contains_key(parameter.name.as_str()) {
+                problems.push(format!("required header `{}` is missing", parameter.name));
+            }
+        }
+        match (&operation.request_body, request.json()) {
+            (Some(schema), Some(body)) => self.field_problems(schema, &body, "body", &mut problems),
+            (Some(_), None) => problems.push("sent no JSON body".to_string()),
+            (None, Some(_)) => problems.push("sent a body the spec does not declare".to_string()),
+            (None, None) => {}
+        }
+        problems
+    }
+
+    /// Compares the fields of `value` with the properties of `schema`.
+    fn field_problems(&self, schema: &Value, value: &Value, at: &str, problems: &mut Vec<String>) {
+        let schema = self.spec.resolve(schema);
+        match value {
+            Value::Object(fields) => {
+                let (properties, required) = self.properties(schema);
+                if properties.is_empty() {
+                    return;
+                }
+                for (name, field) in fields {
+                    match properties.get(name.as_str()) {
+                        Some(property) => {
+                            self.field_problems(property, field, &format!("{at}.{name}"), problems);
+                        }
+                        None => problems.push(format!("field `{at}.{name}` is not in the spec")),
+                    }
+                }
+                for name in required {
+                    if !fields.contains_key(name) {
+                        problems.push(format!("required field `{at}.{name}` is missing"));
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = schema.get("items") {
+                    for item in items {
+                        self.field_problems(item_schema, item, &format!("{at}[]"), problems);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Properties of an object schema, following `allOf` and the union of
+    /// `oneOf`/`anyOf` branches; required names only from `allOf` parts.
+    fn properties<'a>(&'a self, schema: &'a Value) -> (BTreeMap<&'a str, &'a Value>, Vec<&'a str>) {
+        let schema = self.spec.resolve(schema);
+        let mut properties: BTreeMap<&str, &Value> = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(name, property)| (name.as_str(), property))
+            .collect();
+        let mut required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        for part in schema.get("allOf").and_then(Value::as_array).into_iter().flatten() {
+            let (more, also_required) = self.properties(part);
+            properties.extend(more);
+            required.extend(also_required);
+        }
+        for key in ["oneOf", "anyOf"] {
+            for branch in schema.get(key).and_then(Value::as_array).into_iter().flatten() {
+                properties.extend(self.properties(branch).0);
+            }
+        }
+        (properties, required)
+    }
+
+    fn stray(&mut self, subject: Subject, name: &str, problem: &str) {
+        self.stray.push(Line {
+            subject,
+            name: name.to_string(),
+            outcome: Outcome::Gap(vec![problem.to_string()]),
+        });
+    }
+}
+
+/// `/v1/w3s/wallets/{id}` and `/v1/w3s/wallets/{wallet_id}` compare equal.
+fn normalize(template: &str) -> String {
+    let mut normalized = String::with_capacity(template.len());
+    let mut in_parameter = false;
+    for c in template.chars() {
+        match c {
+            '{' => {
+                in_parameter = true;
+                normalized.push_str("{}");
+            }
+            '}' => in_parameter = false,
+            _ if !in_parameter => normalized.push(c),
+            _ => {}
+        }
+    }
+    normalized
+}
+
+/// `examples` with the fields at `pointers` copied from the first (full)
+/// example into the others where they are missing, without duplicates.
+fn with_fields(examples: Vec<Value>, pointers: &[String]) -> Vec<Value> {
+    let Some(full) = examples.first().cloned() else {
+        return examples;
+    };
+    let mut completed: Vec<Value> = Vec::new();
+    for mut example in examples {
+        for pointer in pointers {
+            if let Some(value) =
+                full.pointer(pointer).filter(|_| example.pointer(pointer).is_none())
+            {
+                insert(&mut example, pointer, value.clone());
+            }
+        }
+        if !completed.contains(&example) {
+            completed.push(example);
+        }
+    }
+    completed
+}
+
+/// Sets the field at `pointer`, creating missing parent objects.
+fn insert(mut node: &mut Value, pointer: &str, value: Value) {
+    let keys: Vec<String> =
+        pointer.split('/').skip(1).map(|key| key.replace("~1", "/").replace("~0", "~")).collect();
+    let Some((last, parents)) = keys.split_last() else {
+        return;
+    };
+    for key in parents {
+        let Some(object) = node.as_object_mut() else {
+            return;
+        };
+        node = object.entry(key.clone()).or_insert_with(|| Value::Object(Map::new()));
+    }
+    if let Some(object) = node.as_object_mut() {
+        object.insert(last.clone(), value);
+    }
+}
+
+/// Whether `value` parses to one of `E`'s known variants.
+fn accepts<E: ApiEnum + DeserializeOwned>(value: &Value) -> bool {
+    serde_json::from_value::<E>(value.clone()).is_ok_and(|parsed| parsed.is_known())
+}
+
+/// The serde names of `E`'s known variants.
+fn variant_names<E: ApiEnum + Serialize>() -> Option<Vec<String>> {
+    E::KNOWN
+        .iter()
+        .map(|variant| match serde_json::to_value(variant) {
+            Ok(Value::String(name)) => Some(name),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use circle_compliance::models::screening::{RiskScore, RiskType};
+
+    use super::*;
+
+    #[test]
+    fn reads_variant_names_from_serde() {
+        assert_eq!(
+            variant_names::<RiskType>(),
+            Some(vec!["OWNERSHIP".to_string(), "COUNTERPARTY".to_string(), "INDIRECT".to_string()])
+        );
+    }
+
+    #[test]
+    fn only_known_variants_count_as_accepted() {
+        assert!(accepts::<RiskScore>(&Value::from("UNKNOWN")));
+        assert!(!accepts::<RiskScore>(&Value::from("EXTREME")));
+        assert!(!accepts::<RiskScore>(&Value::from(3)));
+    }
+
+    #[test]
+    fn parameter_names_do_not_matter_in_templates() {
+        assert_eq!(
+            normalize("/v1/w3s/wallets/{wallet_id}/nfts"),
+            normalize("/v1/w3s/wallets/{id}/nfts")
+        );
+        assert_ne!(normalize("/v1/w3s/wallets/{id}"), normalize("/v1/w3s/walletSets/{id}"));
+    }
+
+    #[test]
+    fn always_present_fields_are_copied_into_the_minimal_example() {
+        let full = serde_json::json!({ "data": { "transaction": { "id": "1" }, "note": "x" } });
+        let examples = with_fields(
+            vec![full.clone(), serde_json::json!({})],
+            &["/data/transaction".to_string()],
+        );
+        assert_eq!(
+            examples,
+            vec![full, serde_json::json!({ "data": { "transaction": { "id": "1" } } })]
+        );
+    }
+}
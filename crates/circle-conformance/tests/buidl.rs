@@ -0,0 +1,122 @@
+#![allow(missing_docs)]
+// Conformance of circle-buidl-wallets to docs/wallets/buidl-wallets.yaml.
+// Run via: just conformance
+
+use chrono::{TimeZone, Utc};
+use circle_buidl_wallets::{
+    BuidlWalletsClient,
+    ids::{TransferId, UserOpId, WalletId},
+    models::{
+        common::{Blockchain, CursorParams, PageParams},
+        transfer::{ListTransfersParams, TransferErrorReason, TransferState, TransferType},
+        user_op::{ListUserOpsParams, UserOpErrorReason, UserOpState},
+        wallet::{
+            FtStandard, ListWalletBalancesParams, ListWalletNftsParams, NftStandard, TokenStandard,
+        },
+    },
+};
+use circle_conformance::Suite;
+
+const ID: &str = "c4d1da72-111e-4d52-bdbf-2e74a2d803d5";
+const ADDRESS: &str = "0x4b6c0b0078b63f881503e7fd3a9a1061065db242";
+const HASH: &str = "0x4a25cc5e661d8504b59c5f38ba93f010e8518966f00e2ceda7955c4b86a9a1ea";
+
+/// Every pagination parameter set, so each one's name is checked.
+fn page() -> PageParams {
+    PageParams {
+        from: Utc.with_ymd_and_hms(2023, 1, 1, 12, 4, 5).single(),
+        to: Utc.with_ymd_and_hms(2023, 1, 2, 12, 4, 5).single(),
+        page_before: Some(ID.to_string()),
+        page_after: Some(ID.to_string()),
+        page_size: Some(10),
+    }
+}
+
+/// Every cursor parameter set, for the endpoints without a date range.
+fn cursor() -> CursorParams {
+    CursorParams {
+        page_before: Some(ID.to_string()),
+        page_after: Some(ID.to_string()),
+        page_size: Some(10),
+    }
+}
+
+#[tokio::test]
+async fn buidl_wallets_conform_to_the_spec() -> Result<(), Box<dyn std::error::Error>> {
+    let mut suite = Suite::wallets("buidl-wallets.yaml")?;
+    let client: BuidlWalletsClient = suite.client()?;
+    let wallet_id: WalletId = ID.parse()?;
+    let transfer_id: TransferId = ID.parse()?;
+    let user_op_id: UserOpId = ID.parse()?;
+
+    let transfers = ListTransfersParams {
+        wallet_addresses: Some(ADDRESS.to_string()),
+        blockchain: Some(Blockchain::MaticAmoy),
+        state: Some(TransferState::Complete),
+        transfer_type: Some(TransferType::InboundTransfer),
+        tx_hash: Some(HASH.to_string()),
+        user_op_hash: Some(HASH.to_string()),
+        page: page(),
+    };
+    suite.operation("listTransfers", || client.list_transfers(&transfers)).await;
+    suite.operation("getTransfer", || client.get_transfer(&transfer_id)).await;
+
+    let user_ops = ListUserOpsParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        ref_id: Some("customer_12345".to_string()),
+        senders: Some(ADDRESS.to_string()),
+        state: Some(UserOpState::Complete),
+        tx_hash: Some(HASH.to_string()),
+        user_op_hash: Some(HASH.to_string()),
+        page: page(),
+    };
+    suite.operation("listUserOps", || client.list_user_ops(&user_ops)).await;
+    suite.operation("getUserOp", || client.get_user_op(&user_op_id)).await;
+
+    let balances = ListWalletBalancesParams {
+        standard: Some(FtStandard::Erc20),
+        name: Some("USDC".to_string()),
+        token_address: Some(ADDRESS.to_string()),
+        page: cursor(),
+    };
+    let nfts = ListWalletNftsParams {
+        standard: Some(NftStandard::Erc721),
+        name: Some("Punk".to_string()),
+        token_address: Some(ADDRESS.to_string()),
+        page: cursor(),
+    };
+    suite
+        .operation("listWalletBalancesByID", || {
+            client.list_wallet_balances_by_id(&wallet_id, &balances)
+        })
+        .await;
+    suite
+        .operation("listWalletNFTsByID", || client.list_wallet_nfts_by_id(&wallet_id, &nfts))
+        .await;
+    suite
+        .operation("listWalletBalancesByBlockchainAddress", || {
+            client.list_wallet_balances_by_address("MATIC-AMOY", ADDRESS, &balances)
+        })
+        .await;
+    suite
+        .operation("listWalletNFTsByBlockchainAddress", || {
+            client.list_wallet_nfts_by_address("MATIC-AMOY", ADDRESS, &nfts)
+        })
+        .await;
+
+    suite
+        .enumeration::<Blockchain>("Blockchain")
+        .enumeration::<TransferState>("TransferState")
+        .enumeration::<TransferType>("TransferType")
+        .enumeration::<TransferErrorReason>("TransferErrorReason")
+        .enumeration::<UserOpState>("UserOpState")
+        .enumeration::<UserOpErrorReason>("UserOpErrorReason")
+        .enumeration::<FtStandard>("FtStandard")
+        .enumeration::<TokenStandard>("TokenStandard")
+        .enumeration::<NftStandard>("NftStandard");
+
+    let report = suite.finish();
+    report.write_to(concat!(env!("CARGO_TARGET_TMPDIR"), "/conformance"))?;
+    assert!(report.is_clean(), "{report}");
+    Ok(())
+}
@@ -0,0 +1,43 @@
+#![allow(missing_docs)]
+// Conformance of circle-compliance to docs/wallets/compliance.yaml.
+// Run via: just conformance
+
+use circle_compliance::{
+    ComplianceClient,
+    models::screening::{
+        Chain, RiskAction, RiskCategory, RiskScore, RiskSignalSource, RiskType,
+        ScreenAddressRequest, ScreeningResult,
+    },
+};
+use circle_conformance::Suite;
+
+#[tokio::test]
+async fn compliance_conforms_to_the_spec() -> Result<(), Box<dyn std::error::Error>> {
+    let mut suite = Suite::wallets("compliance.yaml")?;
+    let client: ComplianceClient = suite.client()?;
+
+    let screen = ScreenAddressRequest {
+        idempotency_key: "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".parse()?,
+        address: "0x1bf9ad0cc2ad298c69a2995aa806ee832788218c".to_string(),
+        chain: Chain::MaticAmoy,
+    };
+    // The spec documents the bare result; the API wraps it like every other.
+    suite.envelope("screenAddress", "data");
+    suite.operation("screenAddress", || client.screen_address(&screen)).await;
+
+    suite
+        .enumeration::<Chain>("Chain")
+        .enumeration::<RiskAction>("RiskAction")
+        .enumeration::<RiskScore>("RiskScore")
+        .enumeration::<RiskCategory>("RiskCategory")
+        .enumeration::<RiskType>("RiskType")
+        .enumeration::<RiskSignalSource>("/components/schemas/RiskSignal/properties/source")
+        .enumeration::<ScreeningResult>(
+            "/components/schemas/BlockchainAddressScreeningResponse/properties/result",
+        );
+
+    let report = suite.finish();
+    report.write_to(concat!(env!("CARGO_TARGET_TMPDIR"), "/conformance"))?;
+    assert!(report.is_clean(), "{report}");
+    Ok(())
+}
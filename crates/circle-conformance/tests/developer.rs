@@ -0,0 +1,405 @@
+#![allow(missing_docs)]
+// Conformance of circle-developer-controlled-wallets to
+// docs/wallets/developer-controlled-wallets.yaml.
+// Run via: just conformance
+
+use chrono::{TimeZone, Utc};
+use circle_conformance::Suite;
+use circle_developer_controlled_wallets::{
+    DeveloperWalletsClient,
+    ids::{TokenId, TransactionId, WalletId, WalletSetId},
+    models::{
+        common::{
+            AccountType, Blockchain, CursorParams, CustodyType, EvmBlockchain, FeeLevel, Order,
+            PageParams, TokenStandard, WalletState,
+        },
+        signing::{
+            SignDelegateActionRequest, SignMessageRequest, SignTransactionRequest,
+            SignTypedDataRequest,
+        },
+        transaction::{
+            AccelerateTxRequest, CancelTxRequest, CreateContractExecutionTxRequest,
+            CreateTransferTxRequest, CreateWalletUpgradeTxRequest, EstimateContractExecFeeRequest,
+            EstimateTransferFeeRequest, FeeParametersParams, GetLowestNonceTxParams,
+            ListTransactionsParams, Operation, RiskAction, RiskCategory, RiskScore,
+            RiskSignalSource, RiskType, TransactionState, TransactionType, ValidateAddressRequest,
+        },
+        wallet::{
+            CreateWalletsRequest, DeriveWalletByAddressRequest, DeriveWalletRequest,
+            ListWalletBalancesParams, ListWalletNftsParams, ListWalletTokenBalancesParams,
+            ListWalletsParams, NftStandard, ScaCore, UpdateWalletRequest, WalletMetadata,
+        },
+        wallet_set::{CreateWalletSetRequest, ListWalletSetsParams, UpdateWalletSetRequest},
+    },
+};
+
+const ID: &str = "c4d1da72-111e-4d52-bdbf-2e74a2d803d5";
+const ADDRESS: &str = "0x4b6c0b0078b63f881503e7fd3a9a1061065db242";
+const HASH: &str = "0x4a25cc5e661d8504b59c5f38ba93f010e8518966f00e2ceda7955c4b86a9a1ea";
+const CIPHERTEXT: &str = "M7Zk0q2Q4gkmCm5Kv1Yb5hx4HQpCvZ6c4b0m1k9k1O4=";
+
+fn some(value: &str) -> Option<String> {
+    Some(value.to_string())
+}
+
+/// Every pagination parameter set, so each one's name is checked.
+fn page() -> PageParams {
+    PageParams {
+        from: Utc.with_ymd_and_hms(2023, 1, 1, 12, 4, 5).single(),
+        to: Utc.with_ymd_and_hms(2023, 1, 2, 12, 4, 5).single(),
+        page_before: some(ID),
+        page_after: some(ID),
+        page_size: Some(10),
+    }
+}
+
+/// Every cursor parameter set, for the endpoints without a date range.
+fn cursor() -> CursorParams {
+    CursorParams { page_before: some(ID), page_after: some(ID), page_size: Some(10) }
+}
+
+#[tokio::test]
+async fn developer_controlled_wallets_conform_to_the_spec() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut suite = Suite::wallets("developer-controlled-wallets.yaml")?;
+    let client: DeveloperWalletsClient = suite.client()?;
+    let wallet_set_id: WalletSetId = ID.parse()?;
+    let wallet_id: WalletId = ID.parse()?;
+    let transaction_id: TransactionId = ID.parse()?;
+    let token_id: TokenId = ID.parse()?;
+    // Optional in the spec, but always sent by the API.
+    suite
+        .always_present("listTransactions", "/data")
+        .always_present("getTransaction", "/data/transaction")
+        .always_present("createDeveloperTransactionTransfer", "/data")
+        .always_present("createDeveloperTransactionAccelerate", "/data")
+        .always_present("getTokenId", "/data")
+        .always_present("listWalletBalance", "/data/tokenBalances")
+        .always_present("listWalletNfts", "/data/nfts");
+    // Documented as plain strings, but always decimal amounts.
+    suite.field_example("networkFee", serde_json::json!("0.0021"));
+
+    // ── Wallet sets ──
+    let wallet_sets = ListWalletSetsParams { order: Some(Order::Asc), page: page() };
+    suite.operation("getWalletSets", || client.list_wallet_sets(&wallet_sets)).await;
+    suite.operation("getWalletSet", || client.get_wallet_set(&wallet_set_id)).await;
+    let create_wallet_set = CreateWalletSetRequest {
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+        idempotency_key: ID.parse()?,
+        name: some("Treasury"),
+    };
+    suite.operation("createWalletSet", || client.create_wallet_set(&create_wallet_set)).await;
+    let update_wallet_set = UpdateWalletSetRequest { name: some("Treasury") };
+    suite
+        .operation("updateWalletSet", || {
+            client.update_wallet_set(&wallet_set_id, &update_wallet_set)
+        })
+        .await;
+
+    // ── Wallets ──
+    let create_wallets = CreateWalletsRequest {
+        idempotency_key: ID.parse()?,
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+        wallet_set_id,
+        blockchains: vec![Blockchain::MaticAmoy],
+        account_type: Some(AccountType::Sca),
+        count: Some(1),
+        metadata: Some(vec![WalletMetadata { name: some("Hot"), ref_id: some("custom_ref_id") }]),
+    };
+    suite.operation("createWallet", || client.create_wallets(&create_wallets)).await;
+    let wallets = ListWalletsParams {
+        address: some(ADDRESS),
+        blockchain: Some(Blockchain::MaticAmoy),
+        sca_core: Some(ScaCore::Circle6900SingleownerV2),
+        wallet_set_id: Some(wallet_set_id),
+        ref_id: some("custom_ref_id"),
+        order: Some(Order::Desc),
+        page: page(),
+    };
+    suite.operation("getWallets", || client.list_wallets(&wallets)).await;
+    suite.operation("getWallet", || client.get_wallet(&wallet_id)).await;
+    let update_wallet = UpdateWalletRequest { name: some("Hot"), ref_id: some("custom_ref_id") };
+    suite.operation("updateWallet", || client.update_wallet(&wallet_id, &update_wallet)).await;
+    let balances = ListWalletBalancesParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        address: some(ADDRESS),
+        sca_core: Some(ScaCore::Circle6900SingleownerV2),
+        wallet_set_id: Some(wallet_set_id),
+        ref_id: some("custom_ref_id"),
+        amount_gte: Some("1.5".parse()?),
+        token_address: some(ADDRESS),
+        page: page(),
+    };
+    suite.operation("getWalletsWithBalances", || client.list_wallet_balances(&balances)).await;
+    let token_balances = ListWalletTokenBalancesParams {
+        include_all: Some(true),
+        name: some("USDC"),
+        token_address: some(ADDRESS),
+        standard: Some(TokenStandard::Erc20),
+        page: cursor(),
+    };
+    suite
+        .operation("listWalletBalance", || {
+            client.list_wallet_token_balances(&wallet_id, &token_balances)
+        })
+        .await;
+    let nfts = ListWalletNftsParams {
+        include_all: Some(true),
+        standard: Some(NftStandard::Erc721),
+        name: some("Punk"),
+        token_address: some(ADDRESS),
+        page: cursor(),
+    };
+    suite.operation("listWalletNfts", || client.list_wallet_nfts(&wallet_id, &nfts)).await;
+    let metadata = WalletMetadata { name: some("Hot"), ref_id: some("custom_ref_id") };
+    let derive = DeriveWalletRequest { metadata: Some(metadata.clone()) };
+    suite
+        .operation("deriveWallet", || {
+            client.derive_wallet(&wallet_id, &EvmBlockchain::BaseSepolia, &derive)
+        })
+        .await;
+    let derive_by_address = DeriveWalletByAddressRequest {
+        source_blockchain: EvmBlockchain::MaticAmoy,
+        wallet_address: ADDRESS.to_string(),
+        target_blockchain: EvmBlockchain::BaseSepolia,
+        metadata: Some(metadata),
+    };
+    suite
+        .operation("deriveWalletByAddress", || client.derive_wallet_by_address(&derive_by_address))
+        .await;
+
+    // ── Signing ──
+    let sign_message = SignMessageRequest {
+        wallet_id: Some(wallet_id),
+        blockchain: Some(Blockchain::MaticAmoy),
+        wallet_address: some(ADDRESS),
+        message: "I agree".to_string(),
+        encoded_by_hex: Some(false),
+        memo: some("terms"),
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+    };
+    suite.operation("signMessage", || client.sign_message(&sign_message)).await;
+    let sign_typed_data = SignTypedDataRequest {
+        wallet_id: Some(wallet_id),
+        blockchain: Some(Blockchain::MaticAmoy),
+        wallet_address: some(ADDRESS),
+        typed_data: r#"{"types":{},"primaryType":"Mail","domain":{},"message":{}}"#.to_string(),
+        memo: some("permit"),
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+    };
+    suite.operation("signTypedData", || client.sign_typed_data(&sign_typed_data)).await;
+    let sign_transaction = SignTransactionRequest {
+        wallet_id: Some(wallet_id),
+        blockchain: Some(Blockchain::MaticAmoy),
+        wallet_address: some(ADDRESS),
+        raw_transaction: some("0x02f8"),
+        transaction: Some(serde_json::json!({ "nonce": 1 })),
+        memo: some("payout"),
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+    };
+    suite.operation("signTransaction", || client.sign_transaction(&sign_transaction)).await;
+    let sign_delegate_action = SignDelegateActionRequest {
+        wallet_id,
+        unsigned_delegate_action: "AgAAAA==".to_string(),
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+    };
+    suite
+        .operation("signDelegateAction", || client.sign_delegate_action(&sign_delegate_action))
+        .await;
+
+    // ── Transactions ──
+    let transactions = ListTransactionsParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        custody_type: Some(CustodyType::Developer),
+        destination_address: some(ADDRESS),
+        include_all: Some(true),
+        operation: Some(Operation::Transfer),
+        state: Some(TransactionState::Complete),
+        tx_hash: some(HASH),
+        tx_type: Some(TransactionType::Outbound),
+        wallet_ids: some(ID),
+        from: Utc.with_ymd_and_hms(2023, 1, 1, 12, 4, 5).single(),
+        to: Utc.with_ymd_and_hms(2023, 1, 2, 12, 4, 5).single(),
+        page_before: some(ID),
+        page_after: some(ID),
+        page_size: Some(10),
+        order: Some(Order::Asc),
+    };
+    suite.operation("listTransactions", || client.list_transactions(&transactions)).await;
+    suite.operation("getTransaction", || client.get_transaction(&transaction_id)).await;
+    let lowest_nonce = GetLowestNonceTxParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        address: some(ADDRESS),
+        wallet_id: Some(wallet_id),
+    };
+    suite
+        .operation("getLowestNonceTransaction", || {
+            client.get_lowest_nonce_transaction(&lowest_nonce)
+        })
+        .await;
+    let fee_parameters = FeeParametersParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        account_type: Some(AccountType::Sca),
+    };
+    suite.operation("getFeeParameters", || client.get_fee_parameters(&fee_parameters)).await;
+    let transfer = CreateTransferTxRequest {
+        idempotency_key: ID.parse()?,
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+        wallet_id,
+        blockchain: Some(Blockchain::MaticAmoy),
+        token_id: Some(token_id),
+        destination_address: ADDRESS.to_string(),
+        amounts: Some(vec!["1.5".parse()?]),
+        nft_token_ids: Some(vec!["1".to_string()]),
+        ref_id: some("custom_ref_id"),
+        fee_level: Some(FeeLevel::Medium),
+        gas_limit: some("21000"),
+        gas_price: some("5"),
+        max_fee: some("5"),
+        priority_fee: some("1"),
+    };
+    suite
+        .operation("createDeveloperTransactionTransfer", || {
+            client.create_transfer_transaction(&transfer)
+        })
+        .await;
+    let contract_execution = CreateContractExecutionTxRequest {
+        idempotency_key: ID.parse()?,
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+        wallet_id,
+        blockchain: Some(Blockchain::MaticAmoy),
+        contract_address: ADDRESS.to_string(),
+        abi_function_signature: some("burn(uint256)"),
+        abi_parameters: Some(vec![serde_json::json!("100")]),
+        call_data: some("0x42966c68"),
+        fee_level: Some(FeeLevel::Medium),
+        gas_limit: some("21000"),
+        max_fee: some("5"),
+        priority_fee: some("1"),
+        ref_id: some("custom_ref_id"),
+        amount: Some("0.1".parse()?),
+    };
+    suite
+        .operation("createDeveloperTransactionContractExecution", || {
+            client.create_contract_execution_transaction(&contract_execution)
+        })
+        .await;
+    let wallet_upgrade = CreateWalletUpgradeTxRequest {
+        idempotency_key: ID.parse()?,
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+        wallet_id,
+        new_sca_core: ScaCore::Circle6900SingleownerV2,
+        fee_level: Some(FeeLevel::Medium),
+        gas_limit: some("21000"),
+        gas_price: some("5"),
+        max_fee: some("5"),
+        priority_fee: some("1"),
+        ref_id: some("custom_ref_id"),
+    };
+    suite
+        .operation("createDeveloperTransactionWalletUpgrade", || {
+            client.create_wallet_upgrade_transaction(&wallet_upgrade)
+        })
+        .await;
+    let cancel = CancelTxRequest {
+        idempotency_key: ID.parse()?,
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+    };
+    suite
+        .operation("createDeveloperTransactionCancel", || {
+            client.cancel_transaction(&transaction_id, &cancel)
+        })
+        .await;
+    let accelerate = AccelerateTxRequest {
+        idempotency_key: ID.parse()?,
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+    };
+    suite
+        .operation("createDeveloperTransactionAccelerate", || {
+            client.accelerate_transaction(&transaction_id, &accelerate)
+        })
+        .await;
+
+    // ── Tokens and utilities ──
+    suite.operation("getTokenId", || client.get_token(&token_id)).await;
+    let validate =
+        ValidateAddressRequest { blockchain: Blockchain::MaticAmoy, address: ADDRESS.to_string() };
+    suite.operation("createValidateAddress", || client.validate_address(&validate)).await;
+    let transfer_fee = EstimateTransferFeeRequest {
+        amounts: vec!["1.5".parse()?],
+        destination_address: ADDRESS.to_string(),
+        nft_token_ids: Some(vec!["1".to_string()]),
+        source_address: some(ADDRESS),
+        token_id: Some(token_id),
+        token_address: some(ADDRESS),
+        blockchain: Some(Blockchain::MaticAmoy),
+        wallet_id: Some(wallet_id),
+    };
+    suite
+        .operation("createTransferEstimateFee", || client.estimate_transfer_fee(&transfer_fee))
+        .await;
+    let contract_fee = EstimateContractExecFeeRequest {
+        contract_address: ADDRESS.to_string(),
+        abi_function_signature: some("burn(uint256)"),
+        abi_parameters: Some(vec![serde_json::json!("100")]),
+        call_data: some("0x42966c68"),
+        amount: Some("0.1".parse()?),
+        blockchain: Some(Blockchain::MaticAmoy),
+        source_address: some(ADDRESS),
+        wallet_id: Some(wallet_id),
+    };
+    suite
+        .operation("createTransactionEstimateFee", || {
+            client.estimate_contract_execution_fee(&contract_fee)
+        })
+        .await;
+
+    suite
+        .enumeration::<AccountType>("AccountType")
+        .enumeration::<Blockchain>("Blockchain")
+        .enumeration::<CustodyType>("CustodyType")
+        .enumeration::<WalletState>("WalletState")
+        .enumeration::<ScaCore>("ScaCore")
+        .enumeration::<TokenStandard>("TokenStandard")
+        .enumeration::<EvmBlockchain>("EvmBlockchain")
+        .enumeration::<TransactionState>("TransactionState")
+        .enumeration::<TransactionType>("TransactionType")
+        .enumeration::<FeeLevel>("FeeLevel")
+        .enumeration::<Operation>("Operation")
+        .enumeration::<RiskAction>("RiskAction")
+        .enumeration::<RiskScore>("RiskScore")
+        .enumeration::<RiskCategory>("RiskCategory")
+        .enumeration::<RiskType>("RiskType")
+        .enumeration::<RiskSignalSource>("/components/schemas/RiskSignal/properties/source")
+        .enumeration::<Order>("/components/parameters/Order/schema")
+        .enumeration::<Operation>("/components/parameters/DeveloperOperation/schema");
+    // Subsets of a wider Rust enum: every spec value must parse, not vice versa.
+    suite
+        .narrowed_enumeration::<Blockchain>("TransferBlockchain")
+        .narrowed_enumeration::<Blockchain>("TokenBlockchain")
+        .narrowed_enumeration::<Blockchain>("ContractExecutionBlockchain")
+        .narrowed_enumeration::<ScaCore>("NewScaCore")
+        .narrowed_enumeration::<CustodyType>(
+            "/components/schemas/DeveloperWalletSet/allOf/1/properties/custodyType",
+        )
+        .narrowed_enumeration::<CustodyType>(
+            "/components/schemas/EndUserWalletSet/allOf/1/properties/custodyType",
+        )
+        .narrowed_enumeration::<AccountType>(
+            "/components/schemas/EOAWallet/allOf/1/properties/accountType",
+        )
+        .narrowed_enumeration::<AccountType>(
+            "/components/schemas/SCAWallet/allOf/1/properties/accountType",
+        )
+        .narrowed_enumeration::<AccountType>(
+            "/components/schemas/EOAWalletWithBalances/allOf/1/properties/accountType",
+        )
+        .narrowed_enumeration::<AccountType>(
+            "/components/schemas/SCAWalletWithBalances/allOf/1/properties/accountType",
+        );
+
+    let report = suite.finish();
+    report.write_to(concat!(env!("CARGO_TARGET_TMPDIR"), "/conformance"))?;
+    assert!(report.is_clean(), "{report}");
+    Ok(())
+}
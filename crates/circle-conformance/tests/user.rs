@@ -0,0 +1,416 @@
+#![allow(missing_docs)]
+// Conformance of circle-user-controlled-wallets to
+// docs/wallets/user-controlled-wallets.yaml.
+// Run via: just conformance
+
+use chrono::{TimeZone, Utc};
+use circle_conformance::Suite;
+use circle_user_controlled_wallets::{
+    UserWalletsClient,
+    ids::{ChallengeId, TokenId, TransactionId, UserId, WalletId, WalletSetId},
+    models::{
+        auth::{
+            DeviceTokenEmailRequest, DeviceTokenSocialRequest, RefreshUserTokenRequest,
+            ResendOtpRequest,
+        },
+        challenge::{ChallengeStatus, ChallengeType, SetPinAndInitWalletRequest, SetPinRequest},
+        common::{
+            AccountType, Blockchain, CursorParams, CustodyType, FeeLevel, Order, PageParams,
+            ScaCore, TokenStandard, WalletState,
+        },
+        signing::{SignMessageRequest, SignTransactionRequest, SignTypedDataRequest},
+        transaction::{
+            AccelerateTxRequest, CancelTxRequest, CreateContractExecutionTxRequest,
+            CreateTransferTxRequest, CreateWalletUpgradeTxRequest, EstimateContractExecFeeRequest,
+            EstimateTransferFeeRequest, GetLowestNonceTxParams, ListTransactionsParams, Operation,
+            RiskAction, RiskCategory, RiskScore, RiskSignalSource, RiskType, TransactionState,
+            TransactionType, ValidateAddressRequest,
+        },
+        user::{
+            CreateUserRequest, EndUserStatus, GetUserTokenRequest, ListUsersParams, PinStatus,
+            SecurityQuestionStatus,
+        },
+        wallet::{
+            CreateEndUserWalletRequest, ListWalletBalancesParams, ListWalletNftsParams,
+            ListWalletsParams, UpdateWalletRequest, WalletMetadata,
+        },
+    },
+};
+
+const ID: &str = "c4d1da72-111e-4d52-bdbf-2e74a2d803d5";
+const ADDRESS: &str = "0x4b6c0b0078b63f881503e7fd3a9a1061065db242";
+const HASH: &str = "0x4a25cc5e661d8504b59c5f38ba93f010e8518966f00e2ceda7955c4b86a9a1ea";
+const USER_TOKEN: &str = "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9";
+
+fn some(value: &str) -> Option<String> {
+    Some(value.to_string())
+}
+
+/// Every pagination parameter set, so each one's name is checked.
+fn page() -> PageParams {
+    PageParams {
+        from: Utc.with_ymd_and_hms(2023, 1, 1, 12, 4, 5).single(),
+        to: Utc.with_ymd_and_hms(2023, 1, 2, 12, 4, 5).single(),
+        page_before: some(ID),
+        page_after: some(ID),
+        page_size: Some(10),
+    }
+}
+
+fn cursor() -> CursorParams {
+    CursorParams { page_before: some(ID), page_after: some(ID), page_size: Some(10) }
+}
+
+fn metadata() -> Option<Vec<WalletMetadata>> {
+    Some(vec![WalletMetadata { name: some("Hot"), ref_id: some("custom_ref_id") }])
+}
+
+#[tokio::test]
+async fn user_controlled_wallets_conform_to_the_spec() -> Result<(), Box<dyn std::error::Error>> {
+    let mut suite = Suite::wallets("user-controlled-wallets.yaml")?;
+    // Optional in the spec, but always sent by the API.
+    suite
+        .always_present("createUserWallet", "/data")
+        .always_present("createDeviceTokenSocialLogin", "/data")
+        .always_present("createUserTransactionTransferChallenge", "/data")
+        .always_present("createUserTransactionAccelerateChallenge", "/data")
+        .always_present("createUserTransactionCancelChallenge", "/data")
+        .always_present("createUserTransactionContractExecutionChallenge", "/data")
+        .always_present("createUserTransactionWalletUpgradeChallenge", "/data")
+        .always_present("listTransactions", "/data")
+        .always_present("getTransaction", "/data/transaction")
+        .always_present("listUserChallenges", "/data/challenges")
+        .always_present("listWalletBalance", "/data/tokenBalances")
+        .always_present("listWalletNfts", "/data/nfts")
+        .always_present("getTokenId", "/data");
+    // Documented as plain strings, but always decimal amounts.
+    suite.field_example("networkFee", serde_json::json!("0.0021"));
+    let client: UserWalletsClient = suite.client()?;
+    let wallet_set_id: WalletSetId = ID.parse()?;
+    let wallet_id: WalletId = ID.parse()?;
+    let transaction_id: TransactionId = ID.parse()?;
+    let token_id: TokenId = ID.parse()?;
+    let challenge_id: ChallengeId = ID.parse()?;
+    let user_id = UserId::from(ID);
+
+    // ── Users and tokens ──
+    let create_user = CreateUserRequest { user_id: user_id.clone() };
+    suite.operation("createUser", || client.create_user(&create_user)).await;
+    let users = ListUsersParams {
+        pin_status: Some(PinStatus::Enabled),
+        security_question_status: Some(SecurityQuestionStatus::Enabled),
+        order: Some(Order::Asc),
+        page: page(),
+    };
+    suite.operation("listUsers", || client.list_users(&users)).await;
+    suite.operation("getUser", || client.get_user(&user_id)).await;
+    let user_token = GetUserTokenRequest { user_id: user_id.clone() };
+    suite.operation("getUserToken", || client.get_user_token(&user_token)).await;
+    suite.operation("getUserByToken", || client.get_user_by_token(USER_TOKEN)).await;
+    let social = DeviceTokenSocialRequest {
+        idempotency_key: ID.parse()?,
+        device_id: "device-1".to_string(),
+    };
+    suite
+        .operation("createDeviceTokenSocialLogin", || client.get_device_token_social(&social))
+        .await;
+    let email = DeviceTokenEmailRequest {
+        idempotency_key: ID.parse()?,
+        device_id: "device-1".to_string(),
+        email: "user@example.com".to_string(),
+    };
+    suite.operation("createDeviceTokenEmailLogin", || client.get_device_token_email(&email)).await;
+    let refresh = RefreshUserTokenRequest {
+        idempotency_key: ID.parse()?,
+        refresh_token: "refresh".to_string(),
+        device_id: "device-1".to_string(),
+    };
+    suite.operation("refreshUserToken", || client.refresh_user_token(USER_TOKEN, &refresh)).await;
+    let resend = ResendOtpRequest {
+        idempotency_key: ID.parse()?,
+        otp_token: "otp".to_string(),
+        email: "user@example.com".to_string(),
+        device_id: "device-1".to_string(),
+    };
+    suite.operation("resendOTP", || client.resend_otp(USER_TOKEN, &resend)).await;
+
+    // ── PIN and challenges ──
+    let initialize = SetPinAndInitWalletRequest {
+        idempotency_key: ID.parse()?,
+        account_type: Some(AccountType::Sca),
+        blockchains: Some(vec![Blockchain::MaticAmoy]),
+        metadata: metadata(),
+    };
+    suite
+        .operation("createUserWithPinChallenge", || client.initialize_user(USER_TOKEN, &initialize))
+        .await;
+    let pin = SetPinRequest { idempotency_key: ID.parse()? };
+    suite
+        .operation("createUserPinChallenge", || client.create_pin_challenge(USER_TOKEN, &pin))
+        .await;
+    suite
+        .operation("updateUserPinChallenge", || client.update_pin_challenge(USER_TOKEN, &pin))
+        .await;
+    suite
+        .operation("createUserPinRestoreChallenge", || {
+            client.restore_pin_challenge(USER_TOKEN, &pin)
+        })
+        .await;
+    suite.operation("listUserChallenges", || client.list_challenges(USER_TOKEN)).await;
+    suite.operation("getUserChallenge", || client.get_challenge(USER_TOKEN, &challenge_id)).await;
+
+    // ── Wallets ──
+    let create_wallet = CreateEndUserWalletRequest {
+        idempotency_key: ID.parse()?,
+        blockchains: vec![Blockchain::MaticAmoy],
+        account_type: Some(AccountType::Sca),
+        metadata: metadata(),
+    };
+    suite.operation("createUserWallet", || client.create_wallet(USER_TOKEN, &create_wallet)).await;
+    let wallets = ListWalletsParams {
+        address: some(ADDRESS),
+        blockchain: Some(Blockchain::MaticAmoy),
+        sca_core: Some(ScaCore::Circle6900SingleownerV2),
+        wallet_set_id: Some(wallet_set_id),
+        ref_id: some("custom_ref_id"),
+        order: Some(Order::Desc),
+        page: page(),
+    };
+    suite.operation("listWallets", || client.list_wallets(USER_TOKEN, &wallets)).await;
+    suite.operation("getWallet", || client.get_wallet(USER_TOKEN, &wallet_id)).await;
+    let update_wallet = UpdateWalletRequest { name: some("Hot"), ref_id: some("custom_ref_id") };
+    suite
+        .operation("updateWallet", || client.update_wallet(USER_TOKEN, &wallet_id, &update_wallet))
+        .await;
+    let balances = ListWalletBalancesParams {
+        include_all: Some(true),
+        name: some("USDC"),
+        token_address: some(ADDRESS),
+        standard: Some(TokenStandard::Erc20),
+        page: cursor(),
+    };
+    suite
+        .operation("listWalletBalance", || {
+            client.list_wallet_balances(USER_TOKEN, &wallet_id, &balances)
+        })
+        .await;
+    let nfts = ListWalletNftsParams {
+        include_all: Some(true),
+        name: some("Punk"),
+        token_address: some(ADDRESS),
+        standard: Some(TokenStandard::Erc721),
+        page: cursor(),
+    };
+    suite
+        .operation("listWalletNfts", || client.list_wallet_nfts(USER_TOKEN, &wallet_id, &nfts))
+        .await;
+
+    // ── Transactions ──
+    let transfer = CreateTransferTxRequest {
+        idempotency_key: ID.parse()?,
+        wallet_id,
+        destination_address: ADDRESS.to_string(),
+        amounts: Some(vec!["1.5".parse()?]),
+        fee_level: Some(FeeLevel::Medium),
+        gas_limit: some("21000"),
+        gas_price: some("5"),
+        max_fee: some("5"),
+        priority_fee: some("1"),
+        nft_token_ids: Some(vec!["1".to_string()]),
+        ref_id: some("custom_ref_id"),
+        token_id: Some(token_id),
+        token_address: some(ADDRESS),
+        blockchain: Some(Blockchain::MaticAmoy),
+    };
+    suite
+        .operation("createUserTransactionTransferChallenge", || {
+            client.create_transfer_transaction(USER_TOKEN, &transfer)
+        })
+        .await;
+    let accelerate = AccelerateTxRequest { idempotency_key: ID.parse()? };
+    suite
+        .operation("createUserTransactionAccelerateChallenge", || {
+            client.accelerate_transaction(USER_TOKEN, &transaction_id, &accelerate)
+        })
+        .await;
+    let cancel = CancelTxRequest { idempotency_key: ID.parse()? };
+    suite
+        .operation("createUserTransactionCancelChallenge", || {
+            client.cancel_transaction(USER_TOKEN, &transaction_id, &cancel)
+        })
+        .await;
+    let contract_execution = CreateContractExecutionTxRequest {
+        idempotency_key: ID.parse()?,
+        wallet_id,
+        contract_address: ADDRESS.to_string(),
+        abi_function_signature: some("burn(uint256)"),
+        abi_parameters: Some(vec![serde_json::json!("100")]),
+        call_data: some("0x42966c68"),
+        amount: Some("0.1".parse()?),
+        fee_level: Some(FeeLevel::Medium),
+        gas_limit: some("21000"),
+        gas_price: some("5"),
+        max_fee: some("5"),
+        priority_fee: some("1"),
+        ref_id: some("custom_ref_id"),
+    };
+    suite
+        .operation("createUserTransactionContractExecutionChallenge", || {
+            client.create_contract_execution_transaction(USER_TOKEN, &contract_execution)
+        })
+        .await;
+    let wallet_upgrade = CreateWalletUpgradeTxRequest {
+        idempotency_key: ID.parse()?,
+        wallet_id,
+        new_sca_core: "circle_6900_singleowner_v2".to_string(),
+        fee_level: Some(FeeLevel::Medium),
+        gas_limit: some("21000"),
+        gas_price: some("5"),
+        max_fee: some("5"),
+        priority_fee: some("1"),
+        ref_id: some("custom_ref_id"),
+    };
+    suite
+        .operation("createUserTransactionWalletUpgradeChallenge", || {
+            client.create_wallet_upgrade_transaction(USER_TOKEN, &wallet_upgrade)
+        })
+        .await;
+    let transactions = ListTransactionsParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        destination_address: some(ADDRESS),
+        include_all: Some(true),
+        operation: Some(Operation::Transfer),
+        state: Some(TransactionState::Complete),
+        tx_hash: some(HASH),
+        tx_type: Some(TransactionType::Outbound),
+        user_id: Some(user_id.clone()),
+        wallet_ids: some(ID),
+        from: Utc.with_ymd_and_hms(2023, 1, 1, 12, 4, 5).single(),
+        to: Utc.with_ymd_and_hms(2023, 1, 2, 12, 4, 5).single(),
+        order: Some(Order::Asc),
+        page: PageParams { from: None, to: None, ..page() },
+    };
+    suite
+        .operation("listTransactions", || client.list_transactions(USER_TOKEN, &transactions))
+        .await;
+    suite.operation("getTransaction", || client.get_transaction(USER_TOKEN, &transaction_id)).await;
+    let lowest_nonce = GetLowestNonceTxParams {
+        blockchain: Some(Blockchain::MaticAmoy),
+        address: some(ADDRESS),
+        wallet_id: Some(wallet_id),
+    };
+    suite
+        .operation("getLowestNonceTransaction", || {
+            client.get_lowest_nonce_transaction(&lowest_nonce)
+        })
+        .await;
+
+    // ── Fees, tokens and utilities ──
+    let transfer_fee = EstimateTransferFeeRequest {
+        amounts: vec!["1.5".parse()?],
+        destination_address: ADDRESS.to_string(),
+        nft_token_ids: Some(vec!["1".to_string()]),
+        source_address: some(ADDRESS),
+        token_id: Some(token_id),
+        token_address: some(ADDRESS),
+        blockchain: Some(Blockchain::MaticAmoy),
+        wallet_id: Some(wallet_id),
+    };
+    suite
+        .operation("createTransferEstimateFee", || {
+            client.estimate_transfer_fee(USER_TOKEN, &transfer_fee)
+        })
+        .await;
+    let contract_fee = EstimateContractExecFeeRequest {
+        contract_address: ADDRESS.to_string(),
+        abi_function_signature: some("burn(uint256)"),
+        abi_parameters: Some(vec![serde_json::json!("100")]),
+        call_data: some("0x42966c68"),
+        amount: Some("0.1".parse()?),
+        blockchain: Some(Blockchain::MaticAmoy),
+        source_address: some(ADDRESS),
+        wallet_id: Some(wallet_id),
+    };
+    suite
+        .operation("createTransactionEstimateFee", || {
+            client.estimate_contract_execution_fee(USER_TOKEN, &contract_fee)
+        })
+        .await;
+    let validate =
+        ValidateAddressRequest { address: ADDRESS.to_string(), blockchain: Blockchain::MaticAmoy };
+    suite.operation("createValidateAddress", || client.validate_address(&validate)).await;
+    suite.operation("getTokenId", || client.get_token(&token_id)).await;
+
+    // ── Signing ──
+    let sign_message = SignMessageRequest {
+        message: "I agree".to_string(),
+        wallet_id,
+        encoded_by_hex: Some(false),
+        memo: some("terms"),
+    };
+    suite.operation("signUserMessage", || client.sign_message(USER_TOKEN, &sign_message)).await;
+    let sign_typed_data = SignTypedDataRequest {
+        data: r#"{"types":{},"primaryType":"Mail","domain":{},"message":{}}"#.to_string(),
+        wallet_id,
+        memo: some("permit"),
+    };
+    suite
+        .operation("signUserTypedData", || client.sign_typed_data(USER_TOKEN, &sign_typed_data))
+        .await;
+    let sign_transaction = SignTransactionRequest {
+        wallet_id,
+        raw_transaction: some("0x02f8"),
+        transaction: some(r#"{"nonce":1}"#),
+        memo: some("payout"),
+    };
+    suite
+        .operation("signUserTransaction", || client.sign_transaction(USER_TOKEN, &sign_transaction))
+        .await;
+
+    suite
+        .enumeration::<PinStatus>("PinStatus")
+        .enumeration::<PinStatus>("/components/parameters/PinStatus/schema")
+        .enumeration::<SecurityQuestionStatus>(
+            "/components/parameters/SecurityQuestionStatus/schema",
+        )
+        .enumeration::<SecurityQuestionStatus>(
+            "/components/schemas/EndUser/properties/securityQuestionStatus",
+        )
+        .enumeration::<Order>("/components/parameters/Order/schema")
+        .enumeration::<AccountType>("AccountType")
+        .enumeration::<Blockchain>("Blockchain")
+        .enumeration::<EndUserStatus>("EndUserStatus")
+        .enumeration::<ChallengeStatus>("/components/schemas/Challenge/properties/status")
+        .enumeration::<ChallengeType>("/components/schemas/Challenge/properties/type")
+        .enumeration::<TokenStandard>("TokenStandard")
+        .enumeration::<CustodyType>("CustodyType")
+        .enumeration::<WalletState>("WalletState")
+        .enumeration::<ScaCore>("ScaCore")
+        .enumeration::<FeeLevel>("FeeLevel")
+        .enumeration::<TransactionState>("TransactionState")
+        .enumeration::<TransactionType>("TransactionType")
+        .enumeration::<Operation>("Operation")
+        .enumeration::<RiskAction>("RiskAction")
+        .enumeration::<RiskScore>("RiskScore")
+        .enumeration::<RiskCategory>("RiskCategory")
+        .enumeration::<RiskType>("RiskType")
+        .enumeration::<RiskSignalSource>("/components/schemas/RiskSignal/properties/source");
+    // Subsets of a wider Rust enum: every spec value must parse, not vice versa.
+    suite
+        .narrowed_enumeration::<ChallengeStatus>("/components/parameters/Status/schema")
+        .narrowed_enumeration::<Operation>("/components/parameters/EndUserOperation/schema")
+        .narrowed_enumeration::<Blockchain>("TokenBlockchain")
+        .narrowed_enumeration::<Blockchain>("ContractExecutionBlockchain")
+        .narrowed_enumeration::<ScaCore>("NewScaCore")
+        .narrowed_enumeration::<AccountType>(
+            "/components/schemas/EOAWallet/allOf/1/properties/accountType",
+        )
+        .narrowed_enumeration::<AccountType>(
+            "/components/schemas/SCAWallet/allOf/1/properties/accountType",
+        );
+
+    let report = suite.finish();
+    report.write_to(concat!(env!("CARGO_TARGET_TMPDIR"), "/conformance"))?;
+    assert!(report.is_clean(), "{report}");
+    Ok(())
+}
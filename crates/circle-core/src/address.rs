@@ -0,0 +1,348 @@
+//! Offline, chain-aware address parsing and normalization.
+//!
+//! [`Address`] checks an address against the format of its chain locally,
+//! without a round trip to Circle's `validateAddress` endpoint, and keeps it
+//! in a canonical form so that equal addresses compare equal:
+//!
+//! | Chains | Accepted | Canonical form |
+//! |--------|----------|----------------|
+//! | EVM | `0x` + 40 hex digits; mixed case must be a valid EIP-55 checksum | EIP-55 checksummed |
+//! | Solana | base58 of a 32-byte ed25519 public key | unchanged |
+//! | Aptos | `0x` + up to 64 hex digits (32 bytes) | lowercase, zero-padded to 64 digits |
+//! | NEAR | implicit (64 lowercase hex digits) or named (`alice.near`) accounts | unchanged |
+//!
+//! Chains without offline rules, such as the non-wallet chains of the
+//! compliance API, are rejected by [`Address::parse`] and let through by
+//! [`validate`].
+
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+use sha3::{Digest, Keccak256};
+
+use crate::network::Network;
+
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// An address that does not match the format of its chain.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AddressError {
+    /// The address is malformed.
+    #[error("invalid {network} address {address:?}: {reason}")]
+    Invalid {
+        /// The chain it was checked against.
+        network: Network,
+        /// The rejected input.
+        address: String,
+        /// What is wrong with it.
+        reason: &'static str,
+    },
+    /// The chain has no offline address rules.
+    #[error("{0} addresses cannot be checked offline")]
+    Unsupported(Network),
+}
+
+impl From<AddressError> for crate::Error {
+    fn from(error: AddressError) -> Self {
+        Self::InvalidParam(error.to_string())
+    }
+}
+
+/// A syntactically valid address on a [`Network`], in canonical form.
+///
+/// Equality and hashing use the canonical form, so an EVM address equals
+/// its lowercase or checksummed spelling on the same chain, while the same
+/// string on two chains gives two different addresses.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address {
+    network: Network,
+    value: String,
+}
+
+impl Address {
+    /// Parses `address` as an address on `network`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address does not match the
+    /// chain's format and [`AddressError::Unsupported`] for chains without
+    /// offline rules.
+    pub fn parse(network: Network, address: &str) -> Result<Self, AddressError> {
+        let invalid =
+            |reason| AddressError::Invalid { network, address: address.to_string(), reason };
+        let value = match Format::of(network).ok_or(AddressError::Unsupported(network))? {
+            Format::Evm => parse_evm(address).map_err(invalid)?,
+            Format::Solana => parse_solana(address).map_err(invalid)?,
+            Format::Aptos => parse_aptos(address).map_err(invalid)?,
+            Format::Near => parse_near(address).map_err(invalid)?,
+        };
+        Ok(Self { network, value })
+    }
+
+    /// The chain of the address.
+    pub const fn network(&self) -> Network {
+        self.network
+    }
+
+    /// The canonical form of the address.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Consumes the address, returning its canonical form.
+    pub fn into_string(self) -> String {
+        self.value
+    }
+
+    /// Returns `true` if `other` spells this address, e.g. in another case
+    /// on EVM chains.
+    pub fn matches(&self, other: &str) -> bool {
+        Self::parse(self.network, other).is_ok_and(|other| other == *self)
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl AsRef<str> for Address {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.value
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.value)
+    }
+}
+
+/// Checks `address` against the format of `network` before it is sent.
+///
+/// Unlike [`Address::parse`], chains without offline rules pass unchecked,
+/// leaving the decision to the API.
+///
+/// # Errors
+///
+/// Returns [`AddressError::Invalid`] if the address is malformed.
+pub fn validate(network: Network, address: &str) -> Result<(), AddressError> {
+    match Address::parse(network, address) {
+        Ok(_) | Err(AddressError::Unsupported(_)) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+/// The EIP-55 checksummed spelling of 40 hex digits, with `0x` prefix.
+fn eip55(hex: &str) -> String {
+    let lower = hex.to_ascii_lowercase();
+    let hash = Keccak256::digest(lower.as_bytes());
+    let mut checksummed = String::with_capacity(42);
+    checksummed.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0xf };
+        checksummed.push(if nibble >= 8 { c.to_ascii_uppercase() } else { c });
+    }
+    checksummed
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Evm,
+    Solana,
+    Aptos,
+    Near,
+}
+
+impl Format {
+    const fn of(network: Network) -> Option<Self> {
+        match network {
+            Network::Sol | Network::SolDevnet => Some(Self::Solana),
+            Network::Aptos | Network::AptosTestnet => Some(Self::Aptos),
+            Network::Near | Network::NearTestnet => Some(Self::Near),
+            other if other.is_evm() => Some(Self::Evm),
+            _ => None,
+        }
+    }
+}
+
+fn is_hex(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn parse_evm(address: &str) -> Result<String, &'static str> {
+    let hex = address.strip_prefix("0x").ok_or("missing 0x prefix")?;
+    if hex.len() != 40 || !is_hex(hex) {
+        return Err("expected 40 hex digits");
+    }
+    let checksummed = eip55(hex);
+    let mixed_case =
+        hex.bytes().any(|b| b.is_ascii_lowercase()) && hex.bytes().any(|b| b.is_ascii_uppercase());
+    if mixed_case && checksummed[2..] != *hex {
+        return Err("EIP-55 checksum mismatch");
+    }
+    Ok(checksummed)
+}
+
+fn parse_solana(address: &str) -> Result<String, &'static str> {
+    let bytes = base58_decode(address).ok_or("not base58")?;
+    if bytes.len() != 32 {
+        return Err("expected a 32-byte public key");
+    }
+    Ok(address.to_string())
+}
+
+fn parse_aptos(address: &str) -> Result<String, &'static str> {
+    let hex = address.strip_prefix("0x").ok_or("missing 0x prefix")?;
+    if hex.is_empty() || hex.len() > 64 || !is_hex(hex) {
+        return Err("expected up to 64 hex digits");
+    }
+    Ok(format!("0x{:0>64}", hex.to_ascii_lowercase()))
+}
+
+fn parse_near(address: &str) -> Result<String, &'static str> {
+    let implicit = address.len() == 64 &&
+        address.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    if implicit {
+        return Ok(address.to_string());
+    }
+    if !(2..=64).contains(&address.len()) {
+        return Err("account ids are 2 to 64 characters long");
+    }
+    // Named accounts: `.`-separated parts of lowercase alphanumerics, joined
+    // by single `-` or `_` separators.
+    let valid = address.split('.').all(|part| {
+        part.split(['-', '_']).all(|word| {
+            !word.is_empty() && word.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+    });
+    if valid { Ok(address.to_string()) } else { Err("not a NEAR account id") }
+}
+
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    if s.is_empty() {
+        return None;
+    }
+    // Little-endian base-256 digits of the value.
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len());
+    for c in s.bytes() {
+        let mut carry = BASE58.iter().position(|&d| d == c)?;
+        for byte in &mut bytes {
+            carry += usize::from(*byte) * 58;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = s.bytes().take_while(|&b| b == b'1').count();
+    Some(std::iter::repeat_n(0, zeros).chain(bytes.into_iter().rev()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evm_addresses_are_checksummed() -> Result<(), Box<dyn std::error::Error>> {
+        // Test vectors from EIP-55.
+        for checksummed in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let lower = checksummed.to_ascii_lowercase();
+            assert_eq!(Address::parse(Network::Eth, &lower)?.as_str(), checksummed);
+            assert_eq!(Address::parse(Network::Eth, checksummed)?.as_str(), checksummed);
+        }
+        let bad = Address::parse(Network::Eth, "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert!(matches!(
+            bad,
+            Err(AddressError::Invalid { reason: "EIP-55 checksum mismatch", .. })
+        ));
+        assert!(Address::parse(Network::Base, "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+        assert!(Address::parse(Network::Base, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA").is_err());
+        assert!(
+            Address::parse(Network::Base, "0xzaAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn evm_equality_ignores_case_but_not_chain() -> Result<(), Box<dyn std::error::Error>> {
+        let address = Address::parse(Network::Eth, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")?;
+        let upper = Address::parse(Network::Eth, "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED")?;
+        assert_eq!(address, upper);
+        assert!(address.matches("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(!address.matches("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+        let on_base = Address::parse(Network::Base, address.as_str())?;
+        assert_ne!(address, on_base);
+        Ok(())
+    }
+
+    #[test]
+    fn solana_addresses_are_32_byte_base58_keys() -> Result<(), Box<dyn std::error::Error>> {
+        for address in
+            ["11111111111111111111111111111111", "So11111111111111111111111111111111111111112"]
+        {
+            assert_eq!(Address::parse(Network::Sol, address)?.as_str(), address);
+        }
+        // 31 bytes, `0` outside the alphabet, and an EVM address.
+        assert!(Address::parse(Network::SolDevnet, "1111111111111111111111111111111").is_err());
+        assert!(
+            Address::parse(Network::SolDevnet, "0o11111111111111111111111111111111111111112")
+                .is_err()
+        );
+        assert!(
+            Address::parse(Network::Sol, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err()
+        );
+        // Case matters in base58.
+        let address = Address::parse(Network::Sol, "So11111111111111111111111111111111111111112")?;
+        assert!(!address.matches("sO11111111111111111111111111111111111111112"));
+        Ok(())
+    }
+
+    #[test]
+    fn aptos_addresses_are_padded_to_32_bytes() -> Result<(), Box<dyn std::error::Error>> {
+        let short = Address::parse(Network::Aptos, "0x1")?;
+        assert_eq!(short.as_str(), format!("0x{:0>64}", 1));
+        let long = format!("0x{}", "Ab".repeat(32));
+        assert_eq!(Address::parse(Network::AptosTestnet, &long)?.as_str(), long.to_lowercase());
+        assert!(Address::parse(Network::Aptos, &format!("0x{}", "a".repeat(65))).is_err());
+        assert!(Address::parse(Network::Aptos, "0x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn near_accepts_implicit_and_named_accounts() -> Result<(), Box<dyn std::error::Error>> {
+        let implicit = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+        for address in [implicit, "alice.near", "app_1.alice-bob.testnet", "aa"] {
+            assert_eq!(Address::parse(Network::Near, address)?.as_str(), address);
+        }
+        for address in ["a", "Alice.near", "alice..near", "-alice.near", "alice__bob.near", "a b"] {
+            assert!(Address::parse(Network::NearTestnet, address).is_err(), "{address}");
+        }
+        assert!(Address::parse(Network::Near, &"a".repeat(65)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn chains_without_rules_are_only_skipped_by_validate() {
+        assert_eq!(
+            Address::parse(Network::Btc, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"),
+            Err(AddressError::Unsupported(Network::Btc))
+        );
+        assert_eq!(validate(Network::Btc, "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"), Ok(()));
+        assert!(validate(Network::Eth, "0xdest").is_err());
+    }
+}
@@ -0,0 +1,345 @@
+//! Decimal token amounts.
+//!
+//! Circle sends and expects amounts as decimal strings in human units, e.g.
+//! `"12.25"` USDC. [`TokenAmount`] holds such a value exactly, with no
+//! precision limit, and serializes back to the same format. It converts to
+//! and from the integer base units used on chain given a token's decimals,
+//! and supports comparison, summing and checked subtraction.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+pub use num_bigint::BigUint;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Why a [`TokenAmount`] could not be built or converted.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AmountError {
+    /// The string is not a non-negative decimal number such as `"1.5"`.
+    #[error("invalid token amount: {0:?}")]
+    Invalid(String),
+    /// The amount has more fractional digits than the token supports.
+    #[error("amount {amount} has more than {decimals} decimal places")]
+    TooPrecise {
+        /// The amount being converted.
+        amount: TokenAmount,
+        /// The token's number of decimals.
+        decimals: u32,
+    },
+    /// The token does not report a usable number of decimals.
+    #[error("token decimals are unknown")]
+    UnknownDecimals,
+}
+
+impl From<AmountError> for crate::Error {
+    fn from(error: AmountError) -> Self {
+        Self::InvalidParam(error.to_string())
+    }
+}
+
+/// A non-negative decimal amount of a token, in human units.
+///
+/// The value is `units / 10^scale`, kept normalized without trailing
+/// fractional zeros, so `"1.50"` and `"1.5"` are equal and both display as
+/// `1.5`. [`Display`](fmt::Display) honours a precision (`{:.2}`), padding
+/// with zeros or truncating toward zero.
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct TokenAmount {
+    units: BigUint,
+    scale: u32,
+}
+
+impl TokenAmount {
+    /// The amount `units / 10^scale`.
+    pub fn new(units: impl Into<BigUint>, scale: u32) -> Self {
+        let mut amount = Self { units: units.into(), scale };
+        amount.normalize();
+        amount
+    }
+
+    /// Zero.
+    pub const fn zero() -> Self {
+        Self { units: BigUint::ZERO, scale: 0 }
+    }
+
+    /// Whether the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.units == BigUint::ZERO
+    }
+
+    /// Number of fractional digits of the normalized value.
+    pub const fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// The human amount of `units` base units of a token with `decimals`
+    /// decimals, e.g. `1_500_000` with 6 decimals is `1.5`.
+    pub fn from_base_units(units: impl Into<BigUint>, decimals: u32) -> Self {
+        Self::new(units, decimals)
+    }
+
+    /// This amount in base units of a token with `decimals` decimals.
+    ///
+    /// Fails with [`AmountError::TooPrecise`] rather than rounding if the
+    /// amount cannot be represented with that many decimals.
+    pub fn to_base_units(&self, decimals: u32) -> Result<BigUint, AmountError> {
+        if self.scale > decimals {
+            return Err(AmountError::TooPrecise { amount: self.clone(), decimals });
+        }
+        Ok(&self.units * pow10(decimals - self.scale))
+    }
+
+    /// The amount truncated toward zero to at most `decimals` fractional
+    /// digits.
+    pub fn round_down(&self, decimals: u32) -> Self {
+        if self.scale <= decimals {
+            return self.clone();
+        }
+        Self::new(&self.units / pow10(self.scale - decimals), decimals)
+    }
+
+    /// `self + other`. Addition cannot overflow; this exists for symmetry
+    /// with [`checked_sub`](Self::checked_sub).
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    /// `self - other`, or `None` if `other` is larger.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = align(self, other);
+        (left >= right).then(|| Self::new(left - right, scale))
+    }
+
+    /// `self * other`, exact.
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(&self.units * &other.units, self.scale + other.scale))
+    }
+
+    fn normalize(&mut self) {
+        if self.units == BigUint::ZERO {
+            self.scale = 0;
+            return;
+        }
+        let ten = BigUint::from(10u32);
+        while self.scale > 0 && (&self.units % &ten) == BigUint::ZERO {
+            self.units /= &ten;
+            self.scale -= 1;
+        }
+    }
+
+    /// The integer and fractional digits of the value truncated to `scale`
+    /// fractional digits, the latter zero-padded to exactly `scale`.
+    fn digits(&self, scale: u32) -> (String, String) {
+        let truncated = self.round_down(scale);
+        let units = truncated.units.to_str_radix(10);
+        let fraction_len = truncated.scale as usize;
+        let (integer, fraction) = if units.len() > fraction_len {
+            let (integer, fraction) = units.split_at(units.len() - fraction_len);
+            (integer.to_string(), fraction.to_string())
+        } else {
+            ("0".to_string(), format!("{units:0>fraction_len$}"))
+        };
+        (integer, format!("{fraction:0<width$}", width = scale as usize))
+    }
+}
+
+/// A token's `decimals` as reported by Circle, or
+/// [`AmountError::UnknownDecimals`] if it is missing or negative.
+pub fn decimals(reported: Option<i32>) -> Result<u32, AmountError> {
+    reported.and_then(|decimals| u32::try_from(decimals).ok()).ok_or(AmountError::UnknownDecimals)
+}
+
+fn pow10(exponent: u32) -> BigUint {
+    BigUint::from(10u32).pow(exponent)
+}
+
+/// Both amounts' units at their common scale, and that scale.
+fn align(left: &TokenAmount, right: &TokenAmount) -> (BigUint, BigUint, u32) {
+    let scale = left.scale.max(right.scale);
+    (&left.units * pow10(scale - left.scale), &right.units * pow10(scale - right.scale), scale)
+}
+
+impl FromStr for TokenAmount {
+    type Err = AmountError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || AmountError::Invalid(value.to_string());
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+            return Err(invalid());
+        }
+        if value.ends_with('.') {
+            return Err(invalid());
+        }
+        let scale = u32::try_from(fraction.len()).map_err(|_| invalid())?;
+        let units = BigUint::parse_bytes(format!("{integer}{fraction}").as_bytes(), 10)
+            .ok_or_else(invalid)?;
+        Ok(Self::new(units, scale))
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = f.precision().map_or(self.scale, |precision| precision as u32);
+        let (integer, fraction) = self.digits(scale);
+        let text = if fraction.is_empty() { integer } else { format!("{integer}.{fraction}") };
+        f.pad_integral(true, "", &text)
+    }
+}
+
+impl fmt::Debug for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TokenAmount({self})")
+    }
+}
+
+impl PartialOrd for TokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TokenAmount {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (left, right, _) = align(self, other);
+        left.cmp(&right)
+    }
+}
+
+impl Add<&TokenAmount> for &TokenAmount {
+    type Output = TokenAmount;
+
+    fn add(self, other: &TokenAmount) -> TokenAmount {
+        let (left, right, scale) = align(self, other);
+        TokenAmount::new(left + right, scale)
+    }
+}
+
+impl Add for TokenAmount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        &self + &other
+    }
+}
+
+impl AddAssign<&Self> for TokenAmount {
+    fn add_assign(&mut self, other: &Self) {
+        *self = &*self + other;
+    }
+}
+
+impl AddAssign for TokenAmount {
+    fn add_assign(&mut self, other: Self) {
+        *self += &other;
+    }
+}
+
+impl Sum for TokenAmount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |total, amount| total + amount)
+    }
+}
+
+impl<'a> Sum<&'a Self> for TokenAmount {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |total, amount| &total + amount)
+    }
+}
+
+impl From<u64> for TokenAmount {
+    fn from(value: u64) -> Self {
+        Self::new(value, 0)
+    }
+}
+
+impl From<BigUint> for TokenAmount {
+    fn from(value: BigUint) -> Self {
+        Self::new(value, 0)
+    }
+}
+
+impl Serialize for TokenAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(value: &str) -> Result<TokenAmount, AmountError> {
+        value.parse()
+    }
+
+    #[test]
+    fn parses_and_formats_circle_strings() -> Result<(), Box<dyn std::error::Error>> {
+        assert_eq!(amount("12.25")?.to_string(), "12.25");
+        assert_eq!(amount("1.50")?, amount("1.5")?);
+        assert_eq!(amount("0.000001")?.to_string(), "0.000001");
+        assert_eq!(amount("100")?.to_string(), "100");
+        assert_eq!(amount("0.0")?, TokenAmount::zero());
+        assert_eq!(format!("{:.2}", amount("7.5")?), "7.50");
+        assert_eq!(format!("{:.1}", amount("7.59")?), "7.5");
+        assert_eq!(format!("{:>6}", amount("7.5")?), "   7.5");
+        for bad in ["", "-1", "1.", ".5", "1e6", "1.2.3", " 1"] {
+            assert_eq!(amount(bad), Err(AmountError::Invalid(bad.to_string())));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn serializes_as_a_string() -> Result<(), Box<dyn std::error::Error>> {
+        let value: TokenAmount = serde_json::from_str("\"0.10\"")?;
+        assert_eq!(serde_json::to_string(&value)?, "\"0.1\"");
+        assert!(serde_json::from_str::<TokenAmount>("\"ten\"").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn converts_between_human_and_base_units() -> Result<(), Box<dyn std::error::Error>> {
+        let usdc = amount("1.5")?;
+        assert_eq!(usdc.to_base_units(6)?, BigUint::from(1_500_000u32));
+        assert_eq!(TokenAmount::from_base_units(1_500_000u32, 6), usdc);
+
+        let wei = "123456789012345678901234567890".parse::<BigUint>()?;
+        let eth = TokenAmount::from_base_units(wei.clone(), 18);
+        assert_eq!(eth.to_string(), "123456789012.34567890123456789");
+        assert_eq!(eth.to_base_units(18)?, wei);
+
+        assert_eq!(decimals(Some(6)), Ok(6));
+        assert_eq!(decimals(Some(-1)), Err(AmountError::UnknownDecimals));
+        assert_eq!(
+            amount("0.0000001")?.to_base_units(6),
+            Err(AmountError::TooPrecise { amount: amount("0.0000001")?, decimals: 6 })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn arithmetic_is_exact_and_checked() -> Result<(), Box<dyn std::error::Error>> {
+        let balances = [amount("0.1")?, amount("0.2")?, amount("10")?];
+        let total: TokenAmount = balances.iter().sum();
+        assert_eq!(total, amount("10.3")?);
+        assert_eq!(total.checked_sub(&amount("0.3")?), Some(amount("10")?));
+        assert_eq!(amount("1")?.checked_sub(&amount("1.000001")?), None);
+        assert_eq!(amount("2.5")?.checked_mul(&amount("0.4")?), Some(amount("1")?));
+        assert!(amount("9.99")? < amount("10")?);
+        assert_eq!(amount("1.239")?.round_down(2), amount("1.23")?);
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Forward-compatible API enums.
+//!
+//! Circle adds chains, transaction states and risk categories without
+//! versioning the API. Enums declared with [`api_enum!`](macro@crate::api_enum)
+//! keep a value they do not recognise in an `Unknown(String)` variant, which
+//! serializes back to the original string, instead of failing the whole
+//! response.
+//!
+//! Every crate that declares such enums has a `strict` cargo feature. With it
+//! enabled, unrecognised values are deserialization errors again. The
+//! `Unknown` variant exists either way, so code matching on it compiles
+//! whether or not some crate in the build turns `strict` on.
+
+use std::sync::OnceLock;
+
+use serde::Serialize;
+
+/// An enum of string values sent or returned by a Circle API.
+pub trait ApiEnum: Sized + 'static {
+    /// The variants this version of the SDK knows, in declaration order.
+    const KNOWN: &'static [Self];
+
+    /// The raw value, if it is not one of the [`KNOWN`](Self::KNOWN) variants.
+    fn unknown_value(&self) -> Option<&str>;
+
+    /// Whether this is one of the [`KNOWN`](Self::KNOWN) variants.
+    fn is_known(&self) -> bool {
+        self.unknown_value().is_none()
+    }
+}
+
+/// Declares a string enum of a Circle API that tolerates values added after
+/// this SDK was released.
+///
+/// The enum is emitted as written — derives, serde attributes and docs
+/// included — plus a trailing `#[serde(untagged)] Unknown(String)` variant.
+/// The macro implements `Deserialize` itself, matching the wire values the
+/// derived `Serialize` gives the known variants; anything else becomes
+/// `Unknown`, or an error with the invoking crate's `strict` feature. It also
+/// implements [`ApiEnum`](crate::ApiEnum). Only unit variants are supported,
+/// and the derives must include `Clone` and `Serialize` but not
+/// `Deserialize`.
+#[macro_export]
+macro_rules! api_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $( $(#[$variant_meta])* $variant, )+
+            /// A value not known to this version of the SDK, kept verbatim.
+            #[serde(untagged)]
+            Unknown(::std::string::String),
+        }
+
+        impl $crate::ApiEnum for $name {
+            const KNOWN: &'static [Self] = &[$( Self::$variant ),+];
+
+            fn unknown_value(&self) -> ::std::option::Option<&str> {
+                match self {
+                    Self::Unknown(value) => ::std::option::Option::Some(value),
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+
+        impl<'de> $crate::api_enum::__serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::api_enum::__serde::Deserializer<'de>,
+            {
+                static WIRE: $crate::api_enum::WireValues = $crate::api_enum::WireValues::new();
+                let raw = <::std::string::String as $crate::api_enum::__serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+                WIRE.parse(raw, cfg!(feature = "strict"), Self::Unknown)
+                    .map_err(<D::Error as $crate::api_enum::__serde::de::Error>::custom)
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub use serde as __serde;
+
+/// The wire values of the known variants of an [`ApiEnum`], computed on
+/// first use. Used by the `Deserialize` impl of [`api_enum!`](macro@crate::api_enum).
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct WireValues(OnceLock<Vec<Option<String>>>);
+
+impl WireValues {
+    /// An empty cache, for a `static`.
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// The known variant of `T` serialized as `raw`, else `unknown(raw)`,
+    /// or an error if `strict`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message naming `raw` if it is not a known value and
+    /// `strict` is set.
+    pub fn parse<T>(&self, raw: String, strict: bool, unknown: fn(String) -> T) -> Result<T, String>
+    where
+        T: ApiEnum + Clone + Serialize,
+    {
+        let wire = self.0.get_or_init(|| {
+            T::KNOWN
+                .iter()
+                .map(|known| match serde_json::to_value(known) {
+                    Ok(serde_json::Value::String(value)) => Some(value),
+                    _ => None,
+                })
+                .collect()
+        });
+        match wire.iter().position(|value| value.as_deref() == Some(raw.as_str())) {
+            Some(index) => Ok(T::KNOWN[index].clone()),
+            None if strict => Err(format!(
+                "unknown variant `{raw}` of {}",
+                std::any::type_name::<T>().rsplit("::").next().unwrap_or_default()
+            )),
+            None => Ok(unknown(raw)),
+        }
+    }
+}
+
+impl Default for WireValues {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,355 @@
+//! Builder for configuring the HTTP client behind a Circle API client.
+//!
+//! Every API client in the workspace exposes a `builder(api_key)` constructor
+//! returning a [`ClientBuilder`] for itself:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use circle_core::{ClientBuilder, RestClient};
+//!
+//! # fn main() -> Result<(), circle_core::Error> {
+//! let client: RestClient = ClientBuilder::new("your_api_key")
+//!     .timeout(Duration::from_secs(10))
+//!     .user_agent_suffix("my-app/1.0")
+//!     .build()?;
+//! # let _ = client;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Several clients can share one connection pool by passing the same
+//! pre-configured `hpx::Client` to [`ClientBuilder::http_client`], and run on
+//! a different HTTP stack entirely via [`ClientBuilder::transport`].
+
+use std::{marker::PhantomData, sync::Arc, time::Duration};
+
+use hpx::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    client::{DEFAULT_BASE_URL, RestClient},
+    error::Error,
+    middleware::Middleware,
+    retry::RetryPolicy,
+    transport::{HpxTransport, HttpTransport},
+};
+
+/// Default timeout applied to every request (connect + response).
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `User-Agent` sent by every client, optionally followed by a caller suffix.
+pub const DEFAULT_USER_AGENT: &str = concat!("circle-sdk-rs/", env!("CARGO_PKG_VERSION"));
+
+/// A root certificate supplied by the caller, parsed when the client is built.
+#[derive(Debug, Clone)]
+enum RootCertificate {
+    Pem(Vec<u8>),
+    Der(Vec<u8>),
+}
+
+/// Configures and builds a Circle API client of type `C`.
+///
+/// `C` is [`RestClient`] by default; the API crates use
+/// `ClientBuilder<TheirClient>` so that [`ClientBuilder::build`] returns the
+/// typed client directly.
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct ClientBuilder<C = RestClient> {
+    api_key: String,
+    base_url: String,
+    timeout: Duration,
+    connect_timeout: Option<Duration>,
+    user_agent_suffix: Option<String>,
+    proxy: Option<String>,
+    root_certificates: Vec<RootCertificate>,
+    default_headers: Vec<(String, String)>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    retry_policy: RetryPolicy,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    _client: PhantomData<fn() -> C>,
+}
+
+impl<C> std::fmt::Debug for ClientBuilder<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"<redacted>")
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("user_agent_suffix", &self.user_agent_suffix)
+            .field("proxy", &self.proxy.as_ref().map(|_| "<redacted>"))
+            .field("root_certificates", &self.root_certificates.len())
+            .field(
+                "default_headers",
+                &self.default_headers.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .field("custom_transport", &self.transport.is_some())
+            .field("retry_policy", &self.retry_policy)
+            .field("middlewares", &self.middlewares.len())
+            .finish()
+    }
+}
+
+impl<C> ClientBuilder<C>
+where
+    C: From<RestClient>,
+{
+    /// Starts a builder for the given API key, targeting the production base URL.
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            connect_timeout: None,
+            user_agent_suffix: None,
+            proxy: None,
+            root_certificates: Vec::new(),
+            default_headers: Vec::new(),
+            transport: None,
+            retry_policy: RetryPolicy::default(),
+            middlewares: Vec::new(),
+            _client: PhantomData,
+        }
+    }
+
+    /// Overrides the base URL (useful for mock servers).
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Sets the total timeout for each request. Defaults to [`DEFAULT_TIMEOUT`].
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for establishing a connection.
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Appends `suffix` to the SDK `User-Agent`, e.g. `circle-sdk-rs/0.1.0 my-app/1.0`.
+    pub fn user_agent_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Routes all requests (HTTP and HTTPS) through the given proxy URL.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Trusts an additional PEM-encoded root certificate on top of the
+    /// bundled web PKI roots.
+    pub fn add_root_certificate_pem(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(RootCertificate::Pem(pem.into()));
+        self
+    }
+
+    /// Trusts an additional DER-encoded root certificate on top of the
+    /// bundled web PKI roots.
+    pub fn add_root_certificate_der(mut self, der: impl Into<Vec<u8>>) -> Self {
+        self.root_certificates.push(RootCertificate::Der(der.into()));
+        self
+    }
+
+    /// Sends `name: value` on every request.
+    ///
+    /// The SDK's own `Authorization`, `X-Request-Id` and `X-User-Token`
+    /// headers always take precedence.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Uses a shared, pre-configured `hpx::Client` instead of creating one.
+    ///
+    /// Connection-level settings ([`connect_timeout`](Self::connect_timeout),
+    /// [`proxy`](Self::proxy) and root certificates) belong to that client and
+    /// cannot be combined with this option; [`build`](Self::build) rejects
+    /// the combination. The request timeout, `User-Agent` and default headers
+    /// are applied per request and still take effect.
+    pub fn http_client(self, client: hpx::Client) -> Self {
+        self.transport(HpxTransport::new(client))
+    }
+
+    /// Sends requests through a custom [`HttpTransport`], such as a
+    /// [`MockTransport`](crate::MockTransport) in tests.
+    ///
+    /// Like [`http_client`](Self::http_client), this cannot be combined with
+    /// connection-level settings.
+    pub fn transport(mut self, transport: impl HttpTransport) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sets the policy for retrying transient failures. Defaults to
+    /// [`RetryPolicy::default`]; use [`RetryPolicy::none`] to disable retries.
+    pub const fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Adds a [`Middleware`] around every attempt of every request.
+    ///
+    /// Middlewares run in the order they are added, the first one outermost.
+    pub fn middleware(mut self, middleware: impl Middleware) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] for an invalid proxy URL, certificate
+    /// or header, or when connection-level settings are combined with
+    /// [`http_client`](Self::http_client) or [`transport`](Self::transport),
+    /// and [`Error::Http`] if the
+    /// underlying HTTP client cannot be initialised.
+    pub fn build(self) -> Result<C, Error> {
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in &self.default_headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::InvalidParam(format!("invalid header name {name:?}: {e}")))?;
+            let value = HeaderValue::from_str(value).map_err(|e| {
+                Error::InvalidParam(format!("invalid value for header {name}: {e}"))
+            })?;
+            default_headers.append(name, value);
+        }
+
+        let user_agent = match &self.user_agent_suffix {
+            Some(suffix) => format!("{DEFAULT_USER_AGENT} {suffix}"),
+            None => DEFAULT_USER_AGENT.to_string(),
+        };
+        HeaderValue::from_str(&user_agent)
+            .map_err(|e| Error::InvalidParam(format!("invalid User-Agent suffix: {e}")))?;
+
+        let transport = if let Some(transport) = self.transport {
+            if self.connect_timeout.is_some() ||
+                self.proxy.is_some() ||
+                !self.root_certificates.is_empty()
+            {
+                return Err(Error::InvalidParam(
+                    "connect_timeout, proxy and root certificates cannot be combined with a \
+                     custom http_client or transport; configure them on that client instead"
+                        .to_string(),
+                ));
+            }
+            transport
+        } else {
+            Arc::new(HpxTransport::new(self.build_http_client()?))
+        };
+
+        Ok(C::from(
+            RestClient::from_parts(
+                self.api_key,
+                self.base_url,
+                transport,
+                self.timeout,
+                user_agent,
+                default_headers,
+                self.retry_policy,
+            )
+            .with_middlewares(self.middlewares),
+        ))
+    }
+
+    /// Creates a dedicated `hpx::Client` from the connection-level settings.
+    fn build_http_client(&self) -> Result<hpx::Client, Error> {
+        let mut builder = hpx::Client::builder();
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(url) = &self.proxy {
+            let proxy = hpx::Proxy::all(url.as_str())
+                .map_err(|e| Error::InvalidParam(format!("invalid proxy URL: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        if !self.root_certificates.is_empty() {
+            let store =
+                self.root_certificates
+                    .iter()
+                    .fold(hpx::tls::CertStore::builder().set_default_paths(), |store, cert| {
+                        match cert {
+                            RootCertificate::Pem(pem) => store.add_pem_cert(pem),
+                            RootCertificate::Der(der) => store.add_der_cert(der),
+                        }
+                    })
+                    .build()
+                    .map_err(|e| Error::InvalidParam(format!("invalid root certificate: {e}")))?;
+            builder = builder.cert_store(store);
+        }
+        builder.build().map_err(|e| Error::Http { message: e.to_string(), request_id: None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn http_client() -> hpx::Client {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        hpx::Client::new()
+    }
+
+    #[test]
+    fn builds_with_defaults() -> Result<(), Box<dyn std::error::Error>> {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let client: RestClient = ClientBuilder::new("key").build()?;
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+        assert_eq!(client.timeout(), DEFAULT_TIMEOUT);
+        assert_eq!(client.user_agent(), DEFAULT_USER_AGENT);
+        Ok(())
+    }
+
+    #[test]
+    fn applies_user_agent_suffix_and_base_url() -> Result<(), Box<dyn std::error::Error>> {
+        let client: RestClient = ClientBuilder::new("key")
+            .base_url("http://localhost:4010")
+            .timeout(Duration::from_secs(5))
+            .user_agent_suffix("my-app/1.0")
+            .http_client(http_client())
+            .build()?;
+        assert_eq!(client.base_url(), "http://localhost:4010");
+        assert_eq!(client.timeout(), Duration::from_secs(5));
+        assert_eq!(client.user_agent(), format!("{DEFAULT_USER_AGENT} my-app/1.0"));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_default_header() {
+        let result: Result<RestClient, Error> = ClientBuilder::new("key")
+            .default_header("bad header", "value")
+            .http_client(http_client())
+            .build();
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+    }
+
+    #[test]
+    fn rejects_connection_settings_with_shared_client() {
+        let result: Result<RestClient, Error> = ClientBuilder::new("key")
+            .proxy("http://proxy.local:3128")
+            .http_client(http_client())
+            .build();
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+    }
+
+    #[test]
+    fn rejects_invalid_root_certificate() {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let result: Result<RestClient, Error> =
+            ClientBuilder::new("key").add_root_certificate_pem("not a certificate").build();
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+    }
+
+    #[test]
+    fn debug_redacts_api_key_and_proxy() {
+        let builder: ClientBuilder = ClientBuilder::new("secret-key").proxy("http://user:pw@proxy");
+        let debug = format!("{builder:?}");
+        assert!(!debug.contains("secret-key"), "{debug}");
+        assert!(!debug.contains("user:pw"), "{debug}");
+    }
+}
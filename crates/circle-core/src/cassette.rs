@@ -0,0 +1,501 @@
+//! Record/replay HTTP cassettes for deterministic offline tests.
+//!
+//! A [`CassetteTransport`] in record mode forwards every request to a real
+//! [`HttpTransport`] and appends the request/response pair to a JSON
+//! cassette file. In replay mode it answers from that file without touching
+//! the network, and fails every request it has no recording for.
+//!
+//! ```no_run
+//! use circle_core::{CassetteTransport, HpxTransport, RestClient};
+//!
+//! # fn main() -> Result<(), circle_core::Error> {
+//! // Record against the sandbox once...
+//! let recorder = CassetteTransport::record(
+//!     "tests/cassettes/list_wallets.json",
+//!     HpxTransport::new(hpx::Client::new()),
+//! );
+//! // ...then replay in CI forever.
+//! let player = CassetteTransport::replay("tests/cassettes/list_wallets.json")?;
+//! let client: RestClient = RestClient::builder("unused").transport(player).build()?;
+//! # let _ = (recorder, client);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests are matched on method, path, query string and JSON body; each
+//! recorded interaction is replayed at most once, in recording order. The
+//! `idempotencyKey` field is kept in the file but left out of the match, as
+//! clients generate a fresh key for every run.
+//!
+//! Secrets never reach the file: the `Authorization` and `X-User-Token`
+//! headers, and every `entitySecretCiphertext`, `userToken` and
+//! `encryptionKey` JSON field, are replaced with `[REDACTED]` before
+//! writing. Incoming request bodies are scrubbed the same way before
+//! matching, so a freshly encrypted ciphertext still matches its recording.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
+
+use hpx::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    error::Error,
+    middleware::{BoxFuture, HttpRequest, HttpResponse},
+    transport::HttpTransport,
+};
+
+/// Replacement written in place of scrubbed values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values are never written to a cassette.
+const SCRUBBED_HEADERS: [&str; 2] = ["authorization", "x-user-token"];
+
+/// JSON fields whose values are never written to a cassette.
+const SCRUBBED_FIELDS: [&str; 3] = ["entitySecretCiphertext", "userToken", "encryptionKey"];
+
+/// JSON fields that are recorded but ignored when matching a request.
+const UNMATCHED_FIELDS: [&str; 1] = ["idempotencyKey"];
+
+/// A recorded request or response body.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Body {
+    /// A JSON body, stored as structured JSON.
+    Json(serde_json::Value),
+    /// Any other body, stored as (lossy) UTF-8 text.
+    Text(String),
+}
+
+impl Body {
+    fn from_bytes(bytes: &[u8]) -> Self {
+        serde_json::from_slice::<serde_json::Value>(bytes).map_or_else(
+            |_| Self::Text(String::from_utf8_lossy(bytes).into_owned()),
+            |mut json| {
+                scrub_json(&mut json);
+                Self::Json(json)
+            },
+        )
+    }
+
+    /// The body as compared against a recording: without the
+    /// [`UNMATCHED_FIELDS`].
+    fn for_matching(&self) -> Self {
+        match self {
+            Self::Json(json) => {
+                let mut json = json.clone();
+                strip_unmatched(&mut json);
+                Self::Json(json)
+            }
+            Self::Text(text) => Self::Text(text.clone()),
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Self::Json(json) => json.to_string().into_bytes(),
+            Self::Text(text) => text.into_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Body>,
+}
+
+impl RecordedRequest {
+    fn new(request: &HttpRequest) -> Self {
+        Self {
+            method: request.method.to_string(),
+            path: request.path.as_str().to_string(),
+            query: request.query.clone().filter(|query| !query.is_empty()),
+            headers: header_pairs(&request.headers),
+            body: request.body.as_deref().map(Body::from_bytes),
+        }
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.method == other.method &&
+            self.path == other.path &&
+            self.query == other.query &&
+            self.body.as_ref().map(Body::for_matching) ==
+                other.body.as_ref().map(Body::for_matching)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Body,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+enum Mode {
+    Record { inner: Box<dyn HttpTransport>, cassette: Mutex<Cassette> },
+    Replay { interactions: Vec<Interaction>, used: Mutex<Vec<bool>> },
+}
+
+/// [`HttpTransport`] that records interactions to, or replays them from, a
+/// JSON cassette file.
+pub struct CassetteTransport {
+    path: PathBuf,
+    mode: Mode,
+}
+
+impl std::fmt::Debug for CassetteTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self.mode {
+            Mode::Record { .. } => "record",
+            Mode::Replay { .. } => "replay",
+        };
+        f.debug_struct("CassetteTransport")
+            .field("path", &self.path)
+            .field("mode", &mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CassetteTransport {
+    /// Sends requests through `inner` and writes every interaction to the
+    /// cassette at `path`, replacing any previous recording.
+    pub fn record(path: impl Into<PathBuf>, inner: impl HttpTransport) -> Self {
+        Self {
+            path: path.into(),
+            mode: Mode::Record { inner: Box::new(inner), cassette: Mutex::default() },
+        }
+    }
+
+    /// Answers requests from the cassette at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the cassette cannot be read or parsed.
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let bytes = std::fs::read(&path).map_err(|e| {
+            Error::InvalidParam(format!("cannot read cassette {}: {e}", path.display()))
+        })?;
+        let cassette: Cassette = serde_json::from_slice(&bytes).map_err(|e| {
+            Error::InvalidParam(format!("invalid cassette {}: {e}", path.display()))
+        })?;
+        let used = Mutex::new(vec![false; cassette.interactions.len()]);
+        Ok(Self { path, mode: Mode::Replay { interactions: cassette.interactions, used } })
+    }
+
+    /// Replays the cassette at `path` if it exists, and records one through
+    /// `inner` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if an existing cassette cannot be read.
+    pub fn auto(path: impl Into<PathBuf>, inner: impl HttpTransport) -> Result<Self, Error> {
+        let path = path.into();
+        if path.exists() { Self::replay(path) } else { Ok(Self::record(path, inner)) }
+    }
+
+    /// The cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` in replay mode once every recorded interaction has been
+    /// replayed; always `true` in record mode.
+    pub fn is_exhausted(&self) -> bool {
+        match &self.mode {
+            Mode::Record { .. } => true,
+            Mode::Replay { used, .. } => {
+                used.lock().unwrap_or_else(PoisonError::into_inner).iter().all(|used| *used)
+            }
+        }
+    }
+
+    async fn record_one(
+        &self,
+        inner: &dyn HttpTransport,
+        cassette: &Mutex<Cassette>,
+        request: HttpRequest,
+    ) -> Result<HttpResponse, Error> {
+        let recorded = RecordedRequest::new(&request);
+        let response = inner.send(request).await?;
+        let interaction = Interaction {
+            request: recorded,
+            response: RecordedResponse {
+                status: response.status,
+                headers: header_pairs(&response.headers),
+                body: Body::from_bytes(&response.body),
+            },
+        };
+        let json = {
+            let mut cassette = cassette.lock().unwrap_or_else(PoisonError::into_inner);
+            cassette.interactions.push(interaction);
+            serde_json::to_vec_pretty(&*cassette)
+                .map_err(|e| Error::Middleware(format!("cannot encode cassette: {e}")))?
+        };
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| self.write_error(&e))?;
+        }
+        std::fs::write(&self.path, json).map_err(|e| self.write_error(&e))?;
+        Ok(response)
+    }
+
+    fn replay_one(
+        &self,
+        interactions: &[Interaction],
+        used: &Mutex<Vec<bool>>,
+        request: &HttpRequest,
+    ) -> Result<HttpResponse, Error> {
+        let wanted = RecordedRequest::new(request);
+        let mut used = used.lock().unwrap_or_else(PoisonError::into_inner);
+        let found = interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| !used && interaction.request.matches(&wanted));
+        let Some(index) = found else {
+            tracing::error!(
+                cassette = %self.path.display(),
+                method = wanted.method,
+                path = wanted.path,
+                "no recorded interaction matches the request"
+            );
+            return Err(Error::Middleware(format!(
+                "cassette {}: no unused recording matches {} {}{}{} with body {}",
+                self.path.display(),
+                wanted.method,
+                wanted.path,
+                if wanted.query.is_some() { "?" } else { "" },
+                wanted.query.as_deref().unwrap_or_default(),
+                wanted
+                    .body
+                    .as_ref()
+                    .map_or_else(|| "<none>".to_string(), |body| format!("{body:?}")),
+            )));
+        };
+        used[index] = true;
+        let response = interactions[index].response.clone();
+        let mut headers = HeaderMap::new();
+        for (name, value) in &response.headers {
+            if let (Ok(name), Ok(value)) =
+                (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+            {
+                headers.append(name, value);
+            }
+        }
+        Ok(HttpResponse { status: response.status, headers, body: response.body.into_bytes() })
+    }
+
+    fn write_error(&self, e: &std::io::Error) -> Error {
+        Error::Middleware(format!("cannot write cassette {}: {e}", self.path.display()))
+    }
+}
+
+impl HttpTransport for CassetteTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>> {
+        match &self.mode {
+            Mode::Record { inner, cassette } => {
+                Box::pin(self.record_one(inner.as_ref(), cassette, request))
+            }
+            Mode::Replay { interactions, used } => {
+                Box::pin(std::future::ready(self.replay_one(interactions, used, &request)))
+            }
+        }
+    }
+}
+
+/// Lists the headers as `(name, value)` pairs, with secrets redacted.
+fn header_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SCRUBBED_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
+
+/// Redacts every scrubbed field, at any depth.
+fn scrub_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SCRUBBED_FIELDS.contains(&key.as_str()) {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    scrub_json(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(scrub_json),
+        _ => {}
+    }
+}
+
+/// Removes every unmatched field, at any depth.
+fn strip_unmatched(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|key, _| !UNMATCHED_FIELDS.contains(&key.as_str()));
+            map.values_mut().for_each(strip_unmatched);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_unmatched),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        client::RestClient,
+        transport::{Expectation, Method, MockTransport},
+    };
+
+    /// A cassette path in the system temp dir, removed on drop.
+    struct TempCassette(PathBuf);
+
+    impl TempCassette {
+        fn new() -> Self {
+            Self(
+                std::env::temp_dir().join(format!("circle-cassette-{}.json", uuid::Uuid::new_v4())),
+            )
+        }
+    }
+
+    impl Drop for TempCassette {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    async fn create_wallet_set(
+        client: &RestClient,
+        idempotency_key: &str,
+        ciphertext: &str,
+    ) -> Result<serde_json::Value, Error> {
+        client
+            .post("/v1/w3s/developer/walletSets")
+            .user_token("user-token-secret")
+            .json(&json!({
+                "idempotencyKey": idempotency_key,
+                "entitySecretCiphertext": ciphertext,
+                "name": "treasury",
+            }))
+            .send()
+            .await
+    }
+
+    #[tokio::test]
+    async fn records_scrubbed_interactions_and_replays_them()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let cassette = TempCassette::new();
+        let mock = MockTransport::new();
+        mock.expect(Expectation::new(Method::POST, "/v1/w3s/developer/walletSets").respond(
+            201,
+            json!({ "data": { "walletSet": { "id": "ws-1" }, "userToken": "issued-token" } }),
+        ));
+        let recorder = RestClient::builder("api-key-secret")
+            .transport(CassetteTransport::record(&cassette.0, mock))
+            .build()?;
+        let recorded =
+            create_wallet_set(&recorder, "b3d9d2d5-4c12-4946-a09d-953e82fae2b0", "ciphertext-one")
+                .await?;
+        assert_eq!(recorded["data"]["walletSet"]["id"], "ws-1");
+
+        let written = std::fs::read_to_string(&cassette.0)?;
+        for secret in ["api-key-secret", "user-token-secret", "ciphertext-one", "issued-token"] {
+            assert!(!written.contains(secret), "{secret} leaked: {written}");
+        }
+        assert!(written.contains(REDACTED), "{written}");
+        assert!(written.contains("b3d9d2d5-4c12-4946-a09d-953e82fae2b0"), "{written}");
+
+        let player = RestClient::builder("other-key")
+            .transport(CassetteTransport::replay(&cassette.0)?)
+            .build()?;
+        // A fresh ciphertext and idempotency key still match the recording.
+        let replayed =
+            create_wallet_set(&player, "5e0c7f3a-9d21-4b8e-a6f4-3c2d1e0f9a8b", "ciphertext-two")
+                .await?;
+        assert_eq!(replayed["data"]["walletSet"]["id"], "ws-1");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replay_fails_loudly_on_unmatched_requests() -> Result<(), Box<dyn std::error::Error>> {
+        let cassette = TempCassette::new();
+        let mock = MockTransport::new();
+        mock.respond(200, json!({ "data": { "wallets": [] } }));
+        let recorder = RestClient::builder("key")
+            .transport(CassetteTransport::record(&cassette.0, mock))
+            .build()?;
+        let _: serde_json::Value =
+            recorder.get("/v1/w3s/wallets").query(&[("pageSize", "5")]).send().await?;
+
+        let transport = CassetteTransport::replay(&cassette.0)?;
+        assert!(!transport.is_exhausted());
+        let player = RestClient::builder("key").transport(transport).build()?;
+        let other_query: Result<serde_json::Value, Error> =
+            player.get("/v1/w3s/wallets").query(&[("pageSize", "6")]).send().await;
+        assert!(matches!(other_query, Err(Error::Middleware(_))), "{other_query:?}");
+
+        let _: serde_json::Value =
+            player.get("/v1/w3s/wallets").query(&[("pageSize", "5")]).send().await?;
+        let replayed_twice: Result<serde_json::Value, Error> =
+            player.get("/v1/w3s/wallets").query(&[("pageSize", "5")]).send().await;
+        assert!(matches!(replayed_twice, Err(Error::Middleware(_))), "{replayed_twice:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn replay_rejects_missing_cassette() {
+        let result = CassetteTransport::replay(TempCassette::new().0.clone());
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+    }
+
+    #[test]
+    fn idempotency_keys_are_ignored_when_matching() {
+        let request = |key: &str| RecordedRequest {
+            method: "POST".to_string(),
+            path: "/v1/w3s/developer/wallets".to_string(),
+            query: None,
+            headers: Vec::new(),
+            body: Some(Body::Json(json!({ "idempotencyKey": key, "count": 1 }))),
+        };
+        assert!(request("a").matches(&request("b")));
+        let mut other_count = request("a");
+        other_count.body = Some(Body::Json(json!({ "idempotencyKey": "a", "count": 2 })));
+        assert!(!request("a").matches(&other_count));
+    }
+
+    #[test]
+    fn scrubs_nested_fields() {
+        let mut value = json!({ "a": [{ "entitySecretCiphertext": "x", "keep": "y" }] });
+        scrub_json(&mut value);
+        assert_eq!(value, json!({ "a": [{ "entitySecretCiphertext": REDACTED, "keep": "y" }] }));
+    }
+}
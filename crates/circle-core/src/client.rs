@@ -0,0 +1,964 @@
+//! Shared HTTP request pipeline for the Circle Web3 Services APIs.
+//!
+//! [`RestClient`] owns the base URL, the API key and the underlying `hpx`
+//! client. Every request goes through the same steps: the `Authorization`
+//! and `X-Request-Id` headers are attached, the query string or JSON body is
+//! encoded, and the response is decoded into either the expected type or an
+//! [`Error`]. Each request runs in a `circle_request` tracing span keyed by
+//! its path template (see [`api_path!`](crate::api_path)).
+
+use std::{
+    borrow::Cow,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use hpx::header::{HeaderMap, HeaderName, HeaderValue};
+use tracing::{Instrument, field::Empty};
+
+use crate::{
+    builder::{ClientBuilder, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT},
+    error::{ApiErrorBody, Error, ResponseDetails},
+    middleware::{HttpRequest, Middleware, Next},
+    options::RequestOptions,
+    response::{ApiResponse, ResponseMeta},
+    retry::{self, RetryPolicy},
+    transport::{HpxTransport, HttpTransport},
+};
+
+/// Production base URL for all Circle Web3 Services APIs.
+pub const DEFAULT_BASE_URL: &str = "https://api.circle.com";
+
+/// Authenticated HTTP client shared by the per-API Circle clients.
+#[derive(Clone)]
+pub struct RestClient {
+    base_url: String,
+    api_key: String,
+    transport: Arc<dyn HttpTransport>,
+    timeout: Duration,
+    user_agent: String,
+    default_headers: HeaderMap,
+    retry_policy: RetryPolicy,
+    options: RequestOptions,
+    middlewares: Arc<[Arc<dyn Middleware>]>,
+}
+
+impl std::fmt::Debug for RestClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestClient")
+            .field("base_url", &self.base_url)
+            .field("api_key", &"<redacted>")
+            .field("timeout", &self.timeout)
+            .field("user_agent", &self.user_agent)
+            .field("retry_policy", &self.retry_policy)
+            .field("middlewares", &self.middlewares.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RestClient {
+    /// Creates a new client for the given API key and base URL with default settings.
+    pub fn new(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self::from_parts(
+            api_key.into(),
+            base_url.into(),
+            Arc::new(HpxTransport::new(hpx::Client::new())),
+            DEFAULT_TIMEOUT,
+            DEFAULT_USER_AGENT.to_string(),
+            HeaderMap::new(),
+            RetryPolicy::default(),
+        )
+    }
+
+    /// Starts a [`ClientBuilder`] for the given API key.
+    pub fn builder(api_key: impl Into<String>) -> ClientBuilder<Self> {
+        ClientBuilder::new(api_key)
+    }
+
+    pub(crate) fn from_parts(
+        api_key: String,
+        base_url: String,
+        transport: Arc<dyn HttpTransport>,
+        timeout: Duration,
+        user_agent: String,
+        default_headers: HeaderMap,
+        retry_policy: RetryPolicy,
+    ) -> Self {
+        Self {
+            base_url,
+            api_key,
+            transport,
+            timeout,
+            user_agent,
+            default_headers,
+            retry_policy,
+            options: RequestOptions::default(),
+            middlewares: Arc::new([]),
+        }
+    }
+
+    pub(crate) fn with_middlewares(mut self, middlewares: Vec<Arc<dyn Middleware>>) -> Self {
+        self.middlewares = middlewares.into();
+        self
+    }
+
+    /// Returns a copy of this client whose requests use `options`.
+    ///
+    /// The copy shares the connection pool with this client.
+    #[must_use]
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self { options, ..self.clone() }
+    }
+
+    /// Returns the base URL every request path is appended to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the timeout applied to each request.
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Returns the `User-Agent` sent with each request.
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Returns the policy used to retry transient failures.
+    pub const fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Returns the per-call options applied to every request of this client.
+    pub const fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+
+    /// Starts a GET request for `path`.
+    pub fn get(&self, path: impl Into<RequestPath>) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, hpx::Method::GET, path.into())
+    }
+
+    /// Starts a POST request for `path`.
+    pub fn post(&self, path: impl Into<RequestPath>) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, hpx::Method::POST, path.into())
+    }
+
+    /// Starts a PUT request for `path`.
+    pub fn put(&self, path: impl Into<RequestPath>) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, hpx::Method::PUT, path.into())
+    }
+}
+
+/// A request path together with the template it was built from.
+///
+/// The template (e.g. `/v1/w3s/wallets/{id}`) is what gets recorded in
+/// tracing spans, so that ids do not end up in span names or metric labels.
+/// Build one with [`api_path!`](crate::api_path); plain strings are their
+/// own template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestPath {
+    template: Cow<'static, str>,
+    path: String,
+}
+
+impl RequestPath {
+    /// Pairs a formatted `path` with the `template` it was formatted from.
+    pub const fn templated(template: &'static str, path: String) -> Self {
+        Self { template: Cow::Borrowed(template), path }
+    }
+
+    /// The path template, e.g. `/v1/w3s/wallets/{id}`.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// The concrete path, e.g. `/v1/w3s/wallets/9a5f0c1e-...`.
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+}
+
+impl From<&str> for RequestPath {
+    fn from(path: &str) -> Self {
+        Self { template: Cow::Owned(path.to_string()), path: path.to_string() }
+    }
+}
+
+impl From<String> for RequestPath {
+    fn from(path: String) -> Self {
+        Self { template: Cow::Owned(path.clone()), path }
+    }
+}
+
+/// Formats a request path with inline arguments and keeps the literal as its
+/// template:
+///
+/// ```
+/// let id = "9a5f0c1e";
+/// let path = circle_core::api_path!("/v1/w3s/wallets/{id}/balances");
+/// assert_eq!(path.as_str(), "/v1/w3s/wallets/9a5f0c1e/balances");
+/// assert_eq!(path.template(), "/v1/w3s/wallets/{id}/balances");
+/// ```
+#[macro_export]
+macro_rules! api_path {
+    ($template:literal) => {
+        $crate::RequestPath::templated($template, format!($template))
+    };
+}
+
+/// A single request being prepared against a [`RestClient`].
+///
+/// Encoding failures are deferred until [`RequestBuilder::send`] so that the
+/// builder methods can be chained without intermediate `?`.
+#[must_use = "a request does nothing until `send` is awaited"]
+pub struct RequestBuilder<'a> {
+    client: &'a RestClient,
+    method: hpx::Method,
+    path: RequestPath,
+    query: Option<String>,
+    body: Option<Vec<u8>>,
+    has_idempotency_key: bool,
+    body_hook: Option<BodyHook<'a>>,
+    user_token: Option<String>,
+    options: RequestOptions,
+    error: Option<Error>,
+}
+
+/// Rewrites the JSON body of a request before each attempt, see
+/// [`RequestBuilder::refresh_body`].
+type BodyHook<'a> = Box<dyn Fn(&mut serde_json::Value) -> Result<(), Error> + Send + Sync + 'a>;
+
+/// Probe used to detect an `idempotencyKey` in an encoded JSON body.
+#[derive(serde::Deserialize)]
+struct IdempotencyProbe {
+    #[serde(rename = "idempotencyKey")]
+    idempotency_key: Option<serde::de::IgnoredAny>,
+}
+
+impl std::fmt::Debug for RequestBuilder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestBuilder")
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .field("query", &self.query)
+            .field("has_body", &self.body.is_some())
+            .field("has_body_hook", &self.body_hook.is_some())
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> RequestBuilder<'a> {
+    fn new(client: &'a RestClient, method: hpx::Method, path: RequestPath) -> Self {
+        Self {
+            client,
+            method,
+            path,
+            query: None,
+            body: None,
+            has_idempotency_key: false,
+            body_hook: None,
+            user_token: None,
+            options: client.options.clone(),
+            error: None,
+        }
+    }
+
+    /// Encodes `params` as the URL query string.
+    pub fn query<P>(mut self, params: &P) -> Self
+    where
+        P: serde::Serialize + ?Sized,
+    {
+        match serde_urlencoded::to_string(params) {
+            Ok(query) => self.query = Some(query),
+            Err(e) => self.error = Some(Error::InvalidParam(e.to_string())),
+        }
+        self
+    }
+
+    /// Encodes `body` as the JSON request body.
+    ///
+    /// A body with a top-level `idempotencyKey` makes a `POST` safe to retry.
+    pub fn json<B>(mut self, body: &B) -> Self
+    where
+        B: serde::Serialize + ?Sized,
+    {
+        match serde_json::to_vec(body) {
+            Ok(bytes) => {
+                self.has_idempotency_key = serde_json::from_slice::<IdempotencyProbe>(&bytes)
+                    .is_ok_and(|probe| probe.idempotency_key.is_some());
+                self.body = Some(bytes);
+            }
+            Err(e) => self.error = Some(Error::InvalidParam(e.to_string())),
+        }
+        self
+    }
+
+    /// Rewrites the JSON body before every attempt, including retries.
+    ///
+    /// For fields that must differ between attempts, such as single-use
+    /// ciphertexts, which a retry would otherwise resend unchanged.
+    pub fn refresh_body(
+        mut self,
+        hook: impl Fn(&mut serde_json::Value) -> Result<(), Error> + Send + Sync + 'a,
+    ) -> Self {
+        self.body_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Attaches an end-user token as the `X-User-Token` header.
+    pub fn user_token(mut self, user_token: impl Into<String>) -> Self {
+        self.user_token = Some(user_token.into());
+        self
+    }
+
+    /// Replaces the client's [`RequestOptions`] for this request.
+    pub fn options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns `true` if repeating this request cannot apply it twice.
+    fn is_repeatable(&self) -> bool {
+        self.method == hpx::Method::GET ||
+            (self.method == hpx::Method::POST && self.has_idempotency_key)
+    }
+
+    /// Sends the request and decodes the JSON response as `T`.
+    ///
+    /// Transient failures of repeatable requests are retried according to the
+    /// client's [`RetryPolicy`]; every attempt reuses the same `X-Request-Id`.
+    pub async fn send<T>(self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.send_with_meta().await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::send`], but also returns the [`ResponseMeta`] of the
+    /// successful response.
+    ///
+    /// Each call runs in a `circle_request` tracing span recording the method,
+    /// path template, request id, final status, latency, attempt number and
+    /// error kind. Credentials, user tokens and bodies are never recorded.
+    pub async fn send_with_meta<T>(mut self) -> Result<ApiResponse<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let request_id =
+            self.options.request_id.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let span = tracing::info_span!(
+            "circle_request",
+            method = %self.method,
+            path = self.path.template(),
+            request_id = request_id.as_str(),
+            status = Empty,
+            latency_ms = Empty,
+            attempt = Empty,
+            error_kind = Empty,
+        );
+        self.execute(&request_id).instrument(span).await
+    }
+
+    /// Runs the attempts of the request inside the current span.
+    async fn execute<T>(&self, request_id: &str) -> Result<ApiResponse<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let span = tracing::Span::current();
+        let headers = self.headers(request_id)?;
+        let policy = self.options.retry_policy.as_ref().unwrap_or(&self.client.retry_policy);
+        let max_attempts = if self.is_repeatable() { policy.max_attempts } else { 1 };
+        let started = Instant::now();
+        let latency_ms = || u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        let mut attempt = 1;
+        loop {
+            span.record("attempt", attempt);
+            let error = match self.attempt(request_id, &headers, attempt).await {
+                Ok(mut response) => {
+                    response.meta.latency = started.elapsed();
+                    response.meta.attempts = attempt;
+                    span.record("status", response.meta.status);
+                    span.record("latency_ms", latency_ms());
+                    tracing::debug!("Circle API request succeeded");
+                    return Ok(response);
+                }
+                Err(error) => error,
+            };
+            let delay = policy.delay(
+                attempt,
+                error.response().and_then(|response| retry::retry_after(&response.headers)),
+            );
+            let Some(delay) = delay.filter(|_| attempt < max_attempts && error.is_retryable())
+            else {
+                if let Some(status) = error.status() {
+                    span.record("status", status);
+                }
+                span.record("latency_ms", latency_ms());
+                span.record("error_kind", error.kind());
+                tracing::debug!(
+                    error_code = error.error_code().map(i32::from),
+                    "Circle API request failed"
+                );
+                return Err(error);
+            };
+            tracing::debug!(
+                attempt,
+                status = error.status(),
+                error_kind = error.kind(),
+                delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                "retrying Circle API request"
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Assembles the request headers: the client defaults, then the extra
+    /// headers from the [`RequestOptions`], then the SDK's own headers.
+    fn headers(&self, request_id: &str) -> Result<HeaderMap, Error> {
+        let mut headers = self.client.default_headers.clone();
+        for (name, value) in &self.options.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| Error::InvalidParam(format!("invalid header name {name:?}: {e}")))?;
+            headers.insert(name, header_value(value)?);
+        }
+        headers.insert(hpx::header::USER_AGENT, header_value(&self.client.user_agent)?);
+        let mut authorization = header_value(&format!("Bearer {}", self.client.api_key))?;
+        authorization.set_sensitive(true);
+        headers.insert(hpx::header::AUTHORIZATION, authorization);
+        headers.insert("X-Request-Id", header_value(request_id)?);
+        if let Some(user_token) = &self.user_token {
+            let mut user_token = header_value(user_token)?;
+            user_token.set_sensitive(true);
+            headers.insert("X-User-Token", user_token);
+        }
+        if self.body.is_some() {
+            headers.insert(hpx::header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
+        Ok(headers)
+    }
+
+    /// The body of the next attempt, passed through the
+    /// [`refresh_body`](Self::refresh_body) hook if there is one.
+    fn attempt_body(&self) -> Result<Option<Vec<u8>>, Error> {
+        let (Some(body), Some(hook)) = (&self.body, &self.body_hook) else {
+            return Ok(self.body.clone());
+        };
+        let invalid = |e: serde_json::Error| Error::InvalidParam(e.to_string());
+        let mut json = serde_json::from_slice(body).map_err(invalid)?;
+        hook(&mut json)?;
+        serde_json::to_vec(&json).map(Some).map_err(invalid)
+    }
+
+    /// Sends a single attempt of the request.
+    async fn attempt<T>(
+        &self,
+        request_id: &str,
+        headers: &HeaderMap,
+        attempt: u32,
+    ) -> Result<ApiResponse<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let request = HttpRequest {
+            method: self.method.clone(),
+            base_url: self.client.base_url.clone(),
+            path: self.path.clone(),
+            query: self.query.clone(),
+            headers: headers.clone(),
+            body: self.attempt_body()?,
+            timeout: self.options.timeout.unwrap_or(self.client.timeout),
+            attempt,
+        };
+        let response = Next::new(&self.client.middlewares, self.client.transport.as_ref())
+            .run(request)
+            .await?;
+        let status = hpx::StatusCode::from_u16(response.status).map_err(|_| Error::Http {
+            message: format!("invalid HTTP status {}", response.status),
+            request_id: Some(request_id.to_string()),
+        })?;
+        let headers = response.headers;
+        let bytes = response.body;
+        let server_request_id =
+            headers.get("X-Request-Id").and_then(|value| value.to_str().ok()).map(str::to_string);
+        let details = || {
+            Box::new(ResponseDetails {
+                status: status.as_u16(),
+                request_id: request_id.to_string(),
+                server_request_id: server_request_id.clone(),
+                headers: headers.clone(),
+                body: String::from_utf8_lossy(&bytes).into_owned(),
+            })
+        };
+        let body = decode(status, &bytes, details)?;
+        Ok(ApiResponse {
+            body,
+            meta: ResponseMeta {
+                status: status.as_u16(),
+                request_id: request_id.to_string(),
+                server_request_id,
+                headers,
+                latency: Duration::ZERO,
+                attempts: 1,
+            },
+        })
+    }
+}
+
+/// Parses a header value, rejecting control characters.
+fn header_value(value: &str) -> Result<HeaderValue, Error> {
+    HeaderValue::from_str(value)
+        .map_err(|e| Error::InvalidParam(format!("invalid header value: {e}")))
+}
+
+/// Decode a response: if 2xx parse as `T`, otherwise parse as [`ApiErrorBody`].
+///
+/// An empty 2xx body (e.g. `204 No Content`) is read as `null`, so endpoints
+/// that may answer without content decode into an `Option`.
+fn decode<T, F>(status: hpx::StatusCode, bytes: &[u8], details: F) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+    F: FnOnce() -> Box<ResponseDetails>,
+{
+    if status.is_success() {
+        let bytes = if bytes.trim_ascii().is_empty() { b"null".as_slice() } else { bytes };
+        let deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| Error::Decode {
+            path: e.path().to_string(),
+            source: e.into_inner(),
+            response: details(),
+        })
+    } else {
+        match serde_json::from_slice::<ApiErrorBody>(bytes) {
+            Ok(err) => {
+                Err(Error::Api { code: err.code, message: err.message, response: details() })
+            }
+            Err(_) => Err(Error::UnexpectedResponse { response: details() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Params {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        page_size: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        page_after: Option<String>,
+    }
+
+    fn client(api_key: &str) -> RestClient {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        RestClient::new(api_key, DEFAULT_BASE_URL)
+    }
+
+    /// Serves the canned `(status, extra headers, body)` responses in order,
+    /// one per connection, and counts the requests received.
+    async fn serve(
+        responses: Vec<(u16, &'static str, &'static str)>,
+    ) -> Result<(RestClient, Arc<AtomicUsize>), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&hits);
+        tokio::spawn(async move {
+            for (status, headers, body) in responses {
+                let Ok((mut socket, _)) = listener.accept().await else { return };
+                let mut buf = vec![0_u8; 16 * 1024];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\n{headers}\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let client = RestClient::builder("key")
+            .base_url(base_url)
+            .retry_policy(RetryPolicy::default().with_backoff(Duration::ZERO, Duration::ZERO))
+            .build()?;
+        Ok((client, hits))
+    }
+
+    /// Answers a single request with `200 {}` and returns its raw bytes,
+    /// lowercased so header names can be matched regardless of casing.
+    async fn capture_one()
+    -> Result<(String, tokio::task::JoinHandle<String>), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        let handle = tokio::spawn(async move {
+            let Ok((mut socket, _)) = listener.accept().await else { return String::new() };
+            let mut buf = vec![0_u8; 16 * 1024];
+            let n = socket.read(&mut buf).await.unwrap_or(0);
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}")
+                .await;
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+        Ok((base_url, handle))
+    }
+
+    #[test]
+    fn debug_redacts_api_key() {
+        let client = client("secret-key");
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("secret-key"), "{debug}");
+        assert!(debug.contains("<redacted>"), "{debug}");
+    }
+
+    #[test]
+    fn query_encodes_camel_case_params() {
+        let client = client("key");
+        let req = client
+            .get("/v1/w3s/wallets")
+            .query(&Params { page_size: Some(10), page_after: Some("abc".to_string()) });
+        assert_eq!(req.query.as_deref(), Some("pageSize=10&pageAfter=abc"));
+        assert!(req.error.is_none());
+    }
+
+    #[test]
+    fn query_skips_unset_params() {
+        let client = client("key");
+        let req =
+            client.get("/v1/w3s/wallets").query(&Params { page_size: None, page_after: None });
+        assert_eq!(req.query.as_deref(), Some(""));
+    }
+
+    #[tokio::test]
+    async fn invalid_query_surfaces_on_send() {
+        let client = client("key");
+        let nested = vec![vec![1_u8]];
+        let result: Result<serde_json::Value, Error> =
+            client.get("/v1/w3s/wallets").query(&nested).send().await;
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+    }
+
+    #[test]
+    fn json_detects_idempotency_key() {
+        let client = client("key");
+        let keyed = client.post("/x").json(&serde_json::json!({ "idempotencyKey": "k" }));
+        assert!(keyed.is_repeatable());
+        let unkeyed = client.post("/x").json(&serde_json::json!({ "name": "n" }));
+        assert!(!unkeyed.is_repeatable());
+        let put = client.put("/x").json(&serde_json::json!({ "idempotencyKey": "k" }));
+        assert!(!put.is_repeatable());
+        assert!(client.get("/x").is_repeatable());
+    }
+
+    #[tokio::test]
+    async fn get_retries_transient_failures() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, hits) = serve(vec![
+            (503, "", r#"{"code":-1,"message":"unavailable"}"#),
+            (429, "Retry-After: 0\r\n", r#"{"code":-1,"message":"slow down"}"#),
+            (200, "", r#"{"ok":true}"#),
+        ])
+        .await?;
+        let value: serde_json::Value = client.get("/v1/w3s/wallets").send().await?;
+        assert_eq!(value["ok"], true);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn send_with_meta_reports_status_ids_and_attempts()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let (client, _) = serve(vec![
+            (503, "", r#"{"code":-1,"message":"unavailable"}"#),
+            (201, "X-Request-Id: echoed\r\nX-RateLimit-Remaining: 9\r\n", r#"{"ok":true}"#),
+        ])
+        .await?;
+        let response = client.get("/v1/w3s/wallets").send_with_meta::<serde_json::Value>().await?;
+        assert_eq!(response.body["ok"], true);
+        assert_eq!(response.meta.status, 201);
+        assert_eq!(response.meta.attempts, 2);
+        assert_eq!(response.meta.server_request_id.as_deref(), Some("echoed"));
+        assert_eq!(response.meta.rate_limit_remaining(), Some(9));
+        assert!(uuid::Uuid::parse_str(&response.meta.request_id).is_ok());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"code":-1,"message":"bad gateway"}"#;
+        let (client, hits) = serve(vec![(502, "", body), (502, "", body), (502, "", body)]).await?;
+        let result: Result<serde_json::Value, Error> = client.get("/v1/w3s/wallets").send().await;
+        assert_eq!(result.as_ref().err().and_then(Error::status), Some(502), "{result:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn long_retry_after_fails_instead_of_waiting() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"code":-1,"message":"slow down"}"#;
+        let (client, hits) =
+            serve(vec![(429, "Retry-After: 86400\r\n", body), (200, "", r#"{"ok":true}"#)]).await?;
+        let result: Result<serde_json::Value, Error> = client.get("/v1/w3s/wallets").send().await;
+        assert_eq!(result.as_ref().err().and_then(Error::status), Some(429), "{result:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, hits) =
+            serve(vec![(404, "", r#"{"code":156004,"message":"not found"}"#)]).await?;
+        let result: Result<serde_json::Value, Error> = client.get("/v1/w3s/wallets/x").send().await;
+        assert!(matches!(result, Err(Error::Api { code: 156_004, .. })), "{result:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn options_set_request_id_and_extra_headers() -> Result<(), Box<dyn std::error::Error>> {
+        let (base_url, request) = capture_one().await?;
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let client = RestClient::builder("key")
+            .base_url(base_url)
+            .default_header("X-Trace", "default")
+            .build()?
+            .with_options(
+                RequestOptions::new()
+                    .with_request_id("trace-123")
+                    .with_header("X-Trace", "per-call")
+                    .with_header("Authorization", "Bearer other"),
+            );
+        let response = client.get("/v1/w3s/wallets").send_with_meta::<serde_json::Value>().await?;
+        assert_eq!(response.meta.request_id, "trace-123");
+
+        let request = request.await?;
+        assert!(request.contains("x-request-id: trace-123\r\n"), "{request}");
+        assert!(request.contains("x-trace: per-call\r\n"), "{request}");
+        assert!(!request.contains("x-trace: default"), "{request}");
+        assert_eq!(request.matches("authorization:").count(), 1, "{request}");
+        assert!(request.contains("authorization: bearer key\r\n"), "{request}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn options_override_retry_policy() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"code":-1,"message":"unavailable"}"#;
+        let (client, hits) = serve(vec![(503, "", body), (200, "", "{}")]).await?;
+        let result: Result<serde_json::Value, Error> = client
+            .get("/v1/w3s/wallets")
+            .options(RequestOptions::new().with_retry_policy(RetryPolicy::none()))
+            .send()
+            .await;
+        assert_eq!(result.as_ref().err().and_then(Error::status), Some(503), "{result:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn options_timeout_applies_per_call() -> Result<(), Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move {
+            let Ok((socket, _)) = listener.accept().await else { return };
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            drop(socket);
+        });
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let client = RestClient::builder("key").base_url(base_url).build()?;
+        let options = RequestOptions::new()
+            .with_timeout(Duration::from_millis(50))
+            .with_retry_policy(RetryPolicy::none());
+        let started = Instant::now();
+        let result: Result<serde_json::Value, Error> =
+            client.get("/v1/w3s/wallets").options(options).send().await;
+        assert!(matches!(result, Err(Error::Http { .. })), "{result:?}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalid_option_header_surfaces_on_send() {
+        let client = client("key");
+        let result: Result<serde_json::Value, Error> = client
+            .get("/v1/w3s/wallets")
+            .options(RequestOptions::new().with_header("X-Trace", "line\nbreak"))
+            .send()
+            .await;
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+    }
+
+    /// Collects formatted tracing output in memory.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().map_err(|_| std::io::Error::other("poisoned"))?.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn span_records_template_status_and_no_secrets() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(captured.clone())
+            .with_max_level(tracing::Level::TRACE)
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (base_url, request) = capture_one().await?;
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let client = RestClient::builder("secret-api-key").base_url(base_url).build()?;
+        let id = "8d0b4c1e-0000-4000-8000-000000000001";
+        let _: serde_json::Value = client
+            .put(crate::api_path!("/v1/w3s/wallets/{id}"))
+            .user_token("secret-user-token")
+            .json(&serde_json::json!({ "entitySecretCiphertext": "secret-ciphertext" }))
+            .send()
+            .await?;
+        request.await?;
+
+        let output = String::from_utf8(captured.0.lock().map_err(|e| e.to_string())?.clone())?;
+        assert!(output.contains("circle_request"), "{output}");
+        assert!(output.contains("/v1/w3s/wallets/{id}"), "{output}");
+        assert!(output.contains("method=PUT"), "{output}");
+        assert!(output.contains("status=200"), "{output}");
+        assert!(output.contains("attempt=1"), "{output}");
+        assert!(output.contains("latency_ms="), "{output}");
+        assert!(!output.contains(id), "{output}");
+        for secret in ["secret-api-key", "secret-user-token", "secret-ciphertext"] {
+            assert!(!output.contains(secret), "{secret} leaked: {output}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn span_records_error_kind_and_retry_attempt() -> Result<(), Box<dyn std::error::Error>> {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(captured.clone())
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let body = r#"{"code":-1,"message":"unavailable"}"#;
+        let (client, _) = serve(vec![(503, "", body), (503, "", body)]).await?;
+        let result: Result<serde_json::Value, Error> = client
+            .get("/v1/w3s/wallets")
+            .options(
+                RequestOptions::new().with_retry_policy(
+                    RetryPolicy::default()
+                        .with_max_attempts(2)
+                        .with_backoff(Duration::ZERO, Duration::ZERO),
+                ),
+            )
+            .send()
+            .await;
+        assert!(result.is_err());
+
+        let output = String::from_utf8(captured.0.lock().map_err(|e| e.to_string())?.clone())?;
+        assert!(output.contains("status=503"), "{output}");
+        assert!(output.contains("attempt=2"), "{output}");
+        assert!(output.contains("error_kind=\"api\""), "{output}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn post_is_retried_only_with_idempotency_key() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"code":-1,"message":"unavailable"}"#;
+        let (client, hits) = serve(vec![(503, "", body), (200, "", "{}")]).await?;
+        let result: Result<serde_json::Value, Error> = client
+            .post("/v1/w3s/walletSets")
+            .json(&serde_json::json!({ "name": "n" }))
+            .send()
+            .await;
+        assert_eq!(result.as_ref().err().and_then(Error::status), Some(503), "{result:?}");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        let (client, hits) = serve(vec![(503, "", body), (200, "", "{}")]).await?;
+        let _: serde_json::Value = client
+            .post("/v1/w3s/developer/transactions/transfer")
+            .json(&serde_json::json!({ "idempotencyKey": "k" }))
+            .send()
+            .await?;
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn non_json_error_body_keeps_status_and_body() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, _) = serve(vec![(403, "", "<html>Forbidden</html>")]).await?;
+        let result: Result<serde_json::Value, Error> = client.get("/v1/w3s/wallets").send().await;
+        let Err(Error::UnexpectedResponse { response }) = result else {
+            return Err(format!("expected UnexpectedResponse, got {result:?}").into());
+        };
+        assert_eq!(response.status, 403);
+        assert_eq!(response.body, "<html>Forbidden</html>");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_success_body_decodes_as_none() -> Result<(), Box<dyn std::error::Error>> {
+        let (client, _) = serve(vec![(204, "", "")]).await?;
+        let body: Option<serde_json::Value> =
+            client.get("/v1/w3s/transactions/lowestNonceTransaction").send().await?;
+        assert_eq!(body, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn decode_error_reports_json_path_and_request_ids()
+    -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Debug, serde::Deserialize)]
+        struct Wallets {
+            #[expect(dead_code)]
+            wallets: Vec<u32>,
+        }
+
+        let (client, _) =
+            serve(vec![(200, "X-Request-Id: echoed\r\n", r#"{"wallets":[1,"two"]}"#)]).await?;
+        let result: Result<Wallets, Error> = client.get("/v1/w3s/wallets").send().await;
+        let Err(Error::Decode { path, response, .. }) = result else {
+            return Err(format!("expected Decode, got {result:?}").into());
+        };
+        assert_eq!(path, "wallets[1]");
+        assert_eq!(response.server_request_id.as_deref(), Some("echoed"));
+        assert!(uuid::Uuid::parse_str(&response.request_id).is_ok());
+        Ok(())
+    }
+}
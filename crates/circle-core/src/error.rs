@@ -0,0 +1,242 @@
+//! Error types shared by every Circle SDK crate.
+//!
+//! Every error produced after a response was received carries a
+//! [`ResponseDetails`] with the HTTP status, the `X-Request-Id` that was sent
+//! and the one Circle echoed back, the response headers and the raw body.
+//! Include the request id when opening a support ticket with Circle.
+
+use hpx::header::HeaderMap;
+
+use crate::error_code::CircleErrorCode;
+
+/// Errors that can occur when calling a Circle Web3 Services API.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// HTTP transport error from the underlying HTTP client; no response was
+    /// received (connection failure, TLS error, timeout, ...).
+    #[error("HTTP transport error: {message}")]
+    Http {
+        /// Description of the transport failure.
+        message: String,
+        /// `X-Request-Id` sent with the request, if it got that far.
+        request_id: Option<String>,
+    },
+
+    /// The Circle API returned a non-2xx response with an error payload.
+    #[error("Circle API error {code}: {message}")]
+    Api {
+        /// Numeric error code from the Circle API response body.
+        code: i32,
+        /// Human-readable error message from the Circle API response body.
+        message: String,
+        /// The response the error payload was read from.
+        response: Box<ResponseDetails>,
+    },
+
+    /// A non-2xx response whose body is not a Circle error payload, e.g. an
+    /// HTML page from a gateway or an empty body.
+    #[error("Unexpected HTTP {} response without a Circle error payload", .response.status)]
+    UnexpectedResponse {
+        /// The offending response.
+        response: Box<ResponseDetails>,
+    },
+
+    /// A 2xx response body could not be decoded into the expected type.
+    #[error("Failed to decode response at `{path}`: {source}")]
+    Decode {
+        /// JSON path of the value that failed to decode, e.g. `data.wallets[0].state`.
+        path: String,
+        /// The underlying JSON error.
+        source: serde_json::Error,
+        /// The response that failed to decode.
+        response: Box<ResponseDetails>,
+    },
+
+    /// A caller-supplied parameter was invalid before the request was sent.
+    #[error("Invalid parameter: {0}")]
+    InvalidParam(String),
+
+    /// A middleware or transport rejected the request without sending it,
+    /// e.g. an open circuit breaker or a request a `MockTransport` did not
+    /// expect.
+    #[error("Middleware error: {0}")]
+    Middleware(String),
+}
+
+impl Error {
+    /// Returns `true` if the failure is transient and the same request may
+    /// succeed when sent again: transport errors, and responses with a `408`,
+    /// `429` or retryable `5xx` status.
+    ///
+    /// This only classifies the failure; whether a request is *safe* to
+    /// repeat also depends on it being a `GET` or carrying an idempotency key.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Http { .. } => true,
+            Self::Api { response, .. } | Self::UnexpectedResponse { response } => {
+                hpx::StatusCode::from_u16(response.status)
+                    .is_ok_and(crate::retry::is_retryable_status)
+            }
+            Self::Decode { .. } | Self::InvalidParam(_) | Self::Middleware(_) => false,
+        }
+    }
+
+    /// A short, stable name for the kind of failure (`"http"`, `"api"`,
+    /// `"unexpected_response"`, `"decode"`, `"invalid_param"` or
+    /// `"middleware"`), as recorded
+    /// in tracing spans.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Http { .. } => "http",
+            Self::Api { .. } => "api",
+            Self::UnexpectedResponse { .. } => "unexpected_response",
+            Self::Decode { .. } => "decode",
+            Self::InvalidParam(_) => "invalid_param",
+            Self::Middleware(_) => "middleware",
+        }
+    }
+
+    /// The typed Circle error code, for [`Error::Api`] errors.
+    pub const fn error_code(&self) -> Option<CircleErrorCode> {
+        match self {
+            Self::Api { code, .. } => Some(CircleErrorCode::from_code(*code)),
+            _ => None,
+        }
+    }
+
+    /// The response this error was produced from, if one was received.
+    pub fn response(&self) -> Option<&ResponseDetails> {
+        match self {
+            Self::Api { response, .. } |
+            Self::UnexpectedResponse { response } |
+            Self::Decode { response, .. } => Some(response),
+            Self::Http { .. } | Self::InvalidParam(_) | Self::Middleware(_) => None,
+        }
+    }
+
+    /// Returns `true` if Circle rejected the request because its idempotency
+    /// key was already used for a different request.
+    ///
+    /// Circle documents no error code for this; the response is a `409`.
+    pub fn is_idempotency_conflict(&self) -> bool {
+        matches!(self, Self::Api { response, .. } if response.status == 409)
+    }
+
+    /// HTTP status of the response, if one was received.
+    pub fn status(&self) -> Option<u16> {
+        self.response().map(|response| response.status)
+    }
+
+    /// The `X-Request-Id` sent with the failed request, if it was sent.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::Http { request_id, .. } => request_id.as_deref(),
+            _ => self.response().map(|response| response.request_id.as_str()),
+        }
+    }
+
+    /// The `X-Request-Id` Circle echoed back on the response, if any.
+    pub fn server_request_id(&self) -> Option<&str> {
+        self.response().and_then(|response| response.server_request_id.as_deref())
+    }
+}
+
+/// The parts of an HTTP response kept for diagnosing a failed request.
+#[derive(Debug, Clone)]
+pub struct ResponseDetails {
+    /// HTTP status code.
+    pub status: u16,
+    /// `X-Request-Id` sent with the request.
+    pub request_id: String,
+    /// `X-Request-Id` echoed back by the server, if present.
+    pub server_request_id: Option<String>,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// Raw response body (lossily decoded as UTF-8).
+    pub body: String,
+}
+
+/// Error response body returned by the Circle API on non-2xx status codes.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ApiErrorBody {
+    /// Numeric error code.
+    pub code: i32,
+    /// Human-readable error message.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(status: u16) -> Box<ResponseDetails> {
+        Box::new(ResponseDetails {
+            status,
+            request_id: "sent-id".to_string(),
+            server_request_id: Some("echoed-id".to_string()),
+            headers: HeaderMap::new(),
+            body: String::new(),
+        })
+    }
+
+    #[test]
+    fn api_error_body_deserializes() -> Result<(), Box<dyn std::error::Error>> {
+        let body: ApiErrorBody =
+            serde_json::from_str(r#"{"code": 156004, "message": "Cannot find the wallet."}"#)?;
+        assert_eq!(body.code, 156_004);
+        assert_eq!(body.message, "Cannot find the wallet.");
+        Ok(())
+    }
+
+    #[test]
+    fn api_error_displays_code_and_message() {
+        let err =
+            Error::Api { code: 2, message: "Invalid entity".to_string(), response: details(400) };
+        assert_eq!(err.to_string(), "Circle API error 2: Invalid entity");
+        assert_eq!(err.status(), Some(400));
+        assert_eq!(err.request_id(), Some("sent-id"));
+        assert_eq!(err.server_request_id(), Some("echoed-id"));
+        assert_eq!(err.error_code(), Some(CircleErrorCode::ApiParameterInvalid));
+    }
+
+    #[test]
+    fn unexpected_response_displays_status() {
+        let err = Error::UnexpectedResponse { response: details(502) };
+        assert_eq!(err.to_string(), "Unexpected HTTP 502 response without a Circle error payload");
+    }
+
+    #[test]
+    fn transport_error_keeps_request_id() {
+        let err =
+            Error::Http { message: "timed out".to_string(), request_id: Some("id".to_string()) };
+        assert_eq!(err.request_id(), Some("id"));
+        assert_eq!(err.status(), None);
+        assert!(err.response().is_none());
+    }
+
+    #[test]
+    fn classifies_retryable_errors() {
+        let api =
+            |status| Error::Api { code: 0, message: String::new(), response: details(status) };
+        assert!(
+            Error::Http { message: "connection reset".to_string(), request_id: None }
+                .is_retryable()
+        );
+        assert!(api(429).is_retryable());
+        assert!(api(503).is_retryable());
+        assert!(Error::UnexpectedResponse { response: details(504) }.is_retryable());
+        assert!(!Error::UnexpectedResponse { response: details(404) }.is_retryable());
+        assert!(!api(400).is_retryable());
+        assert!(!api(404).is_retryable());
+        assert!(!Error::InvalidParam("bad".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn idempotency_conflicts_are_409_api_errors() {
+        let api =
+            |status| Error::Api { code: -1, message: String::new(), response: details(status) };
+        assert!(api(409).is_idempotency_conflict());
+        assert!(!api(400).is_idempotency_conflict());
+        assert!(!Error::UnexpectedResponse { response: details(409) }.is_idempotency_conflict());
+    }
+}
@@ -0,0 +1,321 @@
+//! Typed Circle Web3 Services error codes.
+//!
+//! Circle reports failures as a numeric `code` in the error payload (and in
+//! `Challenge.errorCode` for failed user challenges). [`CircleErrorCode`]
+//! names the codes documented for the W3S APIs and keeps any other value in
+//! [`CircleErrorCode::Unknown`], so new codes never break deserialization.
+
+/// Broad classification of a [`CircleErrorCode`], useful for deciding how to
+/// react to a failure without matching on individual codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The request itself was malformed or violated a constraint.
+    Request,
+    /// The API key, user token or session was missing, invalid or expired.
+    Authentication,
+    /// The referenced resource does not exist.
+    NotFound,
+    /// The end-user's state (PIN, security questions, lock) prevents the action.
+    User,
+    /// The wallet's state prevents the action.
+    Wallet,
+    /// The wallet holds too little of the asset (or of the fee token).
+    Balance,
+    /// The transaction was rejected during submission or on chain.
+    Transaction,
+    /// A transient condition; the same request may succeed later.
+    Transient,
+    /// A code this SDK does not know about.
+    Unknown,
+}
+
+impl ErrorCategory {
+    /// A short, generic suggestion for resolving errors in this category.
+    pub const fn remediation_hint(self) -> &'static str {
+        match self {
+            Self::Request => "Fix the request parameters; repeating it unchanged will fail again.",
+            Self::Authentication => {
+                "Check the API key, refresh the user token or re-authenticate the user."
+            }
+            Self::NotFound => "Verify the ID and that it belongs to this entity and environment.",
+            Self::User => "Guide the user through the required PIN or security-question flow.",
+            Self::Wallet => "Check the wallet's state and limits before retrying.",
+            Self::Balance => "Fund the wallet (including the native token for fees) and retry.",
+            Self::Transaction => {
+                "Inspect the transaction parameters and chain conditions, then resubmit."
+            }
+            Self::Transient => "Retry the request later, with the same idempotency key.",
+            Self::Unknown => "Consult the Circle error reference and include the request id.",
+        }
+    }
+}
+
+macro_rules! circle_error_codes {
+    ($( $(#[doc = $doc:literal])+ $variant:ident = $code:literal => $category:ident, )+) => {
+        /// A numeric error code returned by the Circle Web3 Services APIs.
+        ///
+        /// Serializes to and from the bare integer. Codes not listed here are
+        /// preserved as [`CircleErrorCode::Unknown`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum CircleErrorCode {
+            $( $(#[doc = $doc])+ $variant, )+
+            /// A code not known to this version of the SDK.
+            Unknown(i32),
+        }
+
+        impl CircleErrorCode {
+            /// Maps a raw code to its typed variant.
+            pub const fn from_code(code: i32) -> Self {
+                match code {
+                    $( $code => Self::$variant, )+
+                    other => Self::Unknown(other),
+                }
+            }
+
+            /// The raw numeric code.
+            pub const fn code(self) -> i32 {
+                match self {
+                    $( Self::$variant => $code, )+
+                    Self::Unknown(code) => code,
+                }
+            }
+
+            /// The broad category of this code.
+            pub const fn category(self) -> ErrorCategory {
+                match self {
+                    $( Self::$variant => ErrorCategory::$category, )+
+                    Self::Unknown(_) => ErrorCategory::Unknown,
+                }
+            }
+        }
+    };
+}
+
+circle_error_codes! {
+    /// Something went wrong on Circle's side.
+    UnknownError = -1 => Transient,
+    /// A required API parameter is missing.
+    ApiParameterMissing = 1 => Request,
+    /// An API parameter is invalid.
+    ApiParameterInvalid = 2 => Request,
+    /// The API key is not allowed to perform this action.
+    Forbidden = 3 => Authentication,
+    /// The request could not be authenticated.
+    Unauthorized = 4 => Authentication,
+    /// The request should be retried.
+    Retry = 9 => Transient,
+    /// The customer account is suspended.
+    CustomerSuspended = 10 => Authentication,
+    /// The request is still pending.
+    Pending = 11 => Transient,
+    /// The session is invalid.
+    InvalidSession = 12 => Authentication,
+    /// The partner ID is invalid.
+    InvalidPartnerId = 13 => Authentication,
+    /// The message is invalid.
+    InvalidMessage = 14 => Request,
+    /// The phone number is invalid.
+    InvalidPhone = 15 => Request,
+    /// A user with this ID already exists.
+    UserAlreadyExisted = 155_101 => User,
+    /// The user does not exist.
+    UserNotFound = 155_102 => NotFound,
+    /// The user token does not exist.
+    UserTokenNotFound = 155_103 => NotFound,
+    /// The user token has expired.
+    UserTokenExpired = 155_104 => Authentication,
+    /// The user token is invalid.
+    InvalidUserToken = 155_105 => Authentication,
+    /// The user has already been initialized.
+    UserWasInitialized = 155_106 => User,
+    /// The user has already set a PIN.
+    UserHasSetPin = 155_107 => User,
+    /// The user has already set security questions.
+    UserHasSetSecurityQuestion = 155_108 => User,
+    /// The user has been disabled.
+    UserWasDisabled = 155_109 => User,
+    /// The user has not set a PIN yet.
+    UserDoesNotSetPinYet = 155_110 => User,
+    /// The user has not set security questions yet.
+    UserDoesNotSetSecurityQuestionYet = 155_111 => User,
+    /// The PIN entered is incorrect.
+    IncorrectUserPin = 155_112 => User,
+    /// The device ID is incorrect.
+    IncorrectDeviceId = 155_113 => Request,
+    /// The app ID is incorrect.
+    IncorrectAppId = 155_114 => Request,
+    /// The security answers are incorrect.
+    IncorrectSecurityAnswers = 155_115 => User,
+    /// The challenge ID is invalid.
+    InvalidChallengeId = 155_116 => NotFound,
+    /// The approval content is invalid.
+    InvalidApproveContent = 155_117 => Request,
+    /// The encryption key is invalid.
+    InvalidEncryptionKey = 155_118 => Request,
+    /// The user's PIN is locked after too many failed attempts.
+    UserPinLocked = 155_119 => User,
+    /// The user's security answers are locked after too many failed attempts.
+    SecurityAnswersLocked = 155_120 => User,
+    /// The wallet does not hold enough funds.
+    NotEnoughFunds = 155_201 => Balance,
+    /// The wallet balance is insufficient for the amount.
+    NotEnoughBalance = 155_202 => Balance,
+    /// The amount exceeds the withdrawal limit.
+    ExceedWithdrawLimit = 155_203 => Transaction,
+    /// A minimum amount of funds is required.
+    MinimumFundsRequired = 155_204 => Balance,
+    /// The transaction fee is invalid.
+    InvalidTransactionFee = 155_205 => Transaction,
+    /// The transaction was rejected by AML screening.
+    RejectedOnAmlScreening = 155_206 => Transaction,
+    /// A destination tag or memo is required.
+    TagRequired = 155_207 => Transaction,
+    /// The gas limit is too low.
+    GasLimitTooLow = 155_208 => Transaction,
+    /// The transaction data is not encoded properly.
+    TransactionDataNotEncodedProperly = 155_209 => Transaction,
+    /// The blockchain node returned an error.
+    FullNodeReturnedError = 155_210 => Transaction,
+    /// The wallet must be set up first.
+    WalletSetupRequired = 155_211 => Wallet,
+    /// The balance would fall below the chain's minimum account balance.
+    LowerThanMinimumAccountBalance = 155_212 => Balance,
+    /// The blockchain rejected the transaction.
+    RejectedByBlockchain = 155_213 => Transaction,
+    /// The transaction was dropped in a chain reorganisation.
+    DroppedAsPartOfReorg = 155_214 => Transaction,
+    /// The operation is not supported.
+    OperationNotSupported = 155_215 => Request,
+    /// The amount is below the minimum.
+    AmountBelowMinimum = 155_216 => Transaction,
+    /// The number of NFT token IDs is wrong.
+    WrongNftTokenIdNumber = 155_217 => Request,
+    /// The destination address is invalid.
+    InvalidDestinationAddress = 155_218 => Request,
+    /// The token and wallet are on different chains.
+    TokenWalletChainMismatch = 155_219 => Request,
+    /// The number of amounts is wrong.
+    WrongAmountsNumber = 155_220 => Request,
+    /// The wallet is frozen.
+    WalletIsFrozen = 155_501 => Wallet,
+    /// The maximum number of wallets has been reached.
+    MaxWalletLimitReached = 155_502 => Wallet,
+    /// `walletSetId` cannot be combined with the other parameters given.
+    WalletSetIdMutuallyExclusive = 155_503 => Request,
+    /// The metadata does not match the wallets requested.
+    MetadataUnmatched = 155_504 => Request,
+    /// The wallet does not exist.
+    WalletIdNotFound = 156_001 => NotFound,
+    /// The token does not exist.
+    TokenIdNotFound = 156_002 => NotFound,
+    /// The transaction does not exist.
+    TransactionIdNotFound = 156_003 => NotFound,
+    /// The wallet set does not exist.
+    WalletSetIdNotFound = 156_004 => NotFound,
+}
+
+impl CircleErrorCode {
+    /// `true` if the referenced resource (user, wallet, token, ...) does not exist.
+    pub const fn is_not_found(self) -> bool {
+        matches!(self.category(), ErrorCategory::NotFound)
+    }
+
+    /// `true` if the API key, user token or session was rejected.
+    pub const fn is_auth(self) -> bool {
+        matches!(self.category(), ErrorCategory::Authentication)
+    }
+
+    /// `true` if the wallet does not hold enough of the asset or fee token.
+    pub const fn is_insufficient_balance(self) -> bool {
+        matches!(self.category(), ErrorCategory::Balance)
+    }
+
+    /// A short suggestion for resolving this error.
+    pub const fn remediation_hint(self) -> &'static str {
+        match self {
+            Self::UserTokenExpired | Self::InvalidUserToken => {
+                "Request a fresh user token and retry."
+            }
+            Self::UserPinLocked | Self::SecurityAnswersLocked => {
+                "Wait for the lock to expire or restore access via the recovery flow."
+            }
+            Self::GasLimitTooLow => "Raise the gas limit or use a fee level instead.",
+            _ => self.category().remediation_hint(),
+        }
+    }
+}
+
+impl From<i32> for CircleErrorCode {
+    fn from(code: i32) -> Self {
+        Self::from_code(code)
+    }
+}
+
+impl From<CircleErrorCode> for i32 {
+    fn from(code: CircleErrorCode) -> Self {
+        code.code()
+    }
+}
+
+impl std::fmt::Display for CircleErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown(code) => write!(f, "{code}"),
+            known => write!(f, "{} ({known:?})", known.code()),
+        }
+    }
+}
+
+impl serde::Serialize for CircleErrorCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.code())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CircleErrorCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i32::deserialize(deserializer).map(Self::from_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_known_and_unknown_codes() {
+        for code in [-1, 2, 155_119, 156_001, 999_999] {
+            assert_eq!(CircleErrorCode::from_code(code).code(), code);
+        }
+        assert_eq!(CircleErrorCode::from_code(155_119), CircleErrorCode::UserPinLocked);
+        assert_eq!(CircleErrorCode::from_code(999_999), CircleErrorCode::Unknown(999_999));
+        assert_eq!(CircleErrorCode::from_code(404), CircleErrorCode::Unknown(404));
+    }
+
+    #[test]
+    fn serde_uses_bare_integer() -> Result<(), Box<dyn std::error::Error>> {
+        let code: CircleErrorCode = serde_json::from_str("155201")?;
+        assert_eq!(code, CircleErrorCode::NotEnoughFunds);
+        assert_eq!(serde_json::to_string(&code)?, "155201");
+        let unknown: CircleErrorCode = serde_json::from_str("123")?;
+        assert_eq!(serde_json::to_string(&unknown)?, "123");
+        Ok(())
+    }
+
+    #[test]
+    fn predicates_follow_categories() {
+        assert!(CircleErrorCode::WalletIdNotFound.is_not_found());
+        assert!(CircleErrorCode::UserNotFound.is_not_found());
+        assert!(CircleErrorCode::InvalidUserToken.is_auth());
+        assert!(CircleErrorCode::Unauthorized.is_auth());
+        assert!(CircleErrorCode::NotEnoughBalance.is_insufficient_balance());
+        assert!(!CircleErrorCode::Unknown(7).is_not_found());
+        assert_eq!(CircleErrorCode::Unknown(7).category(), ErrorCategory::Unknown);
+    }
+
+    #[test]
+    fn displays_code_and_name() {
+        assert_eq!(CircleErrorCode::UserPinLocked.to_string(), "155119 (UserPinLocked)");
+        assert_eq!(CircleErrorCode::Unknown(42).to_string(), "42");
+    }
+}
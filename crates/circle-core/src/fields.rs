@@ -0,0 +1,134 @@
+//! Cross-field rules of request bodies.
+//!
+//! Several Circle requests take groups of fields that only make sense in
+//! certain combinations — a token id *or* a blockchain, a fee level *or*
+//! explicit gas settings. Circle answers a wrong combination with a generic
+//! 400; the request builders of the API crates check these rules first and
+//! fail with an [`Error::InvalidParam`] naming the fields involved.
+
+use crate::Error;
+
+const fn invalid(message: String) -> Result<(), Error> {
+    Err(Error::InvalidParam(message))
+}
+
+/// Checks that at most one of two mutually exclusive fields is set — exactly
+/// one if `required`. Each field is given as its name and whether it is set.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidParam`] if both are set, or neither when one is
+/// required.
+pub fn exclusive(first: (&str, bool), second: (&str, bool), required: bool) -> Result<(), Error> {
+    match (first, second) {
+        ((a, true), (b, true)) => invalid(format!("`{a}` and `{b}` cannot both be set")),
+        ((a, false), (b, false)) if required => {
+            invalid(format!("one of `{a}` or `{b}` is required"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks that `field` is only set together with `dependency`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidParam`] if `field` is set without `dependency`.
+pub fn requires(field: (&str, bool), dependency: (&str, bool)) -> Result<(), Error> {
+    match (field, dependency) {
+        ((a, true), (b, false)) => invalid(format!("`{a}` requires `{b}`")),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the fee settings of a transaction request, given which fields are
+/// set: either `fee_level`, or `gas_limit` with `gas_price` (chains without
+/// EIP-1559), or `gas_limit` with `max_fee` and `priority_fee`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidParam`] naming the first field out of place.
+#[expect(clippy::fn_params_excessive_bools, reason = "one flag per fee field of the request")]
+pub fn fees(
+    fee_level: bool,
+    gas_limit: bool,
+    gas_price: bool,
+    max_fee: bool,
+    priority_fee: bool,
+) -> Result<(), Error> {
+    let explicit = [
+        ("gas_limit", gas_limit),
+        ("gas_price", gas_price),
+        ("max_fee", max_fee),
+        ("priority_fee", priority_fee),
+    ];
+    if fee_level {
+        if let Some((name, _)) = explicit.iter().find(|(_, set)| *set) {
+            return invalid(format!("`fee_level` and `{name}` cannot both be set"));
+        }
+        return Ok(());
+    }
+    if !explicit.iter().any(|(_, set)| *set) {
+        return invalid("one of `fee_level` or `gas_limit` is required".into());
+    }
+    exclusive(("gas_price", gas_price), ("max_fee", max_fee), false)?;
+    exclusive(("gas_price", gas_price), ("priority_fee", priority_fee), false)?;
+    requires(("max_fee", max_fee), ("priority_fee", priority_fee))?;
+    requires(("priority_fee", priority_fee), ("max_fee", max_fee))?;
+    requires(("gas_price", gas_price), ("gas_limit", gas_limit))?;
+    requires(("max_fee", max_fee), ("gas_limit", gas_limit))?;
+    if !gas_price && !max_fee {
+        return invalid("`gas_limit` requires `gas_price`, or `max_fee` and `priority_fee`".into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(result: Result<(), Error>) -> String {
+        match result {
+            Err(Error::InvalidParam(message)) => message,
+            other => format!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn exclusive_fields_name_both_sides() {
+        assert!(exclusive(("a", true), ("b", false), true).is_ok());
+        assert!(exclusive(("a", false), ("b", false), false).is_ok());
+        assert_eq!(
+            message(exclusive(("a", true), ("b", true), false)),
+            "`a` and `b` cannot both be set"
+        );
+        assert_eq!(
+            message(exclusive(("a", false), ("b", false), true)),
+            "one of `a` or `b` is required"
+        );
+        assert_eq!(message(requires(("a", true), ("b", false))), "`a` requires `b`");
+    }
+
+    #[test]
+    fn fee_level_or_a_complete_set_of_gas_fields() {
+        assert!(fees(true, false, false, false, false).is_ok());
+        assert!(fees(false, true, true, false, false).is_ok());
+        assert!(fees(false, true, false, true, true).is_ok());
+
+        let cases = [
+            ((true, true, false, false, false), "`fee_level` and `gas_limit` cannot both be set"),
+            ((true, false, false, true, true), "`fee_level` and `max_fee` cannot both be set"),
+            ((false, false, false, false, false), "one of `fee_level` or `gas_limit` is required"),
+            ((false, true, true, true, true), "`gas_price` and `max_fee` cannot both be set"),
+            ((false, true, false, true, false), "`max_fee` requires `priority_fee`"),
+            ((false, false, true, false, false), "`gas_price` requires `gas_limit`"),
+            (
+                (false, true, false, false, false),
+                "`gas_limit` requires `gas_price`, or `max_fee` and `priority_fee`",
+            ),
+        ];
+        for ((level, limit, price, max, priority), expected) in cases {
+            assert_eq!(message(fees(level, limit, price, max, priority)), expected);
+        }
+    }
+}
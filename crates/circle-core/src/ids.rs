@@ -0,0 +1,259 @@
+//! Typed identifiers of Circle resources.
+//!
+//! Each id is its own type, so a wallet set id cannot be passed where a
+//! wallet id is expected. All of them serialize as the bare string Circle
+//! uses. The system-generated ids wrap a [`Uuid`] and reject anything else
+//! on parsing and deserialization; [`UserId`] is chosen by the developer and
+//! only wraps a string.
+//!
+//! [`IdempotencyKey`] is typed the same way. Besides random keys it can be
+//! derived from a business identifier, so a request retried after a restart
+//! carries the same key and Circle deduplicates it:
+//!
+//! ```
+//! use circle_core::ids::IdempotencyKey;
+//!
+//! let payouts = IdempotencyKey::namespace("payouts");
+//! let key = IdempotencyKey::derive(&payouts, "payout-2024-0042");
+//! assert_eq!(key, IdempotencyKey::derive(&payouts, "payout-2024-0042"));
+//! ```
+
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+pub use uuid::Uuid;
+
+/// A string that is not a valid id of the given kind.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind}: {value:?} is not a UUID")]
+pub struct InvalidId {
+    /// Which id was being parsed, e.g. `"wallet id"`.
+    pub kind: &'static str,
+    /// The rejected input.
+    pub value: String,
+}
+
+impl From<InvalidId> for crate::Error {
+    fn from(error: InvalidId) -> Self {
+        Self::InvalidParam(error.to_string())
+    }
+}
+
+macro_rules! uuid_ids {
+    ($( $(#[doc = $doc:literal])+ $name:ident => $kind:literal, )+) => { $(
+        $(#[doc = $doc])+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(Uuid);
+
+        impl $name {
+            /// Wraps an existing UUID.
+            pub const fn new(uuid: Uuid) -> Self {
+                Self(uuid)
+            }
+
+            /// The underlying UUID.
+            pub const fn as_uuid(&self) -> &Uuid {
+                &self.0
+            }
+        }
+
+        impl From<Uuid> for $name {
+            fn from(uuid: Uuid) -> Self {
+                Self(uuid)
+            }
+        }
+
+        impl From<$name> for Uuid {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = InvalidId;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Uuid::try_parse(value)
+                    .map(Self)
+                    .map_err(|_| InvalidId { kind: $kind, value: value.to_string() })
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.hyphenated().fmt(f)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                value.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    )+ };
+}
+
+uuid_ids! {
+    /// Identifier of a wallet.
+    WalletId => "wallet id",
+    /// Identifier of a wallet set.
+    WalletSetId => "wallet set id",
+    /// Identifier of a transaction.
+    TransactionId => "transaction id",
+    /// Identifier of a token known to Circle.
+    TokenId => "token id",
+    /// Identifier of a user challenge.
+    ChallengeId => "challenge id",
+    /// Identifier of a transfer indexed by Buidl wallets.
+    TransferId => "transfer id",
+    /// Identifier of a user operation indexed by Buidl wallets.
+    UserOpId => "user operation id",
+    /// Idempotency key of a mutating request: Circle performs a request once
+    /// per key and answers repeats with the original result.
+    IdempotencyKey => "idempotency key",
+}
+
+impl IdempotencyKey {
+    /// A new random (UUIDv4) key.
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// The key of `business_id` in `namespace`: the UUIDv5 of the id, so the
+    /// same pair always gives the same key.
+    ///
+    /// Use one namespace per kind of operation, from
+    /// [`IdempotencyKey::namespace`] or a UUID of your own, so that a payout
+    /// and a refund sharing a business id get different keys.
+    pub fn derive(namespace: &Uuid, business_id: &str) -> Self {
+        Self(Uuid::new_v5(namespace, business_id.as_bytes()))
+    }
+
+    /// A namespace UUID for [`IdempotencyKey::derive`], itself derived from
+    /// `name` (e.g. `"payouts"`).
+    pub fn namespace(name: &str) -> Uuid {
+        let root =
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, b"https://github.com/longcipher/circle-sdk-rs");
+        Uuid::new_v5(&root, name.as_bytes())
+    }
+}
+
+/// Identifier of an end user.
+///
+/// Unlike the other ids this one is not generated by Circle: the developer
+/// picks it when creating the user, and Circle accepts any string of 5 to 50
+/// characters. It is therefore not validated as a UUID, so that existing
+/// users with other ids keep working.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UserId(String);
+
+impl UserId {
+    /// Wraps a user id.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The id as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for UserId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for UserId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl From<Uuid> for UserId {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid.to_string())
+    }
+}
+
+impl FromStr for UserId {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from(value))
+    }
+}
+
+impl AsRef<str> for UserId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "c4d1da72-111e-4d52-bdbf-2e74a2d803d5";
+
+    #[test]
+    fn uuid_ids_round_trip_as_bare_strings() -> Result<(), Box<dyn std::error::Error>> {
+        let id: WalletId = serde_json::from_str(&format!("\"{ID}\""))?;
+        assert_eq!(id.to_string(), ID);
+        assert_eq!(serde_json::to_string(&id)?, format!("\"{ID}\""));
+        assert_eq!(ID.parse::<WalletId>()?, id);
+        assert_eq!(WalletId::from(*id.as_uuid()), id);
+        Ok(())
+    }
+
+    #[test]
+    fn uuid_ids_reject_other_strings() {
+        let error = "wallet-1".parse::<TransactionId>().err();
+        assert_eq!(
+            error,
+            Some(InvalidId { kind: "transaction id", value: "wallet-1".to_string() })
+        );
+        assert!(serde_json::from_str::<TokenId>("\"wallet-1\"").is_err());
+    }
+
+    #[test]
+    fn user_ids_accept_any_string() -> Result<(), Box<dyn std::error::Error>> {
+        let id: UserId = serde_json::from_str("\"customer-42\"")?;
+        assert_eq!(id.as_str(), "customer-42");
+        assert_eq!(serde_json::to_string(&id)?, "\"customer-42\"");
+        Ok(())
+    }
+
+    #[test]
+    fn idempotency_keys_derive_deterministically() -> Result<(), Box<dyn std::error::Error>> {
+        let payouts = IdempotencyKey::namespace("payouts");
+        let key = IdempotencyKey::derive(&payouts, "payout-42");
+        assert_eq!(key, IdempotencyKey::derive(&payouts, "payout-42"));
+        assert_eq!(key.as_uuid().get_version_num(), 5);
+        assert_ne!(key, IdempotencyKey::derive(&payouts, "payout-43"));
+        assert_ne!(key, IdempotencyKey::derive(&IdempotencyKey::namespace("refunds"), "payout-42"));
+        assert_ne!(IdempotencyKey::random(), IdempotencyKey::random());
+
+        let namespace: Uuid = "6ba7b810-9dad-11d1-80b4-00c04fd430c8".parse()?;
+        assert_eq!(
+            IdempotencyKey::derive(&namespace, "python.org").to_string(),
+            "886313e1-3b8a-5372-9b90-0c9aee199e5d"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+//! Shared building blocks for the Circle Web3 Services SDK crates.
+//!
+//! This crate owns the HTTP pipeline used by every API-specific client in the
+//! workspace, so that authentication, request IDs, JSON encoding and error
+//! mapping behave identically across all of them.
+//!
+//! ## Contents
+//!
+//! | Module | Functionality |
+//! |--------|---------------|
+//! | [`address`] | Offline, chain-aware address validation and normalization ([`Address`]) |
+//! | [`amount`] | Exact decimal token amounts ([`TokenAmount`]) and base-unit conversion |
+//! | [`api_enum`](mod@api_enum) | Forward-compatible API enums ([`ApiEnum`], [`api_enum!`]) |
+//! | [`builder`] | Client configuration ([`ClientBuilder`]): timeouts, proxy, TLS roots |
+//! | [`cassette`] | Record/replay [`CassetteTransport`] for offline tests |
+//! | [`client`] | Authenticated request pipeline ([`RestClient`]) |
+//! | [`fields`] | Cross-field rules checked by the request builders |
+//! | [`error_code`] | Typed Circle error codes ([`CircleErrorCode`]) and categories |
+//! | [`ids`] | Typed resource ids ([`WalletId`], [`TransactionId`], [`UserId`], …) and [`IdempotencyKey`]s |
+//! | [`middleware`] | Request/response [`Middleware`] chain and stock middlewares |
+//! | [`network`] | Chain registry ([`Network`]): testnets, EVM chain ids, native currencies, explorers |
+//! | [`pagination`] | Auto-paginating list streams ([`paginate`], [`StreamOptions`], [`collect_all`]) |
+//! | [`options`] | Per-call overrides ([`RequestOptions`]): request id, timeout, headers, retries |
+//! | [`response`] | Response metadata ([`ApiResponse`], [`ResponseMeta`]) |
+//! | [`retry`] | Retry policy for transient failures ([`RetryPolicy`]) |
+//! | [`timestamp`] | Fixed-format serialization of date-range filters |
+//! | [`transport`] | Pluggable [`HttpTransport`], [`HpxTransport`] and the in-memory [`MockTransport`] |
+//! | [`unknown_fields`] | Reporting of response fields the models do not declare ([`on_unknown_field`]) |
+//! | [`error`] | The shared [`Error`] type and the Circle error payload |
+//!
+//! Most users do not depend on this crate directly; the API crates
+//! (`circle-developer-controlled-wallets`, `circle-user-controlled-wallets`,
+//! `circle-compliance`, `circle-buidl-wallets`) re-export everything needed.
+
+#![deny(missing_docs)]
+
+pub mod address;
+pub mod amount;
+pub mod api_enum;
+pub mod builder;
+pub mod cassette;
+pub mod client;
+pub mod error;
+pub mod error_code;
+pub mod fields;
+pub mod ids;
+pub mod middleware;
+pub mod network;
+pub mod options;
+pub mod pagination;
+pub mod response;
+pub mod retry;
+pub mod timestamp;
+pub mod transport;
+pub mod unknown_fields;
+
+pub use address::{Address, AddressError};
+pub use amount::{AmountError, BigUint, TokenAmount};
+pub use api_enum::ApiEnum;
+pub use builder::{ClientBuilder, DEFAULT_TIMEOUT, DEFAULT_USER_AGENT};
+pub use cassette::{CassetteTransport, REDACTED};
+pub use client::{DEFAULT_BASE_URL, RequestBuilder, RequestPath, RestClient};
+pub use error::{ApiErrorBody, Error, ResponseDetails};
+pub use error_code::{CircleErrorCode, ErrorCategory};
+pub use ids::{
+    ChallengeId, IdempotencyKey, InvalidId, TokenId, TransactionId, UserId, WalletId, WalletSetId,
+};
+pub use middleware::{
+    AuditLogMiddleware, BoxFuture, CircuitBreakerMiddleware, HttpRequest, HttpResponse,
+    MetricsMiddleware, Middleware, Next, RequestMetrics,
+};
+pub use network::{Explorer, NativeCurrency, Network, UnsupportedNetwork};
+pub use options::RequestOptions;
+pub use pagination::{PageItem, Paginated, StreamOptions, collect_all, paginate};
+pub use response::{ApiResponse, ResponseMeta};
+pub use retry::RetryPolicy;
+pub use transport::{Expectation, HpxTransport, HttpTransport, Method, MockTransport};
+pub use unknown_fields::{UnknownField, on_unknown_field};
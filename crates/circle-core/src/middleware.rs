@@ -0,0 +1,552 @@
+//! Request/response middleware shared by every Circle API client.
+//!
+//! A [`Middleware`] wraps each attempt of each request: it receives the fully
+//! prepared [`HttpRequest`] (with the `Authorization`, `X-Request-Id` and
+//! `User-Agent` headers already set) and a [`Next`] handle to pass it on.
+//! It can rewrite the request, inspect or replace the [`HttpResponse`], or
+//! answer without calling [`Next::run`] at all. Middlewares are registered
+//! with [`ClientBuilder::middleware`](crate::ClientBuilder::middleware) and
+//! run in registration order, the first one outermost.
+//!
+//! ```no_run
+//! use circle_core::{BoxFuture, Error, HttpRequest, HttpResponse, Middleware, Next, RestClient};
+//!
+//! struct Tenant(&'static str);
+//!
+//! impl Middleware for Tenant {
+//!     fn handle<'a>(
+//!         &'a self,
+//!         mut request: HttpRequest,
+//!         next: Next<'a>,
+//!     ) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+//!         Box::pin(async move {
+//!             if let Ok(value) = self.0.parse() {
+//!                 request.headers.insert("X-Tenant", value);
+//!             }
+//!             next.run(request).await
+//!         })
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! let client: RestClient =
+//!     RestClient::builder("your_api_key").middleware(Tenant("acme")).build()?;
+//! # let _ = client;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Stock middlewares: [`MetricsMiddleware`] reports every attempt to a
+//! callback, [`AuditLogMiddleware`] logs mutating calls and
+//! [`CircuitBreakerMiddleware`] stops calling Circle after repeated failures.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use hpx::header::HeaderMap;
+
+use crate::{client::RequestPath, error::Error, transport::HttpTransport};
+
+/// A boxed, `Send` future, as returned by [`Middleware::handle`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A single attempt of a request, as passed through the middleware chain.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    /// HTTP method.
+    pub method: hpx::Method,
+    /// Base URL the path is appended to.
+    pub base_url: String,
+    /// Request path and the template it was built from.
+    pub path: RequestPath,
+    /// Encoded query string, without the leading `?`.
+    pub query: Option<String>,
+    /// Request headers; credentials are marked sensitive and hidden from `Debug`.
+    pub headers: HeaderMap,
+    /// Encoded JSON body.
+    pub body: Option<Vec<u8>>,
+    /// Total timeout of this attempt.
+    pub timeout: Duration,
+    /// Attempt number, starting at 1.
+    pub attempt: u32,
+}
+
+impl HttpRequest {
+    /// The full URL, including the query string.
+    pub fn url(&self) -> String {
+        let mut url = format!("{}{}", self.base_url, self.path.as_str());
+        if let Some(query) = self.query.as_deref().filter(|q| !q.is_empty()) {
+            url.push('?');
+            url.push_str(query);
+        }
+        url
+    }
+
+    /// The `X-Request-Id` header sent with this request.
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get("X-Request-Id").and_then(|value| value.to_str().ok())
+    }
+
+    /// The decoded query string parameters, in order.
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        self.query
+            .as_deref()
+            .and_then(|query| serde_urlencoded::from_str(query).ok())
+            .unwrap_or_default()
+    }
+
+    /// The body parsed as JSON, if there is one and it is valid JSON.
+    pub fn json(&self) -> Option<serde_json::Value> {
+        self.body.as_deref().and_then(|body| serde_json::from_slice(body).ok())
+    }
+}
+
+/// A raw HTTP response, before it is decoded.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// Raw response body.
+    pub body: Vec<u8>,
+}
+
+/// Code run around every attempt of every request.
+pub trait Middleware: Send + Sync + 'static {
+    /// Handles `request`, usually by calling `next.run(request)`.
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<HttpResponse, Error>>;
+}
+
+/// The rest of the middleware chain, ending with the [`HttpTransport`].
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn HttpTransport,
+}
+
+impl std::fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Next").field("remaining", &self.middlewares.len()).finish_non_exhaustive()
+    }
+}
+
+impl<'a> Next<'a> {
+    pub(crate) const fn new(
+        middlewares: &'a [Arc<dyn Middleware>],
+        transport: &'a dyn HttpTransport,
+    ) -> Self {
+        Self { middlewares, transport }
+    }
+
+    /// Passes `request` to the next middleware, or sends it if none is left.
+    pub fn run(self, request: HttpRequest) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => {
+                middleware.handle(request, Self { middlewares: rest, transport: self.transport })
+            }
+            None => self.transport.send(request),
+        }
+    }
+}
+
+/// What [`MetricsMiddleware`] reports about one attempt.
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// HTTP method.
+    pub method: hpx::Method,
+    /// Path template, e.g. `/v1/w3s/wallets/{id}`.
+    pub path_template: String,
+    /// Attempt number, starting at 1.
+    pub attempt: u32,
+    /// HTTP status, if a response was received.
+    pub status: Option<u16>,
+    /// Time spent in the rest of the chain.
+    pub latency: Duration,
+    /// [`Error::kind`] of the failure, if no response was received.
+    pub error_kind: Option<&'static str>,
+}
+
+/// Reports [`RequestMetrics`] for every attempt to a callback, e.g. to feed
+/// a metrics registry.
+pub struct MetricsMiddleware<F> {
+    record: F,
+}
+
+impl<F> std::fmt::Debug for MetricsMiddleware<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsMiddleware").finish_non_exhaustive()
+    }
+}
+
+impl<F> MetricsMiddleware<F>
+where
+    F: Fn(&RequestMetrics) + Send + Sync + 'static,
+{
+    /// Calls `record` after every attempt.
+    pub const fn new(record: F) -> Self {
+        Self { record }
+    }
+}
+
+impl<F> Middleware for MetricsMiddleware<F>
+where
+    F: Fn(&RequestMetrics) + Send + Sync + 'static,
+{
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            let method = request.method.clone();
+            let path_template = request.path.template().to_string();
+            let attempt = request.attempt;
+            let started = Instant::now();
+            let result = next.run(request).await;
+            (self.record)(&RequestMetrics {
+                method,
+                path_template,
+                attempt,
+                status: result.as_ref().ok().map(|response| response.status),
+                latency: started.elapsed(),
+                error_kind: result.as_ref().err().map(Error::kind),
+            });
+            result
+        })
+    }
+}
+
+/// Logs every non-`GET` request and its outcome as an `info` event on the
+/// `circle_sdk::audit` target. Bodies and credentials are never logged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuditLogMiddleware;
+
+impl Middleware for AuditLogMiddleware {
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            if request.method == hpx::Method::GET {
+                return next.run(request).await;
+            }
+            let method = request.method.clone();
+            let path = request.path.as_str().to_string();
+            let request_id = request.request_id().unwrap_or_default().to_string();
+            let result = next.run(request).await;
+            match &result {
+                Ok(response) => tracing::info!(
+                    target: "circle_sdk::audit",
+                    %method,
+                    path,
+                    request_id,
+                    status = response.status,
+                    "Circle API call"
+                ),
+                Err(error) => tracing::info!(
+                    target: "circle_sdk::audit",
+                    %method,
+                    path,
+                    request_id,
+                    error_kind = error.kind(),
+                    "Circle API call failed"
+                ),
+            }
+            result
+        })
+    }
+}
+
+/// Stops sending requests after `failure_threshold` consecutive failures
+/// (transport errors or `5xx` responses) and fails fast with
+/// [`Error::Middleware`] until `cooldown` has passed.
+///
+/// After the cooldown the circuit is half-open: a single probe request is let
+/// through while every other request keeps failing fast. A successful probe
+/// closes the circuit; a failed one reopens it for another `cooldown`.
+#[derive(Debug)]
+pub struct CircuitBreakerMiddleware {
+    failure_threshold: u32,
+    cooldown: Duration,
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    probing: bool,
+}
+
+/// Leave to send one request through a [`CircuitBreakerMiddleware`].
+///
+/// Dropping a probe's permit without recording an outcome (say, because the
+/// request was cancelled) lets the next request probe instead.
+struct Permit<'a> {
+    breaker: &'a CircuitBreakerMiddleware,
+    probe: bool,
+}
+
+impl Permit<'_> {
+    fn record(self, failed: bool) {
+        let mut state = self.breaker.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if failed {
+            state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+            if state.consecutive_failures >= self.breaker.failure_threshold {
+                state.open_until = Some(Instant::now() + self.breaker.cooldown);
+            }
+        } else {
+            *state = CircuitState::default();
+        }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breaker.state.lock().unwrap_or_else(PoisonError::into_inner).probing = false;
+        }
+    }
+}
+
+impl CircuitBreakerMiddleware {
+    /// Opens the circuit after `failure_threshold` (at least one) consecutive
+    /// failures, for `cooldown`.
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            state: Mutex::new(CircuitState::default()),
+        }
+    }
+
+    /// Lets a request through unless the circuit is open, or half-open with
+    /// a probe already in flight.
+    fn admit(&self) -> Option<Permit<'_>> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let Some(until) = state.open_until else {
+            return Some(Permit { breaker: self, probe: false });
+        };
+        if state.probing || Instant::now() < until {
+            return None;
+        }
+        state.probing = true;
+        Some(Permit { breaker: self, probe: true })
+    }
+}
+
+impl Middleware for CircuitBreakerMiddleware {
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            let Some(permit) = self.admit() else {
+                return Err(Error::Middleware(format!(
+                    "circuit breaker open after {} consecutive failures",
+                    self.failure_threshold
+                )));
+            };
+            let result = next.run(request).await;
+            permit.record(match &result {
+                Ok(response) => response.status >= 500,
+                Err(error) => matches!(error, Error::Http { .. }),
+            });
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::{client::RestClient, retry::RetryPolicy};
+
+    /// Answers every attempt from a queue of `(status, body)` pairs, without
+    /// touching the network, and records what it saw.
+    #[derive(Default)]
+    struct Canned {
+        responses: Mutex<VecDeque<(u16, &'static str)>>,
+        seen: Arc<Mutex<Vec<HttpRequest>>>,
+    }
+
+    impl Canned {
+        fn new(responses: &[(u16, &'static str)]) -> (Self, Arc<Mutex<Vec<HttpRequest>>>) {
+            let canned = Self {
+                responses: Mutex::new(responses.iter().copied().collect()),
+                ..Self::default()
+            };
+            let seen = Arc::clone(&canned.seen);
+            (canned, seen)
+        }
+    }
+
+    impl Middleware for Canned {
+        fn handle<'a>(
+            &'a self,
+            request: HttpRequest,
+            _next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+            Box::pin(async move {
+                self.seen.lock().unwrap_or_else(PoisonError::into_inner).push(request);
+                let (status, body) = self
+                    .responses
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .pop_front()
+                    .ok_or_else(|| Error::Middleware("no canned response left".to_string()))?;
+                Ok(HttpResponse {
+                    status,
+                    headers: HeaderMap::new(),
+                    body: body.as_bytes().to_vec(),
+                })
+            })
+        }
+    }
+
+    struct Tag(&'static str, Arc<Mutex<Vec<&'static str>>>);
+
+    impl Middleware for Tag {
+        fn handle<'a>(
+            &'a self,
+            mut request: HttpRequest,
+            next: Next<'a>,
+        ) -> BoxFuture<'a, Result<HttpResponse, Error>> {
+            Box::pin(async move {
+                self.1.lock().unwrap_or_else(PoisonError::into_inner).push(self.0);
+                request.headers.append("X-Tag", hpx::header::HeaderValue::from_static(self.0));
+                next.run(request).await
+            })
+        }
+    }
+
+    fn builder() -> crate::ClientBuilder {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        RestClient::builder("key")
+            .base_url("http://127.0.0.1:9")
+            .retry_policy(RetryPolicy::default().with_backoff(Duration::ZERO, Duration::ZERO))
+    }
+
+    #[tokio::test]
+    async fn runs_in_order_and_can_short_circuit() -> Result<(), Box<dyn std::error::Error>> {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let (canned, seen) = Canned::new(&[(200, r#"{"ok":true}"#)]);
+        let client = builder()
+            .middleware(Tag("outer", Arc::clone(&order)))
+            .middleware(Tag("inner", Arc::clone(&order)))
+            .middleware(canned)
+            .build()?;
+
+        let value: serde_json::Value = client.get("/v1/w3s/wallets").send().await?;
+        assert_eq!(value["ok"], true);
+        assert_eq!(*order.lock().map_err(|e| e.to_string())?, ["outer", "inner"]);
+
+        let seen = seen.lock().map_err(|e| e.to_string())?;
+        let tags: Vec<_> = seen[0].headers.get_all("X-Tag").iter().collect();
+        assert_eq!(tags, ["outer", "inner"]);
+        assert!(seen[0].headers.contains_key("Authorization"));
+        assert!(seen[0].request_id().is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn runs_once_per_attempt() -> Result<(), Box<dyn std::error::Error>> {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&recorded);
+        let (canned, seen) =
+            Canned::new(&[(503, r#"{"code":-1,"message":"unavailable"}"#), (200, "{}")]);
+        let client = builder()
+            .middleware(MetricsMiddleware::new(move |metrics: &RequestMetrics| {
+                sink.lock().unwrap_or_else(PoisonError::into_inner).push((
+                    metrics.attempt,
+                    metrics.status,
+                    metrics.path_template.clone(),
+                ));
+            }))
+            .middleware(canned)
+            .build()?;
+
+        let id = "w1";
+        let _: serde_json::Value =
+            client.get(crate::api_path!("/v1/w3s/wallets/{id}")).send().await?;
+        assert_eq!(
+            *recorded.lock().map_err(|e| e.to_string())?,
+            [
+                (1, Some(503), "/v1/w3s/wallets/{id}".to_string()),
+                (2, Some(200), "/v1/w3s/wallets/{id}".to_string()),
+            ]
+        );
+        let seen = seen.lock().map_err(|e| e.to_string())?;
+        assert_eq!(seen[0].request_id(), seen[1].request_id());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn circuit_breaker_fails_fast_once_open() -> Result<(), Box<dyn std::error::Error>> {
+        let body = r#"{"code":-1,"message":"unavailable"}"#;
+        let (canned, seen) = Canned::new(&[(503, body), (503, body), (200, "{}")]);
+        let client = builder()
+            .retry_policy(RetryPolicy::none())
+            .middleware(CircuitBreakerMiddleware::new(2, Duration::from_secs(60)))
+            .middleware(canned)
+            .build()?;
+
+        for _ in 0..2 {
+            let result: Result<serde_json::Value, Error> =
+                client.get("/v1/w3s/wallets").send().await;
+            assert_eq!(result.as_ref().err().and_then(Error::status), Some(503), "{result:?}");
+        }
+        let result: Result<serde_json::Value, Error> = client.get("/v1/w3s/wallets").send().await;
+        assert!(matches!(result, Err(Error::Middleware(_))), "{result:?}");
+        assert_eq!(seen.lock().map_err(|e| e.to_string())?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn circuit_breaker_half_opens_after_cooldown_and_resets_on_success()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let breaker = CircuitBreakerMiddleware::new(1, Duration::ZERO);
+        breaker.admit().ok_or("the circuit starts closed")?.record(true);
+        let probe = breaker.admit().ok_or("the cooldown has elapsed, so a probe is let through")?;
+        assert!(breaker.admit().is_none(), "only one probe at a time");
+        probe.record(false);
+        let state = breaker.state.lock().map_err(|e| e.to_string())?;
+        assert_eq!(state.consecutive_failures, 0);
+        assert!(state.open_until.is_none());
+        assert!(!state.probing);
+        Ok(())
+    }
+
+    #[test]
+    fn circuit_breaker_reopens_when_the_probe_fails() -> Result<(), Box<dyn std::error::Error>> {
+        let breaker = CircuitBreakerMiddleware::new(1, Duration::from_secs(60));
+        breaker.admit().ok_or("the circuit starts closed")?.record(true);
+        breaker.state.lock().map_err(|e| e.to_string())?.open_until = Some(Instant::now());
+        breaker.admit().ok_or("the cooldown has elapsed")?.record(true);
+        assert!(breaker.admit().is_none(), "a failed probe reopens the circuit");
+        Ok(())
+    }
+
+    #[test]
+    fn circuit_breaker_lets_another_probe_through_when_one_is_dropped()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let breaker = CircuitBreakerMiddleware::new(1, Duration::ZERO);
+        breaker.admit().ok_or("the circuit starts closed")?.record(true);
+        drop(breaker.admit().ok_or("the cooldown has elapsed")?);
+        assert!(breaker.admit().is_some(), "a cancelled probe frees the slot");
+        Ok(())
+    }
+}
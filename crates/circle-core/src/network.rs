@@ -0,0 +1,531 @@
+//! The chains Circle supports, across all of its APIs.
+//!
+//! Each API crate has its own chain enum, covering the chains that API
+//! accepts: `Blockchain` in the wallet crates and `Chain` in compliance.
+//! [`Network`] is the union of them, with the metadata the APIs do not
+//! return — testnet or not, EVM chain id, native currency, the
+//! mainnet/testnet counterpart and block explorer links. Every crate enum
+//! converts to and from it with `TryFrom`, so a wallet's chain can be
+//! passed on to another API, e.g. as the `chain` of an address screening.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A chain that no conversion target supports, or a name no [`Network`] has.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{network} is not supported by {target}")]
+pub struct UnsupportedNetwork {
+    /// The chain's wire name, e.g. `"SOL-DEVNET"`.
+    pub network: String,
+    /// What it was converted to, e.g. `"Chain"`.
+    pub target: &'static str,
+}
+
+impl From<UnsupportedNetwork> for crate::Error {
+    fn from(error: UnsupportedNetwork) -> Self {
+        Self::InvalidParam(error.to_string())
+    }
+}
+
+/// The native currency of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NativeCurrency {
+    /// Ticker symbol, e.g. `"ETH"`.
+    pub symbol: &'static str,
+    /// Number of decimals of its base unit.
+    pub decimals: u32,
+}
+
+/// Block explorer URL templates of a chain.
+///
+/// `{tx}` and `{address}` stand for the transaction hash and the address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explorer {
+    /// Transaction page, e.g. `https://etherscan.io/tx/{tx}`.
+    pub tx: &'static str,
+    /// Address page, e.g. `https://etherscan.io/address/{address}`.
+    pub address: &'static str,
+}
+
+impl Explorer {
+    /// The page of transaction `hash`.
+    pub fn tx_url(&self, hash: &str) -> String {
+        self.tx.replace("{tx}", hash)
+    }
+
+    /// The page of `address`.
+    pub fn address_url(&self, address: &str) -> String {
+        self.address.replace("{address}", address)
+    }
+}
+
+const fn explorer(tx: &'static str, address: &'static str) -> Option<Explorer> {
+    Some(Explorer { tx, address })
+}
+
+const fn native(symbol: &'static str, decimals: u32) -> Option<NativeCurrency> {
+    Some(NativeCurrency { symbol, decimals })
+}
+
+macro_rules! networks {
+    ($( $(#[doc = $doc:literal])+ $variant:ident => $wire:literal, )+) => {
+        /// A chain supported by any of Circle's APIs.
+        ///
+        /// Serializes to the same string the APIs use, e.g. `"ETH-SEPOLIA"`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum Network {
+            $( $(#[doc = $doc])+ $variant, )+
+        }
+
+        impl Network {
+            /// Every network, in declaration order.
+            pub const ALL: &'static [Self] = &[$( Self::$variant ),+];
+
+            /// The name Circle's APIs use for this chain.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $( Self::$variant => $wire, )+
+                }
+            }
+        }
+
+        impl FromStr for Network {
+            type Err = UnsupportedNetwork;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    $( $wire => Ok(Self::$variant), )+
+                    _ => Err(UnsupportedNetwork { network: value.to_string(), target: "Network" }),
+                }
+            }
+        }
+    };
+}
+
+networks! {
+    /// Ethereum mainnet.
+    Eth => "ETH",
+    /// Ethereum Sepolia testnet.
+    EthSepolia => "ETH-SEPOLIA",
+    /// Avalanche C-Chain mainnet.
+    Avax => "AVAX",
+    /// Avalanche Fuji testnet.
+    AvaxFuji => "AVAX-FUJI",
+    /// Polygon PoS mainnet.
+    Matic => "MATIC",
+    /// Polygon Amoy testnet.
+    MaticAmoy => "MATIC-AMOY",
+    /// Solana mainnet.
+    Sol => "SOL",
+    /// Solana devnet.
+    SolDevnet => "SOL-DEVNET",
+    /// Arbitrum One mainnet.
+    Arb => "ARB",
+    /// Arbitrum Sepolia testnet.
+    ArbSepolia => "ARB-SEPOLIA",
+    /// NEAR mainnet.
+    Near => "NEAR",
+    /// NEAR testnet.
+    NearTestnet => "NEAR-TESTNET",
+    /// Any EVM mainnet, for multi-chain smart contract accounts.
+    Evm => "EVM",
+    /// Any EVM testnet, for multi-chain smart contract accounts.
+    EvmTestnet => "EVM-TESTNET",
+    /// Unichain mainnet.
+    Uni => "UNI",
+    /// Unichain Sepolia testnet.
+    UniSepolia => "UNI-SEPOLIA",
+    /// Base mainnet.
+    Base => "BASE",
+    /// Base Sepolia testnet.
+    BaseSepolia => "BASE-SEPOLIA",
+    /// OP Mainnet.
+    Op => "OP",
+    /// OP Sepolia testnet.
+    OpSepolia => "OP-SEPOLIA",
+    /// Aptos mainnet.
+    Aptos => "APTOS",
+    /// Aptos testnet.
+    AptosTestnet => "APTOS-TESTNET",
+    /// Arc testnet.
+    ArcTestnet => "ARC-TESTNET",
+    /// Monad mainnet.
+    Monad => "MONAD",
+    /// Monad testnet.
+    MonadTestnet => "MONAD-TESTNET",
+    /// Algorand.
+    Algo => "ALGO",
+    /// Cosmos Hub.
+    Atom => "ATOM",
+    /// Hedera.
+    Hbar => "HBAR",
+    /// Tron.
+    Trx => "TRX",
+    /// Stellar.
+    Xlm => "XLM",
+    /// Bitcoin Cash.
+    Bch => "BCH",
+    /// Bitcoin.
+    Btc => "BTC",
+    /// Bitcoin SV.
+    Bsv => "BSV",
+    /// Ethereum Classic.
+    Etc => "ETC",
+    /// Litecoin.
+    Ltc => "LTC",
+    /// Monero.
+    Xmr => "XMR",
+    /// XRP Ledger.
+    Xrp => "XRP",
+    /// 0x, screened as an Ethereum token. It is not a chain of its own, so
+    /// it has no chain id or native currency.
+    Zrx => "ZRX",
+    /// Polkadot.
+    Dot => "DOT",
+}
+
+impl Network {
+    /// Whether this is a test network.
+    pub const fn is_testnet(self) -> bool {
+        matches!(
+            self,
+            Self::EthSepolia |
+                Self::AvaxFuji |
+                Self::MaticAmoy |
+                Self::SolDevnet |
+                Self::ArbSepolia |
+                Self::NearTestnet |
+                Self::EvmTestnet |
+                Self::UniSepolia |
+                Self::BaseSepolia |
+                Self::OpSepolia |
+                Self::AptosTestnet |
+                Self::ArcTestnet |
+                Self::MonadTestnet
+        )
+    }
+
+    /// Whether the chain runs the EVM and uses Ethereum addresses.
+    pub const fn is_evm(self) -> bool {
+        matches!(self, Self::Evm | Self::EvmTestnet) || self.chain_id().is_some()
+    }
+
+    /// The EIP-155 chain id, for EVM chains other than the generic
+    /// [`Evm`](Self::Evm) and [`EvmTestnet`](Self::EvmTestnet).
+    pub const fn chain_id(self) -> Option<u64> {
+        match self {
+            Self::Eth => Some(1),
+            Self::EthSepolia => Some(11_155_111),
+            Self::Avax => Some(43_114),
+            Self::AvaxFuji => Some(43_113),
+            Self::Matic => Some(137),
+            Self::MaticAmoy => Some(80_002),
+            Self::Arb => Some(42_161),
+            Self::ArbSepolia => Some(421_614),
+            Self::Uni => Some(130),
+            Self::UniSepolia => Some(1_301),
+            Self::Base => Some(8_453),
+            Self::BaseSepolia => Some(84_532),
+            Self::Op => Some(10),
+            Self::OpSepolia => Some(11_155_420),
+            Self::ArcTestnet => Some(5_042_002),
+            Self::Monad => Some(143),
+            Self::MonadTestnet => Some(10_143),
+            Self::Etc => Some(61),
+            _ => None,
+        }
+    }
+
+    /// The currency gas is paid in; `None` for the generic EVM networks and
+    /// for [`Zrx`](Self::Zrx), which is a token rather than a chain.
+    pub const fn native_currency(self) -> Option<NativeCurrency> {
+        match self {
+            Self::Eth |
+            Self::EthSepolia |
+            Self::Arb |
+            Self::ArbSepolia |
+            Self::Uni |
+            Self::UniSepolia |
+            Self::Base |
+            Self::BaseSepolia |
+            Self::Op |
+            Self::OpSepolia => native("ETH", 18),
+            Self::Avax | Self::AvaxFuji => native("AVAX", 18),
+            Self::Matic | Self::MaticAmoy => native("POL", 18),
+            Self::Sol | Self::SolDevnet => native("SOL", 9),
+            Self::Near | Self::NearTestnet => native("NEAR", 24),
+            Self::Aptos | Self::AptosTestnet => native("APT", 8),
+            Self::ArcTestnet => native("USDC", 18),
+            Self::Monad | Self::MonadTestnet => native("MON", 18),
+            Self::Algo => native("ALGO", 6),
+            Self::Atom => native("ATOM", 6),
+            Self::Hbar => native("HBAR", 8),
+            Self::Trx => native("TRX", 6),
+            Self::Xlm => native("XLM", 7),
+            Self::Bch => native("BCH", 8),
+            Self::Btc => native("BTC", 8),
+            Self::Bsv => native("BSV", 8),
+            Self::Etc => native("ETC", 18),
+            Self::Ltc => native("LTC", 8),
+            Self::Xmr => native("XMR", 12),
+            Self::Xrp => native("XRP", 6),
+            Self::Dot => native("DOT", 10),
+            Self::Evm | Self::EvmTestnet | Self::Zrx => None,
+        }
+    }
+
+    /// The testnet of a mainnet or the mainnet of a testnet, if Circle
+    /// supports both.
+    pub const fn counterpart(self) -> Option<Self> {
+        match self {
+            Self::Eth => Some(Self::EthSepolia),
+            Self::EthSepolia => Some(Self::Eth),
+            Self::Avax => Some(Self::AvaxFuji),
+            Self::AvaxFuji => Some(Self::Avax),
+            Self::Matic => Some(Self::MaticAmoy),
+            Self::MaticAmoy => Some(Self::Matic),
+            Self::Sol => Some(Self::SolDevnet),
+            Self::SolDevnet => Some(Self::Sol),
+            Self::Arb => Some(Self::ArbSepolia),
+            Self::ArbSepolia => Some(Self::Arb),
+            Self::Near => Some(Self::NearTestnet),
+            Self::NearTestnet => Some(Self::Near),
+            Self::Evm => Some(Self::EvmTestnet),
+            Self::EvmTestnet => Some(Self::Evm),
+            Self::Uni => Some(Self::UniSepolia),
+            Self::UniSepolia => Some(Self::Uni),
+            Self::Base => Some(Self::BaseSepolia),
+            Self::BaseSepolia => Some(Self::Base),
+            Self::Op => Some(Self::OpSepolia),
+            Self::OpSepolia => Some(Self::Op),
+            Self::Aptos => Some(Self::AptosTestnet),
+            Self::AptosTestnet => Some(Self::Aptos),
+            Self::Monad => Some(Self::MonadTestnet),
+            Self::MonadTestnet => Some(Self::Monad),
+            _ => None,
+        }
+    }
+
+    /// The mainnet of this network: itself, or its counterpart if it is a
+    /// testnet.
+    pub const fn mainnet(self) -> Option<Self> {
+        if self.is_testnet() { self.counterpart() } else { Some(self) }
+    }
+
+    /// The testnet of this network: itself, or its counterpart if it is a
+    /// mainnet.
+    pub const fn testnet(self) -> Option<Self> {
+        if self.is_testnet() { Some(self) } else { self.counterpart() }
+    }
+
+    /// Block explorer links, for the chains Circle hosts wallets on.
+    pub const fn explorer(self) -> Option<Explorer> {
+        match self {
+            Self::Eth => {
+                explorer("https://etherscan.io/tx/{tx}", "https://etherscan.io/address/{address}")
+            }
+            Self::EthSepolia => explorer(
+                "https://sepolia.etherscan.io/tx/{tx}",
+                "https://sepolia.etherscan.io/address/{address}",
+            ),
+            Self::Avax => {
+                explorer("https://snowtrace.io/tx/{tx}", "https://snowtrace.io/address/{address}")
+            }
+            Self::AvaxFuji => explorer(
+                "https://testnet.snowtrace.io/tx/{tx}",
+                "https://testnet.snowtrace.io/address/{address}",
+            ),
+            Self::Matic => explorer(
+                "https://polygonscan.com/tx/{tx}",
+                "https://polygonscan.com/address/{address}",
+            ),
+            Self::MaticAmoy => explorer(
+                "https://amoy.polygonscan.com/tx/{tx}",
+                "https://amoy.polygonscan.com/address/{address}",
+            ),
+            Self::Sol => explorer(
+                "https://explorer.solana.com/tx/{tx}",
+                "https://explorer.solana.com/address/{address}",
+            ),
+            Self::SolDevnet => explorer(
+                "https://explorer.solana.com/tx/{tx}?cluster=devnet",
+                "https://explorer.solana.com/address/{address}?cluster=devnet",
+            ),
+            Self::Arb => {
+                explorer("https://arbiscan.io/tx/{tx}", "https://arbiscan.io/address/{address}")
+            }
+            Self::ArbSepolia => explorer(
+                "https://sepolia.arbiscan.io/tx/{tx}",
+                "https://sepolia.arbiscan.io/address/{address}",
+            ),
+            Self::Near => explorer(
+                "https://nearblocks.io/txns/{tx}",
+                "https://nearblocks.io/address/{address}",
+            ),
+            Self::NearTestnet => explorer(
+                "https://testnet.nearblocks.io/txns/{tx}",
+                "https://testnet.nearblocks.io/address/{address}",
+            ),
+            Self::Uni => {
+                explorer("https://uniscan.xyz/tx/{tx}", "https://uniscan.xyz/address/{address}")
+            }
+            Self::UniSepolia => explorer(
+                "https://sepolia.uniscan.xyz/tx/{tx}",
+                "https://sepolia.uniscan.xyz/address/{address}",
+            ),
+            Self::Base => {
+                explorer("https://basescan.org/tx/{tx}", "https://basescan.org/address/{address}")
+            }
+            Self::BaseSepolia => explorer(
+                "https://sepolia.basescan.org/tx/{tx}",
+                "https://sepolia.basescan.org/address/{address}",
+            ),
+            Self::Op => explorer(
+                "https://optimistic.etherscan.io/tx/{tx}",
+                "https://optimistic.etherscan.io/address/{address}",
+            ),
+            Self::OpSepolia => explorer(
+                "https://sepolia-optimism.etherscan.io/tx/{tx}",
+                "https://sepolia-optimism.etherscan.io/address/{address}",
+            ),
+            Self::Aptos => explorer(
+                "https://explorer.aptoslabs.com/txn/{tx}?network=mainnet",
+                "https://explorer.aptoslabs.com/account/{address}?network=mainnet",
+            ),
+            Self::AptosTestnet => explorer(
+                "https://explorer.aptoslabs.com/txn/{tx}?network=testnet",
+                "https://explorer.aptoslabs.com/account/{address}?network=testnet",
+            ),
+            Self::ArcTestnet => explorer(
+                "https://testnet.arcscan.app/tx/{tx}",
+                "https://testnet.arcscan.app/address/{address}",
+            ),
+            Self::Monad => {
+                explorer("https://monadscan.com/tx/{tx}", "https://monadscan.com/address/{address}")
+            }
+            Self::MonadTestnet => explorer(
+                "https://testnet.monadexplorer.com/tx/{tx}",
+                "https://testnet.monadexplorer.com/address/{address}",
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Implements lossless `TryFrom` conversions between [`Network`] and an
+/// API crate's chain enum declared with [`api_enum!`](macro@crate::api_enum).
+///
+/// List every known variant of the enum; each must have a [`Network`]
+/// variant of the same name. A value kept in the enum's `Unknown` variant
+/// converts if [`Network`] knows its name.
+#[macro_export]
+macro_rules! network_conversions {
+    ($name:ident { $( $variant:ident ),+ $(,)? }) => {
+        impl ::std::convert::TryFrom<$name> for $crate::network::Network {
+            type Error = $crate::network::UnsupportedNetwork;
+
+            fn try_from(value: $name) -> ::std::result::Result<Self, Self::Error> {
+                match value {
+                    $( $name::$variant => ::std::result::Result::Ok(Self::$variant), )+
+                    $name::Unknown(raw) => raw.parse(),
+                }
+            }
+        }
+
+        impl ::std::convert::TryFrom<$crate::network::Network> for $name {
+            type Error = $crate::network::UnsupportedNetwork;
+
+            fn try_from(
+                network: $crate::network::Network,
+            ) -> ::std::result::Result<Self, Self::Error> {
+                match network {
+                    $( $crate::network::Network::$variant => ::std::result::Result::Ok(Self::$variant), )+
+                    other => ::std::result::Result::Err($crate::network::UnsupportedNetwork {
+                        network: other.as_str().to_string(),
+                        target: stringify!($name),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_names_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        for network in Network::ALL {
+            assert_eq!(network.as_str().parse::<Network>()?, *network);
+            assert_eq!(serde_json::to_string(network)?, format!("\"{network}\""));
+        }
+        assert!("TEMPO".parse::<Network>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn counterparts_pair_mainnets_with_testnets() {
+        for network in Network::ALL {
+            if let Some(other) = network.counterpart() {
+                assert_ne!(network.is_testnet(), other.is_testnet(), "{network}");
+                assert_eq!(other.counterpart(), Some(*network));
+                assert_eq!(network.is_evm(), other.is_evm(), "{network}");
+            }
+        }
+        assert_eq!(Network::BaseSepolia.mainnet(), Some(Network::Base));
+        assert_eq!(Network::Base.testnet(), Some(Network::BaseSepolia));
+        assert_eq!(Network::ArcTestnet.mainnet(), None);
+    }
+
+    #[test]
+    fn metadata_of_well_known_chains() {
+        assert!(Network::Eth.is_evm() && !Network::Eth.is_testnet());
+        assert_eq!(Network::EthSepolia.chain_id(), Some(11_155_111));
+        assert!(!Network::Sol.is_evm());
+        assert_eq!(
+            Network::Sol.native_currency(),
+            Some(NativeCurrency { symbol: "SOL", decimals: 9 })
+        );
+        assert!(Network::Evm.is_evm() && Network::Evm.chain_id().is_none());
+        assert!(!Network::Zrx.is_evm());
+        assert_eq!(Network::Zrx.native_currency(), None);
+        let explorer = Network::Base.explorer();
+        assert_eq!(
+            explorer.map(|explorer| explorer.tx_url("0xabc")).as_deref(),
+            Some("https://basescan.org/tx/0xabc")
+        );
+    }
+
+    #[test]
+    fn chain_ids_are_unique() {
+        let mainnet: Vec<_> = Network::ALL.iter().filter(|n| n.chain_id() == Some(1)).collect();
+        assert_eq!(mainnet, [&Network::Eth]);
+        for network in Network::ALL {
+            let Some(id) = network.chain_id() else { continue };
+            let sharing: Vec<_> =
+                Network::ALL.iter().filter(|other| other.chain_id() == Some(id)).collect();
+            assert_eq!(sharing, [network], "chain id {id}");
+        }
+    }
+}
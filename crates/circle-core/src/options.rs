@@ -0,0 +1,80 @@
+//! Per-call overrides of the client configuration.
+//!
+//! Every API client has a `with_options` method returning a copy of the
+//! client whose requests use the given [`RequestOptions`]:
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use circle_core::{RequestOptions, RestClient};
+//!
+//! # async fn run(client: &RestClient) -> Result<(), circle_core::Error> {
+//! let options = RequestOptions::new()
+//!     .with_request_id("3f1c6a52-8d9e-4c1b-a0d2-5b7e9f4c2a11")
+//!     .with_timeout(Duration::from_secs(120))
+//!     .with_header("X-Trace-Id", "backfill-42");
+//! let wallets: serde_json::Value =
+//!     client.with_options(options).get("/v1/w3s/wallets").send().await?;
+//! # let _ = wallets;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::Duration;
+
+use crate::retry::RetryPolicy;
+
+/// Overrides applied to individual requests on top of the client settings.
+///
+/// Unset fields fall back to the client configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestOptions {
+    /// `X-Request-Id` to send instead of a generated UUID, e.g. to correlate
+    /// Circle calls with your own trace ids. Circle only shows UUIDs in its
+    /// logs and the Developer Console. Every retry of the request reuses it;
+    /// a client configured with it sends it on every request it makes.
+    pub request_id: Option<String>,
+    /// Total timeout of each attempt, replacing the client timeout.
+    pub timeout: Option<Duration>,
+    /// Extra headers, sent after (and overriding) the client default headers.
+    /// The SDK's own `Authorization`, `X-Request-Id` and `X-User-Token`
+    /// headers always take precedence.
+    pub headers: Vec<(String, String)>,
+    /// Retry policy replacing the client policy.
+    pub retry_policy: Option<RetryPolicy>,
+}
+
+impl RequestOptions {
+    /// Options that override nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `request_id` as the `X-Request-Id` header.
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Sets the total timeout of each attempt.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Adds an extra `name: value` header.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the retry policy; use [`RetryPolicy::none`] to disable retries.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+}
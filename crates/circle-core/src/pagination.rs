@@ -0,0 +1,336 @@
+//! Auto-paginating streams over list endpoints.
+//!
+//! Circle pages its collections with item ids: `pageAfter` asks for the
+//! items after an id, `pageBefore` for those before it, `pageSize` for how
+//! many. [`paginate`] turns a single-page list call into a [`Stream`] of
+//! items that follows those cursors until the collection is exhausted or a
+//! [`StreamOptions::max_items`] cap is reached; the API crates expose it as
+//! `*_stream` variants of their list methods.
+//!
+//! Walks start where the list parameters say: from the first page, after
+//! `page_after`, or backwards from `page_before`. Items come in walking
+//! order, so a backward walk yields each page reversed. A failed page ends
+//! the stream after yielding its error.
+//!
+//! ```no_run
+//! # use circle_core::{Error, pagination::{Stream, StreamExt}};
+//! # async fn run(wallets: impl Stream<Item = Result<String, Error>>) -> Result<(), Error> {
+//! let mut wallets = std::pin::pin!(wallets);
+//! while let Some(wallet) = wallets.next().await {
+//!     let wallet = wallet?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{collections::VecDeque, future::Future};
+
+use futures_util::stream;
+pub use futures_util::{Stream, StreamExt, TryStreamExt};
+
+use crate::Error;
+
+/// The largest `pageSize` Circle's list endpoints accept.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Limits of a paginated stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamOptions {
+    /// Stop after this many items.
+    pub max_items: Option<usize>,
+    /// Items to request per page, overriding the list parameters'
+    /// `page_size`. Pages never ask for more than the items still allowed
+    /// by `max_items`, nor, unless a page size is set, for more than
+    /// [`MAX_PAGE_SIZE`].
+    pub page_size: Option<u32>,
+}
+
+/// The cursor fields of a list endpoint's parameters.
+#[derive(Debug)]
+pub struct Cursors<'a> {
+    /// Exclusive end of the requested page.
+    pub page_before: &'a mut Option<String>,
+    /// Exclusive start of the requested page.
+    pub page_after: &'a mut Option<String>,
+    /// Requested page size.
+    pub page_size: &'a mut Option<u32>,
+}
+
+/// List parameters that [`paginate`] can move from page to page.
+///
+/// Implemented with [`paginated!`](crate::paginated).
+pub trait Paginated: Clone {
+    /// The cursor fields of the parameters.
+    fn cursors(&mut self) -> Cursors<'_>;
+}
+
+/// An item of a paginated collection.
+pub trait PageItem {
+    /// The id the collection pages by, if the item has one. Items without
+    /// one end the walk after their page.
+    fn cursor(&self) -> Option<String>;
+}
+
+/// Implements [`Paginated`] for list parameter structs, given the path of
+/// the struct holding their `page_before`, `page_after` and `page_size`
+/// fields (none if they are the struct's own).
+///
+/// ```ignore
+/// circle_core::paginated!(ListWalletsParams => page, ListTransactionsParams);
+/// ```
+#[macro_export]
+macro_rules! paginated {
+    ($( $name:ident $(=> $field:ident)? ),+ $(,)?) => {
+        $(
+            impl $crate::pagination::Paginated for $name {
+                fn cursors(&mut self) -> $crate::pagination::Cursors<'_> {
+                    let page = &mut (*self) $(.$field)?;
+                    $crate::pagination::Cursors {
+                        page_before: &mut page.page_before,
+                        page_after: &mut page.page_after,
+                        page_size: &mut page.page_size,
+                    }
+                }
+            }
+        )+
+    };
+}
+
+struct Walk<P, T, F> {
+    next: Option<P>,
+    backward: bool,
+    page_size: Option<u32>,
+    buffer: VecDeque<T>,
+    remaining: Option<usize>,
+    fetch: F,
+}
+
+/// Streams the items of every page `fetch` returns for `params`, following
+/// the item cursors.
+///
+/// The walk ends at an empty page, at an item without a cursor, at a page
+/// that contains the item it was requested after or before (endpoints that
+/// ignore cursors), after `max_items` items or after an error.
+pub fn paginate<'a, P, T, F, Fut>(
+    mut params: P,
+    options: StreamOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    P: Paginated + 'a,
+    T: PageItem + 'a,
+    F: FnMut(P) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>, Error>> + 'a,
+{
+    let cursors = params.cursors();
+    let backward = cursors.page_before.is_some();
+    if options.page_size.is_some() {
+        *cursors.page_size = options.page_size;
+    }
+    let page_size = *cursors.page_size;
+    let walk = Walk {
+        next: Some(params),
+        backward,
+        page_size,
+        buffer: VecDeque::new(),
+        remaining: options.max_items,
+        fetch,
+    };
+    stream::unfold(walk, |mut walk| async move {
+        loop {
+            if walk.remaining == Some(0) {
+                return None;
+            }
+            if let Some(item) = walk.buffer.pop_front() {
+                walk.remaining = walk.remaining.map(|remaining| remaining - 1);
+                return Some((Ok(item), walk));
+            }
+            let mut params = walk.next.take()?;
+            if let Some(remaining) = walk.remaining {
+                let remaining = u32::try_from(remaining).unwrap_or(u32::MAX);
+                // Only a page size the caller chose may exceed Circle's limit.
+                let page_size = walk.page_size.unwrap_or(MAX_PAGE_SIZE);
+                *params.cursors().page_size = Some(page_size.min(remaining));
+            }
+            let mut items = match (walk.fetch)(params.clone()).await {
+                Ok(items) => items,
+                Err(error) => return Some((Err(error), walk)),
+            };
+            let cursors = params.cursors();
+            let cursor = if walk.backward { cursors.page_before } else { cursors.page_after };
+            // Cursors are exclusive: seeing the cursor item again means the
+            // endpoint ignored it and returned a page already streamed.
+            if cursor.is_some() && items.iter().any(|item| item.cursor() == *cursor) {
+                return None;
+            }
+            if walk.backward {
+                items.reverse();
+            }
+            if let Some(last) = items.last().and_then(PageItem::cursor) {
+                *cursor = Some(last);
+                walk.next = Some(params);
+            }
+            walk.buffer.extend(items);
+        }
+    })
+}
+
+/// Streams the items of an endpoint that returns its whole collection in one
+/// response, honouring [`StreamOptions::max_items`].
+pub fn single_page<'a, T, Fut>(
+    options: StreamOptions,
+    fetch: Fut,
+) -> impl Stream<Item = Result<T, Error>> + 'a
+where
+    T: 'a,
+    Fut: Future<Output = Result<Vec<T>, Error>> + 'a,
+{
+    stream::once(fetch)
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+        .take(options.max_items.unwrap_or(usize::MAX))
+}
+
+/// Collects every item of a stream, stopping at the first error.
+///
+/// # Errors
+///
+/// Returns the first error the stream yields.
+pub async fn collect_all<T>(stream: impl Stream<Item = Result<T, Error>>) -> Result<Vec<T>, Error> {
+    stream.try_collect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    #[expect(clippy::struct_field_names, reason = "Circle's own parameter names")]
+    struct Params {
+        page_before: Option<String>,
+        page_after: Option<String>,
+        page_size: Option<u32>,
+    }
+
+    paginated!(Params);
+
+    impl PageItem for u32 {
+        fn cursor(&self) -> Option<String> {
+            Some(self.to_string())
+        }
+    }
+
+    /// Pages `1..=total` like Circle, newest (highest) first, and records
+    /// the parameters of every request.
+    fn collection(
+        total: u32,
+        requests: &Arc<Mutex<Vec<Params>>>,
+    ) -> impl FnMut(Params) -> std::future::Ready<Result<Vec<u32>, Error>> + use<> {
+        let requests = Arc::clone(requests);
+        move |params: Params| {
+            if let Ok(mut requests) = requests.lock() {
+                requests.push(params.clone());
+            }
+            let size = params.page_size.unwrap_or(10) as usize;
+            let items: Vec<u32> = (1..=total).rev().collect();
+            let cursor = |id: &String| items.iter().position(|item| item.to_string() == *id);
+            let page = match (&params.page_before, &params.page_after) {
+                (Some(before), _) => {
+                    let end = cursor(before).unwrap_or(0);
+                    items[end.saturating_sub(size)..end].to_vec()
+                }
+                (None, Some(after)) => {
+                    let start = cursor(after).map_or(items.len(), |i| i + 1);
+                    items.iter().skip(start).take(size).copied().collect()
+                }
+                (None, None) => items.iter().take(size).copied().collect(),
+            };
+            std::future::ready(Ok(page))
+        }
+    }
+
+    #[tokio::test]
+    async fn follows_page_after_until_an_empty_page() -> Result<(), Box<dyn std::error::Error>> {
+        let requests = Arc::default();
+        let params = Params { page_size: Some(2), ..Params::default() };
+        let items =
+            collect_all(paginate(params, StreamOptions::default(), collection(5, &requests)))
+                .await?;
+        assert_eq!(items, [5, 4, 3, 2, 1]);
+        let cursors: Vec<_> = requests
+            .lock()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .map(|p| p.page_after.clone())
+            .collect();
+        assert_eq!(cursors, [None, Some("4".into()), Some("2".into()), Some("1".into())]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn caps_items_and_page_sizes() -> Result<(), Box<dyn std::error::Error>> {
+        let requests = Arc::default();
+        let options = StreamOptions { max_items: Some(3), page_size: Some(2) };
+        let items =
+            collect_all(paginate(Params::default(), options, collection(10, &requests))).await?;
+        assert_eq!(items, [10, 9, 8]);
+        let sizes: Vec<_> =
+            requests.lock().map_err(|e| e.to_string())?.iter().map(|p| p.page_size).collect();
+        assert_eq!(sizes, [Some(2), Some(1)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn large_caps_ask_for_at_most_the_largest_page() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let requests = Arc::default();
+        let options = StreamOptions { max_items: Some(70), page_size: None };
+        let items =
+            collect_all(paginate(Params::default(), options, collection(60, &requests))).await?;
+        assert_eq!(items.len(), 60);
+        let sizes: Vec<_> =
+            requests.lock().map_err(|e| e.to_string())?.iter().map(|p| p.page_size).collect();
+        assert_eq!(sizes, [Some(50), Some(20), Some(10)], "the last page comes back empty");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn walks_backwards_from_page_before() -> Result<(), Box<dyn std::error::Error>> {
+        let requests = Arc::default();
+        let params =
+            Params { page_before: Some("2".into()), page_size: Some(2), ..Params::default() };
+        let items =
+            collect_all(paginate(params, StreamOptions::default(), collection(6, &requests)))
+                .await?;
+        assert_eq!(items, [3, 4, 5, 6]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stops_when_the_endpoint_ignores_cursors() -> Result<(), Box<dyn std::error::Error>> {
+        let fetch = |_: Params| std::future::ready(Ok::<_, Error>(vec![2_u32, 1]));
+        let items =
+            collect_all(paginate(Params::default(), StreamOptions::default(), fetch)).await?;
+        assert_eq!(items, [2, 1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_end_the_stream() {
+        let fetch =
+            |_: Params| std::future::ready(Err::<Vec<u32>, _>(Error::InvalidParam("bad".into())));
+        let results: Vec<_> =
+            paginate(Params::default(), StreamOptions::default(), fetch).collect().await;
+        assert!(matches!(results[..], [Err(Error::InvalidParam(_))]));
+    }
+
+    #[tokio::test]
+    async fn single_page_honours_the_cap() -> Result<(), Box<dyn std::error::Error>> {
+        let options = StreamOptions { max_items: Some(2), ..StreamOptions::default() };
+        let items = collect_all(single_page(options, async { Ok(vec![1, 2, 3]) })).await?;
+        assert_eq!(items, [1, 2]);
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+//! Typed response bodies paired with their HTTP metadata.
+//!
+//! Every client method has a `*_with_meta` variant returning an
+//! [`ApiResponse`], which exposes the status, headers, request ids and
+//! latency alongside the decoded body.
+
+use std::time::Duration;
+
+use hpx::header::HeaderMap;
+
+/// HTTP metadata of a successful Circle API response.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status code (e.g. `200` or `201`).
+    pub status: u16,
+    /// Response headers.
+    pub headers: HeaderMap,
+    /// `X-Request-Id` sent with the request.
+    pub request_id: String,
+    /// `X-Request-Id` echoed back by the server, if present.
+    pub server_request_id: Option<String>,
+    /// Wall-clock time from the first attempt to the decoded response,
+    /// including any retries.
+    pub latency: Duration,
+    /// Number of attempts made, including the first one.
+    pub attempts: u32,
+}
+
+impl ResponseMeta {
+    /// Returns the value of a response header, if present and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// The request quota of the current window (`X-RateLimit-Limit`), if sent.
+    pub fn rate_limit_limit(&self) -> Option<u64> {
+        self.numeric_header("X-RateLimit-Limit")
+    }
+
+    /// Requests left in the current window (`X-RateLimit-Remaining`), if sent.
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        self.numeric_header("X-RateLimit-Remaining")
+    }
+
+    /// When the current window resets (`X-RateLimit-Reset`), if sent.
+    pub fn rate_limit_reset(&self) -> Option<u64> {
+        self.numeric_header("X-RateLimit-Reset")
+    }
+
+    fn numeric_header(&self, name: &str) -> Option<u64> {
+        self.header(name)?.trim().parse().ok()
+    }
+}
+
+/// A decoded response body together with its [`ResponseMeta`].
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    /// The decoded response body.
+    pub body: T,
+    /// Status, headers, request ids and latency of the response.
+    pub meta: ResponseMeta,
+}
+
+impl<T> ApiResponse<T> {
+    /// Discards the metadata and returns the body.
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// Splits the response into its body and metadata.
+    pub fn into_parts(self) -> (T, ResponseMeta) {
+        (self.body, self.meta)
+    }
+
+    /// Transforms the body, keeping the metadata.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ApiResponse<U> {
+        ApiResponse { body: f(self.body), meta: self.meta }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hpx::header::HeaderValue;
+
+    use super::*;
+
+    fn meta() -> ResponseMeta {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("100"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static(" 42 "));
+        headers.insert("x-request-id", HeaderValue::from_static("echoed"));
+        ResponseMeta {
+            status: 201,
+            headers,
+            request_id: "sent".to_string(),
+            server_request_id: Some("echoed".to_string()),
+            latency: Duration::from_millis(12),
+            attempts: 1,
+        }
+    }
+
+    #[test]
+    fn reads_rate_limit_headers() {
+        let meta = meta();
+        assert_eq!(meta.rate_limit_limit(), Some(100));
+        assert_eq!(meta.rate_limit_remaining(), Some(42));
+        assert_eq!(meta.rate_limit_reset(), None);
+        assert_eq!(meta.header("X-Request-Id"), Some("echoed"));
+    }
+
+    #[test]
+    fn map_keeps_metadata() {
+        let response = ApiResponse { body: 2, meta: meta() }.map(|n| n * 10);
+        assert_eq!(response.meta.status, 201);
+        let (body, meta) = response.into_parts();
+        assert_eq!(body, 20);
+        assert_eq!(meta.request_id, "sent");
+    }
+}
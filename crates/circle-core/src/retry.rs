@@ -0,0 +1,208 @@
+//! Retry policy for transient Circle API failures.
+//!
+//! Only requests that are safe to repeat are retried: `GET` requests, and
+//! `POST` requests whose JSON body carries an `idempotencyKey`, which Circle
+//! uses to deduplicate repeated submissions. Everything else is sent exactly
+//! once.
+//!
+//! A request is retried after a transport failure or a `408`, `429` or `5xx`
+//! (except `501`/`505`) response, waiting for the `Retry-After` header when
+//! the server sends one and for an exponential backoff with jitter otherwise.
+//! A `Retry-After` longer than [`RetryPolicy::max_retry_after`] is not waited
+//! for: the request fails with the response instead.
+
+use std::time::{Duration, SystemTime};
+
+use hpx::{StatusCode, header::HeaderMap};
+
+/// Controls how many times, and how far apart, a request is retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each subsequent retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the computed backoff.
+    pub max_backoff: Duration,
+    /// Longest `Retry-After` the client waits for; beyond it the request
+    /// fails instead of being retried.
+    pub max_retry_after: Duration,
+    /// Randomise each backoff between half and all of its computed value.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with a 200 ms initial backoff capped at 5 s, with
+    /// jitter, honouring a `Retry-After` of up to 60 s.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            max_retry_after: Duration::from_secs(60),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub const fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            max_retry_after: Duration::ZERO,
+            jitter: false,
+        }
+    }
+
+    /// Sets the total number of attempts (clamped to at least one).
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the initial and maximum backoff.
+    #[must_use]
+    pub const fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Sets the longest `Retry-After` the client waits for.
+    #[must_use]
+    pub const fn with_max_retry_after(mut self, max: Duration) -> Self {
+        self.max_retry_after = max;
+        self
+    }
+
+    /// Enables or disables jitter.
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Backoff to wait after the given failed attempt (1-based).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let delay = self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff);
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+
+    /// Delay before retrying the given failed attempt (1-based): the
+    /// server's `Retry-After` if it sent one, else [`Self::backoff`].
+    ///
+    /// Returns `None`, meaning do not retry, for a `Retry-After` longer than
+    /// [`Self::max_retry_after`].
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(delay) if delay > self.max_retry_after => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+/// Returns `true` if a response with this status is worth retrying.
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::REQUEST_TIMEOUT ||
+        status == StatusCode::TOO_MANY_REQUESTS ||
+        (status.is_server_error() &&
+            status != StatusCode::NOT_IMPLEMENTED &&
+            status != StatusCode::HTTP_VERSION_NOT_SUPPORTED)
+}
+
+/// Parses a `Retry-After` header given either as delay-seconds or an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(hpx::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use hpx::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_and_caps() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(350))
+            .with_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_and_full_delay() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(true);
+        for _ in 0..100 {
+            let delay = policy.backoff(2);
+            assert!(delay >= Duration::from_millis(100), "{delay:?}");
+            assert!(delay <= Duration::from_millis(200), "{delay:?}");
+        }
+    }
+
+    #[test]
+    fn retry_after_beyond_the_limit_is_not_waited_for() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .with_jitter(false)
+            .with_max_retry_after(Duration::from_secs(30));
+        assert_eq!(policy.delay(1, None), Some(Duration::from_millis(100)));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(30))), Some(Duration::from_secs(30)));
+        assert_eq!(policy.delay(1, Some(Duration::from_secs(86_400))), None);
+    }
+
+    #[test]
+    fn max_attempts_is_at_least_one() {
+        assert_eq!(RetryPolicy::default().with_max_attempts(0).max_attempts, 1);
+        assert_eq!(RetryPolicy::none().max_attempts, 1);
+    }
+
+    #[test]
+    fn classifies_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::REQUEST_TIMEOUT));
+        assert!(!is_retryable_status(StatusCode::NOT_IMPLEMENTED));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn parses_retry_after_seconds_and_dates() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(hpx::header::RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(
+            hpx::header::RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(hpx::header::RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(retry_after(&headers), None);
+    }
+}
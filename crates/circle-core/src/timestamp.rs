@@ -0,0 +1,58 @@
+//! Timestamps in the one format Circle's query parameters use.
+//!
+//! chrono's own `Serialize` writes as many fractional digits as the value
+//! happens to have, so two requests for the same range can differ in shape.
+//! Date-range filters serialize through [`serialize_option`] instead, which
+//! always writes RFC 3339 UTC with millisecond precision and a `Z` suffix,
+//! e.g. `2024-05-01T12:30:00.000Z`. Finer precision is truncated.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serializer;
+
+/// `value` as RFC 3339 UTC with millisecond precision and a `Z` suffix.
+pub fn format(value: &DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// `serialize_with` helper for `Option<DateTime<Utc>>` fields, writing them
+/// with [`format()`].
+///
+/// # Errors
+///
+/// Returns whatever error `serializer` reports.
+pub fn serialize_option<S: Serializer>(
+    value: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_str(&format(value)),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct Range {
+        #[serde(serialize_with = "serialize_option")]
+        from: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn writes_milliseconds_whatever_the_precision() -> Result<(), Box<dyn std::error::Error>> {
+        let whole = Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).single().ok_or("invalid date")?;
+        let precise = whole + chrono::Duration::nanoseconds(123_456_789);
+        assert_eq!(format(&whole), "2024-05-01T12:30:00.000Z");
+        assert_eq!(format(&precise), "2024-05-01T12:30:00.123Z");
+        assert_eq!(
+            serde_json::to_string(&Range { from: Some(precise) })?,
+            r#"{"from":"2024-05-01T12:30:00.123Z"}"#
+        );
+        assert_eq!(serde_json::to_string(&Range { from: None })?, r#"{"from":null}"#);
+        Ok(())
+    }
+}
@@ -0,0 +1,376 @@
+//! The HTTP transport underneath the request pipeline.
+//!
+//! [`RestClient`](crate::RestClient) hands every fully prepared
+//! [`HttpRequest`] to an [`HttpTransport`]. The default is [`HpxTransport`];
+//! [`ClientBuilder::transport`](crate::ClientBuilder::transport) swaps in any
+//! other implementation, e.g. one on top of your own `reqwest`/`hyper` stack,
+//! or the in-memory [`MockTransport`] for tests that must not touch the
+//! network:
+//!
+//! ```
+//! use circle_core::{Expectation, Method, MockTransport, RestClient};
+//! use serde_json::json;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mock = MockTransport::new();
+//! mock.expect(
+//!     Expectation::new(Method::GET, "/v1/w3s/wallets")
+//!         .query("pageSize", "5")
+//!         .respond(200, json!({ "data": { "wallets": [] } })),
+//! );
+//! let client: RestClient = RestClient::builder("key").transport(mock.clone()).build()?;
+//!
+//! let body: serde_json::Value =
+//!     client.get("/v1/w3s/wallets").query(&[("pageSize", 5)]).send().await?;
+//! assert_eq!(body["data"]["wallets"], json!([]));
+//! assert_eq!(mock.requests().len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+pub use hpx::Method;
+use hpx::header::HeaderMap;
+
+use crate::{
+    error::Error,
+    middleware::{BoxFuture, HttpRequest, HttpResponse},
+};
+
+/// Sends a prepared request and returns the raw response.
+///
+/// Implementations report connection-level failures as [`Error::Http`];
+/// non-2xx responses are not errors at this layer.
+pub trait HttpTransport: Send + Sync + 'static {
+    /// Sends `request` and reads the whole response body.
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>>;
+}
+
+/// [`HttpTransport`] backed by an `hpx::Client`.
+#[derive(Clone)]
+pub struct HpxTransport {
+    client: hpx::Client,
+}
+
+impl std::fmt::Debug for HpxTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HpxTransport").finish_non_exhaustive()
+    }
+}
+
+impl HpxTransport {
+    /// Wraps an existing `hpx::Client`, sharing its connection pool.
+    pub const fn new(client: hpx::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for HpxTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>> {
+        Box::pin(async move {
+            let transport_error = |e: hpx::Error| Error::Http {
+                message: e.to_string(),
+                request_id: request.request_id().map(str::to_string),
+            };
+            let mut req = self
+                .client
+                .request(request.method.clone(), request.url())
+                .headers(request.headers.clone())
+                .timeout(request.timeout);
+            if let Some(body) = &request.body {
+                req = req.body(body.clone());
+            }
+            let resp = req.send().await.map_err(transport_error)?;
+            let status = resp.status().as_u16();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await.map_err(transport_error)?.to_vec();
+            Ok(HttpResponse { status, headers, body })
+        })
+    }
+}
+
+/// A canned response together with what the request answered by it must
+/// look like.
+///
+/// Every condition left unset matches any request.
+#[derive(Debug, Clone)]
+pub struct Expectation {
+    method: Option<Method>,
+    path: Option<String>,
+    query: Vec<(String, String)>,
+    json_body: Option<serde_json::Value>,
+    headers: Vec<(String, String)>,
+    response: HttpResponse,
+}
+
+impl Expectation {
+    /// Expects a `method` request for exactly `path`, answered with `200 {}`
+    /// unless [`respond`](Self::respond) says otherwise.
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self { method: Some(method), path: Some(path.into()), ..Self::any() }
+    }
+
+    /// Matches any request, answered with `200 {}`.
+    pub fn any() -> Self {
+        Self {
+            method: None,
+            path: None,
+            query: Vec::new(),
+            json_body: None,
+            headers: Vec::new(),
+            response: HttpResponse { status: 200, headers: HeaderMap::new(), body: b"{}".to_vec() },
+        }
+    }
+
+    /// Expects the query parameter `name` to be present with `value`.
+    pub fn query(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((name.into(), value.into()));
+        self
+    }
+
+    /// Expects a JSON body equal to `body`.
+    pub fn json_body(mut self, body: serde_json::Value) -> Self {
+        self.json_body = Some(body);
+        self
+    }
+
+    /// Expects the header `name` to be present with `value`.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Answers with `status` and `body` serialized as JSON.
+    pub fn respond(mut self, status: u16, body: serde_json::Value) -> Self {
+        self.response =
+            HttpResponse { status, headers: HeaderMap::new(), body: body.to_string().into_bytes() };
+        self
+    }
+
+    /// Answers with an arbitrary raw response.
+    pub fn respond_raw(mut self, response: HttpResponse) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Describes the first condition `request` violates, if any.
+    fn mismatch(&self, request: &HttpRequest) -> Option<String> {
+        if let Some(method) = self.method.as_ref().filter(|method| **method != request.method) {
+            return Some(format!("expected method {method}, got {}", request.method));
+        }
+        if let Some(path) = self.path.as_ref().filter(|path| *path != request.path.as_str()) {
+            return Some(format!("expected path {path}, got {}", request.path.as_str()));
+        }
+        let pairs = request.query_pairs();
+        for (name, value) in &self.query {
+            if !pairs.iter().any(|(n, v)| n == name && v == value) {
+                return Some(format!(
+                    "expected query parameter {name}={value}, got {:?}",
+                    request.query.as_deref().unwrap_or_default()
+                ));
+            }
+        }
+        if let Some(expected) = &self.json_body {
+            let actual = request.json();
+            if actual.as_ref() != Some(expected) {
+                return Some(format!("expected JSON body {expected}, got {actual:?}"));
+            }
+        }
+        for (name, value) in &self.headers {
+            let actual = request.headers.get(name.as_str()).and_then(|v| v.to_str().ok());
+            if actual != Some(value.as_str()) {
+                return Some(format!("expected header {name}: {value}, got {actual:?}"));
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    expectations: VecDeque<Expectation>,
+    requests: Vec<HttpRequest>,
+}
+
+/// In-memory [`HttpTransport`] that answers requests from a queue of
+/// [`Expectation`]s, in order, and records every request it receives.
+///
+/// A request that does not match the next expectation, or arrives when the
+/// queue is empty, fails with an [`Error::Middleware`] describing the
+/// mismatch; it is not retried.
+///
+/// Clones share the same queue, so keep one to inspect after the call.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTransport {
+    /// Creates a transport with an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues an expectation.
+    pub fn expect(&self, expectation: Expectation) -> &Self {
+        self.lock().expectations.push_back(expectation);
+        self
+    }
+
+    /// Queues `status` with a JSON `body` for the next request, whatever it is.
+    pub fn respond(&self, status: u16, body: serde_json::Value) -> &Self {
+        self.expect(Expectation::any().respond(status, body))
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// The most recent request, if any.
+    pub fn last_request(&self) -> Option<HttpRequest> {
+        self.lock().requests.last().cloned()
+    }
+
+    /// Number of queued expectations not consumed yet.
+    pub fn pending(&self) -> usize {
+        self.lock().expectations.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, Error>> {
+        let mut state = self.lock();
+        let label = format!("{} {}", request.method, request.path.as_str());
+        let expectation = state.expectations.pop_front();
+        let mismatch = match &expectation {
+            None => Some("no response queued".to_string()),
+            Some(expectation) => expectation.mismatch(&request),
+        };
+        state.requests.push(request);
+        drop(state);
+        let result = match (mismatch, expectation) {
+            (None, Some(expectation)) => Ok(expectation.response),
+            (mismatch, _) => Err(Error::Middleware(format!(
+                "MockTransport: unexpected request {label}: {}",
+                mismatch.unwrap_or_default()
+            ))),
+        };
+        Box::pin(std::future::ready(result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::{client::RestClient, retry::RetryPolicy};
+
+    fn client(mock: &MockTransport) -> Result<RestClient, Error> {
+        RestClient::builder("key").transport(mock.clone()).build()
+    }
+
+    #[tokio::test]
+    async fn answers_matching_requests_and_records_them() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::POST, "/v1/w3s/developer/walletSets")
+                .json_body(json!({ "name": "set", "idempotencyKey": "k" }))
+                .header("Content-Type", "application/json")
+                .header("Authorization", "Bearer key")
+                .respond(201, json!({ "data": { "id": "ws-1" } })),
+        );
+        let response = client(&mock)?
+            .post("/v1/w3s/developer/walletSets")
+            .json(&json!({ "idempotencyKey": "k", "name": "set" }))
+            .send_with_meta::<serde_json::Value>()
+            .await?;
+        assert_eq!(response.meta.status, 201);
+        assert_eq!(response.body["data"]["id"], "ws-1");
+        assert_eq!(mock.pending(), 0);
+
+        let request = mock.last_request().ok_or("no request recorded")?;
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.request_id(), Some(response.meta.request_id.as_str()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn matches_query_parameters() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::GET, "/v1/w3s/wallets")
+                .query("pageSize", "5")
+                .query("blockchain", "ETH-SEPOLIA"),
+        );
+        let _: serde_json::Value = client(&mock)?
+            .get("/v1/w3s/wallets")
+            .query(&[("blockchain", "ETH-SEPOLIA"), ("pageSize", "5")])
+            .send()
+            .await?;
+        let request = mock.last_request().ok_or("no request recorded")?;
+        assert_eq!(
+            request.query_pairs(),
+            [
+                ("blockchain".to_string(), "ETH-SEPOLIA".to_string()),
+                ("pageSize".to_string(), "5".to_string())
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mismatches_fail_without_retrying() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(Expectation::new(Method::GET, "/v1/w3s/wallets/a"));
+        mock.respond(200, json!({}));
+        let client = RestClient::builder("key")
+            .transport(mock.clone())
+            .retry_policy(RetryPolicy::default().with_backoff(Duration::ZERO, Duration::ZERO))
+            .build()?;
+
+        let result: Result<serde_json::Value, Error> = client.get("/v1/w3s/wallets/b").send().await;
+        let Err(Error::Middleware(message)) = result else {
+            return Err(format!("expected a mismatch, got {result:?}").into());
+        };
+        assert!(message.contains("expected path /v1/w3s/wallets/a"), "{message}");
+        assert_eq!(mock.requests().len(), 1);
+        assert_eq!(mock.pending(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_queue_is_an_error() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        let result: Result<serde_json::Value, Error> =
+            client(&mock)?.get("/v1/w3s/wallets").send().await;
+        assert!(
+            matches!(&result, Err(Error::Middleware(message)) if message.contains("no response queued")),
+            "{result:?}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn serves_error_responses() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.respond(404, json!({ "code": 156_004, "message": "Cannot find the wallet." }));
+        let result: Result<serde_json::Value, Error> =
+            client(&mock)?.get("/v1/w3s/wallets/x").send().await;
+        assert!(matches!(result, Err(Error::Api { code: 156_004, .. })), "{result:?}");
+        Ok(())
+    }
+}
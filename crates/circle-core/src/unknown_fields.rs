@@ -0,0 +1,115 @@
+//! Detection of response fields the SDK does not model yet.
+//!
+//! The main response models keep fields they do not declare in an `extra`
+//! map, deserialized through [`capture`]. The first time a model is seen
+//! with a given undeclared field, a `tracing` event is emitted at `INFO`
+//! level on the `circle_core::unknown_fields` target and the hook installed
+//! with [`on_unknown_field`], if any, is called.
+
+use std::{
+    collections::BTreeSet,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{Map, Value};
+
+/// Callback invoked for each newly seen [`UnknownField`].
+pub type UnknownFieldHook = Arc<dyn Fn(&UnknownField) + Send + Sync>;
+
+/// A field present in a response that the receiving model does not declare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownField {
+    /// Full Rust path of the model, e.g.
+    /// `circle_developer_controlled_wallets::models::wallet::Wallet`.
+    pub model: &'static str,
+    /// The field's JSON name.
+    pub field: String,
+}
+
+static HOOK: RwLock<Option<UnknownFieldHook>> = RwLock::new(None);
+static SEEN: Mutex<BTreeSet<(&'static str, String)>> = Mutex::new(BTreeSet::new());
+
+/// Installs `hook`, replacing any previous one, to be called once per model
+/// and undeclared field for the life of the process.
+pub fn on_unknown_field(hook: impl Fn(&UnknownField) + Send + Sync + 'static) {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(hook));
+}
+
+/// Removes the hook installed with [`on_unknown_field`].
+pub fn clear_unknown_field_hook() {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// `deserialize_with` function for a model's flattened `extra` map: keeps the
+/// undeclared fields and reports the ones not seen before on model `M`.
+pub fn capture<'de, M, D>(deserializer: D) -> Result<Map<String, Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let extra = Map::deserialize(deserializer)?;
+    if !extra.is_empty() {
+        report(std::any::type_name::<M>(), &extra);
+    }
+    Ok(extra)
+}
+
+fn report(model: &'static str, extra: &Map<String, Value>) {
+    let new: Vec<UnknownField> = {
+        let mut seen = SEEN.lock().unwrap_or_else(PoisonError::into_inner);
+        extra
+            .keys()
+            .filter(|field| seen.insert((model, (*field).clone())))
+            .map(|field| UnknownField { model, field: field.clone() })
+            .collect()
+    };
+    if new.is_empty() {
+        return;
+    }
+    let hook = HOOK.read().unwrap_or_else(PoisonError::into_inner).clone();
+    for unknown in &new {
+        tracing::info!(
+            target: "circle_core::unknown_fields",
+            model = unknown.model,
+            field = %unknown.field,
+            "response contains a field the SDK does not model"
+        );
+        if let Some(hook) = &hook {
+            hook(unknown);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Probe {
+        #[expect(dead_code, reason = "only the extra map is inspected")]
+        id: String,
+        #[serde(flatten, deserialize_with = "capture::<Probe, _>")]
+        extra: Map<String, Value>,
+    }
+
+    #[test]
+    fn undeclared_fields_are_kept_and_reported_once() -> Result<(), Box<dyn std::error::Error>> {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        on_unknown_field(move |unknown| {
+            if unknown.model.ends_with("::Probe") {
+                sink.lock().unwrap_or_else(PoisonError::into_inner).push(unknown.field.clone());
+            }
+        });
+
+        let first: Probe = serde_json::from_str(r#"{"id":"1","tier":"gold"}"#)?;
+        let second: Probe = serde_json::from_str(r#"{"id":"2","tier":"silver","rank":3}"#)?;
+        clear_unknown_field_hook();
+
+        assert_eq!(first.extra.get("tier"), Some(&Value::from("gold")));
+        assert_eq!(second.extra.len(), 2);
+        let reported = reported.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        assert_eq!(reported, vec!["tier".to_string(), "rank".to_string()]);
+        Ok(())
+    }
+}
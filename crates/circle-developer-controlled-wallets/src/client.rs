@@ -1,27 +1,45 @@
 //! HTTP client for the Developer-Controlled Wallets API.
 
+use std::sync::{Arc, OnceLock};
+
+use circle_core::{
+    ApiResponse, ClientBuilder, DEFAULT_BASE_URL, RequestOptions, RequestPath, RestClient,
+    api_path,
+    ids::{TokenId, TransactionId, WalletId, WalletSetId},
+    pagination::{self, Stream, StreamOptions},
+};
+
 use crate::{
+    entity_secret::{EntityPublicKey, EntitySecret, EntitySecretCiphertext, RecoveryFile},
     error::Error,
     models::{
-        common::ApiErrorBody,
+        common::EvmBlockchain,
+        config::{
+            EntityPublicKeyResponse, EntitySecretRecoveryResponse, RegisterEntitySecretRequest,
+        },
         signing::{
-            SignMessageRequest, SignTransactionRequest, SignTransactionResponse,
-            SignTypedDataRequest, SignatureResponse,
+            SignDelegateActionRequest, SignDelegateActionResponse, SignMessageRequest,
+            SignTransactionRequest, SignTransactionResponse, SignTypedDataRequest,
+            SignatureResponse,
         },
         token::TokenResponse,
         transaction::{
             AccelerateTxRequest, CancelTxRequest, CreateContractExecutionTxRequest,
-            CreateTransferTxRequest, EstimateFeeResponse, EstimateTransferFeeRequest,
-            ListTransactionsParams, TransactionResponse, Transactions, ValidateAddressRequest,
+            CreateTransferTxRequest, CreateWalletUpgradeTxRequest, EstimateContractExecFeeRequest,
+            EstimateFeeResponse, EstimateTransferFeeRequest, FeeParametersParams,
+            FeeParametersResponse, GetLowestNonceTransactionResponse, GetLowestNonceTxParams,
+            ListTransactionsParams, Transaction, TransactionIdResponse, TransactionResponse,
+            TransactionStateResponse, Transactions, ValidateAddressRequest,
             ValidateAddressResponse,
         },
         wallet::{
-            Balances, CreateWalletsRequest, ListWalletBalancesParams, ListWalletNftsParams,
-            ListWalletsParams, Nfts, UpdateWalletRequest, WalletNftsParams, WalletResponse,
-            Wallets, WalletsWithBalances,
+            Balance, Balances, CreateWalletsRequest, DeriveWalletByAddressRequest,
+            DeriveWalletRequest, DerivedWallet, ListWalletBalancesParams, ListWalletNftsParams,
+            ListWalletTokenBalancesParams, ListWalletsParams, Nft, Nfts, UpdateWalletRequest,
+            Wallet, WalletResponse, Wallets, WalletsWithBalances,
         },
         wallet_set::{
-            CreateWalletSetRequest, ListWalletSetsParams, UpdateWalletSetRequest,
+            CreateWalletSetRequest, ListWalletSetsParams, UpdateWalletSetRequest, WalletSet,
             WalletSetResponse, WalletSets,
         },
     },
@@ -29,104 +47,239 @@ use crate::{
 
 /// Async HTTP client for the Circle W3S Developer-Controlled Wallets API.
 pub struct DeveloperWalletsClient {
-    base_url: String,
-    api_key: String,
-    http: hpx::Client,
+    inner: RestClient,
+    entity_secret: Option<Arc<EntitySecretState>>,
+}
+
+/// The entity secret of a client and the public key it is encrypted to,
+/// fetched on first use and shared by the client's copies.
+struct EntitySecretState {
+    secret: EntitySecret,
+    public_key: OnceLock<EntityPublicKey>,
 }
 
 impl std::fmt::Debug for DeveloperWalletsClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DeveloperWalletsClient")
-            .field("base_url", &self.base_url)
+            .field("base_url", &self.inner.base_url())
             .field("api_key", &"<redacted>")
+            .field("entity_secret", &self.entity_secret.as_ref().map(|_| "<redacted>"))
             .finish_non_exhaustive()
     }
 }
 
+impl From<RestClient> for DeveloperWalletsClient {
+    fn from(inner: RestClient) -> Self {
+        Self { inner, entity_secret: None }
+    }
+}
+
 impl DeveloperWalletsClient {
     /// Creates a new client using the Circle production base URL.
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self::with_base_url(api_key, "https://api.circle.com")
+        Self::with_base_url(api_key, DEFAULT_BASE_URL)
     }
 
-    /// Creates a new client with a custom base URL (useful for Prism mock servers).
+    /// Creates a new client with a custom base URL (useful for the `circle-mock` server).
     pub fn with_base_url(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
-        Self { base_url: base_url.into(), api_key: api_key.into(), http: hpx::Client::new() }
+        Self::from(RestClient::new(api_key, base_url))
+    }
+
+    /// Starts a [`ClientBuilder`] for timeouts, proxy, TLS roots, default
+    /// headers or a shared `hpx::Client`.
+    pub fn builder(api_key: impl Into<String>) -> ClientBuilder<Self> {
+        ClientBuilder::new(api_key)
+    }
+
+    /// Returns a copy of this client whose requests use `options`: a caller
+    /// request id, a per-call timeout, extra headers or a retry override.
+    ///
+    /// The copy shares the connection pool, so it is cheap to create one per
+    /// call:
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use circle_developer_controlled_wallets::{
+    ///     DeveloperWalletsClient, RequestOptions, models::transaction::ListTransactionsParams,
+    /// };
+    ///
+    /// # async fn run(client: &DeveloperWalletsClient) -> Result<(), circle_developer_controlled_wallets::Error> {
+    /// let backfill = RequestOptions::new()
+    ///     .with_request_id("0f8e5c1a-7b2d-4e39-9a61-3c4d5e6f7a8b")
+    ///     .with_timeout(Duration::from_secs(120));
+    /// let txs = client
+    ///     .with_options(backfill)
+    ///     .list_transactions(&ListTransactionsParams::default())
+    ///     .await?;
+    /// # let _ = txs;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self { inner: self.inner.with_options(options), entity_secret: self.entity_secret.clone() }
+    }
+
+    /// Returns this client with an entity secret, which it encrypts into a
+    /// fresh ciphertext for every request whose `entity_secret_ciphertext`
+    /// is left empty — and again for each retry of such a request.
+    ///
+    /// The entity public key is fetched on the first such request and kept
+    /// for the lifetime of the client and its [`Self::with_options`] copies.
+    #[must_use]
+    pub fn with_entity_secret(mut self, secret: EntitySecret) -> Self {
+        self.entity_secret =
+            Some(Arc::new(EntitySecretState { secret, public_key: OnceLock::new() }));
+        self
     }
 
     /// Dispatch a GET request and decode the JSON response.
-    async fn get<T, P>(&self, path: &str, params: &P) -> Result<T, Error>
+    async fn get<T, P>(
+        &self,
+        path: impl Into<RequestPath>,
+        params: &P,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         P: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .query(params)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        if resp.status().is_success() {
-            resp.json::<T>().await.map_err(|e| Error::Http(e.to_string()))
-        } else {
-            let err: ApiErrorBody = resp.json().await.map_err(|e| Error::Http(e.to_string()))?;
-            Err(Error::Api { code: err.code, message: err.message })
-        }
+        self.inner.get(path).query(params).send_with_meta().await
     }
 
     /// Dispatch a POST request with a JSON body and decode the JSON response.
-    async fn post<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+    async fn post<T, B>(
+        &self,
+        path: impl Into<RequestPath>,
+        body: &B,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        if resp.status().is_success() {
-            resp.json::<T>().await.map_err(|e| Error::Http(e.to_string()))
-        } else {
-            let err: ApiErrorBody = resp.json().await.map_err(|e| Error::Http(e.to_string()))?;
-            Err(Error::Api { code: err.code, message: err.message })
-        }
+        self.inner.post(path).json(body).send_with_meta().await
     }
 
     /// Dispatch a PUT request with a JSON body and decode the JSON response.
-    async fn put<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+    async fn put<T, B>(
+        &self,
+        path: impl Into<RequestPath>,
+        body: &B,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
+        self.inner.put(path).json(body).send_with_meta().await
+    }
+
+    /// Dispatch a POST request whose body carries an `entity_secret_ciphertext`.
+    ///
+    /// If the ciphertext is empty and the client has an entity secret, every
+    /// attempt is sent with a freshly encrypted one: Circle rejects a reused
+    /// ciphertext, so a retry must not resend the first.
+    async fn post_sealed<T, B>(
+        &self,
+        path: impl Into<RequestPath>,
+        body: &B,
+    ) -> Result<ApiResponse<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize + EntitySecretCiphertext,
+    {
+        let request = self.inner.post(path).json(body);
+        let Some(state) = self.entity_secret.as_deref() else {
+            return request.send_with_meta().await;
+        };
+        if !body.entity_secret_ciphertext().is_empty() {
+            return request.send_with_meta().await;
+        }
+        let public_key = self.cached_public_key(state).await?;
+        request
+            .refresh_body(move |body| {
+                let ciphertext = state.secret.encrypt(public_key)?;
+                if let Some(body) = body.as_object_mut() {
+                    body.insert("entitySecretCiphertext".to_string(), ciphertext.into());
+                }
+                Ok(())
+            })
+            .send_with_meta()
             .await
-            .map_err(|e| Error::Http(e.to_string()))?;
+    }
 
-        if resp.status().is_success() {
-            resp.json::<T>().await.map_err(|e| Error::Http(e.to_string()))
-        } else {
-            let err: ApiErrorBody = resp.json().await.map_err(|e| Error::Http(e.to_string()))?;
-            Err(Error::Api { code: err.code, message: err.message })
+    /// The entity public key of `state`, fetched on first use.
+    async fn cached_public_key<'s>(
+        &self,
+        state: &'s EntitySecretState,
+    ) -> Result<&'s EntityPublicKey, Error> {
+        if let Some(public_key) = state.public_key.get() {
+            return Ok(public_key);
         }
+        let public_key = self.entity_public_key().await?;
+        Ok(state.public_key.get_or_init(|| public_key))
+    }
+
+    // ── Entity Secret ──────────────────────────────────────────────────────
+
+    /// Get the entity public key that entity secrets are encrypted to.
+    pub async fn get_public_key(&self) -> Result<EntityPublicKeyResponse, Error> {
+        self.get_public_key_with_meta().await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_public_key`], but also returns the response metadata.
+    pub async fn get_public_key_with_meta(
+        &self,
+    ) -> Result<ApiResponse<EntityPublicKeyResponse>, Error> {
+        self.get("/v1/w3s/config/entity/publicKey", &[("", "")][..0]).await
+    }
+
+    /// Fetch and parse the entity public key.
+    pub async fn entity_public_key(&self) -> Result<EntityPublicKey, Error> {
+        let response = self.get_public_key().await?;
+        Ok(EntityPublicKey::from_pem(&response.data.public_key)?)
+    }
+
+    /// A fresh ciphertext of the entity secret set with
+    /// [`Self::with_entity_secret`], for requests built by hand.
+    ///
+    /// Fails with [`Error::InvalidParam`] if the client has no entity secret.
+    pub async fn entity_secret_ciphertext(&self) -> Result<String, Error> {
+        let state = self.entity_secret.as_ref().ok_or_else(|| {
+            Error::InvalidParam("no entity secret configured on this client".into())
+        })?;
+        let public_key = self.cached_public_key(state).await?;
+        Ok(state.secret.encrypt(public_key)?)
+    }
+
+    /// Register an entity secret ciphertext, once per entity, and get its
+    /// recovery file.
+    pub async fn register_entity_secret_ciphertext(
+        &self,
+        req: &RegisterEntitySecretRequest,
+    ) -> Result<EntitySecretRecoveryResponse, Error> {
+        self.register_entity_secret_ciphertext_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::register_entity_secret_ciphertext`], but also returns the response
+    /// metadata.
+    pub async fn register_entity_secret_ciphertext_with_meta(
+        &self,
+        req: &RegisterEntitySecretRequest,
+    ) -> Result<ApiResponse<EntitySecretRecoveryResponse>, Error> {
+        self.post("/v1/w3s/config/entity/entitySecret", req).await
+    }
+
+    /// Encrypt `secret` to the entity public key and register it, returning
+    /// the recovery file to store with [`RecoveryFile::write`].
+    pub async fn register_entity_secret(
+        &self,
+        secret: &EntitySecret,
+    ) -> Result<RecoveryFile, Error> {
+        let public_key = self.entity_public_key().await?;
+        let req =
+            RegisterEntitySecretRequest { entity_secret_ciphertext: secret.encrypt(&public_key)? };
+        let response = self.register_entity_secret_ciphertext(&req).await?;
+        Ok(RecoveryFile::parse(&response.data.recovery_file)?)
     }
 
     // ── Wallet Sets ────────────────────────────────────────────────────────
@@ -136,23 +289,48 @@ impl DeveloperWalletsClient {
         &self,
         req: &CreateWalletSetRequest,
     ) -> Result<WalletSetResponse, Error> {
-        self.post("/v1/w3s/developer/walletSets", req).await
+        self.create_wallet_set_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_wallet_set`], but also returns the response metadata.
+    pub async fn create_wallet_set_with_meta(
+        &self,
+        req: &CreateWalletSetRequest,
+    ) -> Result<ApiResponse<WalletSetResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/walletSets", req).await
     }
 
     /// Get a wallet set by its UUID.
-    pub async fn get_wallet_set(&self, id: &str) -> Result<WalletSetResponse, Error> {
-        let path = format!("/v1/w3s/developer/walletSets/{}", id);
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_wallet_set(&self, id: &WalletSetId) -> Result<WalletSetResponse, Error> {
+        self.get_wallet_set_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_wallet_set`], but also returns the response metadata.
+    pub async fn get_wallet_set_with_meta(
+        &self,
+        id: &WalletSetId,
+    ) -> Result<ApiResponse<WalletSetResponse>, Error> {
+        let path = api_path!("/v1/w3s/walletSets/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     /// Update the name of a wallet set.
     pub async fn update_wallet_set(
         &self,
-        id: &str,
+        id: &WalletSetId,
         req: &UpdateWalletSetRequest,
     ) -> Result<WalletSetResponse, Error> {
-        let path = format!("/v1/w3s/developer/walletSets/{}", id);
-        self.put(&path, req).await
+        self.update_wallet_set_with_meta(id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::update_wallet_set`], but also returns the response metadata.
+    pub async fn update_wallet_set_with_meta(
+        &self,
+        id: &WalletSetId,
+        req: &UpdateWalletSetRequest,
+    ) -> Result<ApiResponse<WalletSetResponse>, Error> {
+        let path = api_path!("/v1/w3s/developer/walletSets/{id}");
+        self.put(path, req).await
     }
 
     /// List all wallet sets belonging to the entity.
@@ -160,35 +338,164 @@ impl DeveloperWalletsClient {
         &self,
         params: &ListWalletSetsParams,
     ) -> Result<WalletSets, Error> {
+        self.list_wallet_sets_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_sets`], but also returns the response metadata.
+    pub async fn list_wallet_sets_with_meta(
+        &self,
+        params: &ListWalletSetsParams,
+    ) -> Result<ApiResponse<WalletSets>, Error> {
         self.get("/v1/w3s/walletSets", params).await
     }
 
+    /// Stream all wallet sets belonging to the entity, [page by page](circle_core::pagination).
+    pub fn list_wallet_sets_stream<'a>(
+        &'a self,
+        params: &ListWalletSetsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<WalletSet, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_sets(&params).await.map(|page| page.data.wallet_sets)
+        })
+    }
+
     // ── Wallets ────────────────────────────────────────────────────────────
 
     /// Create one or more developer-controlled wallets.
     pub async fn create_wallets(&self, req: &CreateWalletsRequest) -> Result<Wallets, Error> {
-        self.post("/v1/w3s/developer/wallets", req).await
+        self.create_wallets_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_wallets`], but also returns the response metadata.
+    pub async fn create_wallets_with_meta(
+        &self,
+        req: &CreateWalletsRequest,
+    ) -> Result<ApiResponse<Wallets>, Error> {
+        self.post_sealed("/v1/w3s/developer/wallets", req).await
     }
 
     /// List wallets matching the given filters.
     pub async fn list_wallets(&self, params: &ListWalletsParams) -> Result<Wallets, Error> {
+        self.list_wallets_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallets`], but also returns the response metadata.
+    pub async fn list_wallets_with_meta(
+        &self,
+        params: &ListWalletsParams,
+    ) -> Result<ApiResponse<Wallets>, Error> {
         self.get("/v1/w3s/wallets", params).await
     }
 
+    /// Stream wallets matching the given filters, [page by page](circle_core::pagination).
+    pub fn list_wallets_stream<'a>(
+        &'a self,
+        params: &ListWalletsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Wallet, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallets(&params).await.map(|page| page.data.wallets)
+        })
+    }
+
     /// Get a wallet by its UUID.
-    pub async fn get_wallet(&self, id: &str) -> Result<WalletResponse, Error> {
-        let path = format!("/v1/w3s/wallets/{}", id);
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_wallet(&self, id: &WalletId) -> Result<WalletResponse, Error> {
+        self.get_wallet_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_wallet`], but also returns the response metadata.
+    pub async fn get_wallet_with_meta(
+        &self,
+        id: &WalletId,
+    ) -> Result<ApiResponse<WalletResponse>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     /// Update the name or reference ID of a wallet.
     pub async fn update_wallet(
         &self,
-        id: &str,
+        id: &WalletId,
         req: &UpdateWalletRequest,
     ) -> Result<WalletResponse, Error> {
-        let path = format!("/v1/w3s/wallets/{}", id);
-        self.put(&path, req).await
+        self.update_wallet_with_meta(id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::update_wallet`], but also returns the response metadata.
+    pub async fn update_wallet_with_meta(
+        &self,
+        id: &WalletId,
+        req: &UpdateWalletRequest,
+    ) -> Result<ApiResponse<WalletResponse>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{id}");
+        self.put(path, req).await
+    }
+
+    /// Derive a wallet onto another EVM blockchain with the same address.
+    ///
+    /// If the wallet already exists there, its metadata is updated from
+    /// `req` instead; [`DerivedWallet::created`] tells the two apart.
+    pub async fn derive_wallet(
+        &self,
+        id: &WalletId,
+        blockchain: &EvmBlockchain,
+        req: &DeriveWalletRequest,
+    ) -> Result<DerivedWallet, Error> {
+        self.derive_wallet_with_meta(id, blockchain, req).await.map(derived_wallet)
+    }
+
+    /// Same as [`Self::derive_wallet`], but also returns the response metadata.
+    pub async fn derive_wallet_with_meta(
+        &self,
+        id: &WalletId,
+        blockchain: &EvmBlockchain,
+        req: &DeriveWalletRequest,
+    ) -> Result<ApiResponse<WalletResponse>, Error> {
+        let blockchain = path_segment(blockchain)?;
+        let path = api_path!("/v1/w3s/developer/wallets/{id}/blockchains/{blockchain}");
+        self.put(path, req).await
+    }
+
+    /// Derive a wallet onto each of `blockchains` in turn, giving it the same
+    /// address on all of them.
+    ///
+    /// Stops at the first error. Wallets derived before it are kept, and
+    /// re-deriving one of them returns `200` with [`DerivedWallet::created`]
+    /// `false` (its metadata updated from `req`), so the call can simply be
+    /// retried.
+    pub async fn derive_wallet_onto(
+        &self,
+        id: &WalletId,
+        blockchains: impl IntoIterator<Item = EvmBlockchain>,
+        req: &DeriveWalletRequest,
+    ) -> Result<Vec<DerivedWallet>, Error> {
+        let mut wallets = Vec::new();
+        for blockchain in blockchains {
+            wallets.push(self.derive_wallet(id, &blockchain, req).await?);
+        }
+        Ok(wallets)
+    }
+
+    /// Derive the wallet at a source blockchain and address onto another EVM
+    /// blockchain with the same address.
+    ///
+    /// If the wallet already exists there, its metadata is updated from
+    /// `req` instead; [`DerivedWallet::created`] tells the two apart.
+    pub async fn derive_wallet_by_address(
+        &self,
+        req: &DeriveWalletByAddressRequest,
+    ) -> Result<DerivedWallet, Error> {
+        self.derive_wallet_by_address_with_meta(req).await.map(derived_wallet)
+    }
+
+    /// Same as [`Self::derive_wallet_by_address`], but also returns the response metadata.
+    pub async fn derive_wallet_by_address_with_meta(
+        &self,
+        req: &DeriveWalletByAddressRequest,
+    ) -> Result<ApiResponse<WalletResponse>, Error> {
+        req.validate()?;
+        self.put("/v1/w3s/developer/wallets/derive", req).await
     }
 
     /// List developer wallets with their token balances.
@@ -196,34 +503,109 @@ impl DeveloperWalletsClient {
         &self,
         params: &ListWalletBalancesParams,
     ) -> Result<WalletsWithBalances, Error> {
+        self.list_wallet_balances_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_balances`], but also returns the response metadata.
+    pub async fn list_wallet_balances_with_meta(
+        &self,
+        params: &ListWalletBalancesParams,
+    ) -> Result<ApiResponse<WalletsWithBalances>, Error> {
         self.get("/v1/w3s/developer/wallets/balances", params).await
     }
 
+    /// Stream developer wallets with their token balances, [page by page](circle_core::pagination).
+    pub fn list_wallet_balances_stream<'a>(
+        &'a self,
+        params: &ListWalletBalancesParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Wallet, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_balances(&params).await.map(|page| page.data.wallets)
+        })
+    }
+
     /// Retrieve token balances for a single wallet by its UUID.
     pub async fn list_wallet_token_balances(
         &self,
-        wallet_id: &str,
-        params: &WalletNftsParams,
+        wallet_id: &WalletId,
+        params: &ListWalletTokenBalancesParams,
     ) -> Result<Balances, Error> {
-        let path = format!("/v1/w3s/wallets/{}/balances", wallet_id);
-        self.get(&path, params).await
+        self.list_wallet_token_balances_with_meta(wallet_id, params)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_token_balances`], but also returns the response metadata.
+    pub async fn list_wallet_token_balances_with_meta(
+        &self,
+        wallet_id: &WalletId,
+        params: &ListWalletTokenBalancesParams,
+    ) -> Result<ApiResponse<Balances>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{wallet_id}/balances");
+        self.get(path, params).await
+    }
+
+    /// Stream token balances for a single wallet, [page by page](circle_core::pagination).
+    pub fn list_wallet_token_balances_stream<'a>(
+        &'a self,
+        wallet_id: &WalletId,
+        params: &ListWalletTokenBalancesParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Balance, Error>> + use<'a> {
+        let wallet_id = *wallet_id;
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_token_balances(&wallet_id, &params)
+                .await
+                .map(|page| page.data.token_balances)
+        })
     }
 
     /// Retrieve NFTs held by a wallet by its UUID.
     pub async fn list_wallet_nfts(
         &self,
-        wallet_id: &str,
+        wallet_id: &WalletId,
         params: &ListWalletNftsParams,
     ) -> Result<Nfts, Error> {
-        let path = format!("/v1/w3s/wallets/{}/nfts", wallet_id);
-        self.get(&path, params).await
+        self.list_wallet_nfts_with_meta(wallet_id, params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_nfts`], but also returns the response metadata.
+    pub async fn list_wallet_nfts_with_meta(
+        &self,
+        wallet_id: &WalletId,
+        params: &ListWalletNftsParams,
+    ) -> Result<ApiResponse<Nfts>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{wallet_id}/nfts");
+        self.get(path, params).await
+    }
+
+    /// Stream NFTs held by a wallet, [page by page](circle_core::pagination).
+    pub fn list_wallet_nfts_stream<'a>(
+        &'a self,
+        wallet_id: &WalletId,
+        params: &ListWalletNftsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Nft, Error>> + use<'a> {
+        let wallet_id = *wallet_id;
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_nfts(&wallet_id, &params).await.map(|page| page.data.nfts)
+        })
     }
 
     // ── Signing ────────────────────────────────────────────────────────────
 
     /// Sign a plain or hex-encoded message.
     pub async fn sign_message(&self, req: &SignMessageRequest) -> Result<SignatureResponse, Error> {
-        self.post("/v1/w3s/developer/sign/message", req).await
+        self.sign_message_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_message`], but also returns the response metadata.
+    pub async fn sign_message_with_meta(
+        &self,
+        req: &SignMessageRequest,
+    ) -> Result<ApiResponse<SignatureResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/sign/message", req).await
     }
 
     /// Sign an EIP-712 typed data payload.
@@ -231,7 +613,15 @@ impl DeveloperWalletsClient {
         &self,
         req: &SignTypedDataRequest,
     ) -> Result<SignatureResponse, Error> {
-        self.post("/v1/w3s/developer/sign/typedData", req).await
+        self.sign_typed_data_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_typed_data`], but also returns the response metadata.
+    pub async fn sign_typed_data_with_meta(
+        &self,
+        req: &SignTypedDataRequest,
+    ) -> Result<ApiResponse<SignatureResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/sign/typedData", req).await
     }
 
     /// Sign a raw transaction.
@@ -239,7 +629,31 @@ impl DeveloperWalletsClient {
         &self,
         req: &SignTransactionRequest,
     ) -> Result<SignTransactionResponse, Error> {
-        self.post("/v1/w3s/developer/sign/transaction", req).await
+        self.sign_transaction_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_transaction`], but also returns the response metadata.
+    pub async fn sign_transaction_with_meta(
+        &self,
+        req: &SignTransactionRequest,
+    ) -> Result<ApiResponse<SignTransactionResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/sign/transaction", req).await
+    }
+
+    /// Sign a NEAR delegate action.
+    pub async fn sign_delegate_action(
+        &self,
+        req: &SignDelegateActionRequest,
+    ) -> Result<SignDelegateActionResponse, Error> {
+        self.sign_delegate_action_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_delegate_action`], but also returns the response metadata.
+    pub async fn sign_delegate_action_with_meta(
+        &self,
+        req: &SignDelegateActionRequest,
+    ) -> Result<ApiResponse<SignDelegateActionResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/sign/delegateAction", req).await
     }
 
     // ── Transactions ───────────────────────────────────────────────────────
@@ -249,89 +663,270 @@ impl DeveloperWalletsClient {
         &self,
         params: &ListTransactionsParams,
     ) -> Result<Transactions, Error> {
+        self.list_transactions_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_transactions`], but also returns the response metadata.
+    pub async fn list_transactions_with_meta(
+        &self,
+        params: &ListTransactionsParams,
+    ) -> Result<ApiResponse<Transactions>, Error> {
         self.get("/v1/w3s/transactions", params).await
     }
 
+    /// Stream transactions matching the given filters, [page by page](circle_core::pagination).
+    pub fn list_transactions_stream<'a>(
+        &'a self,
+        params: &ListTransactionsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Transaction, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_transactions(&params).await.map(|page| page.data.transactions)
+        })
+    }
+
     /// Get a transaction by its UUID.
-    pub async fn get_transaction(&self, id: &str) -> Result<TransactionResponse, Error> {
-        let path = format!("/v1/w3s/transactions/{}", id);
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_transaction(&self, id: &TransactionId) -> Result<TransactionResponse, Error> {
+        self.get_transaction_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_transaction`], but also returns the response metadata.
+    pub async fn get_transaction_with_meta(
+        &self,
+        id: &TransactionId,
+    ) -> Result<ApiResponse<TransactionResponse>, Error> {
+        let path = api_path!("/v1/w3s/transactions/{id}");
+        self.get(path, &[("", "")][..0]).await
+    }
+
+    /// Get the stuck transaction with the lowest nonce of a wallet, with the fee
+    /// needed to replace it.
+    ///
+    /// Returns `None` when the wallet has no such transaction (`204 No Content`).
+    pub async fn get_lowest_nonce_transaction(
+        &self,
+        params: &GetLowestNonceTxParams,
+    ) -> Result<Option<GetLowestNonceTransactionResponse>, Error> {
+        self.get_lowest_nonce_transaction_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_lowest_nonce_transaction`], but also returns the response metadata.
+    pub async fn get_lowest_nonce_transaction_with_meta(
+        &self,
+        params: &GetLowestNonceTxParams,
+    ) -> Result<ApiResponse<Option<GetLowestNonceTransactionResponse>>, Error> {
+        self.get("/v1/w3s/transactions/lowestNonceTransaction", params).await
     }
 
     /// Create a developer-controlled transfer transaction.
+    ///
+    /// The destination address is checked offline first, see
+    /// [`CreateTransferTxRequest::validate`].
     pub async fn create_transfer_transaction(
         &self,
         req: &CreateTransferTxRequest,
-    ) -> Result<TransactionResponse, Error> {
-        self.post("/v1/w3s/developer/transactions/transfer", req).await
+    ) -> Result<TransactionStateResponse, Error> {
+        self.create_transfer_transaction_with_meta(req).await.map(ApiResponse::into_body)
     }
 
-    /// Get fee parameters for a transfer.
-    pub async fn get_fee_parameters(
+    /// Same as [`Self::create_transfer_transaction`], but also returns the response metadata.
+    pub async fn create_transfer_transaction_with_meta(
         &self,
         req: &CreateTransferTxRequest,
-    ) -> Result<EstimateFeeResponse, Error> {
-        self.post("/v1/w3s/developer/transactions/feeParameters", req).await
+    ) -> Result<ApiResponse<TransactionStateResponse>, Error> {
+        req.validate()?;
+        self.post_sealed("/v1/w3s/developer/transactions/transfer", req).await
+    }
+
+    /// Get the current gas price parameters of a blockchain per fee level.
+    pub async fn get_fee_parameters(
+        &self,
+        params: &FeeParametersParams,
+    ) -> Result<FeeParametersResponse, Error> {
+        self.get_fee_parameters_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_fee_parameters`], but also returns the response metadata.
+    pub async fn get_fee_parameters_with_meta(
+        &self,
+        params: &FeeParametersParams,
+    ) -> Result<ApiResponse<FeeParametersResponse>, Error> {
+        self.get("/v1/w3s/developer/transactions/feeParameters", params).await
     }
 
     /// Create a developer-controlled contract execution transaction.
     pub async fn create_contract_execution_transaction(
         &self,
         req: &CreateContractExecutionTxRequest,
-    ) -> Result<TransactionResponse, Error> {
-        self.post("/v1/w3s/developer/transactions/contractExecution", req).await
+    ) -> Result<TransactionStateResponse, Error> {
+        self.create_contract_execution_transaction_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_contract_execution_transaction`], but also returns the response
+    /// metadata.
+    pub async fn create_contract_execution_transaction_with_meta(
+        &self,
+        req: &CreateContractExecutionTxRequest,
+    ) -> Result<ApiResponse<TransactionStateResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/transactions/contractExecution", req).await
+    }
+
+    /// Upgrade an SCA wallet to a newer SCA core implementation.
+    pub async fn create_wallet_upgrade_transaction(
+        &self,
+        req: &CreateWalletUpgradeTxRequest,
+    ) -> Result<TransactionStateResponse, Error> {
+        self.create_wallet_upgrade_transaction_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_wallet_upgrade_transaction`], but also returns the response
+    /// metadata.
+    pub async fn create_wallet_upgrade_transaction_with_meta(
+        &self,
+        req: &CreateWalletUpgradeTxRequest,
+    ) -> Result<ApiResponse<TransactionStateResponse>, Error> {
+        self.post_sealed("/v1/w3s/developer/transactions/walletUpgrade", req).await
     }
 
     /// Cancel a stuck or queued transaction.
     pub async fn cancel_transaction(
         &self,
-        id: &str,
+        id: &TransactionId,
+        req: &CancelTxRequest,
+    ) -> Result<TransactionStateResponse, Error> {
+        self.cancel_transaction_with_meta(id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::cancel_transaction`], but also returns the response metadata.
+    pub async fn cancel_transaction_with_meta(
+        &self,
+        id: &TransactionId,
         req: &CancelTxRequest,
-    ) -> Result<TransactionResponse, Error> {
-        let path = format!("/v1/w3s/developer/transactions/{}/cancel", id);
-        self.post(&path, req).await
+    ) -> Result<ApiResponse<TransactionStateResponse>, Error> {
+        let path = api_path!("/v1/w3s/developer/transactions/{id}/cancel");
+        self.post_sealed(path, req).await
     }
 
     /// Accelerate a stuck transaction by resubmitting with higher fees.
     pub async fn accelerate_transaction(
         &self,
-        id: &str,
+        id: &TransactionId,
         req: &AccelerateTxRequest,
-    ) -> Result<TransactionResponse, Error> {
-        let path = format!("/v1/w3s/developer/transactions/{}/accelerate", id);
-        self.post(&path, req).await
+    ) -> Result<TransactionIdResponse, Error> {
+        self.accelerate_transaction_with_meta(id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::accelerate_transaction`], but also returns the response metadata.
+    pub async fn accelerate_transaction_with_meta(
+        &self,
+        id: &TransactionId,
+        req: &AccelerateTxRequest,
+    ) -> Result<ApiResponse<TransactionIdResponse>, Error> {
+        let path = api_path!("/v1/w3s/developer/transactions/{id}/accelerate");
+        self.post_sealed(path, req).await
     }
 
     // ── Tokens ─────────────────────────────────────────────────────────────
 
     /// Get a token by its UUID.
-    pub async fn get_token(&self, id: &str) -> Result<TokenResponse, Error> {
-        let path = format!("/v1/w3s/tokens/{}", id);
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_token(&self, id: &TokenId) -> Result<TokenResponse, Error> {
+        self.get_token_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_token`], but also returns the response metadata.
+    pub async fn get_token_with_meta(
+        &self,
+        id: &TokenId,
+    ) -> Result<ApiResponse<TokenResponse>, Error> {
+        let path = api_path!("/v1/w3s/tokens/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     // ── Utilities ──────────────────────────────────────────────────────────
 
     /// Estimate fees for a transfer transaction.
+    ///
+    /// The addresses are checked offline first, see
+    /// [`EstimateTransferFeeRequest::validate`].
     pub async fn estimate_transfer_fee(
         &self,
         req: &EstimateTransferFeeRequest,
     ) -> Result<EstimateFeeResponse, Error> {
+        self.estimate_transfer_fee_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::estimate_transfer_fee`], but also returns the response metadata.
+    pub async fn estimate_transfer_fee_with_meta(
+        &self,
+        req: &EstimateTransferFeeRequest,
+    ) -> Result<ApiResponse<EstimateFeeResponse>, Error> {
+        req.validate()?;
         self.post("/v1/w3s/transactions/transfer/estimateFee", req).await
     }
 
+    /// Estimate fees for a contract execution transaction.
+    pub async fn estimate_contract_execution_fee(
+        &self,
+        req: &EstimateContractExecFeeRequest,
+    ) -> Result<EstimateFeeResponse, Error> {
+        self.estimate_contract_execution_fee_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::estimate_contract_execution_fee`], but also returns the response metadata.
+    pub async fn estimate_contract_execution_fee_with_meta(
+        &self,
+        req: &EstimateContractExecFeeRequest,
+    ) -> Result<ApiResponse<EstimateFeeResponse>, Error> {
+        self.post("/v1/w3s/transactions/contractExecution/estimateFee", req).await
+    }
+
     /// Validate a blockchain address.
     pub async fn validate_address(
         &self,
         req: &ValidateAddressRequest,
     ) -> Result<ValidateAddressResponse, Error> {
+        self.validate_address_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::validate_address`], but also returns the response metadata.
+    pub async fn validate_address_with_meta(
+        &self,
+        req: &ValidateAddressRequest,
+    ) -> Result<ApiResponse<ValidateAddressResponse>, Error> {
         self.post("/v1/w3s/transactions/validateAddress", req).await
     }
 }
 
+/// Reads a derive-wallet response: `201` for a created wallet, `200` for an
+/// existing one whose metadata was updated.
+fn derived_wallet(response: ApiResponse<WalletResponse>) -> DerivedWallet {
+    DerivedWallet { created: response.meta.status == 201, wallet: response.body.data.wallet }
+}
+
+/// The wire value of an enum, for use as a path segment.
+fn path_segment(value: &impl serde::Serialize) -> Result<String, Error> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(segment)) => Ok(segment),
+        _ => Err(Error::InvalidParam("path segment must serialize to a string".to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::models::transaction::TransactionState;
+    use std::time::Duration;
+
+    use circle_core::{Expectation, Method, MockTransport, RetryPolicy, ids::IdempotencyKey};
+    use serde_json::json;
+
+    use super::*;
+    use crate::models::{
+        common::{Blockchain, PageParams},
+        transaction::TransactionState,
+    };
+
+    fn mock_client(mock: &MockTransport) -> Result<DeveloperWalletsClient, Error> {
+        DeveloperWalletsClient::builder("key").transport(mock.clone()).build()
+    }
 
     /// Verify we can construct the types without panic (static / non-network tests).
     #[test]
@@ -344,9 +939,11 @@ mod tests {
     #[test]
     fn cancel_tx_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = crate::models::transaction::CancelTxRequest {
+            idempotency_key: IdempotencyKey::random(),
             entity_secret_ciphertext: "cipher".to_string(),
         };
         let json = serde_json::to_string(&req)?;
+        assert!(json.contains("idempotencyKey"));
         assert!(json.contains("entitySecretCiphertext"));
         Ok(())
     }
@@ -354,10 +951,250 @@ mod tests {
     #[test]
     fn accelerate_tx_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = crate::models::transaction::AccelerateTxRequest {
+            idempotency_key: IdempotencyKey::random(),
             entity_secret_ciphertext: "cipher".to_string(),
         };
         let json = serde_json::to_string(&req)?;
+        assert!(json.contains("idempotencyKey"));
         assert!(json.contains("entitySecretCiphertext"));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn create_wallet_set_posts_request_body() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::POST, "/v1/w3s/developer/walletSets")
+                .json_body(json!({
+                    "entitySecretCiphertext": "cipher",
+                    "idempotencyKey": "9f2c6a1e-4b7d-4e3a-8c5f-1d2e3f4a5b6c",
+                    "name": "treasury",
+                }))
+                .respond(
+                    201,
+                    json!({ "data": { "walletSet": {
+                        "id": "0189bc61-7fe4-70f3-8a1b-0d14426397cb",
+                        "custodyType": "DEVELOPER",
+                        "createDate": "2024-01-01T00:00:00Z",
+                        "updateDate": "2024-01-01T00:00:00Z",
+                        "name": "treasury",
+                    }}}),
+                ),
+        );
+        let response = mock_client(&mock)?
+            .create_wallet_set_with_meta(&CreateWalletSetRequest {
+                entity_secret_ciphertext: "cipher".to_string(),
+                idempotency_key: "9f2c6a1e-4b7d-4e3a-8c5f-1d2e3f4a5b6c".parse()?,
+                name: Some("treasury".to_string()),
+            })
+            .await?;
+        assert_eq!(response.meta.status, 201);
+        assert_eq!(response.body.data.wallet_set.name.as_deref(), Some("treasury"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_ciphertexts_are_filled_fresh_per_request()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::GET, "/v1/w3s/config/entity/publicKey")
+                .respond(200, json!({ "data": { "publicKey": circle_mock::ENTITY_PUBLIC_KEY } })),
+        );
+        let wallet_set = json!({ "data": { "walletSet": {
+            "id": "0189bc61-7fe4-70f3-8a1b-0d14426397cb",
+            "custodyType": "DEVELOPER",
+            "createDate": "2024-01-01T00:00:00Z",
+            "updateDate": "2024-01-01T00:00:00Z",
+        }}});
+        for _ in 0..3 {
+            mock.expect(
+                Expectation::new(Method::POST, "/v1/w3s/developer/walletSets")
+                    .respond(201, wallet_set.clone()),
+            );
+        }
+        let client = mock_client(&mock)?.with_entity_secret(EntitySecret::generate());
+        let mut req = CreateWalletSetRequest {
+            entity_secret_ciphertext: String::new(),
+            idempotency_key: "9f2c6a1e-4b7d-4e3a-8c5f-1d2e3f4a5b6c".parse()?,
+            name: None,
+        };
+        client.create_wallet_set(&req).await?;
+        client.with_options(RequestOptions::new()).create_wallet_set(&req).await?;
+        req.entity_secret_ciphertext = "cipher".to_string();
+        client.create_wallet_set(&req).await?;
+
+        let requests = mock.requests();
+        let key_fetches = requests
+            .iter()
+            .filter(|request| request.path.as_str() == "/v1/w3s/config/entity/publicKey")
+            .count();
+        assert_eq!(key_fetches, 1, "one public key fetch for both copies");
+        let ciphertexts = requests
+            .iter()
+            .filter_map(|request| request.body.as_deref())
+            .map(serde_json::from_slice::<serde_json::Value>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let ciphertexts: Vec<_> =
+            ciphertexts.iter().map(|body| body["entitySecretCiphertext"].as_str()).collect();
+        assert_eq!(ciphertexts.len(), 3, "one body per wallet set request");
+        assert_eq!(ciphertexts[0].map(str::len), Some(344));
+        assert_ne!(ciphertexts[0], ciphertexts[1]);
+        assert_eq!(ciphertexts[2], Some("cipher"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn retries_are_sent_with_a_fresh_ciphertext() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::GET, "/v1/w3s/config/entity/publicKey")
+                .respond(200, json!({ "data": { "publicKey": circle_mock::ENTITY_PUBLIC_KEY } })),
+        );
+        mock.expect(
+            Expectation::new(Method::POST, "/v1/w3s/developer/walletSets")
+                .respond(503, json!({ "code": -1, "message": "unavailable" })),
+        );
+        mock.expect(Expectation::new(Method::POST, "/v1/w3s/developer/walletSets").respond(
+            201,
+            json!({ "data": { "walletSet": {
+                "id": "0189bc61-7fe4-70f3-8a1b-0d14426397cb",
+                "custodyType": "DEVELOPER",
+                "createDate": "2024-01-01T00:00:00Z",
+                "updateDate": "2024-01-01T00:00:00Z",
+            }}}),
+        ));
+        let client = DeveloperWalletsClient::builder("key")
+            .transport(mock.clone())
+            .retry_policy(
+                RetryPolicy::default()
+                    .with_backoff(Duration::from_millis(1), Duration::from_millis(1)),
+            )
+            .build()?
+            .with_entity_secret(EntitySecret::generate());
+        let response = client
+            .create_wallet_set_with_meta(&CreateWalletSetRequest {
+                entity_secret_ciphertext: String::new(),
+                idempotency_key: IdempotencyKey::random(),
+                name: None,
+            })
+            .await?;
+        assert_eq!(response.meta.attempts, 2);
+
+        let ciphertexts: Vec<_> = mock
+            .requests()
+            .iter()
+            .filter_map(|request| request.json())
+            .map(|body| body["entitySecretCiphertext"].as_str().map(str::to_string))
+            .collect();
+        assert_eq!(ciphertexts.len(), 2, "one body per attempt");
+        assert!(ciphertexts.iter().all(Option::is_some));
+        assert_ne!(ciphertexts[0], ciphertexts[1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn derive_wallet_tells_created_from_updated() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        let path = "/v1/w3s/developer/wallets/0189bc61-7fe4-70f3-8a1b-0d14426397cb/blockchains/BASE-SEPOLIA";
+        let wallet = json!({ "data": { "wallet": {
+            "id": "5e0c7a4b-3f21-4d8e-9b6a-c1d2e3f4a5b6",
+            "address": "0x4b6c0b0078b63f881503e7fd3a9a1061065db242",
+            "blockchain": "BASE-SEPOLIA",
+            "createDate": "2024-01-01T00:00:00Z",
+            "updateDate": "2024-01-01T00:00:00Z",
+            "custodyType": "DEVELOPER",
+            "state": "LIVE",
+            "walletSetId": "0189bc61-7fe4-70f3-8a1b-0d14426397cb",
+        }}});
+        mock.expect(
+            Expectation::new(Method::PUT, path).json_body(json!({})).respond(201, wallet.clone()),
+        );
+        mock.expect(Expectation::new(Method::PUT, path).respond(200, wallet));
+        let client = mock_client(&mock)?;
+        let id: WalletId = "0189bc61-7fe4-70f3-8a1b-0d14426397cb".parse()?;
+
+        let derived = client
+            .derive_wallet_onto(
+                &id,
+                [EvmBlockchain::BaseSepolia, EvmBlockchain::BaseSepolia],
+                &DeriveWalletRequest::default(),
+            )
+            .await?;
+        let created: Vec<_> = derived.iter().map(|derived| derived.created).collect();
+        assert_eq!(created, [true, false]);
+        assert_eq!(derived[0].wallet.id, derived[1].wallet.id);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn list_wallets_encodes_filters_as_query() -> Result<(), Box<dyn std::error::Error>> {
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::GET, "/v1/w3s/wallets")
+                .query("walletSetId", "5e0c7a4b-3f21-4d8e-9b6a-c1d2e3f4a5b6")
+                .query("pageSize", "5")
+                .respond(200, json!({ "data": { "wallets": [] } })),
+        );
+        let wallets = mock_client(&mock)?
+            .list_wallets(&ListWalletsParams {
+                wallet_set_id: Some("5e0c7a4b-3f21-4d8e-9b6a-c1d2e3f4a5b6".parse()?),
+                page: PageParams { page_size: Some(5), ..PageParams::default() },
+                ..ListWalletsParams::default()
+            })
+            .await?;
+        assert!(wallets.data.wallets.is_empty());
+        let request = mock.last_request().ok_or("no request recorded")?;
+        assert!(request.body.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn malformed_destination_fails_before_sending() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mock = MockTransport::new();
+        let result = mock_client(&mock)?
+            .create_transfer_transaction(&CreateTransferTxRequest {
+                idempotency_key: IdempotencyKey::random(),
+                entity_secret_ciphertext: "cipher".to_string(),
+                wallet_id: "0189bc61-7fe4-70f3-8a1b-0d14426397cb".parse()?,
+                blockchain: Some(Blockchain::EthSepolia),
+                token_id: None,
+                destination_address: "0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_string(),
+                amounts: Some(vec!["1".parse()?]),
+                nft_token_ids: None,
+                ref_id: None,
+                fee_level: None,
+                gas_limit: None,
+                gas_price: None,
+                max_fee: None,
+                priority_fee: None,
+            })
+            .await;
+        assert!(matches!(result, Err(Error::InvalidParam(_))), "{result:?}");
+        assert!(mock.last_request().is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn date_filters_are_sent_as_rfc3339_utc() -> Result<(), Box<dyn std::error::Error>> {
+        use chrono::{TimeZone, Utc};
+
+        let mock = MockTransport::new();
+        mock.expect(
+            Expectation::new(Method::GET, "/v1/w3s/transactions")
+                .query("from", "2024-01-01T00:00:00.000Z")
+                .query("to", "2024-01-31T23:59:59.500Z")
+                .respond(200, json!({ "data": { "transactions": [] } })),
+        );
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single();
+        let to = Utc
+            .with_ymd_and_hms(2024, 1, 31, 23, 59, 59)
+            .single()
+            .map(|to| to + chrono::Duration::nanoseconds(500_123_456));
+        mock_client(&mock)?
+            .list_transactions(&ListTransactionsParams { from, to, ..Default::default() })
+            .await?;
+        Ok(())
+    }
 }
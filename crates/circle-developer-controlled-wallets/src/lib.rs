@@ -9,10 +9,12 @@
 //! | Module | Functionality |
 //! |--------|---------------|
 //! | [`models::wallet_set`] | Create and manage wallet sets |
-//! | [`models::wallet`] | Create wallets, query balances and NFTs |
+//! | [`models::wallet`] | Create and derive wallets, query balances and NFTs |
 //! | [`models::transaction`] | Initiate and track on-chain transactions |
 //! | [`models::signing`] | Sign messages and typed data |
 //! | [`models::token`] | Look up token metadata |
+//! | [`models::config`] | Fetch the entity public key, register an entity secret |
+//! | [`entity_secret`] | Encrypt entity secrets into per-request ciphertexts, recovery files |
 //!
 //! ## Quick Start
 //!
@@ -44,8 +46,13 @@
 #![deny(missing_docs)]
 
 pub mod client;
+pub mod entity_secret;
 pub mod error;
 pub mod models;
 
+pub use circle_core::{
+    ApiEnum, ApiResponse, ClientBuilder, RequestOptions, ResponseMeta, RetryPolicy, address,
+    amount, ids, middleware, network, pagination, transport, unknown_fields,
+};
 pub use client::DeveloperWalletsClient;
 pub use error::Error;
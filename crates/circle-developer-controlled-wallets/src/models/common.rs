@@ -3,220 +3,334 @@
 //! Includes shared pagination, blockchain, error, and identifier types used
 //! across developer-controlled wallet endpoints.
 
-/// Blockchain network identifier.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum Blockchain {
-    /// Ethereum mainnet.
-    #[serde(rename = "ETH")]
+use chrono::{DateTime, Utc};
+use circle_core::{
+    address::{self, AddressError},
+    amount::TokenAmount,
+    api_enum,
+    network::Network,
+};
+
+api_enum! {
+    /// Blockchain network identifier.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub enum Blockchain {
+        /// Ethereum mainnet.
+        #[serde(rename = "ETH")]
+        Eth,
+        /// Ethereum Sepolia testnet.
+        #[serde(rename = "ETH-SEPOLIA")]
+        EthSepolia,
+        /// Avalanche C-Chain mainnet.
+        #[serde(rename = "AVAX")]
+        Avax,
+        /// Avalanche Fuji testnet.
+        #[serde(rename = "AVAX-FUJI")]
+        AvaxFuji,
+        /// Polygon PoS mainnet.
+        #[serde(rename = "MATIC")]
+        Matic,
+        /// Polygon Amoy testnet.
+        #[serde(rename = "MATIC-AMOY")]
+        MaticAmoy,
+        /// Solana mainnet.
+        #[serde(rename = "SOL")]
+        Sol,
+        /// Solana devnet.
+        #[serde(rename = "SOL-DEVNET")]
+        SolDevnet,
+        /// Arbitrum One mainnet.
+        #[serde(rename = "ARB")]
+        Arb,
+        /// Arbitrum Sepolia testnet.
+        #[serde(rename = "ARB-SEPOLIA")]
+        ArbSepolia,
+        /// NEAR mainnet.
+        #[serde(rename = "NEAR")]
+        Near,
+        /// NEAR testnet.
+        #[serde(rename = "NEAR-TESTNET")]
+        NearTestnet,
+        /// Generic EVM mainnet.
+        #[serde(rename = "EVM")]
+        Evm,
+        /// Generic EVM testnet.
+        #[serde(rename = "EVM-TESTNET")]
+        EvmTestnet,
+        /// Unichain mainnet.
+        #[serde(rename = "UNI")]
+        Uni,
+        /// Unichain Sepolia testnet.
+        #[serde(rename = "UNI-SEPOLIA")]
+        UniSepolia,
+        /// Base mainnet.
+        #[serde(rename = "BASE")]
+        Base,
+        /// Base Sepolia testnet.
+        #[serde(rename = "BASE-SEPOLIA")]
+        BaseSepolia,
+        /// Optimism mainnet.
+        #[serde(rename = "OP")]
+        Op,
+        /// Optimism Sepolia testnet.
+        #[serde(rename = "OP-SEPOLIA")]
+        OpSepolia,
+        /// Aptos mainnet.
+        #[serde(rename = "APTOS")]
+        Aptos,
+        /// Aptos testnet.
+        #[serde(rename = "APTOS-TESTNET")]
+        AptosTestnet,
+        /// ARC testnet.
+        #[serde(rename = "ARC-TESTNET")]
+        ArcTestnet,
+        /// Monad mainnet.
+        #[serde(rename = "MONAD")]
+        Monad,
+        /// Monad testnet.
+        #[serde(rename = "MONAD-TESTNET")]
+        MonadTestnet,
+    }
+}
+
+circle_core::network_conversions!(Blockchain {
     Eth,
-    /// Ethereum Sepolia testnet.
-    #[serde(rename = "ETH-SEPOLIA")]
     EthSepolia,
-    /// Avalanche C-Chain mainnet.
-    #[serde(rename = "AVAX")]
     Avax,
-    /// Avalanche Fuji testnet.
-    #[serde(rename = "AVAX-FUJI")]
     AvaxFuji,
-    /// Polygon PoS mainnet.
-    #[serde(rename = "MATIC")]
     Matic,
-    /// Polygon Amoy testnet.
-    #[serde(rename = "MATIC-AMOY")]
     MaticAmoy,
-    /// Solana mainnet.
-    #[serde(rename = "SOL")]
     Sol,
-    /// Solana devnet.
-    #[serde(rename = "SOL-DEVNET")]
     SolDevnet,
-    /// Arbitrum One mainnet.
-    #[serde(rename = "ARB")]
     Arb,
-    /// Arbitrum Sepolia testnet.
-    #[serde(rename = "ARB-SEPOLIA")]
     ArbSepolia,
-    /// NEAR mainnet.
-    #[serde(rename = "NEAR")]
     Near,
-    /// NEAR testnet.
-    #[serde(rename = "NEAR-TESTNET")]
     NearTestnet,
-    /// Generic EVM mainnet.
-    #[serde(rename = "EVM")]
     Evm,
-    /// Generic EVM testnet.
-    #[serde(rename = "EVM-TESTNET")]
     EvmTestnet,
-    /// Unichain mainnet.
-    #[serde(rename = "UNI")]
     Uni,
-    /// Unichain Sepolia testnet.
-    #[serde(rename = "UNI-SEPOLIA")]
     UniSepolia,
-    /// Base mainnet.
-    #[serde(rename = "BASE")]
     Base,
-    /// Base Sepolia testnet.
-    #[serde(rename = "BASE-SEPOLIA")]
     BaseSepolia,
-    /// Optimism mainnet.
-    #[serde(rename = "OP")]
     Op,
-    /// Optimism Sepolia testnet.
-    #[serde(rename = "OP-SEPOLIA")]
     OpSepolia,
-    /// Aptos mainnet.
-    #[serde(rename = "APTOS")]
     Aptos,
-    /// Aptos testnet.
-    #[serde(rename = "APTOS-TESTNET")]
     AptosTestnet,
-    /// ARC testnet.
-    #[serde(rename = "ARC-TESTNET")]
     ArcTestnet,
-    /// Monad mainnet.
-    #[serde(rename = "MONAD")]
     Monad,
-    /// Monad testnet.
-    #[serde(rename = "MONAD-TESTNET")]
     MonadTestnet,
+});
+
+impl Blockchain {
+    /// Checks `address` against the address format of this chain offline,
+    /// see [`circle_core::address`].
+    ///
+    /// Unknown chains and chains without offline rules pass unchecked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn check_address(&self, address: &str) -> Result<(), AddressError> {
+        Network::try_from(self.clone())
+            .map_or(Ok(()), |network| address::validate(network, address))
+    }
 }
 
-/// EVM-compatible blockchain network identifier.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum EvmBlockchain {
-    /// Ethereum mainnet.
-    #[serde(rename = "ETH")]
+api_enum! {
+    /// EVM-compatible blockchain network identifier.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub enum EvmBlockchain {
+        /// Ethereum mainnet.
+        #[serde(rename = "ETH")]
+        Eth,
+        /// Ethereum Sepolia testnet.
+        #[serde(rename = "ETH-SEPOLIA")]
+        EthSepolia,
+        /// Avalanche C-Chain mainnet.
+        #[serde(rename = "AVAX")]
+        Avax,
+        /// Avalanche Fuji testnet.
+        #[serde(rename = "AVAX-FUJI")]
+        AvaxFuji,
+        /// Polygon PoS mainnet.
+        #[serde(rename = "MATIC")]
+        Matic,
+        /// Polygon Amoy testnet.
+        #[serde(rename = "MATIC-AMOY")]
+        MaticAmoy,
+        /// Arbitrum One mainnet.
+        #[serde(rename = "ARB")]
+        Arb,
+        /// Arbitrum Sepolia testnet.
+        #[serde(rename = "ARB-SEPOLIA")]
+        ArbSepolia,
+        /// Unichain mainnet.
+        #[serde(rename = "UNI")]
+        Uni,
+        /// Unichain Sepolia testnet.
+        #[serde(rename = "UNI-SEPOLIA")]
+        UniSepolia,
+        /// Base mainnet.
+        #[serde(rename = "BASE")]
+        Base,
+        /// Base Sepolia testnet.
+        #[serde(rename = "BASE-SEPOLIA")]
+        BaseSepolia,
+        /// Optimism mainnet.
+        #[serde(rename = "OP")]
+        Op,
+        /// Optimism Sepolia testnet.
+        #[serde(rename = "OP-SEPOLIA")]
+        OpSepolia,
+        /// Generic EVM mainnet.
+        #[serde(rename = "EVM")]
+        Evm,
+        /// Generic EVM testnet.
+        #[serde(rename = "EVM-TESTNET")]
+        EvmTestnet,
+        /// ARC testnet.
+        #[serde(rename = "ARC-TESTNET")]
+        ArcTestnet,
+        /// Monad mainnet.
+        #[serde(rename = "MONAD")]
+        Monad,
+        /// Monad testnet.
+        #[serde(rename = "MONAD-TESTNET")]
+        MonadTestnet,
+    }
+}
+
+circle_core::network_conversions!(EvmBlockchain {
     Eth,
-    /// Ethereum Sepolia testnet.
-    #[serde(rename = "ETH-SEPOLIA")]
     EthSepolia,
-    /// Avalanche C-Chain mainnet.
-    #[serde(rename = "AVAX")]
     Avax,
-    /// Avalanche Fuji testnet.
-    #[serde(rename = "AVAX-FUJI")]
     AvaxFuji,
-    /// Polygon PoS mainnet.
-    #[serde(rename = "MATIC")]
     Matic,
-    /// Polygon Amoy testnet.
-    #[serde(rename = "MATIC-AMOY")]
     MaticAmoy,
-    /// Arbitrum One mainnet.
-    #[serde(rename = "ARB")]
     Arb,
-    /// Arbitrum Sepolia testnet.
-    #[serde(rename = "ARB-SEPOLIA")]
     ArbSepolia,
-    /// Unichain mainnet.
-    #[serde(rename = "UNI")]
     Uni,
-    /// Unichain Sepolia testnet.
-    #[serde(rename = "UNI-SEPOLIA")]
     UniSepolia,
-    /// Base mainnet.
-    #[serde(rename = "BASE")]
     Base,
-    /// Base Sepolia testnet.
-    #[serde(rename = "BASE-SEPOLIA")]
     BaseSepolia,
-    /// Optimism mainnet.
-    #[serde(rename = "OP")]
     Op,
-    /// Optimism Sepolia testnet.
-    #[serde(rename = "OP-SEPOLIA")]
     OpSepolia,
-    /// Generic EVM mainnet.
-    #[serde(rename = "EVM")]
     Evm,
-    /// Generic EVM testnet.
-    #[serde(rename = "EVM-TESTNET")]
     EvmTestnet,
-    /// ARC testnet.
-    #[serde(rename = "ARC-TESTNET")]
     ArcTestnet,
-    /// Monad mainnet.
-    #[serde(rename = "MONAD")]
     Monad,
-    /// Monad testnet.
-    #[serde(rename = "MONAD-TESTNET")]
     MonadTestnet,
+});
+
+impl EvmBlockchain {
+    /// Checks `address` against the address format of this chain offline,
+    /// see [`circle_core::address`].
+    ///
+    /// Unknown chains pass unchecked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn check_address(&self, address: &str) -> Result<(), AddressError> {
+        Network::try_from(self.clone())
+            .map_or(Ok(()), |network| address::validate(network, address))
+    }
 }
 
-/// Custody type for a wallet.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum CustodyType {
-    /// Wallet is under developer custody.
-    Developer,
-    /// Wallet is under end-user custody.
-    Enduser,
+api_enum! {
+    /// Custody type for a wallet.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum CustodyType {
+        /// Wallet is under developer custody.
+        Developer,
+        /// Wallet is under end-user custody.
+        Enduser,
+    }
 }
 
-/// Smart contract account type.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum AccountType {
-    /// Smart Contract Account (ERC-4337).
-    Sca,
-    /// Externally Owned Account.
-    Eoa,
+api_enum! {
+    /// Smart contract account type.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum AccountType {
+        /// Smart Contract Account (ERC-4337).
+        Sca,
+        /// Externally Owned Account.
+        Eoa,
+    }
 }
 
-/// Wallet lifecycle state.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum WalletState {
-    /// Wallet is active and can send transactions.
-    Live,
-    /// Wallet is frozen and cannot send transactions.
-    Frozen,
+api_enum! {
+    /// Wallet lifecycle state.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum WalletState {
+        /// Wallet is active and can send transactions.
+        Live,
+        /// Wallet is frozen and cannot send transactions.
+        Frozen,
+    }
 }
 
-/// Transaction fee priority level.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum FeeLevel {
-    /// Low priority.
-    Low,
-    /// Medium priority.
-    Medium,
-    /// High priority.
-    High,
+api_enum! {
+    /// Transaction fee priority level.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum FeeLevel {
+        /// Low priority.
+        Low,
+        /// Medium priority.
+        Medium,
+        /// High priority.
+        High,
+    }
 }
 
-/// Token standard.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum TokenStandard {
-    /// ERC-20 fungible token.
-    #[serde(rename = "ERC20")]
-    Erc20,
-    /// ERC-721 non-fungible token.
-    #[serde(rename = "ERC721")]
-    Erc721,
-    /// ERC-1155 multi-token.
-    #[serde(rename = "ERC1155")]
-    Erc1155,
-    /// Solana fungible token.
-    #[serde(rename = "Fungible")]
-    Fungible,
-    /// Aptos fungible asset.
-    #[serde(rename = "FungibleAsset")]
-    FungibleAsset,
-    /// Solana non-fungible token.
-    #[serde(rename = "NonFungible")]
-    NonFungible,
-    /// Solana non-fungible edition.
-    #[serde(rename = "NonFungibleEdition")]
-    NonFungibleEdition,
-    /// Solana programmable non-fungible token.
-    #[serde(rename = "ProgrammableNonFungible")]
-    ProgrammableNonFungible,
-    /// Solana programmable non-fungible edition.
-    #[serde(rename = "ProgrammableNonFungibleEdition")]
-    ProgrammableNonFungibleEdition,
+api_enum! {
+    /// Sort order of a list, by creation date.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum Order {
+        /// Oldest first.
+        Asc,
+        /// Newest first (the API default).
+        Desc,
+    }
+}
+
+api_enum! {
+    /// Token standard.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub enum TokenStandard {
+        /// ERC-20 fungible token.
+        #[serde(rename = "ERC20")]
+        Erc20,
+        /// ERC-721 non-fungible token.
+        #[serde(rename = "ERC721")]
+        Erc721,
+        /// ERC-1155 multi-token.
+        #[serde(rename = "ERC1155")]
+        Erc1155,
+        /// Solana fungible token.
+        #[serde(rename = "Fungible")]
+        Fungible,
+        /// Aptos fungible asset.
+        #[serde(rename = "FungibleAsset")]
+        FungibleAsset,
+        /// Solana non-fungible token.
+        #[serde(rename = "NonFungible")]
+        NonFungible,
+        /// Solana non-fungible edition.
+        #[serde(rename = "NonFungibleEdition")]
+        NonFungibleEdition,
+        /// Solana programmable non-fungible token.
+        #[serde(rename = "ProgrammableNonFungible")]
+        ProgrammableNonFungible,
+        /// Solana programmable non-fungible edition.
+        #[serde(rename = "ProgrammableNonFungibleEdition")]
+        ProgrammableNonFungibleEdition,
+    }
 }
 
 /// Pagination cursor parameters shared across list endpoints.
@@ -224,11 +338,33 @@ pub enum TokenStandard {
 #[serde(rename_all = "camelCase")]
 pub struct PageParams {
     /// Start of date-time range (ISO-8601, inclusive).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub from: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "circle_core::timestamp::serialize_option"
+    )]
+    pub from: Option<DateTime<Utc>>,
     /// End of date-time range (ISO-8601, inclusive).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "circle_core::timestamp::serialize_option"
+    )]
+    pub to: Option<DateTime<Utc>>,
+    /// Cursor for the previous page (exclusive end).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_before: Option<String>,
+    /// Cursor for the next page (exclusive start).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub to: Option<String>,
+    pub page_after: Option<String>,
+    /// Maximum number of items to return (1–50, default 10).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+}
+
+/// Cursor-only pagination parameters, for list endpoints without a
+/// date-time range.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorParams {
     /// Cursor for the previous page (exclusive end).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_before: Option<String>,
@@ -261,7 +397,7 @@ pub struct TransactionFee {
     pub base_fee: Option<String>,
     /// Total network fee in native currency.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub network_fee: Option<String>,
+    pub network_fee: Option<TokenAmount>,
     /// Raw network fee value.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_fee_raw: Option<String>,
@@ -271,18 +407,37 @@ pub struct TransactionFee {
 }
 
 /// Error response body returned by the Circle API on non-2xx status codes.
-#[derive(Debug, Clone, serde::Deserialize)]
-pub struct ApiErrorBody {
-    /// Numeric error code.
-    pub code: i32,
-    /// Human-readable error message.
-    pub message: String,
+pub use circle_core::ApiErrorBody;
+
+/// Chainable setters for optional fields of the request a builder holds in
+/// `self.req`.
+macro_rules! optional_setters {
+    ($( $(#[doc = $doc:literal])+ $field:ident: $ty:ty ),+ $(,)?) => { $(
+        $(#[doc = $doc])+
+        pub fn $field(mut self, $field: impl Into<$ty>) -> Self {
+            self.req.$field = Some($field.into());
+            self
+        }
+    )+ };
 }
 
+pub(crate) use optional_setters;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn blockchains_convert_through_network() -> Result<(), Box<dyn std::error::Error>> {
+        let network = Network::try_from(Blockchain::BaseSepolia)?;
+        assert_eq!(network, Network::BaseSepolia);
+        assert_eq!(EvmBlockchain::try_from(network)?, EvmBlockchain::BaseSepolia);
+        assert_eq!(Blockchain::try_from(Network::Sol)?, Blockchain::Sol);
+        assert!(EvmBlockchain::try_from(Network::Sol).is_err());
+        assert!(Blockchain::try_from(Network::Btc).is_err());
+        Ok(())
+    }
+
     #[test]
     fn blockchain_eth_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string(&Blockchain::Eth)?;
@@ -0,0 +1,42 @@
+//! Entity configuration models for the Circle Developer-Controlled Wallets API.
+//!
+//! Contains the entity public key and entity secret registration types used
+//! by [`crate::entity_secret`].
+
+/// Inner data of a get-public-key response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntityPublicKeyData {
+    /// PEM-encoded RSA public key that entity secrets are encrypted to.
+    pub public_key: String,
+}
+
+/// Response wrapper for the get-public-key endpoint.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EntityPublicKeyResponse {
+    /// Response data.
+    pub data: EntityPublicKeyData,
+}
+
+/// Request body for registering an entity secret.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterEntitySecretRequest {
+    /// The entity secret encrypted to the entity public key.
+    pub entity_secret_ciphertext: String,
+}
+
+/// Inner data of a register-entity-secret response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntitySecretRecoveryData {
+    /// Base64 recovery file content, needed to reset a lost entity secret.
+    pub recovery_file: String,
+}
+
+/// Response wrapper for the register-entity-secret endpoint.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EntitySecretRecoveryResponse {
+    /// Response data.
+    pub data: EntitySecretRecoveryData,
+}
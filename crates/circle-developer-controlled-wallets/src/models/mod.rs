@@ -3,6 +3,7 @@
 //! This module groups all request and response types by resource area.
 
 pub mod common;
+pub mod config;
 pub mod signing;
 pub mod token;
 pub mod transaction;
@@ -3,7 +3,67 @@
 //! Contains request parameters and response types for message and transaction
 //! signing endpoints.
 
+use circle_core::{fields, ids::WalletId};
+
 use super::common::Blockchain;
+use crate::Error;
+
+/// Setters for the signer, memo and ciphertext of a signing request builder.
+macro_rules! signer_setters {
+    () => {
+        /// Signs with the wallet `wallet_id`.
+        pub const fn wallet_id(mut self, wallet_id: WalletId) -> Self {
+            self.req.wallet_id = Some(wallet_id);
+            self
+        }
+
+        /// Signs with the wallet at `wallet_address` on `blockchain`, instead
+        /// of a `wallet_id`.
+        pub fn wallet_address(
+            mut self,
+            blockchain: Blockchain,
+            wallet_address: impl Into<String>,
+        ) -> Self {
+            self.req.blockchain = Some(blockchain);
+            self.req.wallet_address = Some(wallet_address.into());
+            self
+        }
+
+        /// Memo for record-keeping.
+        pub fn memo(mut self, memo: impl Into<String>) -> Self {
+            self.req.memo = Some(memo.into());
+            self
+        }
+
+        /// Entity secret ciphertext, instead of the client's own.
+        pub fn entity_secret_ciphertext(mut self, ciphertext: impl Into<String>) -> Self {
+            self.req.entity_secret_ciphertext = ciphertext.into();
+            self
+        }
+    };
+}
+
+/// Checks that a signing request names its wallet either by `wallet_id` or
+/// by `blockchain` and a `wallet_address` valid for it.
+fn check_signer(
+    wallet_id: Option<&WalletId>,
+    blockchain: Option<&Blockchain>,
+    wallet_address: Option<&str>,
+) -> Result<(), Error> {
+    fields::exclusive(
+        ("wallet_id", wallet_id.is_some()),
+        ("wallet_address", wallet_address.is_some()),
+        true,
+    )?;
+    fields::requires(
+        ("wallet_address", wallet_address.is_some()),
+        ("blockchain", blockchain.is_some()),
+    )?;
+    if let (Some(chain), Some(address)) = (blockchain, wallet_address) {
+        chain.check_address(address)?;
+    }
+    Ok(())
+}
 
 /// Request body for signing a plain or hex-encoded message.
 #[derive(Debug, Clone, serde::Serialize)]
@@ -11,7 +71,7 @@ use super::common::Blockchain;
 pub struct SignMessageRequest {
     /// Source wallet ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
     /// Blockchain network (required when wallet_id is absent).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
@@ -26,10 +86,67 @@ pub struct SignMessageRequest {
     /// Optional memo for record-keeping.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memo: Option<String>,
-    /// Encrypted entity secret ciphertext.
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
 }
 
+impl SignMessageRequest {
+    /// Starts a builder for signing `message`.
+    pub fn builder(message: impl Into<String>) -> SignMessageRequestBuilder {
+        SignMessageRequestBuilder {
+            req: Self {
+                wallet_id: None,
+                blockchain: None,
+                wallet_address: None,
+                message: message.into(),
+                encoded_by_hex: None,
+                memo: None,
+                entity_secret_ciphertext: String::new(),
+            },
+        }
+    }
+}
+
+/// Builds a [`SignMessageRequest`], checking that the signing wallet is
+/// given either by `wallet_id` or by `blockchain` and `wallet_address`.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct SignMessageRequestBuilder {
+    req: SignMessageRequest,
+}
+
+impl SignMessageRequestBuilder {
+    signer_setters!();
+
+    /// Treats the message as hex-encoded bytes.
+    pub const fn encoded_by_hex(mut self, encoded_by_hex: bool) -> Self {
+        self.req.encoded_by_hex = Some(encoded_by_hex);
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the message is empty, or the
+    /// signing wallet is missing, given twice or has an address malformed
+    /// for its blockchain.
+    pub fn build(self) -> Result<SignMessageRequest, Error> {
+        let req = &self.req;
+        if req.message.is_empty() {
+            return Err(Error::InvalidParam("`message` must not be empty".into()));
+        }
+        check_signer(
+            req.wallet_id.as_ref(),
+            req.blockchain.as_ref(),
+            req.wallet_address.as_deref(),
+        )?;
+        Ok(self.req)
+    }
+}
+
 /// Inner data of a sign-message response.
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -51,7 +168,7 @@ pub struct SignatureResponse {
 pub struct SignTypedDataRequest {
     /// Source wallet ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
     /// Blockchain network (required when wallet_id is absent).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
@@ -59,18 +176,71 @@ pub struct SignTypedDataRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wallet_address: Option<String>,
     /// JSON-encoded EIP-712 typed data object.
+    #[serde(rename = "data")]
     pub typed_data: String,
-    /// Encrypted entity secret ciphertext.
+    /// Optional memo for record-keeping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
 }
 
+impl SignTypedDataRequest {
+    /// Starts a builder for signing the JSON-encoded EIP-712 `typed_data`.
+    pub fn builder(typed_data: impl Into<String>) -> SignTypedDataRequestBuilder {
+        SignTypedDataRequestBuilder {
+            req: Self {
+                wallet_id: None,
+                blockchain: None,
+                wallet_address: None,
+                typed_data: typed_data.into(),
+                memo: None,
+                entity_secret_ciphertext: String::new(),
+            },
+        }
+    }
+}
+
+/// Builds a [`SignTypedDataRequest`], checking that the signing wallet is
+/// given either by `wallet_id` or by `blockchain` and `wallet_address`.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct SignTypedDataRequestBuilder {
+    req: SignTypedDataRequest,
+}
+
+impl SignTypedDataRequestBuilder {
+    signer_setters!();
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the typed data is not JSON, or the
+    /// signing wallet is missing, given twice or has an address malformed
+    /// for its blockchain.
+    pub fn build(self) -> Result<SignTypedDataRequest, Error> {
+        let req = &self.req;
+        serde_json::from_str::<serde::de::IgnoredAny>(&req.typed_data)
+            .map_err(|e| Error::InvalidParam(format!("`typed_data` is not JSON: {e}")))?;
+        check_signer(
+            req.wallet_id.as_ref(),
+            req.blockchain.as_ref(),
+            req.wallet_address.as_deref(),
+        )?;
+        Ok(self.req)
+    }
+}
+
 /// Request body for signing a raw transaction.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SignTransactionRequest {
     /// Source wallet ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
     /// Blockchain network (required when wallet_id is absent).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
@@ -83,10 +253,81 @@ pub struct SignTransactionRequest {
     /// Structured transaction object (alternative to raw_transaction).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction: Option<serde_json::Value>,
-    /// Encrypted entity secret ciphertext.
+    /// Optional memo for record-keeping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
 }
 
+impl SignTransactionRequest {
+    /// Starts a builder for signing a transaction, given with
+    /// [`SignTransactionRequestBuilder::raw_transaction`] or
+    /// [`SignTransactionRequestBuilder::transaction`].
+    pub const fn builder() -> SignTransactionRequestBuilder {
+        SignTransactionRequestBuilder {
+            req: Self {
+                wallet_id: None,
+                blockchain: None,
+                wallet_address: None,
+                raw_transaction: None,
+                transaction: None,
+                memo: None,
+                entity_secret_ciphertext: String::new(),
+            },
+        }
+    }
+}
+
+/// Builds a [`SignTransactionRequest`], checking that the signing wallet is
+/// given either by `wallet_id` or by `blockchain` and `wallet_address`, and
+/// the transaction either raw or structured.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct SignTransactionRequestBuilder {
+    req: SignTransactionRequest,
+}
+
+impl SignTransactionRequestBuilder {
+    signer_setters!();
+
+    /// Hex-encoded raw unsigned transaction.
+    pub fn raw_transaction(mut self, raw_transaction: impl Into<String>) -> Self {
+        self.req.raw_transaction = Some(raw_transaction.into());
+        self
+    }
+
+    /// Structured transaction object, instead of a raw transaction.
+    pub fn transaction(mut self, transaction: serde_json::Value) -> Self {
+        self.req.transaction = Some(transaction);
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the signing wallet or the
+    /// transaction is missing or given twice, or the wallet address is
+    /// malformed for its blockchain.
+    pub fn build(self) -> Result<SignTransactionRequest, Error> {
+        let req = &self.req;
+        check_signer(
+            req.wallet_id.as_ref(),
+            req.blockchain.as_ref(),
+            req.wallet_address.as_deref(),
+        )?;
+        fields::exclusive(
+            ("raw_transaction", req.raw_transaction.is_some()),
+            ("transaction", req.transaction.is_some()),
+            true,
+        )?;
+        Ok(self.req)
+    }
+}
+
 /// Inner data of a sign-transaction response.
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -107,6 +348,37 @@ pub struct SignTransactionResponse {
     pub data: SignTransactionData,
 }
 
+/// Request body for signing a NEAR delegate action.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignDelegateActionRequest {
+    /// Source wallet ID.
+    pub wallet_id: WalletId,
+    /// Base64-encoded unsigned delegate action.
+    pub unsigned_delegate_action: String,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
+    pub entity_secret_ciphertext: String,
+}
+
+/// Inner data of a sign-delegate-action response.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignDelegateActionData {
+    /// Signature, in the NEAR encoding.
+    pub signature: String,
+    /// Base64-encoded signed delegate action.
+    pub signed_delegate_action: String,
+}
+
+/// Response wrapper for the sign-delegate-action endpoint.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct SignDelegateActionResponse {
+    /// Response data.
+    pub data: SignDelegateActionData,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,7 +410,7 @@ mod tests {
     #[test]
     fn sign_message_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = SignMessageRequest {
-            wallet_id: Some("wallet-1".to_string()),
+            wallet_id: Some("a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?),
             blockchain: None,
             wallet_address: None,
             message: "Hello, World!".to_string(),
@@ -156,14 +428,62 @@ mod tests {
     #[test]
     fn sign_typed_data_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = SignTypedDataRequest {
-            wallet_id: Some("wallet-1".to_string()),
+            wallet_id: Some("a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?),
             blockchain: None,
             wallet_address: None,
             typed_data: r#"{"types":{}}"#.to_string(),
+            memo: None,
             entity_secret_ciphertext: "cipher".to_string(),
         };
         let json = serde_json::to_string(&req)?;
-        assert!(json.contains("typedData"));
+        assert!(json.contains(r#""data":"#));
+        assert!(!json.contains("typedData"));
+        Ok(())
+    }
+
+    #[test]
+    fn builders_need_exactly_one_signer() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let req = SignMessageRequest::builder("hello").wallet_id(wallet_id).build()?;
+        assert_eq!(req.wallet_id, Some(wallet_id));
+        assert!(req.entity_secret_ciphertext.is_empty());
+
+        let missing = SignTypedDataRequest::builder("{}").build();
+        assert!(
+            matches!(&missing, Err(Error::InvalidParam(m)) if m == "one of `wallet_id` or `wallet_address` is required"),
+            "{missing:?}"
+        );
+        let both = SignMessageRequest::builder("hello")
+            .wallet_id(wallet_id)
+            .wallet_address(Blockchain::EthSepolia, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+            .build();
+        assert!(matches!(both, Err(Error::InvalidParam(_))), "{both:?}");
+        let malformed = SignMessageRequest::builder("hello")
+            .wallet_address(Blockchain::EthSepolia, "0x1234")
+            .build();
+        assert!(matches!(malformed, Err(Error::InvalidParam(_))), "{malformed:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn sign_transaction_builder_needs_one_transaction() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        assert!(
+            SignTransactionRequest::builder()
+                .wallet_id(wallet_id)
+                .raw_transaction("0x02")
+                .build()
+                .is_ok()
+        );
+        let both = SignTransactionRequest::builder()
+            .wallet_id(wallet_id)
+            .raw_transaction("0x02")
+            .transaction(serde_json::json!({ "nonce": 1 }))
+            .build();
+        assert!(
+            matches!(&both, Err(Error::InvalidParam(m)) if m == "`raw_transaction` and `transaction` cannot both be set"),
+            "{both:?}"
+        );
         Ok(())
     }
 }
@@ -30,7 +30,7 @@ mod tests {
         let json = r#"{
             "data": {
                 "token": {
-                    "id": "token-id-1",
+                    "id": "e2f3a4b5-c6d7-4e8f-9a0b-1c2d3e4f5a6b",
                     "blockchain": "ETH",
                     "isNative": false,
                     "name": "USD Coin",
@@ -3,141 +3,184 @@
 //! Contains request parameters and response types for transaction management
 //! endpoints including transfers, contract execution, signing, and fee estimation.
 
-use super::common::{Blockchain, CustodyType, FeeLevel, TransactionFee};
-
-/// Transaction lifecycle state.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransactionState {
-    /// Transaction was cancelled.
-    Cancelled,
-    /// Transaction has received required confirmations.
-    Confirmed,
-    /// Transaction is complete.
-    Complete,
-    /// Transaction was denied by screening.
-    Denied,
-    /// Transaction failed on-chain.
-    Failed,
-    /// Transaction was just initiated.
-    Initiated,
-    /// Transaction cleared pre-chain checks.
-    Cleared,
-    /// Transaction is queued for broadcast.
-    Queued,
-    /// Transaction was sent to the network.
-    Sent,
-    /// Transaction is stuck (e.g. low gas).
-    Stuck,
-}
-
-/// Transaction directional type.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransactionType {
-    /// Incoming transaction.
-    Inbound,
-    /// Outgoing transaction.
-    Outbound,
-}
-
-/// The operation performed by the transaction.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum Operation {
-    /// Token or native asset transfer.
-    Transfer,
-    /// Smart contract function call.
-    ContractExecution,
-    /// Smart contract deployment.
-    ContractDeployment,
-}
-
-/// Risk score label.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskScore {
-    /// Risk is unknown.
-    Unknown,
-    /// Low risk.
-    Low,
-    /// Medium risk.
-    Medium,
-    /// High risk.
-    High,
-    /// Severe risk.
-    Severe,
-    /// Address is on a blocklist.
-    Blocklist,
-}
-
-/// Risk category label.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskCategory {
-    /// Sanctions-related risk.
-    Sanctions,
-    /// Child sexual abuse material.
-    Csam,
-    /// Illicit behavior.
-    IllicitBehavior,
-    /// Gambling.
-    Gambling,
-    /// Terrorist financing.
-    TerroristFinancing,
-    /// Unsupported.
-    Unsupported,
-    /// Frozen address.
-    Frozen,
-    /// Other risk.
-    Other,
-    /// High-risk industry.
-    HighRiskIndustry,
-    /// Politically exposed person.
-    Pep,
-    /// Trusted entity.
-    Trusted,
-    /// Hacking.
-    Hacking,
-    /// Human trafficking.
-    HumanTrafficking,
-    /// Special measures.
-    SpecialMeasures,
-}
-
-/// Risk exposure type.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskType {
-    /// Direct ownership.
-    Ownership,
-    /// Counterparty relationship.
-    Counterparty,
-    /// Indirect relationship.
-    Indirect,
-}
-
-/// Recommended action for a screened transaction.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskAction {
-    /// Approve the transaction.
-    Approve,
-    /// Flag for manual review.
-    Review,
-    /// Freeze the wallet.
-    FreezeWallet,
-    /// Deny the transaction.
-    Deny,
+use chrono::{DateTime, Utc};
+use circle_core::{
+    address::AddressError,
+    amount::TokenAmount,
+    api_enum, fields,
+    ids::{IdempotencyKey, TokenId, TransactionId, UserId, WalletId},
+};
+
+use super::{
+    common::{
+        AccountType, Blockchain, CustodyType, FeeLevel, Order, TransactionFee, optional_setters,
+    },
+    wallet::ScaCore,
+};
+use crate::Error;
+
+api_enum! {
+    /// Transaction lifecycle state.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransactionState {
+        /// Transaction was cancelled.
+        Cancelled,
+        /// Transaction has received required confirmations.
+        Confirmed,
+        /// Transaction is complete.
+        Complete,
+        /// Transaction was denied by screening.
+        Denied,
+        /// Transaction failed on-chain.
+        Failed,
+        /// Transaction was just initiated.
+        Initiated,
+        /// Transaction cleared pre-chain checks.
+        Cleared,
+        /// Transaction is queued for broadcast.
+        Queued,
+        /// Transaction was sent to the network.
+        Sent,
+        /// Transaction is stuck (e.g. low gas).
+        Stuck,
+    }
+}
+
+api_enum! {
+    /// Transaction directional type.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransactionType {
+        /// Incoming transaction.
+        Inbound,
+        /// Outgoing transaction.
+        Outbound,
+    }
+}
+
+api_enum! {
+    /// The operation performed by the transaction.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum Operation {
+        /// Token or native asset transfer.
+        Transfer,
+        /// Smart contract function call.
+        ContractExecution,
+        /// Smart contract deployment.
+        ContractDeployment,
+    }
+}
+
+api_enum! {
+    /// Risk score label.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskScore {
+        /// Risk could not be determined (`UNKNOWN` on the wire).
+        #[serde(rename = "UNKNOWN")]
+        Undetermined,
+        /// Low risk.
+        Low,
+        /// Medium risk.
+        Medium,
+        /// High risk.
+        High,
+        /// Severe risk.
+        Severe,
+        /// Address is on a blocklist.
+        Blocklist,
+    }
+}
+
+api_enum! {
+    /// Risk category label.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskCategory {
+        /// Sanctions-related risk.
+        Sanctions,
+        /// Child sexual abuse material.
+        Csam,
+        /// Illicit behavior.
+        IllicitBehavior,
+        /// Gambling.
+        Gambling,
+        /// Terrorist financing.
+        TerroristFinancing,
+        /// Unsupported.
+        Unsupported,
+        /// Frozen address.
+        Frozen,
+        /// Other risk.
+        Other,
+        /// High-risk industry.
+        HighRiskIndustry,
+        /// Politically exposed person.
+        Pep,
+        /// Trusted entity.
+        Trusted,
+        /// Hacking.
+        Hacking,
+        /// Human trafficking.
+        HumanTrafficking,
+        /// Special measures.
+        SpecialMeasures,
+    }
+}
+
+api_enum! {
+    /// Risk exposure type.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskType {
+        /// Direct ownership.
+        Ownership,
+        /// Counterparty relationship.
+        Counterparty,
+        /// Indirect relationship.
+        Indirect,
+    }
+}
+
+api_enum! {
+    /// Recommended action for a screened transaction.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskAction {
+        /// Approve the transaction.
+        Approve,
+        /// Flag for manual review.
+        Review,
+        /// Freeze the wallet.
+        FreezeWallet,
+        /// Deny the transaction.
+        Deny,
+    }
+}
+
+api_enum! {
+    /// Kind of entity a risk signal was raised on.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskSignalSource {
+        /// The counterparty address.
+        Address,
+        /// The blockchain.
+        Blockchain,
+        /// The transferred asset.
+        Asset,
+    }
 }
 
 /// An individual risk signal from the screening evaluation.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RiskSignal {
-    /// Risk signal source (e.g. screening provider name).
+    /// Kind of entity the signal was raised on.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub source: Option<String>,
+    pub source: Option<RiskSignalSource>,
     /// Source-specific value.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_value: Option<String>,
@@ -159,7 +202,7 @@ pub struct RiskSignal {
 pub struct TransactionScreeningDecision {
     /// ISO-8601 timestamp when screening occurred.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub screening_date: Option<String>,
+    pub screening_date: Option<DateTime<Utc>>,
     /// Name of the compliance rule that triggered.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rule_name: Option<String>,
@@ -176,7 +219,7 @@ pub struct TransactionScreeningDecision {
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     /// Unique transaction ID.
-    pub id: String,
+    pub id: TransactionId,
     /// Current lifecycle state.
     pub state: TransactionState,
     /// Blockchain network.
@@ -186,9 +229,9 @@ pub struct Transaction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_type: Option<TransactionType>,
     /// ISO-8601 creation timestamp.
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
     /// ISO-8601 last-update timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// ABI function signature (contract executions).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub abi_function_signature: Option<String>,
@@ -197,10 +240,10 @@ pub struct Transaction {
     pub abi_parameters: Option<Vec<serde_json::Value>>,
     /// Token amounts being transferred.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amounts: Option<Vec<String>>,
+    pub amounts: Option<Vec<TokenAmount>>,
     /// Transfer amount in USD.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amount_in_usd: Option<String>,
+    pub amount_in_usd: Option<TokenAmount>,
     /// Block hash of the confirming block.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_hash: Option<String>,
@@ -230,13 +273,13 @@ pub struct Transaction {
     pub fee_level: Option<FeeLevel>,
     /// ISO-8601 first confirmation timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub first_confirm_date: Option<String>,
+    pub first_confirm_date: Option<DateTime<Utc>>,
     /// Actual network fee paid.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub network_fee: Option<String>,
+    pub network_fee: Option<TokenAmount>,
     /// Network fee denominated in USD.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub network_fee_in_usd: Option<String>,
+    pub network_fee_in_usd: Option<TokenAmount>,
     /// NFT token IDs being transferred.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nfts: Option<Vec<String>>,
@@ -251,19 +294,28 @@ pub struct Transaction {
     pub source_address: Option<String>,
     /// Token ID for the asset being transferred.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub token_id: Option<String>,
+    pub token_id: Option<TokenId>,
     /// On-chain transaction hash.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tx_hash: Option<String>,
     /// User ID (user-controlled wallets).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
     /// Source wallet ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
     /// Compliance screening evaluation result.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_screening_evaluation: Option<TransactionScreeningDecision>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<Transaction, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for Transaction {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
 }
 
 /// Inner data of a list-transactions response.
@@ -289,13 +341,46 @@ pub struct TransactionData {
     pub transaction: Transaction,
 }
 
-/// Response wrapper for get/create/cancel/accelerate transaction endpoints.
+/// Response wrapper for the get-transaction endpoint.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct TransactionResponse {
     /// Response data.
     pub data: TransactionData,
 }
 
+/// Inner data of the create-transaction and cancel-transaction responses.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStateData {
+    /// Transaction UUID.
+    pub id: TransactionId,
+    /// Current state of the transaction.
+    pub state: TransactionState,
+}
+
+/// Response wrapper for the create-transfer, create-contract-execution and
+/// cancel-transaction endpoints.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransactionStateResponse {
+    /// Response data.
+    pub data: TransactionStateData,
+}
+
+/// Inner data of the accelerate-transaction response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionIdData {
+    /// UUID of the accelerated transaction.
+    pub id: TransactionId,
+}
+
+/// Response wrapper for the accelerate-transaction endpoint.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransactionIdResponse {
+    /// Response data.
+    pub data: TransactionIdData,
+}
+
 /// Query parameters for the list-transactions endpoint.
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -315,33 +400,30 @@ pub struct ListTransactionsParams {
     /// Filter by operation type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub operation: Option<Operation>,
-    /// Filter by external reference ID.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub ref_id: Option<String>,
-    /// Filter by source address.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub source_address: Option<String>,
     /// Filter by state.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<TransactionState>,
-    /// Filter by token contract address.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub token_address: Option<String>,
     /// Filter by transaction hash.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub transaction_hash: Option<String>,
+    pub tx_hash: Option<String>,
     /// Filter by transaction type.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub transaction_type: Option<TransactionType>,
+    pub tx_type: Option<TransactionType>,
     /// Filter by specific wallet IDs (comma-separated).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub wallet_ids: Option<String>,
     /// Start of date-time range (ISO-8601).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub from: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "circle_core::timestamp::serialize_option"
+    )]
+    pub from: Option<DateTime<Utc>>,
     /// End of date-time range (ISO-8601).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub to: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "circle_core::timestamp::serialize_option"
+    )]
+    pub to: Option<DateTime<Utc>>,
     /// Cursor to page before.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_before: Option<String>,
@@ -351,6 +433,104 @@ pub struct ListTransactionsParams {
     /// Page size (1–50).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_size: Option<u32>,
+    /// Sort order by creation date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Order>,
+}
+
+circle_core::paginated!(ListTransactionsParams);
+
+/// Query parameters for the lowest-nonce-transaction endpoint.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLowestNonceTxParams {
+    /// Blockchain to search on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain: Option<Blockchain>,
+    /// On-chain address to search for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Wallet ID to search for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<WalletId>,
+}
+
+/// Fee information for replacing the lowest-nonce transaction.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LowestNonceTransactionFeeInfo {
+    /// Suggested high-end fee for the replacement transaction.
+    pub new_high_estimated_fee: TransactionFee,
+    /// Difference in fee between the stuck and the replacement transaction.
+    pub fee_difference_amount: TokenAmount,
+}
+
+/// Inner data of the lowest-nonce-transaction response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LowestNonceTransactionData {
+    /// The stuck transaction.
+    pub transaction: Transaction,
+    /// Fee information for replacing it.
+    pub fee_info: LowestNonceTransactionFeeInfo,
+}
+
+/// Response wrapper for the lowest-nonce-transaction endpoint.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GetLowestNonceTransactionResponse {
+    /// Response data.
+    pub data: LowestNonceTransactionData,
+}
+
+/// Query parameters for the fee-parameters endpoint.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeParametersParams {
+    /// Blockchain to get fee parameters for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockchain: Option<Blockchain>,
+    /// Account type the fees apply to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<AccountType>,
+}
+
+/// Gas price parameters for one fee level.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeParameters {
+    /// Gas price in gwei (legacy transactions).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
+    /// Max fee per gas in gwei (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<String>,
+    /// Max priority fee per gas in gwei (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee: Option<String>,
+    /// Base fee per gas in gwei (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<String>,
+}
+
+/// Fee parameters for low, medium, and high priority.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FeeParametersData {
+    /// Low-priority fee parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low: Option<FeeParameters>,
+    /// Medium-priority fee parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub medium: Option<FeeParameters>,
+    /// High-priority fee parameters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high: Option<FeeParameters>,
+}
+
+/// Response wrapper for the fee-parameters endpoint.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FeeParametersResponse {
+    /// Response data.
+    pub data: FeeParametersData,
 }
 
 /// Request body for creating a developer-controlled transfer transaction.
@@ -358,22 +538,24 @@ pub struct ListTransactionsParams {
 #[serde(rename_all = "camelCase")]
 pub struct CreateTransferTxRequest {
     /// Idempotency key (UUID).
-    pub idempotency_key: String,
-    /// Encrypted entity secret ciphertext.
+    pub idempotency_key: IdempotencyKey,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
     /// Source wallet ID.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Blockchain (required for native asset transfers).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
     /// Token ID for the asset being transferred.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub token_id: Option<String>,
+    pub token_id: Option<TokenId>,
     /// Destination address.
     pub destination_address: String,
     /// Token amounts to transfer.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amounts: Option<Vec<String>>,
+    pub amounts: Option<Vec<TokenAmount>>,
     /// NFT token IDs to transfer.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nft_token_ids: Option<Vec<String>>,
@@ -397,16 +579,149 @@ pub struct CreateTransferTxRequest {
     pub priority_fee: Option<String>,
 }
 
+impl CreateTransferTxRequest {
+    /// Checks `destination_address` offline against `blockchain`, when set,
+    /// so a malformed address fails before the request is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn validate(&self) -> Result<(), AddressError> {
+        self.blockchain
+            .as_ref()
+            .map_or(Ok(()), |chain| chain.check_address(&self.destination_address))
+    }
+
+    /// Starts a builder for a transfer from `wallet_id` to
+    /// `destination_address`.
+    pub fn builder(
+        wallet_id: WalletId,
+        destination_address: impl Into<String>,
+    ) -> CreateTransferTxRequestBuilder {
+        CreateTransferTxRequestBuilder {
+            req: Self {
+                idempotency_key: IdempotencyKey::random(),
+                entity_secret_ciphertext: String::new(),
+                wallet_id,
+                blockchain: None,
+                token_id: None,
+                destination_address: destination_address.into(),
+                amounts: None,
+                nft_token_ids: None,
+                ref_id: None,
+                fee_level: None,
+                gas_limit: None,
+                gas_price: None,
+                max_fee: None,
+                priority_fee: None,
+            },
+        }
+    }
+}
+
+/// Builds a [`CreateTransferTxRequest`], checking the field combinations
+/// Circle accepts:
+///
+/// - `amounts` or `nft_token_ids`, not both;
+/// - `token_id`, or `blockchain` for the native asset, not both;
+/// - `fee_level`, or `gas_limit` with either `gas_price` or `max_fee` and `priority_fee`.
+///
+/// The idempotency key defaults to a random UUID and the entity secret
+/// ciphertext to empty, for the client to fill in.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct CreateTransferTxRequestBuilder {
+    req: CreateTransferTxRequest,
+}
+
+impl CreateTransferTxRequestBuilder {
+    optional_setters! {
+        /// Blockchain of a native asset transfer.
+        blockchain: Blockchain,
+        /// Token to transfer.
+        token_id: TokenId,
+        /// External reference ID.
+        ref_id: String,
+        /// Fee priority level.
+        fee_level: FeeLevel,
+        /// Custom gas limit.
+        gas_limit: String,
+        /// Custom gas price.
+        gas_price: String,
+        /// Max fee per gas (EIP-1559).
+        max_fee: String,
+        /// Max priority fee per gas (EIP-1559).
+        priority_fee: String,
+    }
+
+    /// Idempotency key, instead of a random one — typically
+    /// [`IdempotencyKey::derive`]d from a business id, so that a retry after
+    /// a restart is deduplicated.
+    pub const fn idempotency_key(mut self, idempotency_key: IdempotencyKey) -> Self {
+        self.req.idempotency_key = idempotency_key;
+        self
+    }
+
+    /// Entity secret ciphertext, instead of the client's own.
+    pub fn entity_secret_ciphertext(mut self, ciphertext: impl Into<String>) -> Self {
+        self.req.entity_secret_ciphertext = ciphertext.into();
+        self
+    }
+
+    /// Token amounts to transfer.
+    pub fn amounts(mut self, amounts: impl IntoIterator<Item = TokenAmount>) -> Self {
+        self.req.amounts = Some(amounts.into_iter().collect());
+        self
+    }
+
+    /// NFT token IDs to transfer.
+    pub fn nft_token_ids(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.req.nft_token_ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] naming the fields of an invalid
+    /// combination, or for a destination address malformed for `blockchain`.
+    pub fn build(self) -> Result<CreateTransferTxRequest, Error> {
+        let req = &self.req;
+        fields::exclusive(
+            ("amounts", req.amounts.is_some()),
+            ("nft_token_ids", req.nft_token_ids.is_some()),
+            true,
+        )?;
+        fields::exclusive(
+            ("token_id", req.token_id.is_some()),
+            ("blockchain", req.blockchain.is_some()),
+            true,
+        )?;
+        fields::fees(
+            req.fee_level.is_some(),
+            req.gas_limit.is_some(),
+            req.gas_price.is_some(),
+            req.max_fee.is_some(),
+            req.priority_fee.is_some(),
+        )?;
+        req.validate()?;
+        Ok(self.req)
+    }
+}
+
 /// Request body for creating a contract execution transaction.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateContractExecutionTxRequest {
     /// Idempotency key (UUID).
-    pub idempotency_key: String,
-    /// Encrypted entity secret ciphertext.
+    pub idempotency_key: IdempotencyKey,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
     /// Source wallet ID.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Blockchain network for the call.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
@@ -438,14 +753,170 @@ pub struct CreateContractExecutionTxRequest {
     pub ref_id: Option<String>,
     /// ETH value to send with the call.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amount: Option<String>,
+    pub amount: Option<TokenAmount>,
+}
+
+impl CreateContractExecutionTxRequest {
+    /// Starts a builder for a call of `contract_address` from `wallet_id`.
+    pub fn builder(
+        wallet_id: WalletId,
+        contract_address: impl Into<String>,
+    ) -> CreateContractExecutionTxRequestBuilder {
+        CreateContractExecutionTxRequestBuilder {
+            req: Self {
+                idempotency_key: IdempotencyKey::random(),
+                entity_secret_ciphertext: String::new(),
+                wallet_id,
+                blockchain: None,
+                contract_address: contract_address.into(),
+                abi_function_signature: None,
+                abi_parameters: None,
+                call_data: None,
+                fee_level: None,
+                gas_limit: None,
+                max_fee: None,
+                priority_fee: None,
+                ref_id: None,
+                amount: None,
+            },
+        }
+    }
+}
+
+/// Builds a [`CreateContractExecutionTxRequest`], checking the field
+/// combinations Circle accepts:
+///
+/// - `abi_function_signature` (with optional `abi_parameters`) or `call_data`, not both;
+/// - `fee_level`, or `gas_limit` with `max_fee` and `priority_fee`.
+///
+/// The idempotency key defaults to a random UUID and the entity secret
+/// ciphertext to empty, for the client to fill in.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct CreateContractExecutionTxRequestBuilder {
+    req: CreateContractExecutionTxRequest,
+}
+
+impl CreateContractExecutionTxRequestBuilder {
+    optional_setters! {
+        /// Blockchain network for the call.
+        blockchain: Blockchain,
+        /// ABI function signature (e.g. `transfer(address,uint256)`).
+        abi_function_signature: String,
+        /// Raw call data.
+        call_data: String,
+        /// External reference ID.
+        ref_id: String,
+        /// Native amount to send with the call.
+        amount: TokenAmount,
+        /// Fee priority level.
+        fee_level: FeeLevel,
+        /// Custom gas limit.
+        gas_limit: String,
+        /// Max fee per gas (EIP-1559).
+        max_fee: String,
+        /// Max priority fee per gas (EIP-1559).
+        priority_fee: String,
+    }
+
+    /// Idempotency key, instead of a random one — typically
+    /// [`IdempotencyKey::derive`]d from a business id, so that a retry after
+    /// a restart is deduplicated.
+    pub const fn idempotency_key(mut self, idempotency_key: IdempotencyKey) -> Self {
+        self.req.idempotency_key = idempotency_key;
+        self
+    }
+
+    /// Entity secret ciphertext, instead of the client's own.
+    pub fn entity_secret_ciphertext(mut self, ciphertext: impl Into<String>) -> Self {
+        self.req.entity_secret_ciphertext = ciphertext.into();
+        self
+    }
+
+    /// Parameters of the ABI function call.
+    pub fn abi_parameters(
+        mut self,
+        parameters: impl IntoIterator<Item = serde_json::Value>,
+    ) -> Self {
+        self.req.abi_parameters = Some(parameters.into_iter().collect());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] naming the fields of an invalid
+    /// combination, or for a contract address malformed for `blockchain`.
+    pub fn build(self) -> Result<CreateContractExecutionTxRequest, Error> {
+        let req = &self.req;
+        let signature = req.abi_function_signature.is_some();
+        fields::exclusive(
+            ("abi_function_signature", signature),
+            ("call_data", req.call_data.is_some()),
+            true,
+        )?;
+        fields::requires(
+            ("abi_parameters", req.abi_parameters.is_some()),
+            ("abi_function_signature", signature),
+        )?;
+        fields::fees(
+            req.fee_level.is_some(),
+            req.gas_limit.is_some(),
+            false,
+            req.max_fee.is_some(),
+            req.priority_fee.is_some(),
+        )?;
+        if let Some(chain) = &req.blockchain {
+            chain.check_address(&req.contract_address)?;
+        }
+        Ok(self.req)
+    }
+}
+
+/// Request body for upgrading an SCA wallet to a newer core implementation.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWalletUpgradeTxRequest {
+    /// Idempotency key (UUID).
+    pub idempotency_key: IdempotencyKey,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
+    pub entity_secret_ciphertext: String,
+    /// Wallet to upgrade.
+    pub wallet_id: WalletId,
+    /// SCA core implementation to upgrade to.
+    pub new_sca_core: ScaCore,
+    /// Fee priority level.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_level: Option<FeeLevel>,
+    /// Custom gas limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    /// Custom gas price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<String>,
+    /// Max fee per gas (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee: Option<String>,
+    /// Max priority fee per gas (EIP-1559).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority_fee: Option<String>,
+    /// External reference ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ref_id: Option<String>,
 }
 
 /// Request body for cancelling a transaction.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelTxRequest {
-    /// Encrypted entity secret ciphertext.
+    /// Idempotency key (UUID).
+    pub idempotency_key: IdempotencyKey,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
 }
 
@@ -453,7 +924,11 @@ pub struct CancelTxRequest {
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccelerateTxRequest {
-    /// Encrypted entity secret ciphertext.
+    /// Idempotency key (UUID).
+    pub idempotency_key: IdempotencyKey,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
 }
 
@@ -486,33 +961,71 @@ pub struct ValidateAddressResponse {
 #[derive(Debug, Clone, Default, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EstimateTransferFeeRequest {
+    /// Token amounts to estimate fees for.
+    pub amounts: Vec<TokenAmount>,
+    /// Destination address.
+    pub destination_address: String,
+    /// NFT token IDs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nft_token_ids: Option<Vec<String>>,
     /// Source wallet address.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_address: Option<String>,
+    /// Token ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<TokenId>,
+    /// Token contract address (alternative to `token_id`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_address: Option<String>,
     /// Blockchain network.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
-    /// Destination address.
+    /// Source wallet ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub destination_address: Option<String>,
-    /// Token amounts to estimate fees for.
+    pub wallet_id: Option<WalletId>,
+}
+
+impl EstimateTransferFeeRequest {
+    /// Checks `destination_address` and `source_address` offline against
+    /// `blockchain`, when set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if an address is malformed.
+    pub fn validate(&self) -> Result<(), AddressError> {
+        let Some(chain) = &self.blockchain else { return Ok(()) };
+        chain.check_address(&self.destination_address)?;
+        self.source_address.as_deref().map_or(Ok(()), |address| chain.check_address(address))
+    }
+}
+
+/// Request body for estimating contract execution fees.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateContractExecFeeRequest {
+    /// Contract address to call.
+    pub contract_address: String,
+    /// ABI function signature (e.g. `transfer(address,uint256)`).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub amounts: Option<Vec<String>>,
-    /// NFT token IDs.
+    pub abi_function_signature: Option<String>,
+    /// ABI-encoded parameters.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub nfts: Option<Vec<String>>,
-    /// Token ID.
+    pub abi_parameters: Option<Vec<serde_json::Value>>,
+    /// Raw call data (alternative to abi_function_signature + abi_parameters).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub token_id: Option<String>,
-    /// Fee priority level.
+    pub call_data: Option<String>,
+    /// Native amount to send with the call.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub fee_level: Option<FeeLevel>,
-    /// Custom gas limit.
+    pub amount: Option<TokenAmount>,
+    /// Blockchain network (required with `source_address`).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_limit: Option<String>,
-    /// Custom gas price.
+    pub blockchain: Option<Blockchain>,
+    /// Source wallet address.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub gas_price: Option<String>,
+    pub source_address: Option<String>,
+    /// Source wallet ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<WalletId>,
 }
 
 /// Fee estimate breakdown for low, medium, and high priority.
@@ -550,12 +1063,28 @@ pub struct EstimateFeeResponse {
 mod tests {
     use super::*;
 
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn unknown_state_and_chain_do_not_fail_the_response() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let json = r#"{"data": {"transaction": {
+            "id": "c0a8f9d2-6b1e-4f3a-9d7c-2e5b8a1f4c60", "state": "REORGED", "blockchain": "TEMPO",
+            "createDate": "2024-01-01T00:00:00Z", "updateDate": "2024-01-02T00:00:00Z"
+        }}}"#;
+        let resp: TransactionResponse = serde_json::from_str(json)?;
+        let tx = resp.data.transaction;
+        assert_eq!(tx.state, TransactionState::Unknown("REORGED".to_string()));
+        assert_eq!(tx.blockchain, Some(Blockchain::Unknown("TEMPO".to_string())));
+        assert!(serde_json::to_string(&tx)?.contains("\"state\":\"REORGED\""));
+        Ok(())
+    }
+
     #[test]
     fn transaction_response_deserializes() -> Result<(), Box<dyn std::error::Error>> {
         let json = r#"{
             "data": {
                 "transaction": {
-                    "id": "tx-id-1",
+                    "id": "c0a8f9d2-6b1e-4f3a-9d7c-2e5b8a1f4c60",
                     "state": "COMPLETE",
                     "blockchain": "ETH",
                     "createDate": "2024-01-01T00:00:00Z",
@@ -566,19 +1095,29 @@ mod tests {
             }
         }"#;
         let resp: TransactionResponse = serde_json::from_str(json)?;
-        assert_eq!(resp.data.transaction.id, "tx-id-1");
+        assert_eq!(resp.data.transaction.id.to_string(), "c0a8f9d2-6b1e-4f3a-9d7c-2e5b8a1f4c60");
         assert_eq!(resp.data.transaction.state, TransactionState::Complete);
         assert_eq!(resp.data.transaction.tx_hash.as_deref(), Some("0xdeadbeef"));
         Ok(())
     }
 
+    #[test]
+    fn transaction_state_response_deserializes() -> Result<(), Box<dyn std::error::Error>> {
+        let json =
+            r#"{"data": {"id": "c0a8f9d2-6b1e-4f3a-9d7c-2e5b8a1f4c60", "state": "INITIATED"}}"#;
+        let resp: TransactionStateResponse = serde_json::from_str(json)?;
+        assert_eq!(resp.data.id.to_string(), "c0a8f9d2-6b1e-4f3a-9d7c-2e5b8a1f4c60");
+        assert_eq!(resp.data.state, TransactionState::Initiated);
+        Ok(())
+    }
+
     #[test]
     fn transactions_list_deserializes() -> Result<(), Box<dyn std::error::Error>> {
         let json = r#"{
             "data": {
                 "transactions": [
                     {
-                        "id": "tx-1",
+                        "id": "7d3e1b9a-2c4f-4e8d-b6a5-0f1e2d3c4b5a",
                         "state": "SENT",
                         "createDate": "2024-01-01T00:00:00Z",
                         "updateDate": "2024-01-01T00:00:00Z"
@@ -603,13 +1142,13 @@ mod tests {
     #[test]
     fn create_transfer_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = CreateTransferTxRequest {
-            idempotency_key: "key".to_string(),
+            idempotency_key: IdempotencyKey::random(),
             entity_secret_ciphertext: "cipher".to_string(),
-            wallet_id: "wallet-1".to_string(),
+            wallet_id: "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?,
             blockchain: None,
-            token_id: Some("token-1".to_string()),
+            token_id: Some("e2f3a4b5-c6d7-4e8f-9a0b-1c2d3e4f5a6b".parse()?),
             destination_address: "0xdest".to_string(),
-            amounts: Some(vec!["1.0".to_string()]),
+            amounts: Some(vec!["1.0".parse()?]),
             nft_token_ids: None,
             ref_id: None,
             fee_level: Some(FeeLevel::Medium),
@@ -639,4 +1178,91 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn transfer_builder_fills_the_idempotency_key() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let req = CreateTransferTxRequest::builder(
+            wallet_id,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        )
+        .token_id("e2f3a4b5-c6d7-4e8f-9a0b-1c2d3e4f5a6b".parse::<TokenId>()?)
+        .amounts(["1.5".parse()?])
+        .fee_level(FeeLevel::Medium)
+        .build()?;
+        assert_eq!(req.idempotency_key.as_uuid().get_version_num(), 4);
+        assert!(req.entity_secret_ciphertext.is_empty());
+
+        let key = IdempotencyKey::derive(&IdempotencyKey::namespace("payouts"), "payout-1");
+
+        let keyed = CreateTransferTxRequest::builder(
+            wallet_id,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        )
+        .idempotency_key(key)
+        .blockchain(Blockchain::EthSepolia)
+        .amounts(["1".parse()?])
+        .gas_limit("21000")
+        .max_fee("5.9")
+        .priority_fee("1.0")
+        .build()?;
+        assert_eq!(keyed.idempotency_key, key);
+        Ok(())
+    }
+
+    #[test]
+    fn transfer_builder_rejects_conflicting_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let base = CreateTransferTxRequest::builder(
+            wallet_id,
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        )
+        .blockchain(Blockchain::EthSepolia)
+        .fee_level(FeeLevel::Low);
+        let cases = [
+            (base.clone(), "one of `amounts` or `nft_token_ids` is required"),
+            (
+                base.clone().amounts(["1".parse()?]).nft_token_ids(["7"]),
+                "`amounts` and `nft_token_ids` cannot both be set",
+            ),
+            (
+                base.clone()
+                    .amounts(["1".parse()?])
+                    .token_id("e2f3a4b5-c6d7-4e8f-9a0b-1c2d3e4f5a6b".parse::<TokenId>()?),
+                "`token_id` and `blockchain` cannot both be set",
+            ),
+            (
+                base.amounts(["1".parse()?]).gas_limit("21000"),
+                "`fee_level` and `gas_limit` cannot both be set",
+            ),
+        ];
+        for (builder, expected) in cases {
+            let result = builder.build();
+            assert!(matches!(&result, Err(Error::InvalidParam(m)) if m == expected), "{result:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn contract_execution_builder_needs_one_call() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let contract = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        let req = CreateContractExecutionTxRequest::builder(wallet_id, contract)
+            .abi_function_signature("transfer(address,uint256)")
+            .abi_parameters([serde_json::json!(contract), serde_json::json!("1")])
+            .fee_level(FeeLevel::High)
+            .build()?;
+        assert_eq!(req.abi_parameters.as_ref().map(Vec::len), Some(2));
+
+        let params_only = CreateContractExecutionTxRequest::builder(wallet_id, contract)
+            .call_data("0x")
+            .abi_parameters([serde_json::json!("1")])
+            .fee_level(FeeLevel::High)
+            .build();
+        assert!(
+            matches!(&params_only, Err(Error::InvalidParam(m)) if m == "`abi_parameters` requires `abi_function_signature`"),
+            "{params_only:?}"
+        );
+        Ok(())
+    }
 }
@@ -3,45 +3,64 @@
 //! Contains request parameters and response types for wallet management
 //! endpoints including balances and NFTs.
 
-use super::common::{AccountType, Blockchain, CustodyType, PageParams, TokenStandard, WalletState};
-
-/// NFT token standard.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum NftStandard {
-    /// ERC-721 non-fungible token.
-    #[serde(rename = "ERC721")]
-    Erc721,
-    /// ERC-1155 multi-token.
-    #[serde(rename = "ERC1155")]
-    Erc1155,
-}
-
-/// Fungible token standard (for wallet balance queries).
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum FtStandard {
-    /// Native coin (no ERC standard).
-    #[serde(rename = "")]
-    Native,
-    /// ERC-20 fungible token.
-    #[serde(rename = "ERC20")]
-    Erc20,
-}
-
-/// Smart Contract Account core implementation variant.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-pub enum ScaCore {
-    /// Circle ERC-4337 v1 implementation.
-    #[serde(rename = "circle_4337_v1")]
-    Circle4337V1,
-    /// Circle ERC-6900 single-owner v1 implementation.
-    #[serde(rename = "circle_6900_singleowner_v1")]
-    Circle6900SingleownerV1,
-    /// Circle ERC-6900 single-owner v2 implementation.
-    #[serde(rename = "circle_6900_singleowner_v2")]
-    Circle6900SingleownerV2,
-    /// Circle ERC-6900 single-owner v3 implementation.
-    #[serde(rename = "circle_6900_singleowner_v3")]
-    Circle6900SingleownerV3,
+use chrono::{DateTime, Utc};
+use circle_core::{
+    Error,
+    address::{Address, AddressError},
+    amount::{self, AmountError, BigUint, TokenAmount},
+    api_enum,
+    ids::{IdempotencyKey, TokenId, UserId, WalletId, WalletSetId},
+    network::Network,
+};
+
+use super::common::{
+    AccountType, Blockchain, CursorParams, CustodyType, EvmBlockchain, Order, PageParams,
+    TokenStandard, WalletState,
+};
+
+api_enum! {
+    /// NFT token standard.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub enum NftStandard {
+        /// ERC-721 non-fungible token.
+        #[serde(rename = "ERC721")]
+        Erc721,
+        /// ERC-1155 multi-token.
+        #[serde(rename = "ERC1155")]
+        Erc1155,
+    }
+}
+
+api_enum! {
+    /// Fungible token standard (for wallet balance queries).
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub enum FtStandard {
+        /// Native coin (no ERC standard).
+        #[serde(rename = "")]
+        Native,
+        /// ERC-20 fungible token.
+        #[serde(rename = "ERC20")]
+        Erc20,
+    }
+}
+
+api_enum! {
+    /// Smart Contract Account core implementation variant.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+    pub enum ScaCore {
+        /// Circle ERC-4337 v1 implementation.
+        #[serde(rename = "circle_4337_v1")]
+        Circle4337V1,
+        /// Circle ERC-6900 single-owner v1 implementation.
+        #[serde(rename = "circle_6900_singleowner_v1")]
+        Circle6900SingleownerV1,
+        /// Circle ERC-6900 single-owner v2 implementation.
+        #[serde(rename = "circle_6900_singleowner_v2")]
+        Circle6900SingleownerV2,
+        /// Circle ERC-6900 single-owner v3 implementation.
+        #[serde(rename = "circle_6900_singleowner_v3")]
+        Circle6900SingleownerV3,
+    }
 }
 
 /// A blockchain token definition.
@@ -50,7 +69,7 @@ pub enum ScaCore {
 pub struct Token {
     /// Unique token ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    pub id: Option<TokenId>,
     /// Blockchain network the token lives on.
     pub blockchain: Blockchain,
     /// Whether this token is the native coin of its chain.
@@ -72,22 +91,43 @@ pub struct Token {
     pub token_address: Option<String>,
     /// ISO-8601 last-update timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub update_date: Option<String>,
+    pub update_date: Option<DateTime<Utc>>,
     /// ISO-8601 creation timestamp.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub create_date: Option<String>,
+    pub create_date: Option<DateTime<Utc>>,
+}
+
+impl Token {
+    /// The human amount of `units` base units of this token.
+    pub fn amount_from_base_units(
+        &self,
+        units: impl Into<BigUint>,
+    ) -> Result<TokenAmount, AmountError> {
+        Ok(TokenAmount::from_base_units(units, amount::decimals(self.decimals)?))
+    }
+
+    /// `amount` in base units of this token.
+    pub fn to_base_units(&self, amount: &TokenAmount) -> Result<BigUint, AmountError> {
+        amount.to_base_units(amount::decimals(self.decimals)?)
+    }
 }
 
 /// A single fungible token balance entry.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Balance {
-    /// Token amount as a decimal string.
-    pub amount: String,
+    /// Token amount held.
+    pub amount: TokenAmount,
     /// Token definition.
     pub token: Token,
     /// ISO-8601 last-update timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
+}
+
+impl circle_core::pagination::PageItem for Balance {
+    fn cursor(&self) -> Option<String> {
+        self.token.id.as_ref().map(ToString::to_string)
+    }
 }
 
 /// Wallet metadata for creation requests.
@@ -107,15 +147,15 @@ pub struct WalletMetadata {
 #[serde(rename_all = "camelCase")]
 pub struct Wallet {
     /// Unique wallet ID (UUID).
-    pub id: String,
+    pub id: WalletId,
     /// On-chain wallet address.
     pub address: String,
     /// Blockchain network this wallet is on.
     pub blockchain: Blockchain,
     /// ISO-8601 creation timestamp.
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
     /// ISO-8601 last-update timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// Custody type.
     pub custody_type: CustodyType,
     /// Human-readable name.
@@ -129,10 +169,10 @@ pub struct Wallet {
     pub state: Option<WalletState>,
     /// User ID (user-controlled wallets).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
     /// Wallet set ID the wallet belongs to.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_set_id: Option<String>,
+    pub wallet_set_id: Option<WalletSetId>,
     /// Initial public key.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_public_key: Option<String>,
@@ -145,6 +185,28 @@ pub struct Wallet {
     /// Token balances (populated when requested via `include_all` or balance endpoints).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token_balances: Option<Vec<Balance>>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<Wallet, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for Wallet {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
+}
+
+impl Wallet {
+    /// [`Self::address`] parsed for [`Self::blockchain`], in canonical form.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the chain is unknown to this SDK
+    /// version or the address is malformed.
+    pub fn parsed_address(&self) -> Result<Address, Error> {
+        let network = Network::try_from(self.blockchain.clone())?;
+        Ok(Address::parse(network, &self.address)?)
+    }
 }
 
 /// Inner data of a list-wallets response.
@@ -216,7 +278,7 @@ pub struct Nft {
     /// Token definition.
     pub token: Token,
     /// ISO-8601 last-update timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// On-chain token ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nft_token_id: Option<String>,
@@ -225,6 +287,12 @@ pub struct Nft {
     pub metadata: Option<String>,
 }
 
+impl circle_core::pagination::PageItem for Nft {
+    fn cursor(&self) -> Option<String> {
+        self.token.id.as_ref().map(ToString::to_string)
+    }
+}
+
 /// Inner data of a list-wallet-NFTs response.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NftsData {
@@ -244,11 +312,13 @@ pub struct Nfts {
 #[serde(rename_all = "camelCase")]
 pub struct CreateWalletsRequest {
     /// Idempotency key (UUID) to deduplicate requests.
-    pub idempotency_key: String,
-    /// Encrypted entity secret ciphertext.
+    pub idempotency_key: IdempotencyKey,
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
     /// Wallet set ID the wallets should belong to.
-    pub wallet_set_id: String,
+    pub wallet_set_id: WalletSetId,
     /// Blockchain networks to create wallets on.
     pub blockchains: Vec<Blockchain>,
     /// Account type (SCA or EOA); defaults to EOA.
@@ -274,28 +344,77 @@ pub struct UpdateWalletRequest {
     pub ref_id: Option<String>,
 }
 
+/// Request body for deriving a wallet onto another EVM blockchain.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeriveWalletRequest {
+    /// Name and reference ID of the derived wallet. If the wallet already
+    /// exists, its metadata is updated with these.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<WalletMetadata>,
+}
+
+/// Request body for deriving the wallet at an address onto another EVM
+/// blockchain.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeriveWalletByAddressRequest {
+    /// Blockchain of the source wallet.
+    pub source_blockchain: EvmBlockchain,
+    /// Address of the source wallet.
+    pub wallet_address: String,
+    /// Blockchain to derive the wallet onto.
+    pub target_blockchain: EvmBlockchain,
+    /// Name and reference ID of the derived wallet. If the wallet already
+    /// exists, its metadata is updated with these.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<WalletMetadata>,
+}
+
+impl DeriveWalletByAddressRequest {
+    /// Checks `wallet_address` against the address format of
+    /// `source_blockchain` offline.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn validate(&self) -> Result<(), AddressError> {
+        self.source_blockchain.check_address(&self.wallet_address)
+    }
+}
+
+/// The wallet returned by a derive-wallet endpoint.
+#[derive(Debug, Clone)]
+pub struct DerivedWallet {
+    /// The wallet on the target blockchain.
+    pub wallet: Wallet,
+    /// Whether the wallet was created (`201`), rather than already existing
+    /// and only having its metadata updated (`200`).
+    pub created: bool,
+}
+
 /// Query parameters for the list-wallets endpoint.
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListWalletsParams {
+    /// Filter by wallet address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
     /// Filter by blockchain.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blockchain: Option<Blockchain>,
-    /// Filter by wallet address.
+    /// Filter by SCA core implementation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub address: Option<String>,
+    pub sca_core: Option<ScaCore>,
     /// Filter by wallet set ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_set_id: Option<String>,
+    pub wallet_set_id: Option<WalletSetId>,
     /// Filter by external reference ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ref_id: Option<String>,
-    /// Filter by wallet state.
+    /// Sort order by creation date.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub state: Option<WalletState>,
-    /// Filter by custody type.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub custody_type: Option<CustodyType>,
+    pub order: Option<Order>,
     /// Pagination parameters.
     #[serde(flatten)]
     pub page: PageParams,
@@ -305,48 +424,60 @@ pub struct ListWalletsParams {
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListWalletBalancesParams {
-    /// Include all wallets, not just those with balances.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub include_all: Option<bool>,
-    /// Filter by token name.
+    /// Blockchain of the wallets; the API requires it.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    /// Filter by token contract address.
+    pub blockchain: Option<Blockchain>,
+    /// Filter by wallet address.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub token_address: Option<String>,
-    /// Filter by blockchain.
+    pub address: Option<String>,
+    /// Filter by SCA core implementation.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub blockchain: Option<Blockchain>,
+    pub sca_core: Option<ScaCore>,
     /// Filter by wallet set ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_set_id: Option<String>,
-    /// Filter by specific wallet IDs (comma-separated string).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub wallet_ids: Option<String>,
-    /// Filter by custody type.
+    pub wallet_set_id: Option<WalletSetId>,
+    /// Filter by external reference ID.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub custody_type: Option<CustodyType>,
-    /// Filter by wallet address.
+    pub ref_id: Option<String>,
+    /// Only include balances greater than or equal to this amount.
+    #[serde(rename = "amount__gte", skip_serializing_if = "Option::is_none")]
+    pub amount_gte: Option<TokenAmount>,
+    /// Filter by token contract address.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub address: Option<String>,
+    pub token_address: Option<String>,
     /// Pagination parameters.
     #[serde(flatten)]
     pub page: PageParams,
 }
 
-/// Query parameters for per-wallet balance and NFT endpoints.
+/// Query parameters for the per-wallet token balances endpoint.
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct WalletNftsParams {
+pub struct ListWalletTokenBalancesParams {
+    /// Also return zero balances.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_all: Option<bool>,
+    /// Filter by token name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Filter by token contract address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_address: Option<String>,
+    /// Filter by token standard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standard: Option<TokenStandard>,
     /// Pagination parameters.
     #[serde(flatten)]
-    pub page: PageParams,
+    pub page: CursorParams,
 }
 
 /// Query parameters for the list-wallet-NFTs endpoint.
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListWalletNftsParams {
+    /// Also return NFTs with a zero amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_all: Option<bool>,
     /// Filter by NFT standard.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub standard: Option<NftStandard>,
@@ -358,9 +489,11 @@ pub struct ListWalletNftsParams {
     pub token_address: Option<String>,
     /// Pagination parameters.
     #[serde(flatten)]
-    pub page: PageParams,
+    pub page: CursorParams,
 }
 
+circle_core::paginated!(ListWalletsParams => page, ListWalletBalancesParams => page, ListWalletTokenBalancesParams => page, ListWalletNftsParams => page);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,7 +503,7 @@ mod tests {
         let json = r#"{
             "data": {
                 "wallet": {
-                    "id": "wallet-id-1",
+                    "id": "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d",
                     "address": "0x1234",
                     "blockchain": "ETH",
                     "createDate": "2024-01-01T00:00:00Z",
@@ -381,9 +514,27 @@ mod tests {
             }
         }"#;
         let resp: WalletResponse = serde_json::from_str(json)?;
-        assert_eq!(resp.data.wallet.id, "wallet-id-1");
+        assert_eq!(resp.data.wallet.id.to_string(), "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d");
         assert_eq!(resp.data.wallet.blockchain, Blockchain::Eth);
         assert_eq!(resp.data.wallet.state, Some(WalletState::Live));
+        assert!(resp.data.wallet.extra.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn wallet_keeps_undeclared_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let json = r#"{
+            "id": "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d",
+            "address": "0x1234",
+            "blockchain": "ETH",
+            "createDate": "2024-01-01T00:00:00Z",
+            "updateDate": "2024-01-01T00:00:00Z",
+            "custodyType": "DEVELOPER",
+            "recoveryTier": "STANDARD"
+        }"#;
+        let wallet: Wallet = serde_json::from_str(json)?;
+        assert_eq!(wallet.extra.len(), 1);
+        assert_eq!(wallet.extra.get("recoveryTier"), Some(&serde_json::Value::from("STANDARD")));
         Ok(())
     }
 
@@ -393,7 +544,7 @@ mod tests {
             "data": {
                 "wallets": [
                     {
-                        "id": "w1",
+                        "id": "b8c2d3e4-f5a6-4b7c-9d8e-0f1a2b3c4d5e",
                         "address": "0xabc",
                         "blockchain": "MATIC",
                         "createDate": "2024-01-01T00:00:00Z",
@@ -444,9 +595,9 @@ mod tests {
     #[test]
     fn create_wallets_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = CreateWalletsRequest {
-            idempotency_key: "key".to_string(),
+            idempotency_key: IdempotencyKey::random(),
             entity_secret_ciphertext: "cipher".to_string(),
-            wallet_set_id: "set-id".to_string(),
+            wallet_set_id: "5e0c7a4b-3f21-4d8e-9b6a-c1d2e3f4a5b6".parse()?,
             blockchains: vec![Blockchain::Eth],
             account_type: None,
             count: Some(2),
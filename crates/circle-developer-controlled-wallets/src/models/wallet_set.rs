@@ -3,26 +3,38 @@
 //! Contains request parameters and response types for wallet set management
 //! endpoints.
 
-use super::common::{CustodyType, PageParams};
+use chrono::{DateTime, Utc};
+use circle_core::ids::{IdempotencyKey, UserId, WalletSetId};
+
+use super::common::{CustodyType, Order, PageParams};
 
 /// A wallet set resource.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WalletSet {
     /// Unique wallet set ID (UUID).
-    pub id: String,
+    pub id: WalletSetId,
     /// Custody type for wallets within this set.
     pub custody_type: CustodyType,
     /// ISO-8601 creation timestamp.
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
     /// ISO-8601 last-update timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// Human-readable name.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// User ID associated with this wallet set (user-controlled wallets).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<WalletSet, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for WalletSet {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
 }
 
 /// Inner data of a list-wallet-sets response.
@@ -59,10 +71,12 @@ pub struct WalletSetResponse {
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateWalletSetRequest {
-    /// Encrypted entity secret ciphertext.
+    /// Encrypted entity secret ciphertext. Left empty, it is filled in by a
+    /// client configured with
+    /// [`DeveloperWalletsClient::with_entity_secret`](crate::DeveloperWalletsClient::with_entity_secret).
     pub entity_secret_ciphertext: String,
     /// Idempotency key (UUID) to deduplicate requests.
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Optional human-readable name for the wallet set.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -81,11 +95,16 @@ pub struct UpdateWalletSetRequest {
 #[derive(Debug, Default, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListWalletSetsParams {
+    /// Sort order by creation date.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<Order>,
     /// Pagination parameters.
     #[serde(flatten)]
     pub page: PageParams,
 }
 
+circle_core::paginated!(ListWalletSetsParams => page);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,7 +123,7 @@ mod tests {
             }
         }"#;
         let resp: WalletSetResponse = serde_json::from_str(json)?;
-        assert_eq!(resp.data.wallet_set.id, "f5d71c75-b8e3-4d1e-ae38-e8c3c3c3c3c3");
+        assert_eq!(resp.data.wallet_set.id.to_string(), "f5d71c75-b8e3-4d1e-ae38-e8c3c3c3c3c3");
         assert_eq!(resp.data.wallet_set.name.as_deref(), Some("My Wallet Set"));
         Ok(())
     }
@@ -115,7 +134,7 @@ mod tests {
             "data": {
                 "walletSets": [
                     {
-                        "id": "aaa",
+                        "id": "5e0c7a4b-3f21-4d8e-9b6a-c1d2e3f4a5b6",
                         "custodyType": "DEVELOPER",
                         "createDate": "2024-01-01T00:00:00Z",
                         "updateDate": "2024-01-01T00:00:00Z"
@@ -125,7 +144,7 @@ mod tests {
         }"#;
         let resp: WalletSets = serde_json::from_str(json)?;
         assert_eq!(resp.data.wallet_sets.len(), 1);
-        assert_eq!(resp.data.wallet_sets[0].id, "aaa");
+        assert_eq!(resp.data.wallet_sets[0].id.to_string(), "5e0c7a4b-3f21-4d8e-9b6a-c1d2e3f4a5b6");
         Ok(())
     }
 
@@ -133,7 +152,7 @@ mod tests {
     fn create_wallet_set_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
         let req = CreateWalletSetRequest {
             entity_secret_ciphertext: "cipher123".to_string(),
-            idempotency_key: "key123".to_string(),
+            idempotency_key: IdempotencyKey::random(),
             name: Some("Test".to_string()),
         };
         let json = serde_json::to_string(&req)?;
@@ -1,4 +1,391 @@
 #![allow(missing_docs)]
 // Integration tests for circle-developer-controlled-wallets.
-// Requires Prism mock server on port 4012.
+// Each test runs against its own in-process circle-mock server.
 // Run via: just integration-test
+
+use std::time::Duration;
+
+use circle_core::{CassetteTransport, HpxTransport};
+use circle_developer_controlled_wallets::{
+    DeveloperWalletsClient, Error, RetryPolicy,
+    amount::TokenAmount,
+    entity_secret::EntitySecret,
+    error::CircleErrorCode,
+    ids::{IdempotencyKey, TokenId, WalletId},
+    models::{
+        common::{Blockchain, EvmBlockchain, FeeLevel},
+        transaction::{CreateTransferTxRequest, TransactionState},
+        wallet::{
+            CreateWalletsRequest, DeriveWalletByAddressRequest, DeriveWalletRequest,
+            ListWalletTokenBalancesParams, ListWalletsParams, WalletMetadata,
+        },
+        wallet_set::CreateWalletSetRequest,
+    },
+    pagination::{self, StreamOptions},
+};
+use circle_mock::{Fault, Method, MockServer};
+
+const CIPHERTEXT: &str = "bW9jay1lbnRpdHktc2VjcmV0LWNpcGhlcnRleHQ=";
+
+async fn start() -> Result<(MockServer, DeveloperWalletsClient), Box<dyn std::error::Error>> {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let server = MockServer::start().await?;
+    let client = DeveloperWalletsClient::builder("TEST_API_KEY:id:secret")
+        .base_url(server.base_url())
+        .retry_policy(
+            RetryPolicy::default().with_backoff(Duration::from_millis(1), Duration::from_millis(5)),
+        )
+        .build()?;
+    Ok((server, client))
+}
+
+fn wallet_set_request(idempotency_key: IdempotencyKey) -> CreateWalletSetRequest {
+    CreateWalletSetRequest {
+        entity_secret_ciphertext: CIPHERTEXT.to_string(),
+        idempotency_key,
+        name: Some("integration".to_string()),
+    }
+}
+
+async fn usdc_balance(
+    client: &DeveloperWalletsClient,
+    wallet_id: &WalletId,
+) -> Result<Option<TokenAmount>, Error> {
+    let balances = client
+        .list_wallet_token_balances(wallet_id, &ListWalletTokenBalancesParams::default())
+        .await?;
+    Ok(balances
+        .data
+        .token_balances
+        .into_iter()
+        .find(|balance| balance.token.symbol.as_deref() == Some("USDC"))
+        .map(|balance| balance.amount))
+}
+
+#[tokio::test]
+async fn created_wallets_are_listed() -> Result<(), Box<dyn std::error::Error>> {
+    let (_server, client) = start().await?;
+
+    let wallet_set = client
+        .create_wallet_set(&wallet_set_request("8d2b9a3e-51f4-4c6e-9f0a-1b2c3d4e5f60".parse()?))
+        .await?
+        .data
+        .wallet_set;
+    let fetched = client.get_wallet_set(&wallet_set.id).await?;
+    assert_eq!(fetched.data.wallet_set.name.as_deref(), Some("integration"));
+
+    let created = client
+        .create_wallets(&CreateWalletsRequest {
+            idempotency_key: "0f6a1c52-7d3e-4b8a-a9c1-2e3f4a5b6c7d".parse()?,
+            entity_secret_ciphertext: CIPHERTEXT.to_string(),
+            wallet_set_id: wallet_set.id,
+            blockchains: vec![Blockchain::EthSepolia],
+            account_type: None,
+            count: Some(2),
+            metadata: None,
+        })
+        .await?;
+    assert_eq!(created.data.wallets.len(), 2);
+
+    let params = ListWalletsParams { wallet_set_id: Some(wallet_set.id), ..Default::default() };
+    let listed = client.list_wallets(&params).await?;
+    let mut listed: Vec<WalletId> = listed.data.wallets.into_iter().map(|w| w.id).collect();
+    let mut expected: Vec<WalletId> = created.data.wallets.into_iter().map(|w| w.id).collect();
+    listed.sort();
+    expected.sort();
+    assert_eq!(listed, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn wallet_stream_follows_cursors_both_ways() -> Result<(), Box<dyn std::error::Error>> {
+    let (_server, client) = start().await?;
+    let wallet_set = client
+        .create_wallet_set(&wallet_set_request("3a7c1e9b-5d2f-4a6b-8c0d-9e1f2a3b4c5d".parse()?))
+        .await?
+        .data
+        .wallet_set;
+    client
+        .create_wallets(&CreateWalletsRequest {
+            idempotency_key: "b4c5d6e7-f809-4a1b-9c2d-3e4f5a6b7c8d".parse()?,
+            entity_secret_ciphertext: CIPHERTEXT.to_string(),
+            wallet_set_id: wallet_set.id,
+            blockchains: vec![Blockchain::EthSepolia],
+            account_type: None,
+            count: Some(5),
+            metadata: None,
+        })
+        .await?;
+    let mut params = ListWalletsParams { wallet_set_id: Some(wallet_set.id), ..Default::default() };
+    let one_page = client.list_wallets(&params).await?.data.wallets;
+    let newest_first: Vec<WalletId> = one_page.iter().map(|wallet| wallet.id).collect();
+    assert_eq!(newest_first.len(), 5);
+
+    let options = StreamOptions { page_size: Some(2), ..StreamOptions::default() };
+    let streamed = pagination::collect_all(client.list_wallets_stream(&params, options)).await?;
+    assert_eq!(streamed.iter().map(|wallet| wallet.id).collect::<Vec<_>>(), newest_first);
+
+    let capped = StreamOptions { max_items: Some(3), page_size: Some(2) };
+    let first_three = pagination::collect_all(client.list_wallets_stream(&params, capped)).await?;
+    assert_eq!(first_three.len(), 3);
+
+    params.page.page_before = Some(newest_first[4].to_string());
+    let backwards = pagination::collect_all(client.list_wallets_stream(&params, options)).await?;
+    let mut expected = newest_first[..4].to_vec();
+    expected.reverse();
+    assert_eq!(backwards.iter().map(|wallet| wallet.id).collect::<Vec<_>>(), expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn large_item_caps_stay_within_the_page_size_limit() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (server, client) = start().await?;
+    for _ in 0..60 {
+        server.state().create_wallet("ETH-SEPOLIA")?;
+    }
+    let params = ListWalletsParams::default();
+
+    let capped = StreamOptions { max_items: Some(55), ..StreamOptions::default() };
+    let first = pagination::collect_all(client.list_wallets_stream(&params, capped)).await?;
+    assert_eq!(first.len(), 55);
+
+    let generous = StreamOptions { max_items: Some(100), ..StreamOptions::default() };
+    let all = pagination::collect_all(client.list_wallets_stream(&params, generous)).await?;
+    assert_eq!(all.len(), 60);
+    Ok(())
+}
+
+#[tokio::test]
+async fn entity_secret_is_registered_and_encrypted_per_request()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let secret = EntitySecret::generate();
+
+    let recovery_file = client.register_entity_secret(&secret).await?;
+    assert!(!recovery_file.as_str().is_empty());
+
+    let client = client.with_entity_secret(secret);
+    for key in ["3c1f6a2e-8b4d-4e7a-9c5f-0d1e2f3a4b5c", "5a7b9c1d-2e3f-4a5b-8c6d-7e8f9a0b1c2d"] {
+        let mut request = wallet_set_request(key.parse()?);
+        request.entity_secret_ciphertext.clear();
+        client.create_wallet_set(&request).await?;
+    }
+    let key_fetches = server
+        .state()
+        .requests()
+        .iter()
+        .filter(|(_, path)| path == "/v1/w3s/config/entity/publicKey")
+        .count();
+    assert_eq!(key_fetches, 2, "one for the registration, one for the client");
+    Ok(())
+}
+
+#[tokio::test]
+async fn cassette_replays_requests_with_fresh_keys_and_ciphertexts()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let secret = EntitySecret::generate();
+    client.register_entity_secret(&secret).await?;
+    let cassette =
+        std::env::temp_dir().join(format!("circle-developer-cassette-{}.json", std::process::id()));
+    let create_wallet_set = async |client: DeveloperWalletsClient| {
+        let mut request = wallet_set_request(IdempotencyKey::random());
+        request.entity_secret_ciphertext.clear();
+        let wallet_set = client.create_wallet_set(&request).await?.data.wallet_set;
+        client.get_wallet_set(&wallet_set.id).await.map(|fetched| fetched.data.wallet_set)
+    };
+
+    let recorder = DeveloperWalletsClient::builder("TEST_API_KEY:id:secret")
+        .base_url(server.base_url())
+        .transport(CassetteTransport::record(&cassette, HpxTransport::new(hpx::Client::new())))
+        .build()?
+        .with_entity_secret(secret);
+    let recorded = create_wallet_set(recorder).await?;
+    drop(server);
+
+    let player = DeveloperWalletsClient::builder("TEST_API_KEY:id:secret")
+        .transport(CassetteTransport::replay(&cassette)?)
+        .build()?
+        .with_entity_secret(EntitySecret::generate());
+    let replayed = create_wallet_set(player).await;
+    std::fs::remove_file(&cassette)?;
+    assert_eq!(replayed?.id, recorded.id);
+    Ok(())
+}
+
+#[tokio::test]
+async fn idempotent_replay_returns_the_same_wallet_set() -> Result<(), Box<dyn std::error::Error>> {
+    let (_server, client) = start().await?;
+    let request = wallet_set_request("3c4d5e6f-7a8b-4c9d-8e0f-a1b2c3d4e5f6".parse()?);
+
+    let first = client.create_wallet_set_with_meta(&request).await?;
+    let replay = client.create_wallet_set_with_meta(&request).await?;
+    assert_eq!(first.meta.status, 201);
+    assert_eq!(replay.meta.status, 200);
+    assert_eq!(first.body.data.wallet_set.id, replay.body.data.wallet_set.id);
+    Ok(())
+}
+
+#[tokio::test]
+async fn reusing_a_key_for_another_request_conflicts() -> Result<(), Box<dyn std::error::Error>> {
+    let (_server, client) = start().await?;
+    let mut request = wallet_set_request("6e7f8a9b-0c1d-4e2f-a3b4-c5d6e7f8a9b0".parse()?);
+    client.create_wallet_set(&request).await?;
+
+    request.name = Some("another".to_string());
+    match client.create_wallet_set(&request).await {
+        Err(error) => assert!(error.is_idempotency_conflict(), "{error}"),
+        Ok(other) => return Err(format!("expected an idempotency conflict, got {other:?}").into()),
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn derived_key_deduplicates_a_retry_after_restart() -> Result<(), Box<dyn std::error::Error>>
+{
+    let (_server, client) = start().await?;
+    let namespace = IdempotencyKey::namespace("wallet-sets");
+
+    let first = client
+        .create_wallet_set_with_meta(&wallet_set_request(IdempotencyKey::derive(
+            &namespace, "treasury",
+        )))
+        .await?;
+    // A restarted process derives the same key from the same business id.
+    let retry = client
+        .create_wallet_set_with_meta(&wallet_set_request(IdempotencyKey::derive(
+            &namespace, "treasury",
+        )))
+        .await?;
+    assert_eq!(retry.meta.status, 200);
+    assert_eq!(first.body.data.wallet_set.id, retry.body.data.wallet_set.id);
+
+    let other = client
+        .create_wallet_set_with_meta(&wallet_set_request(IdempotencyKey::derive(
+            &namespace, "payroll",
+        )))
+        .await?;
+    assert_eq!(other.meta.status, 201);
+    Ok(())
+}
+
+#[tokio::test]
+async fn derived_wallets_share_the_source_address() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let source = server.state().create_wallet("ETH-SEPOLIA")?;
+    let source_id: WalletId = source["id"].as_str().unwrap_or_default().parse()?;
+    let address = source["address"].as_str().unwrap_or_default();
+
+    let chains = [EvmBlockchain::BaseSepolia, EvmBlockchain::ArbSepolia];
+    let derived = client
+        .derive_wallet_onto(&source_id, chains.clone(), &DeriveWalletRequest::default())
+        .await?;
+    assert_eq!(derived.len(), 2);
+    for derived in &derived {
+        assert!(derived.created);
+        assert_eq!(derived.wallet.address, address);
+    }
+    let retried =
+        client.derive_wallet_onto(&source_id, chains, &DeriveWalletRequest::default()).await?;
+    assert!(retried.iter().all(|wallet| !wallet.created), "{retried:?}");
+    assert_eq!(retried[1].wallet.id, derived[1].wallet.id);
+
+    let renamed = client
+        .derive_wallet_by_address(&DeriveWalletByAddressRequest {
+            source_blockchain: EvmBlockchain::EthSepolia,
+            wallet_address: address.to_string(),
+            target_blockchain: EvmBlockchain::BaseSepolia,
+            metadata: Some(WalletMetadata { name: Some("base".to_string()), ref_id: None }),
+        })
+        .await?;
+    assert!(!renamed.created);
+    assert_eq!(renamed.wallet.id, derived[0].wallet.id);
+    assert_eq!(renamed.wallet.name.as_deref(), Some("base"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transfer_settles_balances_on_completion() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let source = server.state().create_wallet("ETH-SEPOLIA")?;
+    let destination = server.state().create_wallet("ETH-SEPOLIA")?;
+    let source_id: WalletId = source["id"].as_str().unwrap_or_default().parse()?;
+    let destination_id: WalletId = destination["id"].as_str().unwrap_or_default().parse()?;
+    let usdc = server.state().token_id("ETH-SEPOLIA", "USDC").unwrap_or_default();
+    server.state().set_balance(&source_id.to_string(), &usdc, "100")?;
+
+    let created = client
+        .create_transfer_transaction(&CreateTransferTxRequest {
+            idempotency_key: "5a6b7c8d-9e0f-4a1b-8c2d-3e4f5a6b7c8d".parse()?,
+            entity_secret_ciphertext: CIPHERTEXT.to_string(),
+            wallet_id: source_id,
+            blockchain: None,
+            token_id: Some(usdc.parse::<TokenId>()?),
+            destination_address: destination["address"].as_str().unwrap_or_default().to_string(),
+            amounts: Some(vec![TokenAmount::from(40)]),
+            nft_token_ids: None,
+            ref_id: None,
+            fee_level: Some(FeeLevel::Medium),
+            gas_limit: None,
+            gas_price: None,
+            max_fee: None,
+            priority_fee: None,
+        })
+        .await?;
+    assert_eq!(created.data.state, TransactionState::Initiated);
+
+    // Every fetch advances the transaction one state.
+    let mut state = created.data.state;
+    for _ in 0..10 {
+        state = client.get_transaction(&created.data.id).await?.data.transaction.state;
+        if state == TransactionState::Complete {
+            break;
+        }
+    }
+    assert_eq!(state, TransactionState::Complete);
+
+    assert_eq!(usdc_balance(&client, &source_id).await?, Some(TokenAmount::from(60)));
+    assert_eq!(usdc_balance(&client, &destination_id).await?, Some(TokenAmount::from(40)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transient_faults_are_retried() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    server
+        .state()
+        .inject(Fault::new(503).method(Method::POST).path("/v1/w3s/developer/walletSets").times(2));
+
+    let created = client
+        .create_wallet_set(&wallet_set_request("9b8a7c6d-5e4f-4a3b-9c2d-1e0f9a8b7c6d".parse()?))
+        .await?;
+    assert!(!created.data.wallet_set.id.as_uuid().is_nil());
+
+    let attempts = server
+        .state()
+        .requests()
+        .iter()
+        .filter(|(method, path)| method == Method::POST && path == "/v1/w3s/developer/walletSets")
+        .count();
+    assert_eq!(attempts, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn injected_circle_errors_surface_their_code() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    server.state().inject(
+        Fault::new(400)
+            .path("/v1/w3s/wallets/*")
+            .code(CircleErrorCode::WalletIdNotFound.code())
+            .message("Cannot find wallet"),
+    );
+
+    let result = client.get_wallet(&"8f1e2d3c-4b5a-4968-8776-a5b4c3d2e1f0".parse()?).await;
+    match result {
+        Err(Error::Api { code, .. }) => assert_eq!(code, CircleErrorCode::WalletIdNotFound.code()),
+        other => return Err(format!("expected an API error, got {other:?}").into()),
+    }
+    Ok(())
+}
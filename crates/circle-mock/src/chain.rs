@@ -0,0 +1,212 @@
+//! Blockchains known to the mock: address formats and native tokens.
+
+use circle_core::Network;
+
+/// Address family of a blockchain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Family {
+    Evm,
+    Solana,
+    Near,
+    Aptos,
+}
+
+/// Every blockchain of the developer- and user-controlled wallet specs.
+const WALLET_NETWORKS: [Network; 25] = [
+    Network::Eth,
+    Network::EthSepolia,
+    Network::Avax,
+    Network::AvaxFuji,
+    Network::Matic,
+    Network::MaticAmoy,
+    Network::Arb,
+    Network::ArbSepolia,
+    Network::Uni,
+    Network::UniSepolia,
+    Network::Base,
+    Network::BaseSepolia,
+    Network::Op,
+    Network::OpSepolia,
+    Network::Monad,
+    Network::MonadTestnet,
+    Network::ArcTestnet,
+    Network::Evm,
+    Network::EvmTestnet,
+    Network::Sol,
+    Network::SolDevnet,
+    Network::Near,
+    Network::NearTestnet,
+    Network::Aptos,
+    Network::AptosTestnet,
+];
+
+/// USDC deployments seeded into the token registry: blockchain and address.
+pub(crate) const USDC: [(&str, &str); 9] = [
+    ("ETH", "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+    ("ETH-SEPOLIA", "0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238"),
+    ("AVAX-FUJI", "0x5425890298aed601595a70AB815c96711a31Bc65"),
+    ("MATIC-AMOY", "0x41E94Eb019C0762f9Bfcf9Fb1E58725BfB0e7582"),
+    ("ARB-SEPOLIA", "0x75faf114eafb1BDbe2F0316DF893fd58CE46AA4d"),
+    ("BASE-SEPOLIA", "0x036CbD53842c5426634e7929541eC2318f3dCF7e"),
+    ("OP-SEPOLIA", "0x5fd84259d66Cd46123540766Be93DFE6D43130D7"),
+    ("UNI-SEPOLIA", "0x31d0220469e10c4E71834a79b1f276d740d3768F"),
+    ("SOL-DEVNET", "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU"),
+];
+
+/// Looks up a wallet blockchain by its API name, e.g. `ETH-SEPOLIA`.
+pub(crate) fn lookup(name: &str) -> Option<Network> {
+    name.parse().ok().filter(|network| WALLET_NETWORKS.contains(network))
+}
+
+/// Every known blockchain.
+pub(crate) fn all() -> impl Iterator<Item = Network> {
+    WALLET_NETWORKS.into_iter()
+}
+
+/// The address family of a wallet blockchain.
+pub(crate) const fn family(network: Network) -> Family {
+    match network {
+        Network::Sol | Network::SolDevnet => Family::Solana,
+        Network::Near | Network::NearTestnet => Family::Near,
+        Network::Aptos | Network::AptosTestnet => Family::Aptos,
+        _ => Family::Evm,
+    }
+}
+
+/// The display name of a native token, by symbol.
+pub(crate) fn native_name(symbol: &str) -> &str {
+    match symbol {
+        "ETH" => "Ethereum",
+        "AVAX" => "Avalanche",
+        "POL" => "Polygon",
+        "SOL" => "Solana",
+        "APT" => "Aptos Coin",
+        "MON" => "Monad",
+        other => other,
+    }
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Random hex string of `bytes` bytes, without prefix.
+pub(crate) fn random_hex(bytes: usize) -> String {
+    (0..bytes).fold(String::with_capacity(bytes * 2), |mut hex, _| {
+        let byte = fastrand::u8(..);
+        hex.push(char::from(HEX[usize::from(byte >> 4)]));
+        hex.push(char::from(HEX[usize::from(byte & 0xf)]));
+        hex
+    })
+}
+
+/// A fresh, well-formed address for `family`.
+pub(crate) fn random_address(family: Family) -> String {
+    match family {
+        Family::Evm => format!("0x{}", random_hex(20)),
+        Family::Aptos => format!("0x{}", random_hex(32)),
+        // NEAR implicit account: the hex-encoded ed25519 public key.
+        Family::Near => random_hex(32),
+        Family::Solana => {
+            let mut bytes = [0_u8; 32];
+            fastrand::fill(&mut bytes);
+            base58(&bytes)
+        }
+    }
+}
+
+/// Returns `true` if `address` is syntactically valid for `family`.
+pub(crate) fn is_valid_address(family: Family, address: &str) -> bool {
+    let is_hex = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit());
+    match family {
+        Family::Evm => address.strip_prefix("0x").is_some_and(|hex| hex.len() == 40 && is_hex(hex)),
+        Family::Aptos => {
+            address.strip_prefix("0x").is_some_and(|hex| hex.len() <= 64 && is_hex(hex))
+        }
+        Family::Solana => {
+            (32..=44).contains(&address.len()) && address.bytes().all(|b| BASE58.contains(&b))
+        }
+        Family::Near => {
+            let implicit = address.len() == 64 &&
+                address.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+            let named = (2..=64).contains(&address.len()) &&
+                address.split('.').all(|part| {
+                    !part.is_empty() &&
+                        part.bytes().all(|b| {
+                            b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_'
+                        }) &&
+                        !part.starts_with(['-', '_']) &&
+                        !part.ends_with(['-', '_'])
+                });
+            implicit || named
+        }
+    }
+}
+
+/// Compares two addresses the way the chain does: EVM addresses are
+/// case-insensitive, everything else is exact.
+pub(crate) fn same_address(family: Family, a: &str, b: &str) -> bool {
+    match family {
+        Family::Evm | Family::Aptos => a.eq_ignore_ascii_case(b),
+        Family::Solana | Family::Near => a == b,
+    }
+}
+
+fn base58(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    std::iter::repeat_n(b'1', zeros)
+        .chain(digits.iter().rev().map(|&d| BASE58[usize::from(d)]))
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_addresses_are_valid() {
+        for family in [Family::Evm, Family::Solana, Family::Near, Family::Aptos] {
+            for _ in 0..32 {
+                let address = random_address(family);
+                assert!(is_valid_address(family, &address), "{family:?}: {address}");
+            }
+        }
+    }
+
+    #[test]
+    fn wallet_networks_have_a_native_token_unless_generic() {
+        for network in all() {
+            let generic = matches!(network, Network::Evm | Network::EvmTestnet);
+            assert_eq!(network.native_currency().is_none(), generic, "{network}");
+            assert_eq!(network.is_evm(), family(network) == Family::Evm, "{network}");
+        }
+        assert_eq!(lookup("ETH-SEPOLIA"), Some(Network::EthSepolia));
+        assert_eq!(lookup("BTC"), None, "screening only, not a wallet chain");
+    }
+
+    #[test]
+    fn base58_matches_reference_vectors() {
+        assert_eq!(base58(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(base58(&[0, 0, 1]), "112");
+    }
+
+    #[test]
+    fn near_accepts_named_accounts() {
+        assert!(is_valid_address(Family::Near, "circle.testnet"));
+        assert!(!is_valid_address(Family::Near, "Circle.testnet"));
+        assert!(!is_valid_address(Family::Near, "-circle.near"));
+    }
+}
@@ -0,0 +1,103 @@
+//! Error types of the mock server.
+//!
+//! [`Error`] is returned by the Rust API ([`crate::MockServer`] and
+//! [`crate::MockState`]); over HTTP, failures are answered with the same
+//! `{"code": …, "message": …}` payload Circle sends.
+
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use circle_core::CircleErrorCode;
+
+/// Errors returned by the mock server's Rust API.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The listener could not be bound.
+    #[error("mock server I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A seeding or control call was rejected, for the same reason the HTTP
+    /// API would reject it (unknown wallet, malformed amount, ...).
+    #[error("mock rejected the call ({code}): {message}")]
+    Rejected {
+        /// HTTP status the API would have answered with.
+        status: u16,
+        /// Circle error code.
+        code: i32,
+        /// Human-readable reason.
+        message: String,
+    },
+}
+
+/// A Circle error response: HTTP status plus the `{code, message}` payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApiError {
+    pub(crate) status: StatusCode,
+    pub(crate) code: i32,
+    pub(crate) message: String,
+}
+
+impl ApiError {
+    pub(crate) fn new(
+        status: StatusCode,
+        code: CircleErrorCode,
+        message: impl Into<String>,
+    ) -> Self {
+        Self { status, code: code.code(), message: message.into() }
+    }
+
+    /// An error with no W3S error code, which Circle answers with the HTTP
+    /// status as the code.
+    pub(crate) fn status(status: StatusCode, message: impl Into<String>) -> Self {
+        Self { status, code: status.as_u16().into(), message: message.into() }
+    }
+
+    /// `400` for a required field that is absent.
+    pub(crate) fn missing(field: &str) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            CircleErrorCode::ApiParameterMissing,
+            format!("Missing required parameter: {field}"),
+        )
+    }
+
+    /// `400` for a field with an unusable value.
+    pub(crate) fn invalid(field: &str, reason: impl std::fmt::Display) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            CircleErrorCode::ApiParameterInvalid,
+            format!("Invalid value for {field}: {reason}"),
+        )
+    }
+
+    /// `400` for a request that is well-formed but not allowed in the
+    /// current state.
+    pub(crate) fn rejected(code: CircleErrorCode, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, code, message)
+    }
+
+    /// `404` for an unknown resource.
+    pub(crate) fn not_found(code: CircleErrorCode, what: &str, id: &str) -> Self {
+        Self::new(StatusCode::NOT_FOUND, code, format!("Cannot find {what} {id}"))
+    }
+
+    /// `401` for a missing or malformed credential.
+    pub(crate) fn unauthorized(code: CircleErrorCode, message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, code, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "code": self.code, "message": self.message });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(error: ApiError) -> Self {
+        Self::Rejected { status: error.status.as_u16(), code: error.code, message: error.message }
+    }
+}
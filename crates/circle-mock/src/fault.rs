@@ -0,0 +1,207 @@
+//! Error injection.
+//!
+//! A [`Fault`] makes the mock answer matching requests with an error instead
+//! of handling them, e.g. to exercise retries, rate limiting or a specific
+//! Circle error code:
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use circle_core::CircleErrorCode;
+//! use circle_mock::{Fault, Method};
+//!
+//! // The next two wallet creations fail with 503 and a Retry-After hint.
+//! let outage = Fault::new(503)
+//!     .method(Method::POST)
+//!     .path("/v1/w3s/developer/wallets")
+//!     .header("Retry-After", "1")
+//!     .times(2);
+//!
+//! // Every transfer from now on is rejected for lack of funds.
+//! let broke = Fault::new(400)
+//!     .path("/v1/w3s/developer/transactions/transfer")
+//!     .code(CircleErrorCode::NotEnoughFunds.code())
+//!     .message("Asset amount owned by the wallet is insufficient for the transaction.")
+//!     .always();
+//!
+//! // Token lookups hang long enough to trip a client timeout.
+//! let slow = Fault::new(504).path("/v1/w3s/tokens/*").delay(Duration::from_secs(5));
+//! # let _ = (outage, broke, slow);
+//! ```
+
+use std::time::Duration;
+
+use axum::{
+    Json,
+    http::{HeaderName, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+/// An injected error response.
+///
+/// Matches every request unless narrowed with [`Fault::method`] and
+/// [`Fault::path`], and fires once unless changed with [`Fault::times`] or
+/// [`Fault::always`]. Faults are checked in the order they were injected,
+/// before authentication and before any state is touched.
+#[derive(Debug, Clone)]
+pub struct Fault {
+    status: StatusCode,
+    method: Option<Method>,
+    path: Option<String>,
+    code: Option<i32>,
+    message: Option<String>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    delay: Option<Duration>,
+    remaining: Option<usize>,
+}
+
+impl Fault {
+    /// A fault answering with HTTP `status`; an invalid status becomes `500`.
+    ///
+    /// The Circle error code defaults to the status itself, as Circle does
+    /// for generic errors such as `429`.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            method: None,
+            path: None,
+            code: None,
+            message: None,
+            headers: Vec::new(),
+            delay: None,
+            remaining: Some(1),
+        }
+    }
+
+    /// Only matches requests with this method.
+    #[must_use]
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Only matches requests to this path; a `*` segment matches any single
+    /// segment, e.g. `/v1/w3s/transactions/*`.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the Circle error `code` of the payload.
+    #[must_use]
+    pub const fn code(mut self, code: i32) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the error `message` of the payload.
+    #[must_use]
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Adds a response header, e.g. `Retry-After`. Invalid headers are
+    /// ignored.
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            self.headers.push((name, value));
+        }
+        self
+    }
+
+    /// Waits this long before answering.
+    #[must_use]
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Fires for the next `times` matching requests, then expires.
+    #[must_use]
+    pub const fn times(mut self, times: usize) -> Self {
+        self.remaining = Some(times);
+        self
+    }
+
+    /// Fires for every matching request until the faults are cleared.
+    #[must_use]
+    pub const fn always(mut self) -> Self {
+        self.remaining = None;
+        self
+    }
+
+    pub(crate) fn matches(&self, method: &Method, path: &str) -> bool {
+        self.remaining != Some(0) &&
+            self.method.as_ref().is_none_or(|m| m == method) &&
+            self.path.as_deref().is_none_or(|pattern| path_matches(pattern, path))
+    }
+
+    /// Consumes one use; returns `true` once the fault has expired.
+    pub(crate) fn consume(&mut self) -> bool {
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+        self.remaining == Some(0)
+    }
+
+    pub(crate) const fn pending_delay(&self) -> Option<Duration> {
+        self.delay
+    }
+
+    pub(crate) fn response(&self) -> Response {
+        let code = self.code.unwrap_or_else(|| i32::from(self.status.as_u16()));
+        let message = self.message.clone().unwrap_or_else(|| {
+            self.status.canonical_reason().unwrap_or("Injected fault").to_string()
+        });
+        let mut response =
+            (self.status, Json(serde_json::json!({ "code": code, "message": message })))
+                .into_response();
+        for (name, value) in &self.headers {
+            response.headers_mut().append(name.clone(), value.clone());
+        }
+        response
+    }
+}
+
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern.trim_end_matches('/').split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some(expected), Some(actual)) if expected == "*" || expected == actual => {}
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_method_and_wildcard_path() {
+        let fault = Fault::new(503).method(Method::GET).path("/v1/w3s/transactions/*");
+        assert!(fault.matches(&Method::GET, "/v1/w3s/transactions/abc"));
+        assert!(!fault.matches(&Method::POST, "/v1/w3s/transactions/abc"));
+        assert!(!fault.matches(&Method::GET, "/v1/w3s/transactions"));
+        assert!(!fault.matches(&Method::GET, "/v1/w3s/transactions/abc/cancel"));
+        assert!(Fault::new(500).matches(&Method::PUT, "/anything"));
+    }
+
+    #[test]
+    fn expires_after_the_configured_number_of_uses() {
+        let mut fault = Fault::new(429).times(2);
+        assert!(!fault.consume());
+        assert!(fault.matches(&Method::GET, "/"));
+        assert!(fault.consume());
+        assert!(!fault.matches(&Method::GET, "/"));
+
+        let mut forever = Fault::new(429).always();
+        assert!(!forever.consume());
+        assert!(forever.matches(&Method::GET, "/"));
+    }
+}
@@ -0,0 +1,76 @@
+//! Stateful in-process mock of the Circle Web3 Services APIs.
+//!
+//! Unlike a schema-driven mock, `circle-mock` remembers what it is told:
+//! wallets created through the developer API show up in list calls, a
+//! transfer debits the source wallet when it completes, and user-controlled
+//! operations wait on challenges until the test approves them. That makes it
+//! possible to run real end-to-end flows of the SDK clients without network
+//! access or Circle credentials.
+//!
+//! ## Contents
+//!
+//! | Module | Functionality |
+//! |--------|---------------|
+//! | [`server`] | [`MockServer`] lifecycle and the embeddable [`router`] |
+//! | [`state`] | [`MockState`]: seeding, lifecycle control and inspection |
+//! | [`fault`] | [`Fault`] injection: error statuses, Circle codes, delays |
+//! | [`error`] | The [`Error`] type of the Rust API |
+//!
+//! ## Example
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use circle_mock::MockServer;
+//!
+//! let server = MockServer::start().await?;
+//! let wallet = server.state().create_wallet("ETH-SEPOLIA")?;
+//! let wallet_id = wallet["id"].as_str().unwrap_or_default();
+//! let usdc = server.state().token_id("ETH-SEPOLIA", "USDC").unwrap_or_default();
+//! server.state().set_balance(wallet_id, &usdc, "100")?;
+//!
+//! // Any SDK client pointed at `server.base_url()` now sees a wallet holding
+//! // 100 USDC. Transfers move one state per fetch (see
+//! // `MockState::set_auto_advance`) and settle the balances on `COMPLETE`.
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The same server runs standalone as the `circle-mock` binary, driven over
+//! HTTP through the `/__mock` control API.
+//!
+//! ## Fidelity
+//!
+//! Requests are validated like Circle does (required fields, idempotency
+//! keys, entity secret ciphertext presence, address formats) and errors use
+//! Circle's codes, but the mock does not verify signatures, decrypt entity
+//! secrets or talk to any blockchain. Fees are reported, never charged.
+
+pub mod error;
+pub mod fault;
+pub mod server;
+pub mod state;
+
+mod chain;
+mod query;
+mod routes;
+mod transactions;
+mod users;
+mod wallets;
+
+pub use axum::http::Method;
+pub use error::Error;
+pub use fault::Fault;
+pub use server::{MockServer, router};
+pub use state::MockState;
+
+/// The PEM (PKCS#1) RSA key served as the entity public key. Ciphertexts
+/// encrypted to it are only checked for presence, never decrypted.
+pub const ENTITY_PUBLIC_KEY: &str = "-----BEGIN RSA PUBLIC KEY-----
+MIIBCgKCAQEAt/ZrRV7wbSEs0sXEVxFCPbX4A7o5H5C996UANSa4Vy5r6nxU8dyZ
+U6rScnU1BjaLVxKGMuMYDGhJDiMCo7hpDHOnYNugxwCySZiLpoFkXCls+r3CR0J9
+MHUV4PIzye5V+Vp7nspRsqz3F8QOqZB/ucAlj7a5DYnR8lsCTLrkIfMQpFo/Ea6H
+JsvTklFgnPvYAGFHwEw/gn7z7PUp8rEEHW3sU8JmCCcSfNOWkfgUEE35yGjKXwhf
+5v41PHeFwhZxNooCFaTIjLWcIw4hibX9A6kusVt5YoK0sVfk1gkNZdRMgxrTENH7
+JLD4rDH/mEAaQpMvdVfJJsnIlUNIkEYZIwIDAQAB
+-----END RSA PUBLIC KEY-----
+";
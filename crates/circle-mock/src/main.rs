@@ -0,0 +1,37 @@
+//! Standalone `circle-mock` server.
+//!
+//! Listens on the address given as first argument, else `CIRCLE_MOCK_ADDR`,
+//! else `127.0.0.1:4010`, until interrupted.
+
+use std::net::SocketAddr;
+
+use circle_mock::{MockState, router};
+use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+/// Address used when none is configured.
+const DEFAULT_ADDR: &str = "127.0.0.1:4010";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        )
+        .init();
+
+    let addr: SocketAddr = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("CIRCLE_MOCK_ADDR").ok())
+        .unwrap_or_else(|| DEFAULT_ADDR.to_string())
+        .parse()?;
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!(addr = %listener.local_addr()?, "circle-mock listening");
+
+    axum::serve(listener, router(MockState::new()))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
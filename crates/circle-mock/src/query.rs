@@ -0,0 +1,136 @@
+//! Query-string filters and cursor pagination shared by list endpoints.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
+use serde_json::Value;
+
+use crate::{error::ApiError, state::str_of};
+
+/// Page size when `pageSize` is absent.
+const DEFAULT_PAGE_SIZE: usize = 10;
+/// Largest `pageSize` Circle accepts.
+const MAX_PAGE_SIZE: usize = 50;
+
+/// Parsed query string of a list request.
+#[derive(Debug, Default)]
+pub(crate) struct Params(pub(crate) HashMap<String, String>);
+
+impl Params {
+    /// The value of `key`, `None` if absent or empty.
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str).filter(|value| !value.is_empty())
+    }
+
+    /// Comma-separated values of `key`, e.g. `walletIds`.
+    pub(crate) fn list(&self, key: &str) -> Vec<&str> {
+        self.get(key).map(|value| value.split(',').map(str::trim).collect()).unwrap_or_default()
+    }
+
+    /// `true` if `item` passes the equality filter on `key` (string field
+    /// `field` of the item); absent filters pass everything.
+    pub(crate) fn matches(&self, item: &Value, key: &str, field: &str) -> bool {
+        self.get(key).is_none_or(|expected| str_of(item, field).eq_ignore_ascii_case(expected))
+    }
+
+    /// Applies the `from`/`to` window on `createDate`, orders newest first
+    /// and cuts the page selected by `pageBefore`/`pageAfter`/`pageSize`.
+    pub(crate) fn page(&self, items: Vec<Value>) -> Result<Vec<Value>, ApiError> {
+        let from = self.date("from")?;
+        let to = self.date("to")?;
+        let size = match self.get("pageSize") {
+            Some(size) => size
+                .parse::<usize>()
+                .ok()
+                .filter(|size| (1..=MAX_PAGE_SIZE).contains(size))
+                .ok_or_else(|| ApiError::invalid("pageSize", size))?,
+            None => DEFAULT_PAGE_SIZE,
+        };
+        let mut items: Vec<Value> = items
+            .into_iter()
+            .rev()
+            .filter(|item| {
+                let created = DateTime::parse_from_rfc3339(str_of(item, "createDate")).ok();
+                from.is_none_or(|from| created.is_some_and(|c| c >= from)) &&
+                    to.is_none_or(|to| created.is_some_and(|c| c <= to))
+            })
+            .collect();
+        let position = |cursor: &str, items: &[Value]| {
+            items
+                .iter()
+                .position(|item| str_of(item, "id") == cursor)
+                .ok_or_else(|| ApiError::invalid("page cursor", cursor))
+        };
+        match (self.get("pageBefore"), self.get("pageAfter")) {
+            (Some(_), Some(_)) => Err(ApiError::invalid(
+                "pageBefore",
+                "pageBefore and pageAfter are mutually exclusive",
+            )),
+            (Some(cursor), None) => {
+                let end = position(cursor, &items)?;
+                items.truncate(end);
+                let start = items.len().saturating_sub(size);
+                Ok(items.split_off(start))
+            }
+            (None, Some(cursor)) => {
+                let start = position(cursor, &items)? + 1;
+                Ok(items.into_iter().skip(start).take(size).collect())
+            }
+            (None, None) => {
+                items.truncate(size);
+                Ok(items)
+            }
+        }
+    }
+
+    fn date(&self, key: &str) -> Result<Option<DateTime<FixedOffset>>, ApiError> {
+        self.get(key)
+            .map(|value| {
+                DateTime::parse_from_rfc3339(value).map_err(|_| ApiError::invalid(key, value))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn page(pairs: &[(&str, &str)], items: &[Value]) -> Result<Vec<Value>, crate::Error> {
+        Ok(params(pairs).page(items.to_vec())?)
+    }
+
+    fn params(pairs: &[(&str, &str)]) -> Params {
+        Params(pairs.iter().map(|(k, v)| ((*k).to_string(), (*v).to_string())).collect())
+    }
+
+    fn ids(items: &[Value]) -> Vec<&str> {
+        items.iter().map(|item| str_of(item, "id")).collect()
+    }
+
+    #[test]
+    fn pages_newest_first_with_cursors() -> Result<(), Box<dyn std::error::Error>> {
+        let items: Vec<Value> = (1..=5)
+            .map(
+                |i| json!({ "id": i.to_string(), "createDate": format!("2024-01-0{i}T00:00:00Z") }),
+            )
+            .collect();
+
+        let first = page(&[("pageSize", "2")], &items)?;
+        assert_eq!(ids(&first), ["5", "4"]);
+        let next = page(&[("pageSize", "2"), ("pageAfter", "4")], &items)?;
+        assert_eq!(ids(&next), ["3", "2"]);
+        let previous = page(&[("pageSize", "2"), ("pageBefore", "2")], &items)?;
+        assert_eq!(ids(&previous), ["4", "3"]);
+        let window =
+            page(&[("from", "2024-01-02T00:00:00Z"), ("to", "2024-01-03T00:00:00Z")], &items)?;
+        assert_eq!(ids(&window), ["3", "2"]);
+
+        assert!(params(&[("pageBefore", "2"), ("pageAfter", "4")]).page(items.clone()).is_err());
+        assert!(params(&[("pageSize", "51")]).page(items.clone()).is_err());
+        assert!(params(&[("from", "yesterday")]).page(items).is_err());
+        Ok(())
+    }
+}
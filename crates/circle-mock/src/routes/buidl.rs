@@ -0,0 +1,143 @@
+//! Buidl wallets: indexed transfers, user operations and balances.
+
+use std::collections::HashMap;
+
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::get,
+};
+use circle_core::CircleErrorCode;
+use serde_json::{Value, json};
+
+use super::{Reply, ok};
+use crate::{
+    chain,
+    error::ApiError,
+    query::Params,
+    state::{MockState, Store, by_id, str_of},
+    wallets::known_chain,
+};
+
+pub(super) fn routes() -> Router<MockState> {
+    Router::new()
+        .route("/v1/w3s/buidl/transfers", get(list_transfers))
+        .route("/v1/w3s/buidl/transfers/{id}", get(get_transfer))
+        .route("/v1/w3s/buidl/userOps", get(list_user_ops))
+        .route("/v1/w3s/buidl/userOps/{id}", get(get_user_op))
+        .route("/v1/w3s/buidl/wallets/{id}/balances", get(balances_by_id))
+        .route("/v1/w3s/buidl/wallets/{id}/nfts", get(nfts_by_id))
+        .route("/v1/w3s/buidl/wallets/{blockchain}/{address}/balances", get(balances_by_address))
+        .route("/v1/w3s/buidl/wallets/{blockchain}/{address}/nfts", get(nfts_by_address))
+}
+
+async fn list_transfers(
+    State(state): State<MockState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let addresses = params.list("walletAddresses");
+    if addresses.is_empty() {
+        return Err(ApiError::missing("walletAddresses"));
+    }
+    let store = state.lock();
+    let transfers: Vec<Value> = store
+        .transfers
+        .iter()
+        .filter(|transfer| {
+            addresses
+                .iter()
+                .any(|address| str_of(transfer, "walletAddress").eq_ignore_ascii_case(address)) &&
+                params.matches(transfer, "blockchain", "blockchain") &&
+                params.matches(transfer, "state", "state") &&
+                params.matches(transfer, "transferType", "transferType") &&
+                params.matches(transfer, "txHash", "txHash") &&
+                params.matches(transfer, "userOpHash", "userOpHash")
+        })
+        .cloned()
+        .collect();
+    ok(json!({ "transfers": params.page(transfers)? }))
+}
+
+async fn get_transfer(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let store = state.lock();
+    let transfer = by_id(&store.transfers, &id).ok_or_else(|| {
+        ApiError::status(StatusCode::NOT_FOUND, format!("Cannot find transfer {id}"))
+    })?;
+    ok(json!({ "transfer": transfer }))
+}
+
+async fn list_user_ops(
+    State(state): State<MockState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let senders = params.list("senders");
+    let store = state.lock();
+    let user_ops: Vec<Value> = store
+        .user_ops
+        .iter()
+        .filter(|op| {
+            let sender = str_of(&op["userOperation"], "sender");
+            (senders.is_empty() || senders.iter().any(|s| s.eq_ignore_ascii_case(sender))) &&
+                params.matches(op, "blockchain", "blockchain") &&
+                params.matches(op, "refId", "refId") &&
+                params.matches(op, "state", "state") &&
+                params.matches(op, "txHash", "txHash") &&
+                params.matches(op, "userOpHash", "userOpHash")
+        })
+        .cloned()
+        .collect();
+    ok(json!({ "userOperations": params.page(user_ops)? }))
+}
+
+async fn get_user_op(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let store = state.lock();
+    let user_op = by_id(&store.user_ops, &id).ok_or_else(|| {
+        ApiError::status(StatusCode::NOT_FOUND, format!("Cannot find user operation {id}"))
+    })?;
+    ok(json!({ "userOperation": user_op }))
+}
+
+/// The id of the wallet at `address` on `blockchain`.
+fn wallet_at(store: &Store, blockchain: &str, address: &str) -> Result<String, ApiError> {
+    let chain = known_chain(blockchain)?;
+    if !chain::is_valid_address(chain::family(chain), address) {
+        return Err(ApiError::invalid("address", address));
+    }
+    store
+        .wallet_by_address(blockchain, address)
+        .map(|wallet| str_of(wallet, "id").to_string())
+        .ok_or_else(|| ApiError::not_found(CircleErrorCode::WalletIdNotFound, "wallet", address))
+}
+
+async fn balances_by_id(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let store = state.lock();
+    store.require_wallet(&id)?;
+    ok(json!({ "tokenBalances": store.balances_json(&id) }))
+}
+
+async fn nfts_by_id(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let store = state.lock();
+    store.require_wallet(&id)?;
+    ok(json!({ "nfts": store.nfts_json(&id) }))
+}
+
+async fn balances_by_address(
+    State(state): State<MockState>,
+    Path((blockchain, address)): Path<(String, String)>,
+) -> Reply {
+    let store = state.lock();
+    let id = wallet_at(&store, &blockchain, &address)?;
+    ok(json!({ "tokenBalances": store.balances_json(&id) }))
+}
+
+async fn nfts_by_address(
+    State(state): State<MockState>,
+    Path((blockchain, address)): Path<(String, String)>,
+) -> Reply {
+    let store = state.lock();
+    let id = wallet_at(&store, &blockchain, &address)?;
+    ok(json!({ "nfts": store.nfts_json(&id) }))
+}
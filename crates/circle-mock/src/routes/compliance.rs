@@ -0,0 +1,85 @@
+//! Compliance Engine: address screening.
+//!
+//! Addresses registered with [`crate::MockState::deny_address`] are
+//! `DENIED` by a sanctions rule; everything else is `APPROVED`.
+
+use axum::{Router, body::Bytes, extract::State, http::StatusCode, routing::post};
+use serde_json::{Value, json};
+
+use super::{Reply, idempotent, parse_body, required};
+use crate::{
+    chain,
+    error::ApiError,
+    state::{MockState, new_id, now},
+    transactions,
+};
+
+/// Chains the Compliance Engine screens.
+const CHAINS: [&str; 27] = [
+    "ETH",
+    "ETH-SEPOLIA",
+    "AVAX",
+    "AVAX-FUJI",
+    "MATIC",
+    "MATIC-AMOY",
+    "ALGO",
+    "ATOM",
+    "ARB",
+    "ARB-SEPOLIA",
+    "HBAR",
+    "SOL",
+    "SOL-DEVNET",
+    "UNI",
+    "UNI-SEPOLIA",
+    "TRX",
+    "XLM",
+    "BCH",
+    "BTC",
+    "BSV",
+    "ETC",
+    "LTC",
+    "XMR",
+    "XRP",
+    "ZRX",
+    "OP",
+    "DOT",
+];
+
+pub(super) fn routes() -> axum::Router<MockState> {
+    Router::new().route("/v1/w3s/compliance/screening/addresses", post(screen_address))
+}
+
+async fn screen_address(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let address = required(&body, "address")?;
+    let chain_name = required(&body, "chain")?;
+    if !CHAINS.contains(&chain_name) {
+        return Err(ApiError::invalid("chain", chain_name));
+    }
+    // Chains the mock does not model (BTC, XRP, ...) accept any address.
+    if chain::lookup(chain_name)
+        .is_some_and(|c| !chain::is_valid_address(chain::family(c), address))
+    {
+        return Err(ApiError::invalid("address", address));
+    }
+    idempotent(&mut state.lock(), &body, StatusCode::OK, |store| {
+        let denied = store.is_denied(address);
+        let screening = screening(address, chain_name, denied);
+        store.screenings.push(screening.clone());
+        Ok(screening)
+    })
+}
+
+fn screening(address: &str, chain: &str, denied: bool) -> Value {
+    let timestamp = now();
+    let decision =
+        if denied { transactions::denial(address) } else { json!({ "screeningDate": timestamp }) };
+    json!({
+        "result": if denied { "DENIED" } else { "APPROVED" },
+        "decision": decision,
+        "id": new_id(),
+        "address": address,
+        "chain": chain,
+        "details": [{ "id": new_id(), "vendor": "MOCK", "response": {}, "createDate": timestamp }],
+    })
+}
@@ -0,0 +1,172 @@
+//! The mock's own control API under `/__mock`, for driving a standalone
+//! server from tests written in other languages or from `curl`. It mirrors
+//! [`MockState`] and needs no authentication.
+//!
+//! | Method | Path | Effect |
+//! |--------|------|--------|
+//! | `POST` | `/__mock/reset` | [`MockState::reset`] |
+//! | `POST` | `/__mock/faults` | [`MockState::inject`] (see below) |
+//! | `DELETE` | `/__mock/faults` | [`MockState::clear_faults`] |
+//! | `GET` | `/__mock/requests` | [`MockState::requests`] |
+//! | `POST` | `/__mock/wallets` | [`MockState::create_wallet`], body `{blockchain}` |
+//! | `PUT` | `/__mock/wallets/{id}/balances/{tokenId}` | [`MockState::set_balance`], body `{amount}` |
+//! | `POST` | `/__mock/transactions/{id}/advance` | [`MockState::advance_transaction`] |
+//! | `PUT` | `/__mock/transactions/{id}/state` | [`MockState::set_transaction_state`], body `{state}` |
+//! | `POST` | `/__mock/challenges/{id}/complete` | [`MockState::complete_challenge`] |
+//! | `POST` | `/__mock/challenges/{id}/fail` | [`MockState::fail_challenge`], body `{code, message}` |
+//! | `POST` | `/__mock/addresses/denied` | [`MockState::deny_address`], body `{address}` |
+//!
+//! A fault is described as `{status, method?, path?, code?, message?,
+//! headers?: {name: value}, delayMs?, times?}`; without `times` it fires
+//! once, with `times: null` forever.
+
+use std::time::Duration;
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::{Path, State},
+    http::{Method, StatusCode},
+    response::IntoResponse,
+    routing::{get, post, put},
+};
+use serde_json::{Value, json};
+
+use super::{Reply, ok, parse_body, required};
+use crate::{
+    error::ApiError,
+    fault::Fault,
+    state::{MockState, str_of},
+};
+
+pub(super) fn routes() -> Router<MockState> {
+    Router::new()
+        .route("/__mock/reset", post(reset))
+        .route("/__mock/faults", post(inject).delete(clear_faults))
+        .route("/__mock/requests", get(requests))
+        .route("/__mock/wallets", post(create_wallet))
+        .route("/__mock/wallets/{id}/balances/{token_id}", put(set_balance))
+        .route("/__mock/transactions/{id}/advance", post(advance_transaction))
+        .route("/__mock/transactions/{id}/state", put(set_transaction_state))
+        .route("/__mock/challenges/{id}/complete", post(complete_challenge))
+        .route("/__mock/challenges/{id}/fail", post(fail_challenge))
+        .route("/__mock/addresses/denied", post(deny_address))
+}
+
+async fn reset(State(state): State<MockState>) -> StatusCode {
+    state.reset();
+    StatusCode::NO_CONTENT
+}
+
+async fn inject(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let status = body
+        .get("status")
+        .and_then(Value::as_u64)
+        .and_then(|status| u16::try_from(status).ok())
+        .ok_or_else(|| ApiError::missing("status"))?;
+    let mut fault = Fault::new(status);
+    if let Some(method) = body.get("method").and_then(Value::as_str) {
+        let method = Method::from_bytes(method.as_bytes())
+            .map_err(|_| ApiError::invalid("method", method))?;
+        fault = fault.method(method);
+    }
+    if let Some(path) = body.get("path").and_then(Value::as_str) {
+        fault = fault.path(path);
+    }
+    if let Some(code) = body.get("code").and_then(Value::as_i64) {
+        fault = fault.code(i32::try_from(code).map_err(|_| ApiError::invalid("code", code))?);
+    }
+    if let Some(message) = body.get("message").and_then(Value::as_str) {
+        fault = fault.message(message);
+    }
+    if let Some(headers) = body.get("headers").and_then(Value::as_object) {
+        for (name, value) in headers {
+            fault = fault.header(name, value.as_str().unwrap_or_default());
+        }
+    }
+    if let Some(delay) = body.get("delayMs").and_then(Value::as_u64) {
+        fault = fault.delay(Duration::from_millis(delay));
+    }
+    fault = match body.get("times") {
+        None => fault,
+        Some(Value::Null) => fault.always(),
+        Some(times) => fault.times(
+            times
+                .as_u64()
+                .and_then(|t| usize::try_from(t).ok())
+                .ok_or_else(|| ApiError::invalid("times", times))?,
+        ),
+    };
+    state.inject(fault);
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn clear_faults(State(state): State<MockState>) -> StatusCode {
+    state.clear_faults();
+    StatusCode::NO_CONTENT
+}
+
+async fn requests(State(state): State<MockState>) -> Reply {
+    let requests: Vec<String> =
+        state.requests().iter().map(|(method, path)| format!("{method} {path}")).collect();
+    ok(json!({ "requests": requests }))
+}
+
+async fn create_wallet(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let wallet = state.lock().create_developer_wallet(required(&body, "blockchain")?)?;
+    ok(json!({ "wallet": wallet }))
+}
+
+async fn set_balance(
+    State(state): State<MockState>,
+    Path((id, token_id)): Path<(String, String)>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    state.lock().set_balance(&id, &token_id, required(&body, "amount")?)?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn advance_transaction(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let state = state.lock().advance_transaction(&id)?;
+    ok(json!({ "state": state }))
+}
+
+async fn set_transaction_state(
+    State(state): State<MockState>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    state.lock().set_transaction_state(&id, required(&body, "state")?)?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn complete_challenge(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let ids = state.lock().complete_challenge(&id)?;
+    ok(json!({ "correlationIds": ids }))
+}
+
+async fn fail_challenge(
+    State(state): State<MockState>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    let code = body
+        .get("code")
+        .and_then(Value::as_i64)
+        .and_then(|code| i32::try_from(code).ok())
+        .ok_or_else(|| ApiError::missing("code"))?;
+    let message = str_of(&body, "message");
+    state.lock().fail_challenge(&id, code, message)?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn deny_address(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    state.deny_address(required(&body, "address")?);
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
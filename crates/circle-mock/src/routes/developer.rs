@@ -0,0 +1,337 @@
+//! Developer-controlled wallets: entity configuration, wallet sets, wallet
+//! creation and derivation, signing and transactions.
+
+use std::collections::HashMap;
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post, put},
+};
+use circle_core::CircleErrorCode;
+use serde_json::{Value, json};
+
+use super::{
+    Reply, idempotent, ok, parse_body, require_ciphertext, required, signature, wallet_spec,
+    with_status,
+};
+use crate::{
+    chain::{self, Family},
+    error::ApiError,
+    query::Params,
+    state::{MockState, by_id_mut, now, set, str_of, token_amount},
+    wallets::known_chain,
+};
+
+pub(super) fn routes() -> Router<MockState> {
+    Router::new()
+        .route("/v1/w3s/config/entity/publicKey", get(entity_public_key))
+        .route("/v1/w3s/config/entity/entitySecret", post(register_entity_secret))
+        .route("/v1/w3s/walletSets", get(list_wallet_sets))
+        .route("/v1/w3s/walletSets/{id}", get(get_wallet_set))
+        .route("/v1/w3s/developer/walletSets", post(create_wallet_set))
+        .route("/v1/w3s/developer/walletSets/{id}", put(update_wallet_set))
+        .route("/v1/w3s/developer/wallets", post(create_wallets))
+        .route("/v1/w3s/developer/wallets/balances", get(list_wallets_with_balances))
+        .route("/v1/w3s/developer/wallets/derive", put(derive_wallet_by_address))
+        .route("/v1/w3s/developer/wallets/{id}/blockchains/{blockchain}", put(derive_wallet))
+        .route("/v1/w3s/developer/sign/message", post(sign_message))
+        .route("/v1/w3s/developer/sign/typedData", post(sign_typed_data))
+        .route("/v1/w3s/developer/sign/transaction", post(sign_transaction))
+        .route("/v1/w3s/developer/sign/delegateAction", post(sign_delegate_action))
+        .route("/v1/w3s/developer/transactions/feeParameters", get(fee_parameters))
+        .route("/v1/w3s/developer/transactions/transfer", post(create_transfer))
+        .route("/v1/w3s/developer/transactions/contractExecution", post(create_contract_execution))
+        .route("/v1/w3s/developer/transactions/walletUpgrade", post(create_wallet_upgrade))
+        .route("/v1/w3s/developer/transactions/{id}/cancel", post(cancel_transaction))
+        .route("/v1/w3s/developer/transactions/{id}/accelerate", post(accelerate_transaction))
+}
+
+// ── Entity configuration ──────────────────────────────────────────────────────
+
+/// Recovery file content returned by every entity secret registration.
+const RECOVERY_FILE: &str = "Y2lyY2xlLW1vY2sgcmVjb3ZlcnkgZmlsZQ==";
+
+async fn entity_public_key() -> Reply {
+    ok(json!({ "publicKey": crate::ENTITY_PUBLIC_KEY }))
+}
+
+async fn register_entity_secret(body: Bytes) -> Reply {
+    require_ciphertext(&parse_body(&body)?)?;
+    ok(json!({ "recoveryFile": RECOVERY_FILE }))
+}
+
+// ── Wallet sets ───────────────────────────────────────────────────────────────
+
+async fn list_wallet_sets(
+    State(state): State<MockState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let store = state.lock();
+    let wallet_sets = Params(query).page(store.wallet_sets.clone())?;
+    ok(json!({ "walletSets": wallet_sets }))
+}
+
+async fn get_wallet_set(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let store = state.lock();
+    let wallet_set = store.wallet_set(&id).ok_or_else(|| {
+        ApiError::not_found(CircleErrorCode::WalletSetIdNotFound, "wallet set", &id)
+    })?;
+    ok(json!({ "walletSet": wallet_set }))
+}
+
+async fn create_wallet_set(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    let name = body.get("name").and_then(Value::as_str);
+    idempotent(&mut state.lock(), &body, StatusCode::CREATED, |store| {
+        Ok(json!({ "walletSet": store.create_wallet_set(name, None) }))
+    })
+}
+
+async fn update_wallet_set(
+    State(state): State<MockState>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    let name = required(&body, "name")?;
+    let mut store = state.lock();
+    let wallet_set = by_id_mut(&mut store.wallet_sets, &id).ok_or_else(|| {
+        ApiError::not_found(CircleErrorCode::WalletSetIdNotFound, "wallet set", &id)
+    })?;
+    set(wallet_set, "name", name);
+    set(wallet_set, "updateDate", now());
+    ok(json!({ "walletSet": wallet_set }))
+}
+
+// ── Wallets ───────────────────────────────────────────────────────────────────
+
+async fn create_wallets(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    let wallet_set_id = required(&body, "walletSetId")?;
+    let spec = wallet_spec(&body)?;
+    let mut store = state.lock();
+    let custody = store
+        .wallet_set(wallet_set_id)
+        .map(|wallet_set| str_of(wallet_set, "custodyType").to_string())
+        .ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::WalletSetIdNotFound, "wallet set", wallet_set_id)
+        })?;
+    if custody != "DEVELOPER" {
+        return Err(ApiError::invalid("walletSetId", "not a developer-controlled wallet set"));
+    }
+    idempotent(&mut store, &body, StatusCode::CREATED, |store| {
+        Ok(json!({ "wallets": store.create_wallets(wallet_set_id, &spec, None) }))
+    })
+}
+
+async fn list_wallets_with_balances(
+    State(state): State<MockState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let blockchain = params.get("blockchain").ok_or_else(|| ApiError::missing("blockchain"))?;
+    known_chain(blockchain)?;
+    let store = state.lock();
+    let minimum = params.get("amountGte");
+    let wallets = store
+        .wallets
+        .iter()
+        .filter(|wallet| {
+            str_of(wallet, "blockchain") == blockchain &&
+                str_of(wallet, "custodyType") == "DEVELOPER" &&
+                params.matches(wallet, "address", "address") &&
+                params.matches(wallet, "walletSetId", "walletSetId") &&
+                params.matches(wallet, "refId", "refId") &&
+                params.matches(wallet, "scaCore", "scaCore")
+        })
+        .filter_map(|wallet| {
+            let balances: Vec<Value> = store
+                .balances_json(str_of(wallet, "id"))
+                .into_iter()
+                .filter(|balance| {
+                    params.matches(&balance["token"], "tokenAddress", "tokenAddress") &&
+                        minimum.is_none_or(|minimum| at_least(balance, minimum))
+                })
+                .collect();
+            if minimum.is_some() && balances.is_empty() {
+                return None;
+            }
+            let mut wallet = wallet.clone();
+            set(&mut wallet, "tokenBalances", balances);
+            Some(wallet)
+        })
+        .collect();
+    ok(json!({ "wallets": params.page(wallets)? }))
+}
+
+/// `true` if a balance is at least the decimal `minimum`.
+fn at_least(balance: &Value, minimum: &str) -> bool {
+    let decimals = balance["token"]["decimals"].as_u64().map_or(0, |d| d as u32);
+    let parse = |amount: &str| token_amount(amount, decimals);
+    match (parse(str_of(balance, "amount")), parse(minimum)) {
+        (Some(amount), Some(minimum)) => amount >= minimum,
+        _ => false,
+    }
+}
+
+async fn derive_wallet(
+    State(state): State<MockState>,
+    Path((id, blockchain)): Path<(String, String)>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    let mut store = state.lock();
+    let source = store.require_wallet(&id)?.clone();
+    let (created, wallet) = store.derive_wallet(&source, &blockchain, body.get("metadata"))?;
+    Ok(derived(created, &wallet))
+}
+
+async fn derive_wallet_by_address(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let source_blockchain = required(&body, "sourceBlockchain")?;
+    let address = required(&body, "walletAddress")?;
+    let target = required(&body, "targetBlockchain")?;
+    known_chain(source_blockchain)?;
+    let mut store = state.lock();
+    let source = store
+        .wallet_by_address(source_blockchain, address)
+        .ok_or_else(|| ApiError::not_found(CircleErrorCode::WalletIdNotFound, "wallet", address))?
+        .clone();
+    let (created, wallet) = store.derive_wallet(&source, target, body.get("metadata"))?;
+    Ok(derived(created, &wallet))
+}
+
+/// `201` for a newly derived wallet, `200` for one that already existed.
+fn derived(created: bool, wallet: &Value) -> axum::response::Response {
+    let status = if created { StatusCode::CREATED } else { StatusCode::OK };
+    with_status(status, json!({ "wallet": wallet }))
+}
+
+// ── Signing ───────────────────────────────────────────────────────────────────
+
+/// Validates a signing request and returns the blockchain of its wallet.
+fn signing_wallet(state: &MockState, body: &Value, payload: &[&str]) -> Result<String, ApiError> {
+    require_ciphertext(body)?;
+    if payload.iter().all(|key| body.get(*key).is_none_or(Value::is_null)) {
+        return Err(ApiError::missing(payload[0]));
+    }
+    let wallet = state.lock().source_wallet(body, None)?;
+    Ok(str_of(&wallet, "blockchain").to_string())
+}
+
+async fn sign_message(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let blockchain = signing_wallet(&state, &body, &["message"])?;
+    ok(json!({ "signature": signature(&blockchain) }))
+}
+
+async fn sign_typed_data(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let blockchain = signing_wallet(&state, &body, &["data"])?;
+    ok(json!({ "signature": signature(&blockchain) }))
+}
+
+async fn sign_transaction(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let blockchain = signing_wallet(&state, &body, &["rawTransaction", "transaction"])?;
+    let mut data = json!({
+        "signature": signature(&blockchain),
+        "signedTransaction": format!("0x{}", chain::random_hex(110)),
+    });
+    if chain::lookup(&blockchain).is_some_and(|c| chain::family(c) != Family::Solana) {
+        set(&mut data, "txHash", format!("0x{}", chain::random_hex(32)));
+    }
+    ok(data)
+}
+
+async fn sign_delegate_action(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let blockchain = signing_wallet(&state, &body, &["unsignedDelegateAction"])?;
+    if chain::lookup(&blockchain).is_none_or(|c| chain::family(c) != Family::Near) {
+        return Err(ApiError::rejected(
+            CircleErrorCode::OperationNotSupported,
+            "Delegate actions can only be signed by NEAR wallets.",
+        ));
+    }
+    ok(json!({
+        "signature": signature(&blockchain),
+        "signedDelegateAction": required(&body, "unsignedDelegateAction")?,
+    }))
+}
+
+// ── Transactions ──────────────────────────────────────────────────────────────
+
+async fn fee_parameters(Query(query): Query<HashMap<String, String>>) -> Reply {
+    let params = Params(query);
+    let blockchain = params.get("blockchain").ok_or_else(|| ApiError::missing("blockchain"))?;
+    let estimate = crate::transactions::fee_estimate(known_chain(blockchain)?.as_str());
+    let level = |name: &str| {
+        let mut level = json!({});
+        for key in ["maxFee", "priorityFee", "baseFee"] {
+            if let Some(value) = estimate[name].get(key) {
+                set(&mut level, key, value.clone());
+            }
+        }
+        level
+    };
+    ok(json!({ "low": level("low"), "medium": level("medium"), "high": level("high") }))
+}
+
+async fn create_transfer(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    idempotent(&mut state.lock(), &body, StatusCode::CREATED, |store| {
+        let draft = store.prepare_transfer(&body, None)?;
+        Ok(created(&store.insert_transaction(draft)))
+    })
+}
+
+async fn create_contract_execution(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    idempotent(&mut state.lock(), &body, StatusCode::CREATED, |store| {
+        let draft = store.prepare_contract_execution(&body, None)?;
+        Ok(created(&store.insert_transaction(draft)))
+    })
+}
+
+async fn create_wallet_upgrade(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    idempotent(&mut state.lock(), &body, StatusCode::CREATED, |store| {
+        let draft = store.prepare_wallet_upgrade(&body, None)?;
+        Ok(created(&store.insert_transaction(draft)))
+    })
+}
+
+/// The `{id, state}` answer to a transaction creation.
+fn created(transaction: &Value) -> Value {
+    json!({ "id": str_of(transaction, "id"), "state": str_of(transaction, "state") })
+}
+
+async fn cancel_transaction(
+    State(state): State<MockState>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    let transaction = state.lock().cancel_transaction(&id)?;
+    ok(created(&transaction))
+}
+
+async fn accelerate_transaction(
+    State(state): State<MockState>,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    require_ciphertext(&body)?;
+    let transaction = state.lock().accelerate_transaction(&id)?;
+    ok(json!({ "id": str_of(&transaction, "id") }))
+}
@@ -0,0 +1,290 @@
+//! HTTP routes, one module per API surface.
+//!
+//! | Module | Paths |
+//! |--------|-------|
+//! | [`developer`] | `/v1/w3s/developer/*`, `/v1/w3s/walletSets*`, `/v1/w3s/config/entity/*` |
+//! | [`user`] | `/v1/w3s/users*`, `/v1/w3s/user/*` |
+//! | [`shared`] | `/v1/w3s/wallets*`, `/v1/w3s/transactions*`, `/v1/w3s/tokens/*` |
+//! | [`buidl`] | `/v1/w3s/buidl/*` |
+//! | [`compliance`] | `/v1/w3s/compliance/*` |
+//! | [`control`] | `/__mock/*`, the mock's own control API |
+//!
+//! Every API route sits behind [`gate`], which records the request, fires
+//! injected faults and checks the bearer token. Handlers parse their JSON
+//! body themselves so malformed input gets Circle's `{code, message}` error
+//! rather than axum's plain-text rejection.
+
+mod buidl;
+mod compliance;
+mod control;
+mod developer;
+mod shared;
+mod user;
+
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use circle_core::CircleErrorCode;
+use serde_json::{Value, json};
+
+use crate::{
+    chain::{self, Family},
+    error::ApiError,
+    fault::Fault,
+    state::{MockState, Store, WalletSpec, str_of},
+    wallets::known_chain,
+};
+
+/// Result of a handler: a response or a Circle error.
+pub(crate) type Reply = Result<Response, ApiError>;
+
+/// Largest `count` of a wallet creation request.
+const MAX_WALLET_COUNT: u64 = 200;
+
+/// Builds the router serving every API plus the control API.
+pub(crate) fn router(state: MockState) -> Router {
+    Router::new()
+        .merge(developer::routes())
+        .merge(user::routes())
+        .merge(shared::routes())
+        .merge(buidl::routes())
+        .merge(compliance::routes())
+        .route_layer(middleware::from_fn_with_state(state.clone(), gate))
+        .merge(control::routes())
+        .fallback(not_found)
+        .method_not_allowed_fallback(method_not_allowed)
+        .with_state(state)
+}
+
+/// Records the request, answers with the first matching fault, then
+/// requires a bearer token.
+async fn gate(State(state): State<MockState>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    tracing::debug!(%method, %path, "circle-mock request");
+    let fault = {
+        let mut store = state.lock();
+        store.requests.push((method.clone(), path.clone()));
+        take_fault(&mut store, &method, &path)
+    };
+    if let Some(fault) = fault {
+        if let Some(delay) = fault.pending_delay() {
+            tokio::time::sleep(delay).await;
+        }
+        return fault.response();
+    }
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|key| !key.trim().is_empty());
+    if !authorized {
+        return ApiError::status(
+            StatusCode::UNAUTHORIZED,
+            "Malformed authorization. Are the credentials properly encoded?",
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
+
+/// Consumes one use of the first fault matching the request.
+fn take_fault(store: &mut Store, method: &axum::http::Method, path: &str) -> Option<Fault> {
+    let index = store.faults.iter().position(|fault| fault.matches(method, path))?;
+    let fault = store.faults[index].clone();
+    if store.faults[index].consume() {
+        store.faults.remove(index);
+    }
+    Some(fault)
+}
+
+async fn not_found() -> ApiError {
+    ApiError::status(StatusCode::NOT_FOUND, "Resource not found.")
+}
+
+async fn method_not_allowed() -> ApiError {
+    ApiError::status(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed for this resource.")
+}
+
+// ── Request helpers ───────────────────────────────────────────────────────────
+
+/// Parses a JSON object body; an empty body is an empty object.
+pub(crate) fn parse_body(body: &Bytes) -> Result<Value, ApiError> {
+    if body.is_empty() {
+        return Ok(json!({}));
+    }
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|e| ApiError::invalid("request body", format!("malformed JSON: {e}")))?;
+    if !value.is_object() {
+        return Err(ApiError::invalid("request body", "expected a JSON object"));
+    }
+    Ok(value)
+}
+
+/// The non-empty string field `key`, or a `400` naming it.
+pub(crate) fn required<'a>(body: &'a Value, key: &str) -> Result<&'a str, ApiError> {
+    match body.get(key) {
+        None | Some(Value::Null) => Err(ApiError::missing(key)),
+        Some(Value::String(value)) if !value.is_empty() => Ok(value),
+        Some(other) => Err(ApiError::invalid(key, other)),
+    }
+}
+
+/// Developer-controlled mutations need an entity secret ciphertext. The mock
+/// cannot decrypt it, so any non-empty value is accepted.
+pub(crate) fn require_ciphertext(body: &Value) -> Result<(), ApiError> {
+    required(body, "entitySecretCiphertext").map(|_| ())
+}
+
+/// The user an `X-User-Token` header was issued to, `None` without the
+/// header.
+pub(crate) fn token_user(store: &Store, headers: &HeaderMap) -> Result<Option<String>, ApiError> {
+    let Some(token) = headers.get("X-User-Token") else {
+        return Ok(None);
+    };
+    token
+        .to_str()
+        .ok()
+        .and_then(|token| store.user_for_token(token))
+        .map(|user_id| Some(user_id.to_string()))
+        .ok_or_else(|| {
+            ApiError::unauthorized(
+                CircleErrorCode::InvalidUserToken,
+                "The user token is invalid or expired.",
+            )
+        })
+}
+
+/// Like [`token_user`], for endpoints that require the header.
+pub(crate) fn require_token_user(store: &Store, headers: &HeaderMap) -> Result<String, ApiError> {
+    token_user(store, headers)?.ok_or_else(|| ApiError::missing("X-User-Token"))
+}
+
+// ── Responses ─────────────────────────────────────────────────────────────────
+
+/// `200` with Circle's `{"data": …}` envelope.
+pub(crate) fn ok(data: Value) -> Reply {
+    Ok(with_status(StatusCode::OK, data))
+}
+
+pub(crate) fn with_status(status: StatusCode, data: Value) -> Response {
+    (status, Json(json!({ "data": data }))).into_response()
+}
+
+/// Runs `create` once per `idempotencyKey` and answers with `status`; a
+/// replay of the same request gets the stored body with `200`, a different
+/// request under the same key a `409`.
+///
+/// The entity secret ciphertext is left out of the comparison: it must be
+/// fresh on every request, retries included.
+pub(crate) fn idempotent(
+    store: &mut Store,
+    body: &Value,
+    status: StatusCode,
+    create: impl FnOnce(&mut Store) -> Result<Value, ApiError>,
+) -> Reply {
+    let key = required(body, "idempotencyKey")?;
+    if uuid::Uuid::parse_str(key).is_err() {
+        return Err(ApiError::invalid("idempotencyKey", "must be a UUID"));
+    }
+    let mut fingerprint = body.clone();
+    if let Some(object) = fingerprint.as_object_mut() {
+        object.remove("entitySecretCiphertext");
+    }
+    if let Some(replay) = store.replay(key, &fingerprint) {
+        return Ok((StatusCode::OK, Json(replay?)).into_response());
+    }
+    let response = json!({ "data": create(store)? });
+    store.idempotency.insert(key.to_string(), (fingerprint, response.clone()));
+    Ok((status, Json(response)).into_response())
+}
+
+// ── Shared request parsing ────────────────────────────────────────────────────
+
+/// Parses the `blockchains`, `accountType`, `count` and `metadata` of a
+/// wallet creation request.
+pub(crate) fn wallet_spec(body: &Value) -> Result<WalletSpec, ApiError> {
+    let names = body
+        .get("blockchains")
+        .and_then(Value::as_array)
+        .filter(|names| !names.is_empty())
+        .ok_or_else(|| ApiError::missing("blockchains"))?;
+    let blockchains = names
+        .iter()
+        .map(|name| known_chain(name.as_str().unwrap_or_default()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let account_type = body.get("accountType").and_then(Value::as_str).unwrap_or("EOA");
+    match account_type {
+        "EOA" => {}
+        "SCA"
+            if blockchains
+                .iter()
+                .all(|chain| chain.is_evm() && chain.native_currency().is_some()) => {}
+        "SCA" => {
+            return Err(ApiError::invalid(
+                "accountType",
+                "SCA wallets are only available on EVM chains",
+            ))
+        }
+        other => return Err(ApiError::invalid("accountType", other)),
+    }
+    let count = match body.get("count") {
+        None => 1,
+        Some(count) => count
+            .as_u64()
+            .filter(|count| (1..=MAX_WALLET_COUNT).contains(count))
+            .ok_or_else(|| ApiError::invalid("count", count))?,
+    };
+    let metadata = body.get("metadata").and_then(Value::as_array).cloned().unwrap_or_default();
+    if !metadata.is_empty() && metadata.len() as u64 != count {
+        return Err(ApiError::invalid("metadata", "must have one entry per wallet"));
+    }
+    Ok(WalletSpec {
+        blockchains,
+        account_type: account_type.to_string(),
+        count: count as usize,
+        metadata,
+    })
+}
+
+/// A random signature in the format of `blockchain`.
+pub(crate) fn signature(blockchain: &str) -> String {
+    match chain::lookup(blockchain).map_or(Family::Evm, chain::family) {
+        Family::Evm => format!("0x{}", chain::random_hex(65)),
+        Family::Aptos => format!("0x{}", chain::random_hex(64)),
+        Family::Solana | Family::Near => {
+            format!(
+                "{}{}",
+                chain::random_address(Family::Solana),
+                chain::random_address(Family::Solana)
+            )
+        }
+    }
+}
+
+/// The fee estimate of a transfer or contract execution request, whose
+/// blockchain comes from `blockchain`, the wallet or the token.
+pub(crate) fn estimate(store: &Store, body: &Value) -> Reply {
+    if let Some(blockchain) = body.get("blockchain").and_then(Value::as_str) {
+        return ok(crate::transactions::fee_estimate(known_chain(blockchain)?.as_str()));
+    }
+    let from_wallet = body
+        .get("walletId")
+        .and_then(Value::as_str)
+        .map(|id| store.require_wallet(id).map(|w| str_of(w, "blockchain").to_string()))
+        .transpose()?;
+    let from_token = body
+        .get("tokenId")
+        .and_then(Value::as_str)
+        .and_then(|id| store.token(id))
+        .map(|token| str_of(token, "blockchain").to_string());
+    let name = from_wallet.or(from_token).ok_or_else(|| ApiError::missing("blockchain"))?;
+    let blockchain = known_chain(&name)?.as_str();
+    ok(crate::transactions::fee_estimate(blockchain))
+}
@@ -0,0 +1,273 @@
+//! Endpoints shared by developer- and user-controlled wallets. With an
+//! `X-User-Token` header they only see the user's resources.
+
+use std::collections::HashMap;
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+};
+use circle_core::CircleErrorCode;
+use serde_json::{Value, json};
+
+use super::{Reply, estimate, ok, parse_body, required, token_user};
+use crate::{
+    chain,
+    error::ApiError,
+    query::Params,
+    state::{MockState, Store, now, set, str_of},
+    transactions::{fee_estimate, is_terminal},
+    wallets::known_chain,
+};
+
+pub(super) fn routes() -> Router<MockState> {
+    Router::new()
+        .route("/v1/w3s/wallets", get(list_wallets))
+        .route("/v1/w3s/wallets/{id}", get(get_wallet).put(update_wallet))
+        .route("/v1/w3s/wallets/{id}/balances", get(list_balances))
+        .route("/v1/w3s/wallets/{id}/nfts", get(list_nfts))
+        .route("/v1/w3s/transactions", get(list_transactions))
+        .route("/v1/w3s/transactions/{id}", get(get_transaction))
+        .route("/v1/w3s/transactions/lowestNonceTransaction", get(lowest_nonce_transaction))
+        .route("/v1/w3s/transactions/transfer/estimateFee", post(estimate_transfer_fee))
+        .route(
+            "/v1/w3s/transactions/contractExecution/estimateFee",
+            post(estimate_contract_execution_fee),
+        )
+        .route("/v1/w3s/transactions/validateAddress", post(validate_address))
+        .route("/v1/w3s/tokens/{id}", get(get_token))
+}
+
+/// `true` if `resource` is visible to the caller: everything for the
+/// developer, only their own resources for a user.
+fn visible(resource: &Value, user_id: Option<&str>) -> bool {
+    user_id.is_none_or(|user_id| str_of(resource, "userId") == user_id)
+}
+
+/// The wallet `id`, if visible to the caller.
+fn scoped_wallet<'a>(
+    store: &'a Store,
+    headers: &HeaderMap,
+    id: &str,
+) -> Result<&'a Value, ApiError> {
+    let user_id = token_user(store, headers)?;
+    store
+        .wallet(id)
+        .filter(|wallet| visible(wallet, user_id.as_deref()))
+        .ok_or_else(|| ApiError::not_found(CircleErrorCode::WalletIdNotFound, "wallet", id))
+}
+
+// ── Wallets ───────────────────────────────────────────────────────────────────
+
+async fn list_wallets(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let store = state.lock();
+    let user_id = token_user(&store, &headers)?;
+    let wallets: Vec<Value> = store
+        .wallets
+        .iter()
+        .filter(|wallet| {
+            visible(wallet, user_id.as_deref()) &&
+                params.matches(wallet, "address", "address") &&
+                params.matches(wallet, "blockchain", "blockchain") &&
+                params.matches(wallet, "walletSetId", "walletSetId") &&
+                params.matches(wallet, "refId", "refId") &&
+                params.matches(wallet, "scaCore", "scaCore") &&
+                params.matches(wallet, "userId", "userId")
+        })
+        .cloned()
+        .collect();
+    ok(json!({ "wallets": params.page(wallets)? }))
+}
+
+async fn get_wallet(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Reply {
+    let store = state.lock();
+    ok(json!({ "wallet": scoped_wallet(&store, &headers, &id)? }))
+}
+
+async fn update_wallet(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    let mut store = state.lock();
+    scoped_wallet(&store, &headers, &id)?;
+    let wallet = store
+        .wallet_mut(&id)
+        .ok_or_else(|| ApiError::not_found(CircleErrorCode::WalletIdNotFound, "wallet", &id))?;
+    for key in ["name", "refId"] {
+        if let Some(value) = body.get(key).filter(|value| value.is_string()) {
+            set(wallet, key, value.clone());
+        }
+    }
+    set(wallet, "updateDate", now());
+    ok(json!({ "wallet": wallet }))
+}
+
+/// Applies the token filters of the balance and NFT endpoints.
+fn token_filter(params: &Params, holding: &Value) -> bool {
+    let token = &holding["token"];
+    params.matches(token, "tokenAddress", "tokenAddress") &&
+        params.matches(token, "standard", "standard") &&
+        params.get("name").is_none_or(|name| str_of(token, "name").contains(name))
+}
+
+async fn list_balances(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let store = state.lock();
+    scoped_wallet(&store, &headers, &id)?;
+    let balances: Vec<Value> = store
+        .balances_json(&id)
+        .into_iter()
+        .filter(|balance| token_filter(&params, balance))
+        .collect();
+    ok(json!({ "tokenBalances": balances }))
+}
+
+async fn list_nfts(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let store = state.lock();
+    scoped_wallet(&store, &headers, &id)?;
+    let nfts: Vec<Value> =
+        store.nfts_json(&id).into_iter().filter(|nft| token_filter(&params, nft)).collect();
+    ok(json!({ "nfts": nfts }))
+}
+
+// ── Transactions ──────────────────────────────────────────────────────────────
+
+async fn list_transactions(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let store = state.lock();
+    let user_id = token_user(&store, &headers)?;
+    let wallet_ids = params.list("walletIds");
+    let transactions: Vec<Value> = store
+        .transactions
+        .iter()
+        .filter(|tx| {
+            visible(tx, user_id.as_deref()) &&
+                (wallet_ids.is_empty() || wallet_ids.contains(&str_of(tx, "walletId"))) &&
+                params.matches(tx, "blockchain", "blockchain") &&
+                params.matches(tx, "custodyType", "custodyType") &&
+                params.matches(tx, "destinationAddress", "destinationAddress") &&
+                params.matches(tx, "operation", "operation") &&
+                params.matches(tx, "refId", "refId") &&
+                params.matches(tx, "sourceAddress", "sourceAddress") &&
+                params.matches(tx, "state", "state") &&
+                params.matches(tx, "transactionType", "transactionType") &&
+                params.matches(tx, "txHash", "txHash")
+        })
+        .cloned()
+        .collect();
+    ok(json!({ "transactions": params.page(transactions)? }))
+}
+
+/// Returns the transaction, first advancing it one state when auto-advance
+/// is on, so a polling client sees it progress.
+async fn get_transaction(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Reply {
+    let mut store = state.lock();
+    let user_id = token_user(&store, &headers)?;
+    let transaction = store
+        .transaction(&id)
+        .filter(|transaction| visible(transaction, user_id.as_deref()))
+        .ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TransactionIdNotFound, "transaction", &id)
+        })?;
+    let outbound = str_of(transaction, "transactionType") == "OUTBOUND";
+    if store.auto_advance && outbound && !is_terminal(str_of(transaction, "state")) {
+        store.advance_transaction(&id)?;
+    }
+    ok(json!({ "transaction": store.require_transaction(&id)? }))
+}
+
+async fn lowest_nonce_transaction(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let store = state.lock();
+    let user_id = token_user(&store, &headers)?;
+    if params.get("walletId").is_none() && params.get("address").is_none() {
+        return Err(ApiError::missing("walletId"));
+    }
+    let wallet_ids: Vec<&str> = store
+        .wallets
+        .iter()
+        .filter(|wallet| {
+            visible(wallet, user_id.as_deref()) &&
+                params.get("walletId").is_none_or(|id| str_of(wallet, "id") == id) &&
+                params.matches(wallet, "address", "address") &&
+                params.matches(wallet, "blockchain", "blockchain")
+        })
+        .map(|wallet| str_of(wallet, "id"))
+        .collect();
+    let Some(transaction) = store.lowest_nonce_transaction(&wallet_ids) else {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    };
+    let high = fee_estimate(str_of(transaction, "blockchain"))["high"].clone();
+    ok(json!({
+        "transaction": transaction,
+        "feeInfo": { "newHighEstimatedFee": high, "feeDifferenceAmount": "0.000001" },
+    }))
+}
+
+async fn estimate_transfer_fee(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    required(&body, "destinationAddress")?;
+    estimate(&state.lock(), &body)
+}
+
+async fn estimate_contract_execution_fee(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    required(&body, "contractAddress")?;
+    estimate(&state.lock(), &body)
+}
+
+async fn validate_address(body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let address = required(&body, "address")?;
+    let chain = known_chain(required(&body, "blockchain")?)?;
+    ok(json!({ "isValid": chain::is_valid_address(chain::family(chain), address) }))
+}
+
+// ── Tokens ────────────────────────────────────────────────────────────────────
+
+async fn get_token(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    let store = state.lock();
+    let token = store
+        .token(&id)
+        .ok_or_else(|| ApiError::not_found(CircleErrorCode::TokenIdNotFound, "token", &id))?;
+    ok(json!({ "token": token }))
+}
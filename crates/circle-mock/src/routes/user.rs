@@ -0,0 +1,322 @@
+//! User-controlled wallets: users, tokens, PINs and the challenges behind
+//! every user-approved operation.
+
+use std::collections::HashMap;
+
+use axum::{
+    Router,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+};
+use circle_core::CircleErrorCode;
+use serde_json::{Value, json};
+
+use super::{
+    Reply, idempotent, ok, parse_body, require_token_user, required, wallet_spec, with_status,
+};
+use crate::{
+    chain,
+    error::ApiError,
+    query::Params,
+    state::{Effect, MockState, Store, new_id, set, str_of},
+};
+
+pub(super) fn routes() -> Router<MockState> {
+    Router::new()
+        .route("/v1/w3s/users", post(create_user).get(list_users))
+        .route("/v1/w3s/users/{id}", get(get_user))
+        .route("/v1/w3s/users/token", post(user_token))
+        .route("/v1/w3s/users/token/refresh", post(refresh_user_token))
+        .route("/v1/w3s/users/social/token", post(social_device_token))
+        .route("/v1/w3s/users/email/token", post(email_device_token))
+        .route("/v1/w3s/users/email/resendOTP", post(resend_otp))
+        .route("/v1/w3s/user", get(get_user_by_token))
+        .route("/v1/w3s/user/initialize", post(initialize))
+        .route("/v1/w3s/user/pin", post(set_pin).put(change_pin))
+        .route("/v1/w3s/user/pin/restore", post(restore_pin))
+        .route("/v1/w3s/user/challenges", get(list_challenges))
+        .route("/v1/w3s/user/challenges/{id}", get(get_challenge))
+        .route("/v1/w3s/user/wallets", post(create_wallets))
+        .route("/v1/w3s/user/transactions/transfer", post(create_transfer))
+        .route("/v1/w3s/user/transactions/contractExecution", post(create_contract_execution))
+        .route("/v1/w3s/user/transactions/walletUpgrade", post(create_wallet_upgrade))
+        .route("/v1/w3s/user/transactions/{id}/cancel", post(cancel_transaction))
+        .route("/v1/w3s/user/transactions/{id}/accelerate", post(accelerate_transaction))
+        .route("/v1/w3s/user/sign/message", post(sign_message))
+        .route("/v1/w3s/user/sign/typedData", post(sign_typed_data))
+        .route("/v1/w3s/user/sign/transaction", post(sign_transaction))
+}
+
+// ── Users and tokens ──────────────────────────────────────────────────────────
+
+async fn create_user(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let user = state.lock().create_user(required(&body, "userId")?)?;
+    Ok(with_status(StatusCode::CREATED, user))
+}
+
+async fn list_users(
+    State(state): State<MockState>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Reply {
+    let params = Params(query);
+    let store = state.lock();
+    let users: Vec<Value> = store
+        .users
+        .iter()
+        .filter(|user| {
+            params.matches(user, "pinStatus", "pinStatus") &&
+                params.matches(user, "securityQuestionStatus", "securityQuestionStatus")
+        })
+        .cloned()
+        .collect();
+    ok(json!({ "users": params.page(users)? }))
+}
+
+async fn get_user(State(state): State<MockState>, Path(id): Path<String>) -> Reply {
+    ok(json!({ "user": state.lock().require_user(&id)? }))
+}
+
+async fn user_token(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    ok(state.lock().issue_user_token(required(&body, "userId")?)?)
+}
+
+async fn refresh_user_token(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    required(&body, "refreshToken")?;
+    required(&body, "deviceId")?;
+    let mut store = state.lock();
+    let user_id = require_token_user(&store, &headers)?;
+    idempotent(&mut store, &body, StatusCode::OK, |store| {
+        let mut token = store.issue_user_token(&user_id)?;
+        set(&mut token, "userId", user_id.clone());
+        set(&mut token, "refreshToken", new_id());
+        Ok(token)
+    })
+}
+
+async fn social_device_token(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    required(&body, "deviceId")?;
+    idempotent(&mut state.lock(), &body, StatusCode::OK, |_| Ok(device_token()))
+}
+
+async fn email_device_token(State(state): State<MockState>, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    required(&body, "deviceId")?;
+    required(&body, "email")?;
+    idempotent(&mut state.lock(), &body, StatusCode::OK, |_| {
+        let mut token = device_token();
+        set(&mut token, "otpToken", new_id());
+        Ok(token)
+    })
+}
+
+fn device_token() -> Value {
+    json!({ "deviceToken": new_id(), "deviceEncryptionKey": chain::random_hex(32) })
+}
+
+async fn resend_otp(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    for key in ["otpToken", "email", "deviceId"] {
+        required(&body, key)?;
+    }
+    let mut store = state.lock();
+    require_token_user(&store, &headers)?;
+    idempotent(&mut store, &body, StatusCode::OK, |_| Ok(json!({ "otpToken": new_id() })))
+}
+
+async fn get_user_by_token(State(state): State<MockState>, headers: HeaderMap) -> Reply {
+    let store = state.lock();
+    let user_id = require_token_user(&store, &headers)?;
+    ok(store.require_user(&user_id)?.clone())
+}
+
+// ── Challenges ────────────────────────────────────────────────────────────────
+
+/// Validates the request, then creates a challenge of `kind` whose effect
+/// is built by `effect` and answers `{challengeId}`.
+fn challenge(
+    state: &MockState,
+    headers: &HeaderMap,
+    body: &Value,
+    kind: &str,
+    effect: impl FnOnce(&Store, &str) -> Result<Effect, ApiError>,
+) -> Reply {
+    let mut store = state.lock();
+    let user_id = require_token_user(&store, headers)?;
+    idempotent(&mut store, body, StatusCode::CREATED, |store| {
+        let effect = effect(store, &user_id)?;
+        store.check_pin_for(&user_id, &effect)?;
+        Ok(json!({ "challengeId": store.create_challenge(&user_id, kind, effect) }))
+    })
+}
+
+async fn initialize(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let spec = match body.get("blockchains") {
+        Some(_) => Some(wallet_spec(&body)?),
+        None => None,
+    };
+    challenge(&state, &headers, &body, "INITIALIZE", |_, _| Ok(Effect::Initialize(spec)))
+}
+
+async fn set_pin(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "SET_PIN", |_, _| Ok(Effect::SetPin))
+}
+
+async fn change_pin(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "CHANGE_PIN", |_, _| Ok(Effect::ChangePin))
+}
+
+async fn restore_pin(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "RESTORE_PIN", |_, _| Ok(Effect::RestorePin))
+}
+
+async fn list_challenges(State(state): State<MockState>, headers: HeaderMap) -> Reply {
+    let store = state.lock();
+    let user_id = require_token_user(&store, &headers)?;
+    let challenges: Vec<&Value> = store
+        .challenges
+        .iter()
+        .rev()
+        .filter(|challenge| challenge.user_id == user_id)
+        .map(|challenge| &challenge.value)
+        .collect();
+    ok(json!({ "challenges": challenges }))
+}
+
+async fn get_challenge(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Reply {
+    let store = state.lock();
+    let user_id = require_token_user(&store, &headers)?;
+    let challenge =
+        store.challenge(&id).filter(|challenge| challenge.user_id == user_id).ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::InvalidChallengeId, "challenge", &id)
+        })?;
+    ok(json!({ "challenge": challenge.value }))
+}
+
+// ── Wallets and transactions ──────────────────────────────────────────────────
+
+async fn create_wallets(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    let spec = wallet_spec(&body)?;
+    challenge(&state, &headers, &body, "CREATE_WALLET", |_, _| Ok(Effect::CreateWallets(spec)))
+}
+
+async fn create_transfer(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "CREATE_TRANSACTION", |store, user_id| {
+        Ok(Effect::CreateTransaction(store.prepare_transfer(&body, Some(user_id))?))
+    })
+}
+
+async fn create_contract_execution(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "CONTRACT_EXECUTION", |store, user_id| {
+        Ok(Effect::CreateTransaction(store.prepare_contract_execution(&body, Some(user_id))?))
+    })
+}
+
+async fn create_wallet_upgrade(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "WALLET_UPGRADE", |store, user_id| {
+        Ok(Effect::CreateTransaction(store.prepare_wallet_upgrade(&body, Some(user_id))?))
+    })
+}
+
+/// The id of a transaction owned by `user_id`.
+fn own_transaction(store: &Store, user_id: &str, id: &str) -> Result<String, ApiError> {
+    store
+        .transaction(id)
+        .filter(|transaction| str_of(transaction, "userId") == user_id)
+        .map(|_| id.to_string())
+        .ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TransactionIdNotFound, "transaction", id)
+        })
+}
+
+async fn cancel_transaction(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "CANCEL_TRANSACTION", |store, user_id| {
+        Ok(Effect::Cancel(own_transaction(store, user_id, &id)?))
+    })
+}
+
+async fn accelerate_transaction(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    challenge(&state, &headers, &body, "ACCELERATE_TRANSACTION", |store, user_id| {
+        Ok(Effect::Accelerate(own_transaction(store, user_id, &id)?))
+    })
+}
+
+// ── Signing ───────────────────────────────────────────────────────────────────
+
+/// Creates a signing challenge after checking the payload and that the
+/// wallet belongs to the user.
+fn sign(
+    state: &MockState,
+    headers: &HeaderMap,
+    body: &Value,
+    kind: &str,
+    payload: &[&str],
+) -> Reply {
+    if payload.iter().all(|key| body.get(*key).is_none_or(Value::is_null)) {
+        return Err(ApiError::missing(payload[0]));
+    }
+    challenge(state, headers, body, kind, |store, user_id| {
+        store.source_wallet(body, Some(user_id))?;
+        Ok(Effect::Sign)
+    })
+}
+
+async fn sign_message(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    sign(&state, &headers, &body, "SIGN_MESSAGE", &["message"])
+}
+
+async fn sign_typed_data(State(state): State<MockState>, headers: HeaderMap, body: Bytes) -> Reply {
+    let body = parse_body(&body)?;
+    sign(&state, &headers, &body, "SIGN_TYPEDDATA", &["data"])
+}
+
+async fn sign_transaction(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Reply {
+    let body = parse_body(&body)?;
+    sign(&state, &headers, &body, "SIGN_TRANSACTION", &["rawTransaction", "transaction"])
+}
@@ -0,0 +1,80 @@
+//! In-process server lifecycle.
+
+use std::net::SocketAddr;
+
+use axum::Router;
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::{error::Error, state::MockState};
+
+/// A running mock server, bound to a local port and serving on the current
+/// tokio runtime until dropped.
+///
+/// ```no_run
+/// # async fn run() -> Result<(), circle_mock::Error> {
+/// let server = circle_mock::MockServer::start().await?;
+/// let wallet = server.state().create_wallet("ETH-SEPOLIA")?;
+/// // Point any SDK client at `server.base_url()` with any non-empty API key.
+/// # let _ = wallet;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: MockState,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Starts a server with fresh state on an ephemeral `127.0.0.1` port.
+    pub async fn start() -> Result<Self, Error> {
+        Self::start_on(SocketAddr::from(([127, 0, 0, 1], 0))).await
+    }
+
+    /// Starts a server with fresh state on `addr`.
+    pub async fn start_on(addr: SocketAddr) -> Result<Self, Error> {
+        Self::start_with(addr, MockState::new()).await
+    }
+
+    /// Starts a server on `addr` serving existing `state`, e.g. one shared
+    /// with another server or seeded beforehand.
+    pub async fn start_with(addr: SocketAddr, state: MockState) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let app = router(state.clone());
+        let task = tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, app).await {
+                tracing::error!(%error, "circle-mock server stopped");
+            }
+        });
+        Ok(Self { addr, state, task })
+    }
+
+    /// Base URL to configure SDK clients with, e.g. `http://127.0.0.1:41234`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Address the server is bound to.
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The server's state, for seeding and inspection.
+    pub const fn state(&self) -> &MockState {
+        &self.state
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The mock's [`Router`] over `state`, for embedding into another axum
+/// application or serving with custom settings.
+pub fn router(state: MockState) -> Router {
+    crate::routes::router(state)
+}
@@ -0,0 +1,387 @@
+//! Shared in-memory state of the mock.
+//!
+//! [`MockState`] is a cheap, cloneable handle to everything the mock knows:
+//! wallet sets, wallets, tokens, balances, transactions, users, challenges,
+//! screening results and injected faults. Tests use it to seed data the APIs
+//! cannot create (balances, NFTs, denied addresses), to drive asynchronous
+//! processes (transaction lifecycles, user challenges) and to inspect what
+//! the SDK did.
+//!
+//! Resources are stored as the JSON objects the API returns, so what a test
+//! reads here is exactly what a client would receive.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use axum::http::Method;
+use chrono::{SecondsFormat, Utc};
+use circle_core::{Network, TokenAmount};
+use serde_json::{Value, json};
+
+use crate::{error::Error, fault::Fault};
+
+/// Handle to the mock's state, shared by the server and the test.
+#[derive(Debug, Clone, Default)]
+pub struct MockState {
+    inner: Arc<Mutex<Store>>,
+}
+
+/// Everything the mock knows. Collections are kept in creation order; list
+/// endpoints return them newest first, like Circle does.
+#[derive(Debug)]
+pub(crate) struct Store {
+    pub(crate) wallet_sets: Vec<Value>,
+    pub(crate) wallets: Vec<Value>,
+    pub(crate) tokens: Vec<Value>,
+    /// Fungible balances: wallet id → token id → amount.
+    pub(crate) balances: HashMap<String, BTreeMap<String, TokenAmount>>,
+    /// NFTs held: wallet id → `(token id, nft token id)`.
+    pub(crate) nfts: HashMap<String, Vec<(String, String)>>,
+    pub(crate) transactions: Vec<Value>,
+    pub(crate) users: Vec<Value>,
+    /// Issued user tokens → user id.
+    pub(crate) user_tokens: HashMap<String, String>,
+    pub(crate) challenges: Vec<Challenge>,
+    /// Addresses the compliance engine denies.
+    pub(crate) denied: Vec<String>,
+    pub(crate) screenings: Vec<Value>,
+    pub(crate) transfers: Vec<Value>,
+    pub(crate) user_ops: Vec<Value>,
+    /// Idempotency key → (request fingerprint, response body).
+    pub(crate) idempotency: HashMap<String, (Value, Value)>,
+    pub(crate) faults: Vec<Fault>,
+    pub(crate) requests: Vec<(Method, String)>,
+    /// Advance a transaction one state each time it is fetched.
+    pub(crate) auto_advance: bool,
+}
+
+/// A user challenge and what completing it does.
+#[derive(Debug, Clone)]
+pub(crate) struct Challenge {
+    pub(crate) value: Value,
+    pub(crate) user_id: String,
+    pub(crate) effect: Effect,
+}
+
+/// The deferred effect of a user challenge, applied when it completes.
+#[derive(Debug, Clone)]
+pub(crate) enum Effect {
+    Initialize(Option<WalletSpec>),
+    SetPin,
+    ChangePin,
+    RestorePin,
+    CreateWallets(WalletSpec),
+    /// A transaction draft, created in `INITIATED` state.
+    CreateTransaction(Value),
+    Cancel(String),
+    Accelerate(String),
+    Sign,
+}
+
+/// What to create for a wallet creation request.
+#[derive(Debug, Clone)]
+pub(crate) struct WalletSpec {
+    pub(crate) blockchains: Vec<Network>,
+    pub(crate) account_type: String,
+    pub(crate) count: usize,
+    pub(crate) metadata: Vec<Value>,
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        let mut store = Self {
+            wallet_sets: Vec::new(),
+            wallets: Vec::new(),
+            tokens: Vec::new(),
+            balances: HashMap::new(),
+            nfts: HashMap::new(),
+            transactions: Vec::new(),
+            users: Vec::new(),
+            user_tokens: HashMap::new(),
+            challenges: Vec::new(),
+            denied: Vec::new(),
+            screenings: Vec::new(),
+            transfers: Vec::new(),
+            user_ops: Vec::new(),
+            idempotency: HashMap::new(),
+            faults: Vec::new(),
+            requests: Vec::new(),
+            auto_advance: true,
+        };
+        store.seed_tokens();
+        store
+    }
+}
+
+impl MockState {
+    /// Empty state with the native token of every blockchain and USDC on
+    /// the main testnets already registered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, Store> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Drops all state, faults included, back to [`MockState::new`].
+    pub fn reset(&self) {
+        *self.lock() = Store::default();
+    }
+
+    /// Whether fetching a transaction advances it one state (the default),
+    /// so polling clients see it progress to `COMPLETE`. When disabled,
+    /// transactions only move via [`MockState::advance_transaction`].
+    pub fn set_auto_advance(&self, enabled: bool) {
+        self.lock().auto_advance = enabled;
+    }
+
+    /// Injects a [`Fault`].
+    pub fn inject(&self, fault: Fault) {
+        self.lock().faults.push(fault);
+    }
+
+    /// Removes all injected faults.
+    pub fn clear_faults(&self) {
+        self.lock().faults.clear();
+    }
+
+    /// Method and path of every request received so far, faulted ones
+    /// included.
+    pub fn requests(&self) -> Vec<(Method, String)> {
+        self.lock().requests.clone()
+    }
+
+    // ── Tokens, wallets and balances ──────────────────────────────────────
+
+    /// Id of the token with `symbol` on `blockchain`, e.g. `("ETH-SEPOLIA",
+    /// "USDC")` or `("SOL-DEVNET", "SOL")`.
+    pub fn token_id(&self, blockchain: &str, symbol: &str) -> Option<String> {
+        self.lock().token_by_symbol(blockchain, symbol).map(|token| str_of(token, "id").to_string())
+    }
+
+    /// Registers a fungible token and returns its id.
+    pub fn register_token(
+        &self,
+        blockchain: &str,
+        symbol: &str,
+        decimals: u32,
+        token_address: &str,
+    ) -> Result<String, Error> {
+        Ok(self.lock().register_token(
+            blockchain,
+            symbol,
+            decimals,
+            Some(token_address),
+            "ERC20",
+        )?)
+    }
+
+    /// Registers an NFT collection (`ERC721` or `ERC1155`) and returns its
+    /// token id.
+    pub fn register_nft_collection(
+        &self,
+        blockchain: &str,
+        name: &str,
+        token_address: &str,
+        standard: &str,
+    ) -> Result<String, Error> {
+        Ok(self.lock().register_token(blockchain, name, 0, Some(token_address), standard)?)
+    }
+
+    /// Creates a developer-custody `EOA` wallet on `blockchain` in a fresh
+    /// wallet set, and returns it.
+    pub fn create_wallet(&self, blockchain: &str) -> Result<Value, Error> {
+        Ok(self.lock().create_developer_wallet(blockchain)?)
+    }
+
+    /// The wallet with `id`, as the API returns it.
+    pub fn wallet(&self, id: &str) -> Option<Value> {
+        self.lock().wallet(id).cloned()
+    }
+
+    /// Sets the balance of `token_id` held by `wallet_id` to the decimal
+    /// `amount`, e.g. `"12.5"`.
+    pub fn set_balance(&self, wallet_id: &str, token_id: &str, amount: &str) -> Result<(), Error> {
+        Ok(self.lock().set_balance(wallet_id, token_id, amount)?)
+    }
+
+    /// The decimal balance of `token_id` held by `wallet_id`, `None` if the
+    /// wallet never held it.
+    pub fn balance(&self, wallet_id: &str, token_id: &str) -> Option<String> {
+        self.lock().balance(wallet_id, token_id)
+    }
+
+    /// Gives `wallet_id` the NFT `nft_token_id` of collection `token_id`.
+    pub fn add_nft(
+        &self,
+        wallet_id: &str,
+        token_id: &str,
+        nft_token_id: &str,
+    ) -> Result<(), Error> {
+        Ok(self.lock().add_nft(wallet_id, token_id, nft_token_id)?)
+    }
+
+    // ── Transactions ───────────────────────────────────────────────────────
+
+    /// The transaction with `id`, as the API returns it.
+    pub fn transaction(&self, id: &str) -> Option<Value> {
+        self.lock().transaction(id).cloned()
+    }
+
+    /// Creates an `INITIATED` outbound transfer of the decimal `amount` of
+    /// `token_id` from `wallet_id` to `destination_address`, validated like
+    /// the developer transfer endpoint, and returns it.
+    pub fn create_transfer(
+        &self,
+        wallet_id: &str,
+        destination_address: &str,
+        token_id: &str,
+        amount: &str,
+    ) -> Result<Value, Error> {
+        let mut store = self.lock();
+        let draft = store.prepare_transfer(
+            &json!({
+                "walletId": wallet_id,
+                "destinationAddress": destination_address,
+                "tokenId": token_id,
+                "amounts": [amount],
+            }),
+            None,
+        )?;
+        Ok(store.insert_transaction(draft))
+    }
+
+    /// Moves a transaction one step along `INITIATED → QUEUED → SENT →
+    /// CONFIRMED → COMPLETE` and returns its new state. Balances move when
+    /// it completes; a transfer the wallet cannot fund ends `FAILED`, one to
+    /// a denied address ends `DENIED`. Terminal states do not change.
+    pub fn advance_transaction(&self, id: &str) -> Result<String, Error> {
+        Ok(self.lock().advance_transaction(id)?)
+    }
+
+    /// Advances a transaction until it reaches a terminal state, and
+    /// returns that state.
+    pub fn settle_transaction(&self, id: &str) -> Result<String, Error> {
+        let mut store = self.lock();
+        loop {
+            let state = store.advance_transaction(id)?;
+            if crate::transactions::is_terminal(&state) || state == "STUCK" {
+                return Ok(state);
+            }
+        }
+    }
+
+    /// Forces a transaction into `state`, e.g. `STUCK` to test
+    /// acceleration. Balances are not touched.
+    pub fn set_transaction_state(&self, id: &str, state: &str) -> Result<(), Error> {
+        Ok(self.lock().set_transaction_state(id, state)?)
+    }
+
+    // ── Users and challenges ───────────────────────────────────────────────
+
+    /// The challenge with `id`, as the API returns it.
+    pub fn challenge(&self, id: &str) -> Option<Value> {
+        self.lock().challenge(id).map(|challenge| challenge.value.clone())
+    }
+
+    /// Completes a pending challenge as if the user approved it on their
+    /// device, applies its effect (PIN set, wallets or transaction created,
+    /// ...) and returns the ids of the resources it created.
+    pub fn complete_challenge(&self, id: &str) -> Result<Vec<String>, Error> {
+        Ok(self.lock().complete_challenge(id)?)
+    }
+
+    /// Fails a pending challenge with a Circle error `code` and `message`.
+    pub fn fail_challenge(&self, id: &str, code: i32, message: &str) -> Result<(), Error> {
+        Ok(self.lock().fail_challenge(id, code, message)?)
+    }
+
+    // ── Compliance ─────────────────────────────────────────────────────────
+
+    /// Makes the compliance engine deny `address`: screening returns
+    /// `DENIED`, and transactions to it end `DENIED`.
+    pub fn deny_address(&self, address: &str) {
+        self.lock().denied.push(address.to_string());
+    }
+
+    /// Every screening result returned so far.
+    pub fn screenings(&self) -> Vec<Value> {
+        self.lock().screenings.clone()
+    }
+}
+
+impl Store {
+    /// Answers a repeated idempotency key: the stored response when the
+    /// request is the same, a conflict when it differs.
+    pub(crate) fn replay(
+        &self,
+        key: &str,
+        fingerprint: &Value,
+    ) -> Option<Result<Value, crate::error::ApiError>> {
+        let (stored, body) = self.idempotency.get(key)?;
+        Some(if stored == fingerprint {
+            Ok(body.clone())
+        } else {
+            Err(crate::error::ApiError::status(
+                axum::http::StatusCode::CONFLICT,
+                "The idempotency key was already used with a different request.",
+            ))
+        })
+    }
+}
+
+/// Current time in Circle's timestamp format.
+pub(crate) fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Parses a decimal amount of a token with `decimals` decimals. Returns
+/// `None` for malformed or over-precise input.
+pub(crate) fn token_amount(amount: &str, decimals: u32) -> Option<TokenAmount> {
+    let amount = amount.parse::<TokenAmount>().ok()?;
+    amount.to_base_units(decimals).is_ok().then_some(amount)
+}
+
+/// A fresh resource id.
+pub(crate) fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// String field `key` of a JSON object, `""` if absent.
+pub(crate) fn str_of<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Sets `key` of a JSON object; a no-op on anything else.
+pub(crate) fn set(value: &mut Value, key: &str, new: impl Into<Value>) {
+    if let Some(object) = value.as_object_mut() {
+        object.insert(key.to_string(), new.into());
+    }
+}
+
+/// Finds the object whose `id` is `id`.
+pub(crate) fn by_id<'a>(items: &'a [Value], id: &str) -> Option<&'a Value> {
+    items.iter().find(|item| str_of(item, "id") == id)
+}
+
+/// Mutable variant of [`by_id`].
+pub(crate) fn by_id_mut<'a>(items: &'a mut [Value], id: &str) -> Option<&'a mut Value> {
+    items.iter_mut().find(|item| str_of(item, "id") == id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_amounts_fit_the_token_decimals() {
+        assert_eq!(token_amount("1.25", 6), Some(TokenAmount::new(125_u32, 2)));
+        assert_eq!(token_amount("0.000000000000000001", 18), Some(TokenAmount::new(1_u32, 18)));
+        for amount in ["", ".", ".5", "1.0000001", "-1", "1e6", "1,5", "abc"] {
+            assert_eq!(token_amount(amount, 6), None, "{amount}");
+        }
+    }
+}
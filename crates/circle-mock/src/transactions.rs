@@ -0,0 +1,587 @@
+//! Transactions and their lifecycle.
+//!
+//! Outbound transactions start `INITIATED` and move one state per
+//! [`Store::advance_transaction`] call along `INITIATED → QUEUED → SENT →
+//! CONFIRMED → COMPLETE`. Balances move when a transfer completes; network
+//! fees are reported but never charged. Completing a transfer also records
+//! the matching inbound transaction for a destination wallet the mock
+//! knows, and on EVM chains the Buidl transfers (plus a user operation for
+//! `SCA` wallets).
+
+use circle_core::{CircleErrorCode, Network, TokenAmount};
+use serde_json::{Value, json};
+
+use crate::{
+    chain::{self, Family},
+    error::ApiError,
+    state::{Store, by_id, by_id_mut, new_id, now, set, str_of, token_amount},
+    wallets::known_chain,
+};
+
+/// Every state of `TransactionState`.
+const STATES: [&str; 10] = [
+    "CANCELLED",
+    "CONFIRMED",
+    "COMPLETE",
+    "DENIED",
+    "FAILED",
+    "INITIATED",
+    "CLEARED",
+    "QUEUED",
+    "SENT",
+    "STUCK",
+];
+
+/// Returns `true` for states a transaction never leaves.
+pub(crate) fn is_terminal(state: &str) -> bool {
+    matches!(state, "COMPLETE" | "FAILED" | "CANCELLED" | "DENIED" | "CLEARED")
+}
+
+impl Store {
+    pub(crate) fn transaction(&self, id: &str) -> Option<&Value> {
+        by_id(&self.transactions, id)
+    }
+
+    pub(crate) fn require_transaction(&self, id: &str) -> Result<&Value, ApiError> {
+        self.transaction(id).ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TransactionIdNotFound, "transaction", id)
+        })
+    }
+
+    /// Resolves the source wallet of a request: `walletId`, or
+    /// `walletAddress` plus `blockchain`. With `user_id`, the wallet must
+    /// belong to that user.
+    pub(crate) fn source_wallet(
+        &self,
+        body: &Value,
+        user_id: Option<&str>,
+    ) -> Result<Value, ApiError> {
+        let wallet_id = str_of(body, "walletId");
+        let wallet = if wallet_id.is_empty() {
+            let address = str_of(body, "walletAddress");
+            let blockchain = str_of(body, "blockchain");
+            if address.is_empty() || blockchain.is_empty() {
+                return Err(ApiError::missing("walletId"));
+            }
+            self.wallet_by_address(blockchain, address).ok_or_else(|| {
+                ApiError::not_found(CircleErrorCode::WalletIdNotFound, "wallet", address)
+            })?
+        } else {
+            self.require_wallet(wallet_id)?
+        };
+        if user_id.is_some_and(|user| wallet.get("userId").and_then(Value::as_str) != Some(user)) {
+            return Err(ApiError::not_found(
+                CircleErrorCode::WalletIdNotFound,
+                "wallet",
+                str_of(wallet, "id"),
+            ));
+        }
+        if str_of(wallet, "state") == "FROZEN" {
+            return Err(ApiError::rejected(
+                CircleErrorCode::WalletIsFrozen,
+                "The wallet is frozen.",
+            ));
+        }
+        Ok(wallet.clone())
+    }
+
+    /// Validates a transfer request and returns the transaction to create.
+    pub(crate) fn prepare_transfer(
+        &self,
+        body: &Value,
+        user_id: Option<&str>,
+    ) -> Result<Value, ApiError> {
+        let wallet = self.source_wallet(body, user_id)?;
+        let blockchain = str_of(&wallet, "blockchain");
+        let family = chain::family(known_chain(blockchain)?);
+        let token = self.transfer_token(body, blockchain)?;
+        let destination = str_of(body, "destinationAddress");
+        if destination.is_empty() {
+            return Err(ApiError::missing("destinationAddress"));
+        }
+        if !chain::is_valid_address(family, destination) {
+            return Err(ApiError::rejected(
+                CircleErrorCode::InvalidDestinationAddress,
+                format!("Invalid destination address {destination} for {blockchain}."),
+            ));
+        }
+
+        let mut transaction = outbound(&wallet, "TRANSFER", body);
+        set(&mut transaction, "tokenId", str_of(token, "id"));
+        set(&mut transaction, "destinationAddress", destination);
+        if matches!(str_of(token, "standard"), "ERC721" | "ERC1155") {
+            let nft_ids = strings(body, "nftTokenIds");
+            if nft_ids.is_empty() {
+                return Err(ApiError::rejected(
+                    CircleErrorCode::WrongNftTokenIdNumber,
+                    "nftTokenIds is required for an NFT transfer.",
+                ));
+            }
+            set(&mut transaction, "nfts", nft_ids);
+        } else {
+            let amounts = strings(body, "amounts");
+            let [amount] = amounts.as_slice() else {
+                return Err(ApiError::rejected(
+                    CircleErrorCode::WrongAmountsNumber,
+                    "Exactly one amount is required for a fungible transfer.",
+                ));
+            };
+            let decimals = self.token_decimals(str_of(token, "id"))?;
+            if token_amount(amount, decimals).is_none_or(|amount| amount.is_zero()) {
+                return Err(ApiError::invalid("amounts", amount));
+            }
+            set(&mut transaction, "amounts", amounts);
+        }
+        Ok(transaction)
+    }
+
+    /// Resolves the token of a transfer: `tokenId`, or `tokenAddress` on
+    /// the wallet's blockchain (empty for the native token).
+    fn transfer_token(&self, body: &Value, blockchain: &str) -> Result<&Value, ApiError> {
+        let Some(token_id) = body.get("tokenId").and_then(Value::as_str) else {
+            let address = body
+                .get("tokenAddress")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ApiError::missing("tokenId"))?;
+            return self
+                .token_by_address(blockchain, address)
+                .ok_or_else(|| ApiError::invalid("tokenAddress", address));
+        };
+        let token = self.token(token_id).ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TokenIdNotFound, "token", token_id)
+        })?;
+        if str_of(token, "blockchain") != blockchain {
+            return Err(ApiError::rejected(
+                CircleErrorCode::TokenWalletChainMismatch,
+                "The token is not on the wallet's blockchain.",
+            ));
+        }
+        Ok(token)
+    }
+
+    /// Validates a contract execution request and returns the transaction
+    /// to create.
+    pub(crate) fn prepare_contract_execution(
+        &self,
+        body: &Value,
+        user_id: Option<&str>,
+    ) -> Result<Value, ApiError> {
+        let wallet = self.source_wallet(body, user_id)?;
+        let family = chain::family(known_chain(str_of(&wallet, "blockchain"))?);
+        let contract = str_of(body, "contractAddress");
+        if contract.is_empty() {
+            return Err(ApiError::missing("contractAddress"));
+        }
+        if !chain::is_valid_address(family, contract) {
+            return Err(ApiError::invalid("contractAddress", contract));
+        }
+        let has_abi = body.get("abiFunctionSignature").is_some_and(Value::is_string);
+        let has_call_data = body.get("callData").is_some_and(Value::is_string);
+        if has_abi == has_call_data {
+            return Err(ApiError::invalid(
+                "abiFunctionSignature",
+                "exactly one of abiFunctionSignature and callData is required",
+            ));
+        }
+        let mut transaction = outbound(&wallet, "CONTRACT_EXECUTION", body);
+        set(&mut transaction, "contractAddress", contract);
+        set(&mut transaction, "destinationAddress", contract);
+        for key in ["abiFunctionSignature", "abiParameters"] {
+            if let Some(value) = body.get(key) {
+                set(&mut transaction, key, value.clone());
+            }
+        }
+        if let Some(amount) = body.get("amount").and_then(Value::as_str) {
+            set(&mut transaction, "amounts", vec![amount.to_string()]);
+        }
+        Ok(transaction)
+    }
+
+    /// Validates a wallet upgrade request and returns the transaction to
+    /// create.
+    pub(crate) fn prepare_wallet_upgrade(
+        &self,
+        body: &Value,
+        user_id: Option<&str>,
+    ) -> Result<Value, ApiError> {
+        let wallet = self.source_wallet(body, user_id)?;
+        let new_core = str_of(body, "newScaCore");
+        if new_core.is_empty() {
+            return Err(ApiError::missing("newScaCore"));
+        }
+        if str_of(&wallet, "accountType") != "SCA" {
+            return Err(ApiError::rejected(
+                CircleErrorCode::OperationNotSupported,
+                "Only SCA wallets can be upgraded.",
+            ));
+        }
+        let mut transaction = outbound(&wallet, "CONTRACT_EXECUTION", body);
+        set(&mut transaction, "newScaCore", new_core);
+        Ok(transaction)
+    }
+
+    /// Stores a prepared transaction in `INITIATED` state.
+    pub(crate) fn insert_transaction(&mut self, mut transaction: Value) -> Value {
+        let timestamp = now();
+        set(&mut transaction, "id", new_id());
+        set(&mut transaction, "state", "INITIATED");
+        set(&mut transaction, "createDate", timestamp.clone());
+        set(&mut transaction, "updateDate", timestamp);
+        self.transactions.push(transaction.clone());
+        transaction
+    }
+
+    pub(crate) fn set_transaction_state(&mut self, id: &str, state: &str) -> Result<(), ApiError> {
+        if !STATES.contains(&state) {
+            return Err(ApiError::invalid("state", state));
+        }
+        let transaction = self.transaction_mut(id)?;
+        set(transaction, "state", state);
+        set(transaction, "updateDate", now());
+        Ok(())
+    }
+
+    fn transaction_mut(&mut self, id: &str) -> Result<&mut Value, ApiError> {
+        by_id_mut(&mut self.transactions, id).ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TransactionIdNotFound, "transaction", id)
+        })
+    }
+
+    /// Moves a transaction one state forward and returns the new state.
+    pub(crate) fn advance_transaction(&mut self, id: &str) -> Result<String, ApiError> {
+        let transaction = self.require_transaction(id)?.clone();
+        let family =
+            chain::lookup(str_of(&transaction, "blockchain")).map_or(Family::Evm, chain::family);
+        let mut updated = transaction.clone();
+        match str_of(&transaction, "state") {
+            "INITIATED" => {
+                if self.is_denied(str_of(&transaction, "destinationAddress")) {
+                    set(&mut updated, "state", "DENIED");
+                    set(&mut updated, "errorReason", "TRANSACTION_SCREENING_DENIED");
+                    set(
+                        &mut updated,
+                        "transactionScreeningEvaluation",
+                        denial(str_of(&transaction, "destinationAddress")),
+                    );
+                } else if !self.can_fund(&transaction) {
+                    set(&mut updated, "state", "FAILED");
+                    set(&mut updated, "errorReason", "INSUFFICIENT_TOKEN");
+                    set(
+                        &mut updated,
+                        "errorDetails",
+                        "Asset amount owned by the wallet is insufficient for the transaction.",
+                    );
+                } else {
+                    set(&mut updated, "state", "QUEUED");
+                }
+            }
+            "QUEUED" => {
+                set(&mut updated, "state", "SENT");
+                set(&mut updated, "txHash", tx_hash(family));
+            }
+            "SENT" => {
+                set(&mut updated, "state", "CONFIRMED");
+                set(&mut updated, "blockHash", tx_hash(family));
+                set(&mut updated, "blockHeight", fastrand::u64(1_000_000..50_000_000));
+                set(&mut updated, "firstConfirmDate", now());
+            }
+            "CONFIRMED" => {
+                set(&mut updated, "state", "COMPLETE");
+                set(&mut updated, "networkFee", "0.000021");
+                self.settle(&updated);
+            }
+            // STUCK needs an acceleration; terminal states never change.
+            state => return Ok(state.to_string()),
+        }
+        set(&mut updated, "updateDate", now());
+        let state = str_of(&updated, "state").to_string();
+        *self.transaction_mut(id)? = updated;
+        Ok(state)
+    }
+
+    /// Cancels a transaction that has not been confirmed yet.
+    pub(crate) fn cancel_transaction(&mut self, id: &str) -> Result<Value, ApiError> {
+        let transaction = self.transaction_mut(id)?;
+        let state = str_of(transaction, "state").to_string();
+        if !matches!(state.as_str(), "INITIATED" | "QUEUED" | "SENT" | "STUCK") {
+            return Err(ApiError::rejected(
+                CircleErrorCode::OperationNotSupported,
+                format!("A {state} transaction cannot be cancelled."),
+            ));
+        }
+        set(transaction, "state", "CANCELLED");
+        set(transaction, "updateDate", now());
+        Ok(transaction.clone())
+    }
+
+    /// Re-sends a pending transaction with a higher fee; a `STUCK` one goes
+    /// back to `SENT`.
+    pub(crate) fn accelerate_transaction(&mut self, id: &str) -> Result<Value, ApiError> {
+        let transaction = self.transaction_mut(id)?;
+        let state = str_of(transaction, "state").to_string();
+        if !matches!(state.as_str(), "QUEUED" | "SENT" | "STUCK") {
+            return Err(ApiError::rejected(
+                CircleErrorCode::OperationNotSupported,
+                format!("A {state} transaction cannot be accelerated."),
+            ));
+        }
+        if state == "STUCK" {
+            set(transaction, "state", "SENT");
+        }
+        set(transaction, "feeLevel", "HIGH");
+        set(transaction, "updateDate", now());
+        Ok(transaction.clone())
+    }
+
+    pub(crate) fn is_denied(&self, address: &str) -> bool {
+        !address.is_empty() && self.denied.iter().any(|denied| denied.eq_ignore_ascii_case(address))
+    }
+
+    /// Returns `true` if the source wallet holds what the transaction sends.
+    fn can_fund(&self, transaction: &Value) -> bool {
+        let wallet_id = str_of(transaction, "walletId");
+        let token_id = str_of(transaction, "tokenId");
+        if let Some(nfts) = transaction.get("nfts").and_then(Value::as_array) {
+            let held = self.nfts.get(wallet_id);
+            return nfts.iter().filter_map(Value::as_str).all(|nft| {
+                held.is_some_and(|held| held.iter().any(|(t, n)| t == token_id && n == nft))
+            });
+        }
+        if str_of(transaction, "operation") != "TRANSFER" {
+            return true;
+        }
+        let Some(amount) = self.transfer_amount(transaction) else {
+            return false;
+        };
+        self.balance_amount(wallet_id, token_id) >= amount
+    }
+
+    fn transfer_amount(&self, transaction: &Value) -> Option<TokenAmount> {
+        let amount = transaction.get("amounts")?.get(0)?.as_str()?;
+        token_amount(amount, self.token_decimals(str_of(transaction, "tokenId")).ok()?)
+    }
+
+    /// Applies the effects of a transaction that just completed.
+    fn settle(&mut self, transaction: &Value) {
+        if let Some(core) = transaction.get("newScaCore").and_then(Value::as_str) {
+            let core = core.to_string();
+            if let Some(wallet) = self.wallet_mut(str_of(transaction, "walletId")) {
+                set(wallet, "scaCore", core);
+                set(wallet, "updateDate", now());
+            }
+            return;
+        }
+        if str_of(transaction, "operation") != "TRANSFER" {
+            return;
+        }
+        let source_id = str_of(transaction, "walletId").to_string();
+        let token_id = str_of(transaction, "tokenId").to_string();
+        let blockchain = str_of(transaction, "blockchain").to_string();
+        let destination = self
+            .wallet_by_address(&blockchain, str_of(transaction, "destinationAddress"))
+            .map(|wallet| str_of(wallet, "id").to_string());
+
+        if let Some(nfts) = transaction.get("nfts").and_then(Value::as_array) {
+            for nft in nfts.iter().filter_map(Value::as_str) {
+                if let Some(held) = self.nfts.get_mut(&source_id) {
+                    held.retain(|(t, n)| !(t == &token_id && n == nft));
+                }
+                if let Some(destination) = &destination {
+                    self.nfts
+                        .entry(destination.clone())
+                        .or_default()
+                        .push((token_id.clone(), nft.to_string()));
+                }
+            }
+        } else if let Some(amount) = self.transfer_amount(transaction) {
+            self.debit(&source_id, &token_id, &amount);
+            if let Some(destination) = &destination {
+                self.credit(destination, &token_id, &amount);
+            }
+        }
+
+        if let Some(destination) = &destination {
+            let mut inbound = transaction.clone();
+            set(&mut inbound, "id", new_id());
+            set(&mut inbound, "walletId", destination.clone());
+            set(&mut inbound, "transactionType", "INBOUND");
+            if let Some(wallet) = self.wallet(destination) {
+                set(&mut inbound, "custodyType", str_of(wallet, "custodyType"));
+                inbound.as_object_mut().map(|o| o.remove("refId"));
+            }
+            self.transactions.push(inbound);
+        }
+        self.record_buidl_activity(transaction, destination.as_deref());
+    }
+
+    /// Records the Buidl transfers (and user operation, for `SCA` sources)
+    /// of a completed EVM transfer.
+    fn record_buidl_activity(&mut self, transaction: &Value, destination: Option<&str>) {
+        let blockchain = str_of(transaction, "blockchain");
+        if !chain::lookup(blockchain).is_some_and(|c| c.is_evm() && c.native_currency().is_some()) {
+            return;
+        }
+        let token_address = self
+            .token(str_of(transaction, "tokenId"))
+            .and_then(|token| token.get("tokenAddress"))
+            .cloned();
+        let sca = self
+            .wallet(str_of(transaction, "walletId"))
+            .is_some_and(|wallet| str_of(wallet, "accountType") == "SCA");
+        let user_op_hash = sca.then(|| tx_hash(Family::Evm));
+        let amount = transaction
+            .get("amounts")
+            .and_then(|a| a.get(0))
+            .and_then(Value::as_str)
+            .unwrap_or("1")
+            .to_string();
+        let timestamp = now();
+        let mut base = json!({
+            "amount": amount,
+            "blockDate": timestamp,
+            "blockHash": transaction.get("blockHash"),
+            "blockHeight": transaction.get("blockHeight"),
+            "blockchain": blockchain,
+            "from": str_of(transaction, "sourceAddress"),
+            "state": "COMPLETE",
+            "to": str_of(transaction, "destinationAddress"),
+            "tokenId": str_of(transaction, "tokenId"),
+            "txHash": str_of(transaction, "txHash"),
+            "createDate": timestamp,
+            "updateDate": timestamp,
+        });
+        if let Some(address) = token_address {
+            set(&mut base, "tokenAddress", address);
+        }
+        if let Some(hash) = &user_op_hash {
+            set(&mut base, "userOpHash", hash.clone());
+        }
+        if let Some(nft) = transaction.get("nfts").and_then(|n| n.get(0)).and_then(Value::as_str) {
+            set(&mut base, "nft", json!({ "nftTokenId": nft }));
+        }
+        let legs = [
+            Some((
+                str_of(transaction, "walletId").to_string(),
+                "OUTBOUND_TRANSFER",
+                "sourceAddress",
+            )),
+            destination.map(|id| (id.to_string(), "INBOUND_TRANSFER", "destinationAddress")),
+        ];
+        for (wallet_id, transfer_type, address_key) in legs.into_iter().flatten() {
+            let mut transfer = base.clone();
+            set(&mut transfer, "id", new_id());
+            set(&mut transfer, "walletId", wallet_id);
+            set(&mut transfer, "walletAddress", str_of(transaction, address_key));
+            set(&mut transfer, "transferType", transfer_type);
+            self.transfers.push(transfer);
+        }
+        if let Some(user_op_hash) = user_op_hash {
+            let nonce = self.user_ops.len();
+            self.user_ops.push(json!({
+                "id": new_id(),
+                "blockchain": blockchain,
+                "state": "COMPLETE",
+                "userOpHash": user_op_hash,
+                "userOperation": {
+                    "sender": str_of(transaction, "sourceAddress"),
+                    "nonce": nonce.to_string(),
+                    "callData": format!("0x{}", chain::random_hex(68)),
+                },
+                "refId": transaction.get("refId"),
+                "actualGasCost": "21000000000000",
+                "actualGasUsed": "21000",
+                "blockDate": timestamp,
+                "blockHash": transaction.get("blockHash"),
+                "blockHeight": transaction.get("blockHeight"),
+                "to": str_of(transaction, "destinationAddress"),
+                "txHash": str_of(transaction, "txHash"),
+                "createDate": timestamp,
+                "updateDate": timestamp,
+            }));
+        }
+    }
+
+    /// The lowest-nonce pending transaction of a wallet, if any.
+    pub(crate) fn lowest_nonce_transaction(&self, wallet_ids: &[&str]) -> Option<&Value> {
+        self.transactions.iter().find(|tx| {
+            wallet_ids.contains(&str_of(tx, "walletId")) &&
+                str_of(tx, "transactionType") == "OUTBOUND" &&
+                matches!(str_of(tx, "state"), "QUEUED" | "SENT" | "STUCK")
+        })
+    }
+}
+
+/// The common fields of an outbound transaction from `wallet`.
+fn outbound(wallet: &Value, operation: &str, body: &Value) -> Value {
+    let mut transaction = json!({
+        "blockchain": str_of(wallet, "blockchain"),
+        "walletId": str_of(wallet, "id"),
+        "sourceAddress": str_of(wallet, "address"),
+        "custodyType": str_of(wallet, "custodyType"),
+        "operation": operation,
+        "transactionType": "OUTBOUND",
+    });
+    if let Some(user_id) = wallet.get("userId") {
+        set(&mut transaction, "userId", user_id.clone());
+    }
+    for key in ["refId", "feeLevel"] {
+        if let Some(value) = body.get(key).filter(|v| v.is_string()) {
+            set(&mut transaction, key, value.clone());
+        }
+    }
+    transaction
+}
+
+/// Fee estimate for a transaction on `blockchain`, as returned by the
+/// estimate endpoints.
+pub(crate) fn fee_estimate(blockchain: &str) -> Value {
+    let level = |priority_gwei: u64| {
+        let base_fee = 12;
+        let max_fee = base_fee * 2 + priority_gwei;
+        json!({
+            "gasLimit": "21000",
+            "baseFee": base_fee.to_string(),
+            "priorityFee": priority_gwei.to_string(),
+            "maxFee": max_fee.to_string(),
+            "networkFee": TokenAmount::from_base_units(max_fee * 21_000 * 1_000_000_000, 18)
+                .to_string(),
+        })
+    };
+    let evm = chain::lookup(blockchain).is_none_or(Network::is_evm);
+    if evm {
+        json!({ "low": level(1), "medium": level(2), "high": level(3) })
+    } else {
+        let flat = json!({ "networkFee": "0.000005" });
+        json!({ "low": flat, "medium": flat, "high": flat })
+    }
+}
+
+fn tx_hash(family: Family) -> String {
+    match family {
+        Family::Evm | Family::Aptos => format!("0x{}", chain::random_hex(32)),
+        Family::Solana | Family::Near => chain::random_address(Family::Solana),
+    }
+}
+
+fn strings(body: &Value, key: &str) -> Vec<String> {
+    body.get(key)
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// The screening decision for a denied address.
+pub(crate) fn denial(address: &str) -> Value {
+    json!({
+        "screeningDate": now(),
+        "ruleName": "Sanctioned address",
+        "actions": ["DENY"],
+        "reasons": [{
+            "source": "ADDRESS",
+            "sourceValue": address,
+            "riskScore": "BLOCKLIST",
+            "riskCategories": ["SANCTIONS"],
+            "type": "OWNERSHIP",
+        }],
+    })
+}
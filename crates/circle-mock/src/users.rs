@@ -0,0 +1,217 @@
+//! End users, user tokens and challenges.
+//!
+//! Every user-controlled mutation returns a challenge instead of acting right
+//! away: on Circle the user approves it on their device with their PIN. The
+//! mock keeps the challenge `PENDING` with its deferred [`Effect`] until the
+//! test completes (or fails) it through [`crate::MockState`] or the control
+//! API.
+
+use axum::http::StatusCode;
+use circle_core::CircleErrorCode;
+use serde_json::{Value, json};
+
+use crate::{
+    chain,
+    error::ApiError,
+    state::{Challenge, Effect, Store, by_id, by_id_mut, new_id, now, set, str_of},
+};
+
+impl Store {
+    pub(crate) fn create_user(&mut self, user_id: &str) -> Result<Value, ApiError> {
+        if user_id.is_empty() {
+            return Err(ApiError::missing("userId"));
+        }
+        if self.user(user_id).is_some() {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                CircleErrorCode::UserAlreadyExisted,
+                format!("User {user_id} already exists."),
+            ));
+        }
+        let user = json!({
+            "id": user_id,
+            "createDate": now(),
+            "pinStatus": "UNSET",
+            "status": "ENABLED",
+            "securityQuestionStatus": "UNSET",
+            "pinDetails": { "failedAttempts": 0 },
+            "securityQuestionDetails": { "failedAttempts": 0 },
+        });
+        self.users.push(user.clone());
+        Ok(user)
+    }
+
+    pub(crate) fn user(&self, id: &str) -> Option<&Value> {
+        by_id(&self.users, id)
+    }
+
+    pub(crate) fn require_user(&self, id: &str) -> Result<&Value, ApiError> {
+        self.user(id).ok_or_else(|| ApiError::not_found(CircleErrorCode::UserNotFound, "user", id))
+    }
+
+    /// Issues a user token for `user_id`, valid until the state is reset.
+    pub(crate) fn issue_user_token(&mut self, user_id: &str) -> Result<Value, ApiError> {
+        self.require_user(user_id)?;
+        let token = format!("mock-user-token-{}", new_id());
+        self.user_tokens.insert(token.clone(), user_id.to_string());
+        Ok(json!({ "userToken": token, "encryptionKey": chain::random_hex(32) }))
+    }
+
+    /// The user a token was issued to.
+    pub(crate) fn user_for_token(&self, token: &str) -> Option<&str> {
+        self.user_tokens.get(token).map(String::as_str)
+    }
+
+    /// Fails unless the user has a PIN, which every challenge but the PIN
+    /// setup ones needs.
+    pub(crate) fn require_pin(&self, user_id: &str) -> Result<(), ApiError> {
+        if str_of(self.require_user(user_id)?, "pinStatus") == "UNSET" {
+            return Err(ApiError::rejected(
+                CircleErrorCode::UserDoesNotSetPinYet,
+                "The user has not set a PIN yet.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that `effect` may be requested now, given the user's PIN.
+    pub(crate) fn check_pin_for(&self, user_id: &str, effect: &Effect) -> Result<(), ApiError> {
+        let pin_set = str_of(self.require_user(user_id)?, "pinStatus") != "UNSET";
+        match effect {
+            Effect::Initialize(_) if pin_set => Err(ApiError::rejected(
+                CircleErrorCode::UserWasInitialized,
+                "The user was already initialized.",
+            )),
+            Effect::SetPin if pin_set => Err(ApiError::rejected(
+                CircleErrorCode::UserHasSetPin,
+                "The user has already set a PIN.",
+            )),
+            Effect::Initialize(_) | Effect::SetPin => Ok(()),
+            _ => self.require_pin(user_id),
+        }
+    }
+
+    /// Creates a `PENDING` challenge of `kind` for `user_id` and returns its
+    /// id.
+    pub(crate) fn create_challenge(&mut self, user_id: &str, kind: &str, effect: Effect) -> String {
+        let id = new_id();
+        self.challenges.push(Challenge {
+            value: json!({ "id": id, "type": kind, "status": "PENDING", "correlationIds": [] }),
+            user_id: user_id.to_string(),
+            effect,
+        });
+        id
+    }
+
+    pub(crate) fn challenge(&self, id: &str) -> Option<&Challenge> {
+        self.challenges.iter().find(|challenge| str_of(&challenge.value, "id") == id)
+    }
+
+    fn pending_challenge(&mut self, id: &str) -> Result<&mut Challenge, ApiError> {
+        let challenge = self
+            .challenges
+            .iter_mut()
+            .find(|challenge| str_of(&challenge.value, "id") == id)
+            .ok_or_else(|| {
+                ApiError::not_found(CircleErrorCode::InvalidChallengeId, "challenge", id)
+            })?;
+        if str_of(&challenge.value, "status") != "PENDING" {
+            return Err(ApiError::rejected(
+                CircleErrorCode::InvalidChallengeId,
+                format!("Challenge {id} is no longer pending."),
+            ));
+        }
+        Ok(challenge)
+    }
+
+    pub(crate) fn complete_challenge(&mut self, id: &str) -> Result<Vec<String>, ApiError> {
+        let challenge = self.pending_challenge(id)?.clone();
+        match self.apply(&challenge.user_id, challenge.effect) {
+            Ok(ids) => {
+                let challenge = self.pending_challenge(id)?;
+                set(&mut challenge.value, "status", "COMPLETE");
+                set(&mut challenge.value, "correlationIds", ids.clone());
+                Ok(ids)
+            }
+            Err(error) => {
+                self.fail_challenge(id, error.code, &error.message)?;
+                Err(error)
+            }
+        }
+    }
+
+    pub(crate) fn fail_challenge(
+        &mut self,
+        id: &str,
+        code: i32,
+        message: &str,
+    ) -> Result<(), ApiError> {
+        let challenge = self.pending_challenge(id)?;
+        set(&mut challenge.value, "status", "FAILED");
+        set(&mut challenge.value, "errorCode", code);
+        set(&mut challenge.value, "errorMessage", message);
+        Ok(())
+    }
+
+    /// Applies the effect of an approved challenge and returns the ids of
+    /// the resources it created or changed.
+    fn apply(&mut self, user_id: &str, effect: Effect) -> Result<Vec<String>, ApiError> {
+        let ids = match effect {
+            Effect::Initialize(spec) => {
+                self.set_pin_status(user_id)?;
+                match spec {
+                    Some(spec) => {
+                        let wallet_set_id = self.user_wallet_set(user_id);
+                        self.create_wallets(&wallet_set_id, &spec, Some(user_id))
+                            .iter()
+                            .map(|wallet| str_of(wallet, "id").to_string())
+                            .collect()
+                    }
+                    None => Vec::new(),
+                }
+            }
+            Effect::SetPin => {
+                self.set_pin_status(user_id)?;
+                Vec::new()
+            }
+            Effect::ChangePin | Effect::RestorePin | Effect::Sign => Vec::new(),
+            Effect::CreateWallets(spec) => {
+                let wallet_set_id = self.user_wallet_set(user_id);
+                self.create_wallets(&wallet_set_id, &spec, Some(user_id))
+                    .iter()
+                    .map(|wallet| str_of(wallet, "id").to_string())
+                    .collect()
+            }
+            Effect::CreateTransaction(draft) => {
+                let transaction = self.insert_transaction(draft);
+                vec![str_of(&transaction, "id").to_string()]
+            }
+            Effect::Cancel(id) => {
+                self.cancel_transaction(&id)?;
+                vec![id]
+            }
+            Effect::Accelerate(id) => {
+                self.accelerate_transaction(&id)?;
+                vec![id]
+            }
+        };
+        Ok(ids)
+    }
+
+    fn set_pin_status(&mut self, user_id: &str) -> Result<(), ApiError> {
+        let user = by_id_mut(&mut self.users, user_id)
+            .ok_or_else(|| ApiError::not_found(CircleErrorCode::UserNotFound, "user", user_id))?;
+        set(user, "pinStatus", "ENABLED");
+        set(user, "securityQuestionStatus", "ENABLED");
+        Ok(())
+    }
+
+    /// The id of the user's wallet set, created on first use.
+    fn user_wallet_set(&mut self, user_id: &str) -> String {
+        let existing = self.wallet_sets.iter().find(|set| str_of(set, "userId") == user_id);
+        match existing {
+            Some(wallet_set) => str_of(wallet_set, "id").to_string(),
+            None => str_of(&self.create_wallet_set(None, Some(user_id)), "id").to_string(),
+        }
+    }
+}
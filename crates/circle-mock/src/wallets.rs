@@ -0,0 +1,399 @@
+//! Wallet sets, wallets, tokens, balances and NFTs.
+
+use circle_core::{CircleErrorCode, Network, TokenAmount};
+use serde_json::{Value, json};
+
+use crate::{
+    chain::{self, Family},
+    error::ApiError,
+    state::{Store, WalletSpec, by_id, by_id_mut, new_id, now, set, str_of, token_amount},
+};
+
+/// Smart-contract core of newly created `SCA` wallets.
+const DEFAULT_SCA_CORE: &str = "circle_6900_singleowner_v2";
+
+impl Store {
+    pub(crate) fn seed_tokens(&mut self) {
+        for chain in chain::all() {
+            if let Some(native) = chain.native_currency() {
+                self.tokens.push(token_json(
+                    chain.as_str(),
+                    native.symbol,
+                    chain::native_name(native.symbol),
+                    native.decimals,
+                    None,
+                    None,
+                ));
+            }
+        }
+        for (blockchain, address) in chain::USDC {
+            self.tokens.push(token_json(
+                blockchain,
+                "USDC",
+                "USD Coin",
+                6,
+                Some(address),
+                Some("ERC20"),
+            ));
+        }
+    }
+
+    pub(crate) fn token(&self, id: &str) -> Option<&Value> {
+        by_id(&self.tokens, id)
+    }
+
+    pub(crate) fn token_by_symbol(&self, blockchain: &str, symbol: &str) -> Option<&Value> {
+        self.tokens.iter().find(|token| {
+            str_of(token, "blockchain") == blockchain && str_of(token, "symbol") == symbol
+        })
+    }
+
+    pub(crate) fn native_token(&self, blockchain: &str) -> Option<&Value> {
+        self.tokens.iter().find(|token| {
+            str_of(token, "blockchain") == blockchain && token["isNative"] == Value::Bool(true)
+        })
+    }
+
+    /// The token at `token_address` on `blockchain`; an empty address means
+    /// the native token.
+    pub(crate) fn token_by_address(&self, blockchain: &str, token_address: &str) -> Option<&Value> {
+        if token_address.is_empty() {
+            return self.native_token(blockchain);
+        }
+        let family = chain::family(chain::lookup(blockchain)?);
+        self.tokens.iter().find(|token| {
+            str_of(token, "blockchain") == blockchain &&
+                chain::same_address(family, str_of(token, "tokenAddress"), token_address)
+        })
+    }
+
+    pub(crate) fn register_token(
+        &mut self,
+        blockchain: &str,
+        symbol: &str,
+        decimals: u32,
+        token_address: Option<&str>,
+        standard: &str,
+    ) -> Result<String, ApiError> {
+        let chain = known_chain(blockchain)?;
+        let family = chain::family(chain);
+        if let Some(address) = token_address.filter(|a| !chain::is_valid_address(family, a)) {
+            return Err(ApiError::invalid("tokenAddress", address));
+        }
+        let token =
+            token_json(chain.as_str(), symbol, symbol, decimals, token_address, Some(standard));
+        let id = str_of(&token, "id").to_string();
+        self.tokens.push(token);
+        Ok(id)
+    }
+
+    pub(crate) fn wallet(&self, id: &str) -> Option<&Value> {
+        by_id(&self.wallets, id)
+    }
+
+    pub(crate) fn wallet_mut(&mut self, id: &str) -> Option<&mut Value> {
+        by_id_mut(&mut self.wallets, id)
+    }
+
+    pub(crate) fn require_wallet(&self, id: &str) -> Result<&Value, ApiError> {
+        self.wallet(id)
+            .ok_or_else(|| ApiError::not_found(CircleErrorCode::WalletIdNotFound, "wallet", id))
+    }
+
+    pub(crate) fn wallet_by_address(&self, blockchain: &str, address: &str) -> Option<&Value> {
+        let family = chain::family(chain::lookup(blockchain)?);
+        self.wallets.iter().find(|wallet| {
+            str_of(wallet, "blockchain") == blockchain &&
+                chain::same_address(family, str_of(wallet, "address"), address)
+        })
+    }
+
+    pub(crate) fn wallet_set(&self, id: &str) -> Option<&Value> {
+        by_id(&self.wallet_sets, id)
+    }
+
+    pub(crate) fn create_wallet_set(&mut self, name: Option<&str>, user_id: Option<&str>) -> Value {
+        let timestamp = now();
+        let mut wallet_set = json!({
+            "id": new_id(),
+            "custodyType": if user_id.is_some() { "ENDUSER" } else { "DEVELOPER" },
+            "createDate": timestamp,
+            "updateDate": timestamp,
+        });
+        if let Some(name) = name {
+            set(&mut wallet_set, "name", name);
+        }
+        if let Some(user_id) = user_id {
+            set(&mut wallet_set, "userId", user_id);
+        }
+        self.wallet_sets.push(wallet_set.clone());
+        wallet_set
+    }
+
+    /// Creates `spec.count` wallets on every blockchain of `spec` in
+    /// `wallet_set_id`. EVM wallets with the same index share one address,
+    /// as they do on Circle.
+    pub(crate) fn create_wallets(
+        &mut self,
+        wallet_set_id: &str,
+        spec: &WalletSpec,
+        user_id: Option<&str>,
+    ) -> Vec<Value> {
+        let mut created = Vec::new();
+        for index in 0..spec.count {
+            let evm_address = chain::random_address(Family::Evm);
+            let metadata = spec.metadata.get(index);
+            for &chain in &spec.blockchains {
+                let address = match chain::family(chain) {
+                    Family::Evm => evm_address.clone(),
+                    family => chain::random_address(family),
+                };
+                let wallet = self.insert_wallet(
+                    chain,
+                    &address,
+                    wallet_set_id,
+                    &spec.account_type,
+                    metadata,
+                    user_id,
+                );
+                created.push(wallet);
+            }
+        }
+        created
+    }
+
+    fn insert_wallet(
+        &mut self,
+        chain: Network,
+        address: &str,
+        wallet_set_id: &str,
+        account_type: &str,
+        metadata: Option<&Value>,
+        user_id: Option<&str>,
+    ) -> Value {
+        let timestamp = now();
+        let mut wallet = json!({
+            "id": new_id(),
+            "address": address,
+            "blockchain": chain.as_str(),
+            "createDate": timestamp,
+            "updateDate": timestamp,
+            "custodyType": if user_id.is_some() { "ENDUSER" } else { "DEVELOPER" },
+            "state": "LIVE",
+            "walletSetId": wallet_set_id,
+            "accountType": account_type,
+        });
+        if account_type == "SCA" {
+            set(&mut wallet, "scaCore", DEFAULT_SCA_CORE);
+        }
+        if let Some(user_id) = user_id {
+            set(&mut wallet, "userId", user_id);
+        }
+        for key in ["name", "refId"] {
+            if let Some(value) = metadata.and_then(|m| m.get(key)).filter(|v| v.is_string()) {
+                set(&mut wallet, key, value.clone());
+            }
+        }
+        self.wallets.push(wallet.clone());
+        wallet
+    }
+
+    pub(crate) fn create_developer_wallet(&mut self, blockchain: &str) -> Result<Value, ApiError> {
+        let chain = known_chain(blockchain)?;
+        let wallet_set = self.create_wallet_set(Some("circle-mock"), None);
+        let spec = WalletSpec {
+            blockchains: vec![chain],
+            account_type: "EOA".to_string(),
+            count: 1,
+            metadata: Vec::new(),
+        };
+        let mut wallets = self.create_wallets(str_of(&wallet_set, "id"), &spec, None);
+        wallets.pop().ok_or_else(|| ApiError::invalid("blockchain", blockchain))
+    }
+
+    /// Derives `source` onto `target`: returns the existing wallet with the
+    /// same address and wallet set (`false`), with its metadata updated, or
+    /// creates it (`true`).
+    pub(crate) fn derive_wallet(
+        &mut self,
+        source: &Value,
+        target: &str,
+        metadata: Option<&Value>,
+    ) -> Result<(bool, Value), ApiError> {
+        let target = known_chain(target)?;
+        let source_chain = known_chain(str_of(source, "blockchain"))?;
+        if !source_chain.is_evm() || !target.is_evm() {
+            return Err(ApiError::rejected(
+                CircleErrorCode::OperationNotSupported,
+                "Only EVM wallets can be derived onto another EVM blockchain.",
+            ));
+        }
+        let address = str_of(source, "address");
+        let wallet_set_id = str_of(source, "walletSetId");
+        if let Some(existing) = self.wallets.iter_mut().find(|wallet| {
+            str_of(wallet, "blockchain") == target.as_str() &&
+                str_of(wallet, "walletSetId") == wallet_set_id &&
+                chain::same_address(Family::Evm, str_of(wallet, "address"), address)
+        }) {
+            if let Some(metadata) = metadata {
+                for key in ["name", "refId"] {
+                    if let Some(value) = metadata.get(key).filter(|v| v.is_string()) {
+                        set(existing, key, value.clone());
+                    }
+                }
+                set(existing, "updateDate", now());
+            }
+            return Ok((false, existing.clone()));
+        }
+        let user_id = source.get("userId").and_then(Value::as_str);
+        let metadata = metadata
+            .cloned()
+            .unwrap_or_else(|| json!({ "name": source.get("name"), "refId": source.get("refId") }));
+        let wallet = self.insert_wallet(
+            target,
+            address,
+            wallet_set_id,
+            str_of(source, "accountType"),
+            Some(&metadata),
+            user_id,
+        );
+        Ok((true, wallet))
+    }
+
+    pub(crate) fn set_balance(
+        &mut self,
+        wallet_id: &str,
+        token_id: &str,
+        amount: &str,
+    ) -> Result<(), ApiError> {
+        self.require_wallet(wallet_id)?;
+        let decimals = self.token_decimals(token_id)?;
+        let amount =
+            token_amount(amount, decimals).ok_or_else(|| ApiError::invalid("amount", amount))?;
+        self.balances
+            .entry(wallet_id.to_string())
+            .or_default()
+            .insert(token_id.to_string(), amount);
+        Ok(())
+    }
+
+    pub(crate) fn balance(&self, wallet_id: &str, token_id: &str) -> Option<String> {
+        self.balances.get(wallet_id)?.get(token_id).map(ToString::to_string)
+    }
+
+    pub(crate) fn balance_amount(&self, wallet_id: &str, token_id: &str) -> TokenAmount {
+        self.balances
+            .get(wallet_id)
+            .and_then(|tokens| tokens.get(token_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn credit(&mut self, wallet_id: &str, token_id: &str, amount: &TokenAmount) {
+        let balance = self.balances.entry(wallet_id.to_string()).or_default();
+        *balance.entry(token_id.to_string()).or_default() += amount;
+    }
+
+    pub(crate) fn debit(&mut self, wallet_id: &str, token_id: &str, amount: &TokenAmount) {
+        if let Some(entry) = self.balances.get_mut(wallet_id).and_then(|b| b.get_mut(token_id)) {
+            *entry = entry.checked_sub(amount).unwrap_or_default();
+        }
+    }
+
+    pub(crate) fn token_decimals(&self, token_id: &str) -> Result<u32, ApiError> {
+        let token = self.token(token_id).ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TokenIdNotFound, "token", token_id)
+        })?;
+        Ok(token["decimals"].as_u64().map_or(0, |d| d as u32))
+    }
+
+    pub(crate) fn add_nft(
+        &mut self,
+        wallet_id: &str,
+        token_id: &str,
+        nft_token_id: &str,
+    ) -> Result<(), ApiError> {
+        self.require_wallet(wallet_id)?;
+        let token = self.token(token_id).ok_or_else(|| {
+            ApiError::not_found(CircleErrorCode::TokenIdNotFound, "token", token_id)
+        })?;
+        if !matches!(str_of(token, "standard"), "ERC721" | "ERC1155") {
+            return Err(ApiError::invalid("tokenId", "not an NFT collection"));
+        }
+        self.nfts
+            .entry(wallet_id.to_string())
+            .or_default()
+            .push((token_id.to_string(), nft_token_id.to_string()));
+        Ok(())
+    }
+
+    /// The non-zero fungible balances of a wallet, as the API returns them.
+    pub(crate) fn balances_json(&self, wallet_id: &str) -> Vec<Value> {
+        let Some(balances) = self.balances.get(wallet_id) else {
+            return Vec::new();
+        };
+        balances
+            .iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .filter_map(|(token_id, amount)| {
+                let token = self.token(token_id)?;
+                Some(json!({
+                    "amount": amount.to_string(),
+                    "token": token,
+                    "updateDate": str_of(token, "updateDate"),
+                }))
+            })
+            .collect()
+    }
+
+    /// The NFTs held by a wallet, as the API returns them.
+    pub(crate) fn nfts_json(&self, wallet_id: &str) -> Vec<Value> {
+        let Some(nfts) = self.nfts.get(wallet_id) else {
+            return Vec::new();
+        };
+        nfts.iter()
+            .filter_map(|(token_id, nft_token_id)| {
+                let token = self.token(token_id)?;
+                Some(json!({
+                    "amount": "1",
+                    "token": token,
+                    "nftTokenId": nft_token_id,
+                    "updateDate": str_of(token, "updateDate"),
+                }))
+            })
+            .collect()
+    }
+}
+
+/// The blockchain named `name`, or a `400` naming the bad value.
+pub(crate) fn known_chain(name: &str) -> Result<Network, ApiError> {
+    chain::lookup(name).ok_or_else(|| ApiError::invalid("blockchain", name))
+}
+
+fn token_json(
+    blockchain: &str,
+    symbol: &str,
+    name: &str,
+    decimals: u32,
+    token_address: Option<&str>,
+    standard: Option<&str>,
+) -> Value {
+    let timestamp = now();
+    let mut token = json!({
+        "id": new_id(),
+        "blockchain": blockchain,
+        "name": name,
+        "symbol": symbol,
+        "decimals": decimals,
+        "isNative": token_address.is_none(),
+        "createDate": timestamp,
+        "updateDate": timestamp,
+    });
+    if let Some(address) = token_address {
+        set(&mut token, "tokenAddress", address);
+    }
+    if let Some(standard) = standard {
+        set(&mut token, "standard", standard);
+    }
+    token
+}
@@ -1,5 +1,12 @@
 //! HTTP client for the User-Controlled Wallets API.
 
+use circle_core::{
+    ApiResponse, ClientBuilder, DEFAULT_BASE_URL, RequestOptions, RequestPath, RestClient,
+    api_path,
+    ids::{ChallengeId, TokenId, TransactionId, UserId, WalletId},
+    pagination::{self, Stream, StreamOptions},
+};
+
 use crate::{
     error::Error,
     models::{
@@ -9,208 +16,156 @@ use crate::{
             ResendOtpRequest, ResendOtpResponse,
         },
         challenge::{
-            ChallengeIdResponse, ChallengeResponse, Challenges, SetPinAndInitWalletRequest,
-            SetPinRequest,
+            Challenge, ChallengeIdResponse, ChallengeResponse, Challenges,
+            SetPinAndInitWalletRequest, SetPinRequest,
         },
-        common::ApiErrorBody,
         signing::{SignMessageRequest, SignTransactionRequest, SignTypedDataRequest},
         transaction::{
             AccelerateTxRequest, CancelTxRequest, CreateContractExecutionTxRequest,
             CreateTransferTxRequest, CreateWalletUpgradeTxRequest, EstimateContractExecFeeRequest,
             EstimateTransactionFee, EstimateTransferFeeRequest, GetLowestNonceTransactionResponse,
-            GetLowestNonceTxParams, ListTransactionsParams, TransactionResponse, Transactions,
-            ValidateAddressRequest, ValidateAddressResponse,
+            GetLowestNonceTxParams, ListTransactionsParams, Transaction, TransactionResponse,
+            Transactions, ValidateAddressRequest, ValidateAddressResponse,
         },
         user::{
-            CreateUserRequest, GetUserByIdResponse, GetUserTokenRequest, ListUsersParams,
+            CreateUserRequest, EndUser, GetUserByIdResponse, GetUserTokenRequest, ListUsersParams,
             UserResponse, UserTokenResponse, Users,
         },
         wallet::{
-            Balances, CreateEndUserWalletRequest, ListWalletBalancesParams, ListWalletNftsParams,
-            ListWalletsParams, Nfts, TokenResponse, UpdateWalletRequest, WalletResponse, Wallets,
+            Balance, Balances, CreateEndUserWalletRequest, ListWalletBalancesParams,
+            ListWalletNftsParams, ListWalletsParams, Nft, Nfts, TokenResponse, UpdateWalletRequest,
+            Wallet, WalletResponse, Wallets,
         },
     },
 };
 
 /// Async HTTP client for the Circle W3S User-Controlled Wallets API.
 pub struct UserWalletsClient {
-    /// Base URL for the Circle API (defaults to `https://api.circle.com`).
-    base_url: String,
-    /// API key used in `Authorization: Bearer` header.
-    api_key: String,
-    /// Underlying HTTP client.
-    http: hpx::Client,
+    /// Shared request pipeline (base URL, API key and HTTP client).
+    inner: RestClient,
 }
 
 impl std::fmt::Debug for UserWalletsClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UserWalletsClient")
-            .field("base_url", &self.base_url)
+            .field("base_url", &self.inner.base_url())
             .field("api_key", &"<redacted>")
             .finish_non_exhaustive()
     }
 }
 
+impl From<RestClient> for UserWalletsClient {
+    fn from(inner: RestClient) -> Self {
+        Self { inner }
+    }
+}
+
 impl UserWalletsClient {
     /// Creates a new client using the Circle production base URL.
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self::with_base_url(api_key, "https://api.circle.com")
+        Self::with_base_url(api_key, DEFAULT_BASE_URL)
     }
 
-    /// Creates a new client with a custom base URL (useful for Prism mock servers).
+    /// Creates a new client with a custom base URL (useful for the `circle-mock` server).
     pub fn with_base_url(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
-        Self { base_url: base_url.into(), api_key: api_key.into(), http: hpx::Client::new() }
+        Self { inner: RestClient::new(api_key, base_url) }
+    }
+
+    /// Starts a [`ClientBuilder`] for timeouts, proxy, TLS roots, default
+    /// headers or a shared `hpx::Client`.
+    pub fn builder(api_key: impl Into<String>) -> ClientBuilder<Self> {
+        ClientBuilder::new(api_key)
+    }
+
+    /// Returns a copy of this client whose requests use `options`: a caller
+    /// request id, a per-call timeout, extra headers or a retry override.
+    #[must_use]
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self { inner: self.inner.with_options(options) }
     }
 
     // ── Private HTTP helpers ──────────────────────────────────────────────
 
     /// Authenticated GET request, no user token.
-    async fn get<T, P>(&self, path: &str, params: &P) -> Result<T, Error>
+    async fn get<T, P>(
+        &self,
+        path: impl Into<RequestPath>,
+        params: &P,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         P: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .query(params)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        Self::decode(resp).await
+        self.inner.get(path).query(params).send_with_meta().await
     }
 
     /// Authenticated POST request, no user token.
-    async fn post<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+    async fn post<T, B>(
+        &self,
+        path: impl Into<RequestPath>,
+        body: &B,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        Self::decode(resp).await
+        self.inner.post(path).json(body).send_with_meta().await
     }
 
     /// Authenticated PUT request, no user token.
     #[expect(dead_code)]
-    async fn put<T, B>(&self, path: &str, body: &B) -> Result<T, Error>
+    async fn put<T, B>(
+        &self,
+        path: impl Into<RequestPath>,
+        body: &B,
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        Self::decode(resp).await
+        self.inner.put(path).json(body).send_with_meta().await
     }
 
     /// Authenticated GET request with an additional `X-User-Token` header.
     async fn get_with_user_token<T, P>(
         &self,
-        path: &str,
+        path: impl Into<RequestPath>,
         params: &P,
         user_token: &str,
-    ) -> Result<T, Error>
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         P: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-User-Token", user_token)
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .query(params)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        Self::decode(resp).await
+        self.inner.get(path).user_token(user_token).query(params).send_with_meta().await
     }
 
     /// Authenticated POST request with an additional `X-User-Token` header.
     async fn post_with_user_token<T, B>(
         &self,
-        path: &str,
+        path: impl Into<RequestPath>,
         body: &B,
         user_token: &str,
-    ) -> Result<T, Error>
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-User-Token", user_token)
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        Self::decode(resp).await
+        self.inner.post(path).user_token(user_token).json(body).send_with_meta().await
     }
 
     /// Authenticated PUT request with an additional `X-User-Token` header.
     async fn put_with_user_token<T, B>(
         &self,
-        path: &str,
+        path: impl Into<RequestPath>,
         body: &B,
         user_token: &str,
-    ) -> Result<T, Error>
+    ) -> Result<ApiResponse<T>, Error>
     where
         T: serde::de::DeserializeOwned,
         B: serde::Serialize + ?Sized,
     {
-        let url = format!("{}{}", self.base_url, path);
-        let resp = self
-            .http
-            .put(&url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("X-User-Token", user_token)
-            .header("X-Request-Id", uuid::Uuid::new_v4().to_string())
-            .json(body)
-            .send()
-            .await
-            .map_err(|e| Error::Http(e.to_string()))?;
-
-        Self::decode(resp).await
-    }
-
-    /// Decode a response: if 2xx parse as `T`, otherwise parse as [`ApiErrorBody`].
-    async fn decode<T: serde::de::DeserializeOwned>(resp: hpx::Response) -> Result<T, Error> {
-        if resp.status().is_success() {
-            resp.json::<T>().await.map_err(|e| Error::Http(e.to_string()))
-        } else {
-            let err: ApiErrorBody = resp.json().await.map_err(|e| Error::Http(e.to_string()))?;
-            Err(Error::Api { code: err.code, message: err.message })
-        }
+        self.inner.put(path).user_token(user_token).json(body).send_with_meta().await
     }
 
     // ── User Management ───────────────────────────────────────────────────
@@ -219,6 +174,14 @@ impl UserWalletsClient {
     ///
     /// `POST /v1/w3s/users`
     pub async fn create_user(&self, req: &CreateUserRequest) -> Result<UserResponse, Error> {
+        self.create_user_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_user`], but also returns the response metadata.
+    pub async fn create_user_with_meta(
+        &self,
+        req: &CreateUserRequest,
+    ) -> Result<ApiResponse<UserResponse>, Error> {
         self.post("/v1/w3s/users", req).await
     }
 
@@ -226,15 +189,42 @@ impl UserWalletsClient {
     ///
     /// `GET /v1/w3s/users`
     pub async fn list_users(&self, params: &ListUsersParams) -> Result<Users, Error> {
+        self.list_users_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_users`], but also returns the response metadata.
+    pub async fn list_users_with_meta(
+        &self,
+        params: &ListUsersParams,
+    ) -> Result<ApiResponse<Users>, Error> {
         self.get("/v1/w3s/users", params).await
     }
 
+    /// Stream all end-users matching the given filters, [page by page](circle_core::pagination).
+    pub fn list_users_stream<'a>(
+        &'a self,
+        params: &ListUsersParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<EndUser, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_users(&params).await.map(|page| page.data.users)
+        })
+    }
+
     /// Retrieve an end-user by their Circle user ID.
     ///
     /// `GET /v1/w3s/users/{id}`
-    pub async fn get_user(&self, id: &str) -> Result<GetUserByIdResponse, Error> {
-        let path = format!("/v1/w3s/users/{id}");
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_user(&self, id: &UserId) -> Result<GetUserByIdResponse, Error> {
+        self.get_user_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_user`], but also returns the response metadata.
+    pub async fn get_user_with_meta(
+        &self,
+        id: &UserId,
+    ) -> Result<ApiResponse<GetUserByIdResponse>, Error> {
+        let path = api_path!("/v1/w3s/users/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     /// Obtain a short-lived user token for the given user ID.
@@ -244,6 +234,14 @@ impl UserWalletsClient {
         &self,
         req: &GetUserTokenRequest,
     ) -> Result<UserTokenResponse, Error> {
+        self.get_user_token_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_user_token`], but also returns the response metadata.
+    pub async fn get_user_token_with_meta(
+        &self,
+        req: &GetUserTokenRequest,
+    ) -> Result<ApiResponse<UserTokenResponse>, Error> {
         self.post("/v1/w3s/users/token", req).await
     }
 
@@ -256,6 +254,14 @@ impl UserWalletsClient {
         &self,
         req: &DeviceTokenSocialRequest,
     ) -> Result<DeviceTokenSocialResponse, Error> {
+        self.get_device_token_social_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_device_token_social`], but also returns the response metadata.
+    pub async fn get_device_token_social_with_meta(
+        &self,
+        req: &DeviceTokenSocialRequest,
+    ) -> Result<ApiResponse<DeviceTokenSocialResponse>, Error> {
         self.post("/v1/w3s/users/social/token", req).await
     }
 
@@ -266,6 +272,14 @@ impl UserWalletsClient {
         &self,
         req: &DeviceTokenEmailRequest,
     ) -> Result<DeviceTokenEmailResponse, Error> {
+        self.get_device_token_email_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_device_token_email`], but also returns the response metadata.
+    pub async fn get_device_token_email_with_meta(
+        &self,
+        req: &DeviceTokenEmailRequest,
+    ) -> Result<ApiResponse<DeviceTokenEmailResponse>, Error> {
         self.post("/v1/w3s/users/email/token", req).await
     }
 
@@ -277,6 +291,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &RefreshUserTokenRequest,
     ) -> Result<RefreshUserTokenResponse, Error> {
+        self.refresh_user_token_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::refresh_user_token`], but also returns the response metadata.
+    pub async fn refresh_user_token_with_meta(
+        &self,
+        user_token: &str,
+        req: &RefreshUserTokenRequest,
+    ) -> Result<ApiResponse<RefreshUserTokenResponse>, Error> {
         self.post_with_user_token("/v1/w3s/users/token/refresh", req, user_token).await
     }
 
@@ -288,6 +311,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &ResendOtpRequest,
     ) -> Result<ResendOtpResponse, Error> {
+        self.resend_otp_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::resend_otp`], but also returns the response metadata.
+    pub async fn resend_otp_with_meta(
+        &self,
+        user_token: &str,
+        req: &ResendOtpRequest,
+    ) -> Result<ApiResponse<ResendOtpResponse>, Error> {
         self.post_with_user_token("/v1/w3s/users/email/resendOTP", req, user_token).await
     }
 
@@ -297,6 +329,14 @@ impl UserWalletsClient {
     ///
     /// `GET /v1/w3s/user`
     pub async fn get_user_by_token(&self, user_token: &str) -> Result<UserResponse, Error> {
+        self.get_user_by_token_with_meta(user_token).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_user_by_token`], but also returns the response metadata.
+    pub async fn get_user_by_token_with_meta(
+        &self,
+        user_token: &str,
+    ) -> Result<ApiResponse<UserResponse>, Error> {
         self.get_with_user_token("/v1/w3s/user", &[("", "")][..0], user_token).await
     }
 
@@ -310,6 +350,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SetPinAndInitWalletRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.initialize_user_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::initialize_user`], but also returns the response metadata.
+    pub async fn initialize_user_with_meta(
+        &self,
+        user_token: &str,
+        req: &SetPinAndInitWalletRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/initialize", req, user_token).await
     }
 
@@ -321,6 +370,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SetPinRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.create_pin_challenge_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_pin_challenge`], but also returns the response metadata.
+    pub async fn create_pin_challenge_with_meta(
+        &self,
+        user_token: &str,
+        req: &SetPinRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/pin", req, user_token).await
     }
 
@@ -332,6 +390,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SetPinRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.update_pin_challenge_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::update_pin_challenge`], but also returns the response metadata.
+    pub async fn update_pin_challenge_with_meta(
+        &self,
+        user_token: &str,
+        req: &SetPinRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.put_with_user_token("/v1/w3s/user/pin", req, user_token).await
     }
 
@@ -343,6 +410,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SetPinRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.restore_pin_challenge_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::restore_pin_challenge`], but also returns the response metadata.
+    pub async fn restore_pin_challenge_with_meta(
+        &self,
+        user_token: &str,
+        req: &SetPinRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/pin/restore", req, user_token).await
     }
 
@@ -350,19 +426,50 @@ impl UserWalletsClient {
     ///
     /// `GET /v1/w3s/user/challenges`
     pub async fn list_challenges(&self, user_token: &str) -> Result<Challenges, Error> {
+        self.list_challenges_with_meta(user_token).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_challenges`], but also returns the response metadata.
+    pub async fn list_challenges_with_meta(
+        &self,
+        user_token: &str,
+    ) -> Result<ApiResponse<Challenges>, Error> {
         self.get_with_user_token("/v1/w3s/user/challenges", &[("", "")][..0], user_token).await
     }
 
+    /// Stream every challenge of the user.
+    ///
+    /// The endpoint returns all challenges in one response, so only
+    /// [`StreamOptions::max_items`] applies.
+    pub fn list_challenges_stream<'a>(
+        &'a self,
+        user_token: &'a str,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Challenge, Error>> + use<'a> {
+        pagination::single_page(options, async move {
+            self.list_challenges(user_token).await.map(|page| page.data.challenges)
+        })
+    }
+
     /// Retrieve a single challenge by its ID.
     ///
     /// `GET /v1/w3s/user/challenges/{id}`
     pub async fn get_challenge(
         &self,
         user_token: &str,
-        id: &str,
+        id: &ChallengeId,
     ) -> Result<ChallengeResponse, Error> {
-        let path = format!("/v1/w3s/user/challenges/{id}");
-        self.get_with_user_token(&path, &[("", "")][..0], user_token).await
+        self.get_challenge_with_meta(user_token, id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_challenge`], but also returns the response metadata.
+    pub async fn get_challenge_with_meta(
+        &self,
+        user_token: &str,
+        id: &ChallengeId,
+    ) -> Result<ApiResponse<ChallengeResponse>, Error> {
+        let path = api_path!("/v1/w3s/user/challenges/{id}");
+        self.get_with_user_token(path, &[("", "")][..0], user_token).await
     }
 
     // ── Wallets ───────────────────────────────────────────────────────────
@@ -378,6 +485,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &CreateEndUserWalletRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.create_wallet_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_wallet`], but also returns the response metadata.
+    pub async fn create_wallet_with_meta(
+        &self,
+        user_token: &str,
+        req: &CreateEndUserWalletRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/wallets", req, user_token).await
     }
 
@@ -389,15 +505,49 @@ impl UserWalletsClient {
         user_token: &str,
         params: &ListWalletsParams,
     ) -> Result<Wallets, Error> {
+        self.list_wallets_with_meta(user_token, params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallets`], but also returns the response metadata.
+    pub async fn list_wallets_with_meta(
+        &self,
+        user_token: &str,
+        params: &ListWalletsParams,
+    ) -> Result<ApiResponse<Wallets>, Error> {
         self.get_with_user_token("/v1/w3s/wallets", params, user_token).await
     }
 
+    /// Stream wallets of the authenticated user, [page by page](circle_core::pagination).
+    pub fn list_wallets_stream<'a>(
+        &'a self,
+        user_token: &'a str,
+        params: &ListWalletsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Wallet, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallets(user_token, &params).await.map(|page| page.data.wallets)
+        })
+    }
+
     /// Retrieve a single wallet by its ID.
     ///
     /// `GET /v1/w3s/wallets/{id}`
-    pub async fn get_wallet(&self, user_token: &str, id: &str) -> Result<WalletResponse, Error> {
-        let path = format!("/v1/w3s/wallets/{id}");
-        self.get_with_user_token(&path, &[("", "")][..0], user_token).await
+    pub async fn get_wallet(
+        &self,
+        user_token: &str,
+        id: &WalletId,
+    ) -> Result<WalletResponse, Error> {
+        self.get_wallet_with_meta(user_token, id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_wallet`], but also returns the response metadata.
+    pub async fn get_wallet_with_meta(
+        &self,
+        user_token: &str,
+        id: &WalletId,
+    ) -> Result<ApiResponse<WalletResponse>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{id}");
+        self.get_with_user_token(path, &[("", "")][..0], user_token).await
     }
 
     /// Update the name or reference ID of a wallet.
@@ -406,11 +556,21 @@ impl UserWalletsClient {
     pub async fn update_wallet(
         &self,
         user_token: &str,
-        id: &str,
+        id: &WalletId,
         req: &UpdateWalletRequest,
     ) -> Result<WalletResponse, Error> {
-        let path = format!("/v1/w3s/wallets/{id}");
-        self.put_with_user_token(&path, req, user_token).await
+        self.update_wallet_with_meta(user_token, id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::update_wallet`], but also returns the response metadata.
+    pub async fn update_wallet_with_meta(
+        &self,
+        user_token: &str,
+        id: &WalletId,
+        req: &UpdateWalletRequest,
+    ) -> Result<ApiResponse<WalletResponse>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{id}");
+        self.put_with_user_token(path, req, user_token).await
     }
 
     /// List token balances for a wallet.
@@ -419,11 +579,39 @@ impl UserWalletsClient {
     pub async fn list_wallet_balances(
         &self,
         user_token: &str,
-        wallet_id: &str,
+        wallet_id: &WalletId,
         params: &ListWalletBalancesParams,
     ) -> Result<Balances, Error> {
-        let path = format!("/v1/w3s/wallets/{wallet_id}/balances");
-        self.get_with_user_token(&path, params, user_token).await
+        self.list_wallet_balances_with_meta(user_token, wallet_id, params)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_balances`], but also returns the response metadata.
+    pub async fn list_wallet_balances_with_meta(
+        &self,
+        user_token: &str,
+        wallet_id: &WalletId,
+        params: &ListWalletBalancesParams,
+    ) -> Result<ApiResponse<Balances>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{wallet_id}/balances");
+        self.get_with_user_token(path, params, user_token).await
+    }
+
+    /// Stream token balances for a wallet, [page by page](circle_core::pagination).
+    pub fn list_wallet_balances_stream<'a>(
+        &'a self,
+        user_token: &'a str,
+        wallet_id: &WalletId,
+        params: &ListWalletBalancesParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Balance, Error>> + use<'a> {
+        let wallet_id = *wallet_id;
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_balances(user_token, &wallet_id, &params)
+                .await
+                .map(|page| page.data.token_balances)
+        })
     }
 
     /// List NFTs held by a wallet.
@@ -432,11 +620,37 @@ impl UserWalletsClient {
     pub async fn list_wallet_nfts(
         &self,
         user_token: &str,
-        wallet_id: &str,
+        wallet_id: &WalletId,
         params: &ListWalletNftsParams,
     ) -> Result<Nfts, Error> {
-        let path = format!("/v1/w3s/wallets/{wallet_id}/nfts");
-        self.get_with_user_token(&path, params, user_token).await
+        self.list_wallet_nfts_with_meta(user_token, wallet_id, params)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_wallet_nfts`], but also returns the response metadata.
+    pub async fn list_wallet_nfts_with_meta(
+        &self,
+        user_token: &str,
+        wallet_id: &WalletId,
+        params: &ListWalletNftsParams,
+    ) -> Result<ApiResponse<Nfts>, Error> {
+        let path = api_path!("/v1/w3s/wallets/{wallet_id}/nfts");
+        self.get_with_user_token(path, params, user_token).await
+    }
+
+    /// Stream NFTs held by a wallet, [page by page](circle_core::pagination).
+    pub fn list_wallet_nfts_stream<'a>(
+        &'a self,
+        user_token: &'a str,
+        wallet_id: &WalletId,
+        params: &ListWalletNftsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Nft, Error>> + use<'a> {
+        let wallet_id = *wallet_id;
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_wallet_nfts(user_token, &wallet_id, &params).await.map(|page| page.data.nfts)
+        })
     }
 
     // ── Transactions ──────────────────────────────────────────────────────
@@ -449,6 +663,18 @@ impl UserWalletsClient {
         user_token: &str,
         req: &CreateTransferTxRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.create_transfer_transaction_with_meta(user_token, req)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_transfer_transaction`], but also returns the response metadata.
+    pub async fn create_transfer_transaction_with_meta(
+        &self,
+        user_token: &str,
+        req: &CreateTransferTxRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
+        req.validate()?;
         self.post_with_user_token("/v1/w3s/user/transactions/transfer", req, user_token).await
     }
 
@@ -458,11 +684,21 @@ impl UserWalletsClient {
     pub async fn accelerate_transaction(
         &self,
         user_token: &str,
-        id: &str,
+        id: &TransactionId,
         req: &AccelerateTxRequest,
     ) -> Result<ChallengeIdResponse, Error> {
-        let path = format!("/v1/w3s/user/transactions/{id}/accelerate");
-        self.post_with_user_token(&path, req, user_token).await
+        self.accelerate_transaction_with_meta(user_token, id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::accelerate_transaction`], but also returns the response metadata.
+    pub async fn accelerate_transaction_with_meta(
+        &self,
+        user_token: &str,
+        id: &TransactionId,
+        req: &AccelerateTxRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
+        let path = api_path!("/v1/w3s/user/transactions/{id}/accelerate");
+        self.post_with_user_token(path, req, user_token).await
     }
 
     /// Cancel a pending transaction (returns a challengeId).
@@ -471,11 +707,21 @@ impl UserWalletsClient {
     pub async fn cancel_transaction(
         &self,
         user_token: &str,
-        id: &str,
+        id: &TransactionId,
         req: &CancelTxRequest,
     ) -> Result<ChallengeIdResponse, Error> {
-        let path = format!("/v1/w3s/user/transactions/{id}/cancel");
-        self.post_with_user_token(&path, req, user_token).await
+        self.cancel_transaction_with_meta(user_token, id, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::cancel_transaction`], but also returns the response metadata.
+    pub async fn cancel_transaction_with_meta(
+        &self,
+        user_token: &str,
+        id: &TransactionId,
+        req: &CancelTxRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
+        let path = api_path!("/v1/w3s/user/transactions/{id}/cancel");
+        self.post_with_user_token(path, req, user_token).await
     }
 
     /// Initiate a smart-contract execution transaction (returns a challengeId).
@@ -486,6 +732,18 @@ impl UserWalletsClient {
         user_token: &str,
         req: &CreateContractExecutionTxRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.create_contract_execution_transaction_with_meta(user_token, req)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_contract_execution_transaction`], but also returns the response
+    /// metadata.
+    pub async fn create_contract_execution_transaction_with_meta(
+        &self,
+        user_token: &str,
+        req: &CreateContractExecutionTxRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/transactions/contractExecution", req, user_token)
             .await
     }
@@ -498,6 +756,17 @@ impl UserWalletsClient {
         user_token: &str,
         req: &CreateWalletUpgradeTxRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.create_wallet_upgrade_transaction_with_meta(user_token, req)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::create_wallet_upgrade_transaction`], but also returns the response metadata.
+    pub async fn create_wallet_upgrade_transaction_with_meta(
+        &self,
+        user_token: &str,
+        req: &CreateWalletUpgradeTxRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/transactions/walletUpgrade", req, user_token).await
     }
 
@@ -509,28 +778,68 @@ impl UserWalletsClient {
         user_token: &str,
         params: &ListTransactionsParams,
     ) -> Result<Transactions, Error> {
+        self.list_transactions_with_meta(user_token, params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::list_transactions`], but also returns the response metadata.
+    pub async fn list_transactions_with_meta(
+        &self,
+        user_token: &str,
+        params: &ListTransactionsParams,
+    ) -> Result<ApiResponse<Transactions>, Error> {
         self.get_with_user_token("/v1/w3s/transactions", params, user_token).await
     }
 
+    /// Stream transactions of the authenticated user, [page by page](circle_core::pagination).
+    pub fn list_transactions_stream<'a>(
+        &'a self,
+        user_token: &'a str,
+        params: &ListTransactionsParams,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Transaction, Error>> + use<'a> {
+        pagination::paginate(params.clone(), options, move |params| async move {
+            self.list_transactions(user_token, &params).await.map(|page| page.data.transactions)
+        })
+    }
+
     /// Retrieve a single transaction by its ID.
     ///
     /// `GET /v1/w3s/transactions/{id}`
     pub async fn get_transaction(
         &self,
         user_token: &str,
-        id: &str,
+        id: &TransactionId,
     ) -> Result<TransactionResponse, Error> {
-        let path = format!("/v1/w3s/transactions/{id}");
-        self.get_with_user_token(&path, &[("", "")][..0], user_token).await
+        self.get_transaction_with_meta(user_token, id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_transaction`], but also returns the response metadata.
+    pub async fn get_transaction_with_meta(
+        &self,
+        user_token: &str,
+        id: &TransactionId,
+    ) -> Result<ApiResponse<TransactionResponse>, Error> {
+        let path = api_path!("/v1/w3s/transactions/{id}");
+        self.get_with_user_token(path, &[("", "")][..0], user_token).await
     }
 
     /// Retrieve the transaction with the lowest pending nonce for an address.
     ///
+    /// Returns `None` when there is no such transaction (`204 No Content`).
+    ///
     /// `GET /v1/w3s/transactions/lowestNonceTransaction`
     pub async fn get_lowest_nonce_transaction(
         &self,
         params: &GetLowestNonceTxParams,
-    ) -> Result<GetLowestNonceTransactionResponse, Error> {
+    ) -> Result<Option<GetLowestNonceTransactionResponse>, Error> {
+        self.get_lowest_nonce_transaction_with_meta(params).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_lowest_nonce_transaction`], but also returns the response metadata.
+    pub async fn get_lowest_nonce_transaction_with_meta(
+        &self,
+        params: &GetLowestNonceTxParams,
+    ) -> Result<ApiResponse<Option<GetLowestNonceTransactionResponse>>, Error> {
         self.get("/v1/w3s/transactions/lowestNonceTransaction", params).await
     }
 
@@ -542,6 +851,16 @@ impl UserWalletsClient {
         user_token: &str,
         req: &EstimateTransferFeeRequest,
     ) -> Result<EstimateTransactionFee, Error> {
+        self.estimate_transfer_fee_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::estimate_transfer_fee`], but also returns the response metadata.
+    pub async fn estimate_transfer_fee_with_meta(
+        &self,
+        user_token: &str,
+        req: &EstimateTransferFeeRequest,
+    ) -> Result<ApiResponse<EstimateTransactionFee>, Error> {
+        req.validate()?;
         self.post_with_user_token("/v1/w3s/transactions/transfer/estimateFee", req, user_token)
             .await
     }
@@ -554,6 +873,17 @@ impl UserWalletsClient {
         user_token: &str,
         req: &EstimateContractExecFeeRequest,
     ) -> Result<EstimateTransactionFee, Error> {
+        self.estimate_contract_execution_fee_with_meta(user_token, req)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::estimate_contract_execution_fee`], but also returns the response metadata.
+    pub async fn estimate_contract_execution_fee_with_meta(
+        &self,
+        user_token: &str,
+        req: &EstimateContractExecFeeRequest,
+    ) -> Result<ApiResponse<EstimateTransactionFee>, Error> {
         self.post_with_user_token(
             "/v1/w3s/transactions/contractExecution/estimateFee",
             req,
@@ -569,6 +899,14 @@ impl UserWalletsClient {
         &self,
         req: &ValidateAddressRequest,
     ) -> Result<ValidateAddressResponse, Error> {
+        self.validate_address_with_meta(req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::validate_address`], but also returns the response metadata.
+    pub async fn validate_address_with_meta(
+        &self,
+        req: &ValidateAddressRequest,
+    ) -> Result<ApiResponse<ValidateAddressResponse>, Error> {
         self.post("/v1/w3s/transactions/validateAddress", req).await
     }
 
@@ -577,9 +915,17 @@ impl UserWalletsClient {
     /// Retrieve token metadata by its Circle token ID.
     ///
     /// `GET /v1/w3s/tokens/{id}`
-    pub async fn get_token(&self, id: &str) -> Result<TokenResponse, Error> {
-        let path = format!("/v1/w3s/tokens/{id}");
-        self.get(&path, &[("", "")][..0]).await
+    pub async fn get_token(&self, id: &TokenId) -> Result<TokenResponse, Error> {
+        self.get_token_with_meta(id).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::get_token`], but also returns the response metadata.
+    pub async fn get_token_with_meta(
+        &self,
+        id: &TokenId,
+    ) -> Result<ApiResponse<TokenResponse>, Error> {
+        let path = api_path!("/v1/w3s/tokens/{id}");
+        self.get(path, &[("", "")][..0]).await
     }
 
     // ── Signing ───────────────────────────────────────────────────────────
@@ -592,6 +938,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SignMessageRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.sign_message_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_message`], but also returns the response metadata.
+    pub async fn sign_message_with_meta(
+        &self,
+        user_token: &str,
+        req: &SignMessageRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/sign/message", req, user_token).await
     }
 
@@ -603,6 +958,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SignTypedDataRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.sign_typed_data_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_typed_data`], but also returns the response metadata.
+    pub async fn sign_typed_data_with_meta(
+        &self,
+        user_token: &str,
+        req: &SignTypedDataRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/sign/typedData", req, user_token).await
     }
 
@@ -614,6 +978,15 @@ impl UserWalletsClient {
         user_token: &str,
         req: &SignTransactionRequest,
     ) -> Result<ChallengeIdResponse, Error> {
+        self.sign_transaction_with_meta(user_token, req).await.map(ApiResponse::into_body)
+    }
+
+    /// Same as [`Self::sign_transaction`], but also returns the response metadata.
+    pub async fn sign_transaction_with_meta(
+        &self,
+        user_token: &str,
+        req: &SignTransactionRequest,
+    ) -> Result<ApiResponse<ChallengeIdResponse>, Error> {
         self.post_with_user_token("/v1/w3s/user/sign/transaction", req, user_token).await
     }
 }
@@ -23,7 +23,7 @@
 //! #[tokio::main]
 //! async fn main() -> Result<(), circle_user_controlled_wallets::Error> {
 //!     let client = UserWalletsClient::new("your_api_key");
-//!     let req = CreateUserRequest { user_id: "user-123".to_string() };
+//!     let req = CreateUserRequest { user_id: "user-123".into() };
 //!     let user = client.create_user(&req).await?;
 //!     println!("Created user: {:?}", user.data);
 //!     Ok(())
@@ -46,5 +46,9 @@ pub mod client;
 pub mod error;
 pub mod models;
 
+pub use circle_core::{
+    ApiEnum, ApiResponse, ClientBuilder, RequestOptions, ResponseMeta, RetryPolicy, address,
+    amount, ids, middleware, network, pagination, transport, unknown_fields,
+};
 pub use client::UserWalletsClient;
 pub use error::Error;
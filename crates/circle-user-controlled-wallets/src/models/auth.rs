@@ -3,6 +3,7 @@
 //! Contains request and response types for device-token, email OTP, and
 //! user-token refresh flows.
 
+use circle_core::ids::{IdempotencyKey, UserId};
 use serde::{Deserialize, Serialize};
 
 // ── Social device token ───────────────────────────────────────────────────────
@@ -58,7 +59,7 @@ pub struct RefreshUserTokenData {
     /// New encryption key.
     pub encryption_key: Option<String>,
     /// ID of the authenticated user.
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
     /// Opaque refresh token for the next refresh cycle.
     pub refresh_token: Option<String>,
 }
@@ -96,7 +97,7 @@ pub struct ResendOtpResponse {
 #[serde(rename_all = "camelCase")]
 pub struct DeviceTokenSocialRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Unique identifier for the user's device.
     pub device_id: String,
 }
@@ -106,7 +107,7 @@ pub struct DeviceTokenSocialRequest {
 #[serde(rename_all = "camelCase")]
 pub struct DeviceTokenEmailRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Unique identifier for the user's device.
     pub device_id: String,
     /// Email address for OTP delivery.
@@ -118,7 +119,7 @@ pub struct DeviceTokenEmailRequest {
 #[serde(rename_all = "camelCase")]
 pub struct RefreshUserTokenRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Refresh token from a previous authentication.
     pub refresh_token: String,
     /// Unique identifier for the user's device.
@@ -130,7 +131,7 @@ pub struct RefreshUserTokenRequest {
 #[serde(rename_all = "camelCase")]
 pub struct ResendOtpRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Active OTP token to be superseded.
     pub otp_token: String,
     /// Email address for OTP delivery.
@@ -148,7 +149,7 @@ mod tests {
     #[test]
     fn device_token_social_request_camel_case() -> Result<(), Box<dyn std::error::Error>> {
         let req = DeviceTokenSocialRequest {
-            idempotency_key: "key1".to_string(),
+            idempotency_key: IdempotencyKey::random(),
             device_id: "dev1".to_string(),
         };
         let s = serde_json::to_string(&req)?;
@@ -162,7 +163,7 @@ mod tests {
         let json = r#"{"data":{"userToken":"tok","encryptionKey":"key","userId":"u1","refreshToken":"rt"}}"#;
         let resp: RefreshUserTokenResponse = serde_json::from_str(json)?;
         assert_eq!(resp.data.user_token, "tok");
-        assert_eq!(resp.data.user_id.as_deref(), Some("u1"));
+        assert_eq!(resp.data.user_id.as_ref().map(UserId::as_str), Some("u1"));
         Ok(())
     }
 }
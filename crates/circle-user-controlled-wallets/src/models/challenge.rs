@@ -4,63 +4,75 @@
 //! security question endpoints, as well as the challenge-ID response used
 //! by most write operations.
 
+use circle_core::{
+    api_enum,
+    ids::{ChallengeId, IdempotencyKey},
+};
 use serde::{Deserialize, Serialize};
 
 use super::{
     common::{AccountType, Blockchain},
     wallet::WalletMetadata,
 };
+use crate::error::CircleErrorCode;
 
 // ── Enums ─────────────────────────────────────────────────────────────────────
 
-/// Type of a PIN or device challenge.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ChallengeType {
-    /// Initial user setup challenge.
-    Initialize,
-    /// Challenge for setting a PIN.
-    SetPin,
-    /// Challenge for changing a PIN.
-    ChangePin,
-    /// Challenge for setting security questions.
-    SetSecurityQuestions,
-    /// Challenge for creating a wallet.
-    CreateWallet,
-    /// Challenge for restoring a locked PIN.
-    RestorePin,
-    /// Challenge for creating a transaction.
-    CreateTransaction,
-    /// Challenge for accelerating a stuck transaction.
-    AccelerateTransaction,
-    /// Challenge for cancelling a transaction.
-    CancelTransaction,
-    /// Challenge for a smart-contract execution transaction.
-    ContractExecution,
-    /// Challenge for upgrading a wallet's SCA core.
-    WalletUpgrade,
-    /// Challenge for signing a message.
-    SignMessage,
-    /// Challenge for signing EIP-712 typed data.
-    SignTypeddata,
-    /// Challenge for signing a raw transaction.
-    SignTransaction,
+api_enum! {
+    /// Type of a PIN or device challenge.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum ChallengeType {
+        /// Initial user setup challenge.
+        Initialize,
+        /// Challenge for setting a PIN.
+        SetPin,
+        /// Challenge for changing a PIN.
+        ChangePin,
+        /// Challenge for setting security questions.
+        SetSecurityQuestions,
+        /// Challenge for creating a wallet.
+        CreateWallet,
+        /// Challenge for restoring a locked PIN.
+        RestorePin,
+        /// Challenge for creating a transaction.
+        CreateTransaction,
+        /// Challenge for accelerating a stuck transaction.
+        AccelerateTransaction,
+        /// Challenge for cancelling a transaction.
+        CancelTransaction,
+        /// Challenge for a smart-contract execution transaction.
+        ContractExecution,
+        /// Challenge for upgrading a wallet's SCA core.
+        WalletUpgrade,
+        /// Challenge for signing a message.
+        SignMessage,
+        /// Challenge for signing EIP-712 typed data.
+        SignTypeddata,
+        /// Challenge for signing a raw transaction.
+        SignTransaction,
+        /// Challenge whose type the API left blank.
+        #[serde(rename = "")]
+        Unspecified,
+    }
 }
 
-/// Status of a challenge.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ChallengeStatus {
-    /// Not yet started.
-    Pending,
-    /// Currently being processed by the mobile SDK.
-    InProgress,
-    /// Successfully completed.
-    Complete,
-    /// Finished with an error.
-    Failed,
-    /// Challenge has passed its expiry time.
-    Expired,
+api_enum! {
+    /// Status of a challenge.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum ChallengeStatus {
+        /// Not yet started.
+        Pending,
+        /// Currently being processed by the mobile SDK.
+        InProgress,
+        /// Successfully completed.
+        Complete,
+        /// Finished with an error.
+        Failed,
+        /// Challenge has passed its expiry time.
+        Expired,
+    }
 }
 
 // ── Models ────────────────────────────────────────────────────────────────────
@@ -70,7 +82,7 @@ pub enum ChallengeStatus {
 #[serde(rename_all = "camelCase")]
 pub struct Challenge {
     /// Unique challenge identifier.
-    pub id: String,
+    pub id: ChallengeId,
     /// Type of this challenge.
     #[serde(rename = "type")]
     pub challenge_type: ChallengeType,
@@ -79,7 +91,7 @@ pub struct Challenge {
     /// IDs of resources created or modified by this challenge outcome.
     pub correlation_ids: Option<Vec<String>>,
     /// Machine-readable error code (present on failure).
-    pub error_code: Option<i32>,
+    pub error_code: Option<CircleErrorCode>,
     /// Human-readable error message (present on failure).
     pub error_message: Option<String>,
 }
@@ -121,7 +133,7 @@ pub struct ChallengeResponse {
 #[serde(rename_all = "camelCase")]
 pub struct ChallengeIdData {
     /// UUID of the newly created challenge.
-    pub challenge_id: String,
+    pub challenge_id: ChallengeId,
 }
 
 /// Response envelope for operations that return a challenge ID.
@@ -139,7 +151,7 @@ pub struct ChallengeIdResponse {
 #[serde(rename_all = "camelCase")]
 pub struct SetPinAndInitWalletRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Account type for newly created wallets.
     pub account_type: Option<AccountType>,
     /// Blockchains on which to create wallets.
@@ -153,7 +165,7 @@ pub struct SetPinAndInitWalletRequest {
 #[serde(rename_all = "camelCase")]
 pub struct SetPinRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -178,16 +190,16 @@ mod tests {
 
     #[test]
     fn challenge_id_response_round_trip() -> Result<(), Box<dyn std::error::Error>> {
-        let json = r#"{"data":{"challengeId":"abc-123"}}"#;
+        let json = r#"{"data":{"challengeId":"3b9f2e1a-7c4d-4a5e-8f6b-0d1c2e3f4a5b"}}"#;
         let resp: ChallengeIdResponse = serde_json::from_str(json)?;
-        assert_eq!(resp.data.challenge_id, "abc-123");
+        assert_eq!(resp.data.challenge_id.to_string(), "3b9f2e1a-7c4d-4a5e-8f6b-0d1c2e3f4a5b");
         Ok(())
     }
 
     #[test]
     fn challenge_type_field_renamed_to_type() -> Result<(), Box<dyn std::error::Error>> {
         let c = Challenge {
-            id: "c1".to_string(),
+            id: "3b9f2e1a-7c4d-4a5e-8f6b-0d1c2e3f4a5b".parse()?,
             challenge_type: ChallengeType::SetPin,
             status: ChallengeStatus::Pending,
             correlation_ids: None,
@@ -198,4 +210,15 @@ mod tests {
         assert!(s.contains("\"type\""), "expected type key in {s}");
         Ok(())
     }
+
+    #[test]
+    fn failed_challenge_maps_error_code() -> Result<(), Box<dyn std::error::Error>> {
+        let json = r#"{"id":"3b9f2e1a-7c4d-4a5e-8f6b-0d1c2e3f4a5b","type":"SET_PIN","status":"FAILED","errorCode":155119,"errorMessage":"locked"}"#;
+        let c: Challenge = serde_json::from_str(json)?;
+        assert_eq!(c.error_code, Some(CircleErrorCode::UserPinLocked));
+        assert!(
+            c.error_code.is_some_and(|code| code.category() == crate::error::ErrorCategory::User)
+        );
+        Ok(())
+    }
 }
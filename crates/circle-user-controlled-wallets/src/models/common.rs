@@ -3,182 +3,255 @@
 //! Includes shared pagination, blockchain, error, and identifier types used
 //! across user-controlled wallet endpoints.
 
+// ── Error body ──────────────────────────────────────────────────────────────
+use chrono::{DateTime, Utc};
+/// Error response body returned by the Circle API on non-2xx status codes.
+pub use circle_core::ApiErrorBody;
+use circle_core::{
+    address::{self, AddressError},
+    amount::TokenAmount,
+    api_enum,
+    network::Network,
+};
 use serde::{Deserialize, Serialize};
 
-// ── Error body ──────────────────────────────────────────────────────────────
+// ── Blockchain ───────────────────────────────────────────────────────────────
 
-/// API error response body returned by Circle on non-2xx responses.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ApiErrorBody {
-    /// Numeric error code from Circle.
-    pub code: i32,
-    /// Human-readable error message from Circle.
-    pub message: String,
+api_enum! {
+    /// Blockchain network identifier used throughout the Circle API.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum Blockchain {
+        /// Ethereum mainnet.
+        Eth,
+        /// Ethereum Sepolia testnet.
+        #[serde(rename = "ETH-SEPOLIA")]
+        EthSepolia,
+        /// Avalanche C-Chain mainnet.
+        Avax,
+        /// Avalanche Fuji testnet.
+        #[serde(rename = "AVAX-FUJI")]
+        AvaxFuji,
+        /// Polygon (Matic) mainnet.
+        Matic,
+        /// Polygon Amoy testnet.
+        #[serde(rename = "MATIC-AMOY")]
+        MaticAmoy,
+        /// Solana mainnet.
+        Sol,
+        /// Solana devnet.
+        #[serde(rename = "SOL-DEVNET")]
+        SolDevnet,
+        /// Arbitrum One mainnet.
+        Arb,
+        /// Arbitrum Sepolia testnet.
+        #[serde(rename = "ARB-SEPOLIA")]
+        ArbSepolia,
+        /// NEAR Protocol mainnet.
+        Near,
+        /// NEAR Protocol testnet.
+        #[serde(rename = "NEAR-TESTNET")]
+        NearTestnet,
+        /// Generic EVM-compatible chain.
+        Evm,
+        /// Generic EVM testnet.
+        #[serde(rename = "EVM-TESTNET")]
+        EvmTestnet,
+        /// Unichain mainnet.
+        Uni,
+        /// Unichain Sepolia testnet.
+        #[serde(rename = "UNI-SEPOLIA")]
+        UniSepolia,
+        /// Base mainnet.
+        Base,
+        /// Base Sepolia testnet.
+        #[serde(rename = "BASE-SEPOLIA")]
+        BaseSepolia,
+        /// Optimism mainnet.
+        Op,
+        /// Optimism Sepolia testnet.
+        #[serde(rename = "OP-SEPOLIA")]
+        OpSepolia,
+        /// Aptos mainnet.
+        Aptos,
+        /// Aptos testnet.
+        #[serde(rename = "APTOS-TESTNET")]
+        AptosTestnet,
+        /// ARC testnet.
+        #[serde(rename = "ARC-TESTNET")]
+        ArcTestnet,
+        /// Monad mainnet.
+        Monad,
+        /// Monad testnet.
+        #[serde(rename = "MONAD-TESTNET")]
+        MonadTestnet,
+    }
 }
 
-// ── Blockchain ───────────────────────────────────────────────────────────────
-
-/// Blockchain network identifier used throughout the Circle API.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum Blockchain {
-    /// Ethereum mainnet.
+circle_core::network_conversions!(Blockchain {
     Eth,
-    /// Ethereum Sepolia testnet.
-    #[serde(rename = "ETH-SEPOLIA")]
     EthSepolia,
-    /// Avalanche C-Chain mainnet.
     Avax,
-    /// Avalanche Fuji testnet.
-    #[serde(rename = "AVAX-FUJI")]
     AvaxFuji,
-    /// Polygon (Matic) mainnet.
     Matic,
-    /// Polygon Amoy testnet.
-    #[serde(rename = "MATIC-AMOY")]
     MaticAmoy,
-    /// Solana mainnet.
     Sol,
-    /// Solana devnet.
-    #[serde(rename = "SOL-DEVNET")]
     SolDevnet,
-    /// Arbitrum One mainnet.
     Arb,
-    /// Arbitrum Sepolia testnet.
-    #[serde(rename = "ARB-SEPOLIA")]
     ArbSepolia,
-    /// NEAR Protocol mainnet.
     Near,
-    /// NEAR Protocol testnet.
-    #[serde(rename = "NEAR-TESTNET")]
     NearTestnet,
-    /// Generic EVM-compatible chain.
     Evm,
-    /// Generic EVM testnet.
-    #[serde(rename = "EVM-TESTNET")]
     EvmTestnet,
-    /// Unichain mainnet.
     Uni,
-    /// Unichain Sepolia testnet.
-    #[serde(rename = "UNI-SEPOLIA")]
     UniSepolia,
-    /// Base mainnet.
     Base,
-    /// Base Sepolia testnet.
-    #[serde(rename = "BASE-SEPOLIA")]
     BaseSepolia,
-    /// Optimism mainnet.
     Op,
-    /// Optimism Sepolia testnet.
-    #[serde(rename = "OP-SEPOLIA")]
     OpSepolia,
-    /// Aptos mainnet.
     Aptos,
-    /// Aptos testnet.
-    #[serde(rename = "APTOS-TESTNET")]
     AptosTestnet,
-    /// ARC testnet.
-    #[serde(rename = "ARC-TESTNET")]
     ArcTestnet,
-    /// Monad mainnet.
     Monad,
-    /// Monad testnet.
-    #[serde(rename = "MONAD-TESTNET")]
     MonadTestnet,
+});
+
+impl Blockchain {
+    /// Checks `address` against the address format of this chain offline,
+    /// see [`circle_core::address`].
+    ///
+    /// Unknown chains and chains without offline rules pass unchecked.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn check_address(&self, address: &str) -> Result<(), AddressError> {
+        Network::try_from(self.clone())
+            .map_or(Ok(()), |network| address::validate(network, address))
+    }
 }
 
 // ── Token standard ───────────────────────────────────────────────────────────
 
-/// Token standard identifier.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TokenStandard {
-    /// ERC-20 fungible token.
-    Erc20,
-    /// ERC-721 non-fungible token.
-    Erc721,
-    /// ERC-1155 multi-token standard.
-    Erc1155,
-    /// Solana fungible token.
-    Fungible,
-    /// Solana fungible asset.
-    FungibleAsset,
-    /// Solana non-fungible token.
-    NonFungible,
-    /// Solana non-fungible edition.
-    NonFungibleEdition,
-    /// Solana programmable non-fungible token.
-    ProgrammableNonFungible,
-    /// Solana programmable non-fungible edition.
-    ProgrammableNonFungibleEdition,
+api_enum! {
+    /// Token standard identifier.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TokenStandard {
+        /// ERC-20 fungible token.
+        Erc20,
+        /// ERC-721 non-fungible token.
+        Erc721,
+        /// ERC-1155 multi-token standard.
+        Erc1155,
+        /// Solana fungible token.
+        #[serde(rename = "Fungible")]
+        Fungible,
+        /// Solana fungible asset.
+        #[serde(rename = "FungibleAsset")]
+        FungibleAsset,
+        /// Solana non-fungible token.
+        #[serde(rename = "NonFungible")]
+        NonFungible,
+        /// Solana non-fungible edition.
+        #[serde(rename = "NonFungibleEdition")]
+        NonFungibleEdition,
+        /// Solana programmable non-fungible token.
+        #[serde(rename = "ProgrammableNonFungible")]
+        ProgrammableNonFungible,
+        /// Solana programmable non-fungible edition.
+        #[serde(rename = "ProgrammableNonFungibleEdition")]
+        ProgrammableNonFungibleEdition,
+    }
 }
 
 // ── Account / Custody types ──────────────────────────────────────────────────
 
-/// Wallet account type.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum AccountType {
-    /// Smart Contract Account (ERC-4337 / ERC-6900).
-    Sca,
-    /// Externally Owned Account.
-    Eoa,
+api_enum! {
+    /// Wallet account type.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum AccountType {
+        /// Smart Contract Account (ERC-4337 / ERC-6900).
+        Sca,
+        /// Externally Owned Account.
+        Eoa,
+    }
 }
 
-/// Custody type for a wallet.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum CustodyType {
-    /// Wallet belongs to the developer.
-    Developer,
-    /// Wallet belongs to an end-user.
-    Enduser,
+api_enum! {
+    /// Custody type for a wallet.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum CustodyType {
+        /// Wallet belongs to the developer.
+        Developer,
+        /// Wallet belongs to an end-user.
+        Enduser,
+    }
 }
 
 // ── Wallet state ─────────────────────────────────────────────────────────────
 
-/// Operational state of a wallet.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum WalletState {
-    /// Wallet is active and can transact.
-    Live,
-    /// Wallet has been frozen.
-    Frozen,
+api_enum! {
+    /// Operational state of a wallet.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum WalletState {
+        /// Wallet is active and can transact.
+        Live,
+        /// Wallet has been frozen.
+        Frozen,
+    }
 }
 
 // ── Fee level ─────────────────────────────────────────────────────────────────
 
-/// Gas fee level preference for a transaction.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum FeeLevel {
-    /// Low-priority fee level.
-    Low,
-    /// Standard fee level.
-    Medium,
-    /// High-priority fee level.
-    High,
+api_enum! {
+    /// Sort order of a list, by creation date.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum Order {
+        /// Oldest first.
+        Asc,
+        /// Newest first (the API default).
+        Desc,
+    }
+}
+
+api_enum! {
+    /// Gas fee level preference for a transaction.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum FeeLevel {
+        /// Low-priority fee level.
+        Low,
+        /// Standard fee level.
+        Medium,
+        /// High-priority fee level.
+        High,
+    }
 }
 
 // ── SCA core version ──────────────────────────────────────────────────────────
 
-/// Smart Contract Account core version.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-pub enum ScaCore {
-    /// Circle ERC-4337 v1 implementation.
-    #[serde(rename = "circle_4337_v1")]
-    Circle4337V1,
-    /// Circle ERC-6900 single-owner v1 implementation.
-    #[serde(rename = "circle_6900_singleowner_v1")]
-    Circle6900SingleownerV1,
-    /// Circle ERC-6900 single-owner v2 implementation.
-    #[serde(rename = "circle_6900_singleowner_v2")]
-    Circle6900SingleownerV2,
-    /// Circle ERC-6900 single-owner v3 implementation.
-    #[serde(rename = "circle_6900_singleowner_v3")]
-    Circle6900SingleownerV3,
+api_enum! {
+    /// Smart Contract Account core version.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    pub enum ScaCore {
+        /// Circle ERC-4337 v1 implementation.
+        #[serde(rename = "circle_4337_v1")]
+        Circle4337V1,
+        /// Circle ERC-6900 single-owner v1 implementation.
+        #[serde(rename = "circle_6900_singleowner_v1")]
+        Circle6900SingleownerV1,
+        /// Circle ERC-6900 single-owner v2 implementation.
+        #[serde(rename = "circle_6900_singleowner_v2")]
+        Circle6900SingleownerV2,
+        /// Circle ERC-6900 single-owner v3 implementation.
+        #[serde(rename = "circle_6900_singleowner_v3")]
+        Circle6900SingleownerV3,
+    }
 }
 
 // ── Transaction fee details ───────────────────────────────────────────────────
@@ -198,7 +271,7 @@ pub struct TransactionFee {
     /// Base fee per gas at the time of estimation.
     pub base_fee: Option<String>,
     /// Total network fee amount.
-    pub network_fee: Option<String>,
+    pub network_fee: Option<TokenAmount>,
     /// Total network fee in raw units.
     pub network_fee_raw: Option<String>,
     /// Layer-1 data fee (for L2 networks).
@@ -211,9 +284,11 @@ pub struct TransactionFee {
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct PageParams {
     /// Filter results from this date-time (ISO 8601).
-    pub from: Option<String>,
+    #[serde(serialize_with = "circle_core::timestamp::serialize_option")]
+    pub from: Option<DateTime<Utc>>,
     /// Filter results to this date-time (ISO 8601).
-    pub to: Option<String>,
+    #[serde(serialize_with = "circle_core::timestamp::serialize_option")]
+    pub to: Option<DateTime<Utc>>,
     /// Cursor for the previous page.
     #[serde(rename = "pageBefore", skip_serializing_if = "Option::is_none")]
     pub page_before: Option<String>,
@@ -225,6 +300,36 @@ pub struct PageParams {
     pub page_size: Option<u32>,
 }
 
+/// Cursor-only pagination for list endpoints that take no date range.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CursorParams {
+    /// Cursor for the previous page.
+    #[serde(rename = "pageBefore", skip_serializing_if = "Option::is_none")]
+    pub page_before: Option<String>,
+    /// Cursor for the next page.
+    #[serde(rename = "pageAfter", skip_serializing_if = "Option::is_none")]
+    pub page_after: Option<String>,
+    /// Number of items per page (max 50).
+    #[serde(rename = "pageSize", skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u32>,
+}
+
+// ── Request builders ──────────────────────────────────────────────────────────
+
+/// Chainable setters for optional fields of the request a builder holds in
+/// `self.req`.
+macro_rules! optional_setters {
+    ($( $(#[doc = $doc:literal])+ $field:ident: $ty:ty ),+ $(,)?) => { $(
+        $(#[doc = $doc])+
+        pub fn $field(mut self, $field: impl Into<$ty>) -> Self {
+            self.req.$field = Some($field.into());
+            self
+        }
+    )+ };
+}
+
+pub(crate) use optional_setters;
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -4,8 +4,11 @@
 //! All signing responses return a `challengeId` — the actual signing is
 //! completed on the client side via the Circle Web3 Services mobile SDK.
 
+use circle_core::{fields, ids::WalletId};
 use serde::{Deserialize, Serialize};
 
+use crate::Error;
+
 // ── Request bodies ────────────────────────────────────────────────────────────
 
 /// Request body for `signMessage`.
@@ -15,7 +18,7 @@ pub struct SignMessageRequest {
     /// The message to sign (plain text or hex-encoded bytes).
     pub message: String,
     /// ID of the wallet that should sign.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// If `true`, `message` is interpreted as a hex-encoded byte string.
     pub encoded_by_hex: Option<bool>,
     /// Optional memo stored alongside the signing request.
@@ -29,7 +32,7 @@ pub struct SignTypedDataRequest {
     /// EIP-712 typed data as a JSON string.
     pub data: String,
     /// ID of the wallet that should sign.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Optional memo stored alongside the signing request.
     pub memo: Option<String>,
 }
@@ -39,7 +42,7 @@ pub struct SignTypedDataRequest {
 #[serde(rename_all = "camelCase")]
 pub struct SignTransactionRequest {
     /// ID of the wallet that should sign.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Raw unsigned transaction bytes (hex-encoded).
     pub raw_transaction: Option<String>,
     /// Transaction fields as a JSON string.
@@ -48,6 +51,138 @@ pub struct SignTransactionRequest {
     pub memo: Option<String>,
 }
 
+// ── Request builders ──────────────────────────────────────────────────────────
+
+impl SignMessageRequest {
+    /// Starts a builder for signing `message` with `wallet_id`.
+    pub fn builder(wallet_id: WalletId, message: impl Into<String>) -> SignMessageRequestBuilder {
+        SignMessageRequestBuilder {
+            req: Self { message: message.into(), wallet_id, encoded_by_hex: None, memo: None },
+        }
+    }
+}
+
+/// Builds a [`SignMessageRequest`].
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct SignMessageRequestBuilder {
+    req: SignMessageRequest,
+}
+
+impl SignMessageRequestBuilder {
+    /// Treats the message as a hex-encoded byte string.
+    pub const fn encoded_by_hex(mut self, encoded_by_hex: bool) -> Self {
+        self.req.encoded_by_hex = Some(encoded_by_hex);
+        self
+    }
+
+    /// Memo stored alongside the signing request.
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.req.memo = Some(memo.into());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the message is empty.
+    pub fn build(self) -> Result<SignMessageRequest, Error> {
+        if self.req.message.is_empty() {
+            return Err(Error::InvalidParam("`message` must not be empty".into()));
+        }
+        Ok(self.req)
+    }
+}
+
+impl SignTypedDataRequest {
+    /// Starts a builder for signing the JSON-encoded EIP-712 `data` with
+    /// `wallet_id`.
+    pub fn builder(wallet_id: WalletId, data: impl Into<String>) -> SignTypedDataRequestBuilder {
+        SignTypedDataRequestBuilder { req: Self { data: data.into(), wallet_id, memo: None } }
+    }
+}
+
+/// Builds a [`SignTypedDataRequest`].
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct SignTypedDataRequestBuilder {
+    req: SignTypedDataRequest,
+}
+
+impl SignTypedDataRequestBuilder {
+    /// Memo stored alongside the signing request.
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.req.memo = Some(memo.into());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the typed data is not JSON.
+    pub fn build(self) -> Result<SignTypedDataRequest, Error> {
+        serde_json::from_str::<serde::de::IgnoredAny>(&self.req.data)
+            .map_err(|e| Error::InvalidParam(format!("`data` is not JSON: {e}")))?;
+        Ok(self.req)
+    }
+}
+
+impl SignTransactionRequest {
+    /// Starts a builder for signing a transaction with `wallet_id`, given
+    /// with [`SignTransactionRequestBuilder::raw_transaction`] or
+    /// [`SignTransactionRequestBuilder::transaction`].
+    pub const fn builder(wallet_id: WalletId) -> SignTransactionRequestBuilder {
+        SignTransactionRequestBuilder {
+            req: Self { wallet_id, raw_transaction: None, transaction: None, memo: None },
+        }
+    }
+}
+
+/// Builds a [`SignTransactionRequest`], checking that the transaction is
+/// given either raw or as fields, not both.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct SignTransactionRequestBuilder {
+    req: SignTransactionRequest,
+}
+
+impl SignTransactionRequestBuilder {
+    /// Raw unsigned transaction bytes (hex-encoded).
+    pub fn raw_transaction(mut self, raw_transaction: impl Into<String>) -> Self {
+        self.req.raw_transaction = Some(raw_transaction.into());
+        self
+    }
+
+    /// Transaction fields as a JSON string, instead of a raw transaction.
+    pub fn transaction(mut self, transaction: impl Into<String>) -> Self {
+        self.req.transaction = Some(transaction.into());
+        self
+    }
+
+    /// Memo stored alongside the signing request.
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.req.memo = Some(memo.into());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the transaction is missing or given
+    /// twice.
+    pub fn build(self) -> Result<SignTransactionRequest, Error> {
+        fields::exclusive(
+            ("raw_transaction", self.req.raw_transaction.is_some()),
+            ("transaction", self.req.transaction.is_some()),
+            true,
+        )?;
+        Ok(self.req)
+    }
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -58,7 +193,7 @@ mod tests {
     fn sign_message_request_camel_case() -> Result<(), Box<dyn std::error::Error>> {
         let req = SignMessageRequest {
             message: "hello".to_string(),
-            wallet_id: "w1".to_string(),
+            wallet_id: "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?,
             encoded_by_hex: Some(false),
             memo: Some("test".to_string()),
         };
@@ -72,12 +207,30 @@ mod tests {
     fn sign_typed_data_request_round_trip() -> Result<(), Box<dyn std::error::Error>> {
         let req = SignTypedDataRequest {
             data: r#"{"types":{}}"#.to_string(),
-            wallet_id: "w2".to_string(),
+            wallet_id: "b8c2d3e4-f5a6-4b7c-9d8e-0f1a2b3c4d5e".parse()?,
             memo: None,
         };
         let json = serde_json::to_string(&req)?;
         let decoded: SignTypedDataRequest = serde_json::from_str(&json)?;
-        assert_eq!(decoded.wallet_id, "w2");
+        assert_eq!(decoded.wallet_id.to_string(), "b8c2d3e4-f5a6-4b7c-9d8e-0f1a2b3c4d5e");
+        Ok(())
+    }
+
+    #[test]
+    fn builders_check_their_payloads() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let req =
+            SignMessageRequest::builder(wallet_id, "68656c6c6f").encoded_by_hex(true).build()?;
+        assert_eq!(req.encoded_by_hex, Some(true));
+        assert!(SignMessageRequest::builder(wallet_id, "").build().is_err());
+        assert!(SignTypedDataRequest::builder(wallet_id, r#"{"types":{}}"#).build().is_ok());
+        assert!(SignTypedDataRequest::builder(wallet_id, "{types").build().is_err());
+
+        let missing = SignTransactionRequest::builder(wallet_id).build();
+        assert!(
+            matches!(&missing, Err(Error::InvalidParam(m)) if m == "one of `raw_transaction` or `transaction` is required"),
+            "{missing:?}"
+        );
         Ok(())
     }
 }
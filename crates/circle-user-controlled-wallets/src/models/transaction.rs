@@ -3,141 +3,177 @@
 //! Contains request parameters and response types for transaction management
 //! endpoints, including estimation, acceleration, and cancellation.
 
+use chrono::{DateTime, Utc};
+use circle_core::{
+    address::AddressError,
+    amount::TokenAmount,
+    api_enum, fields,
+    ids::{IdempotencyKey, TokenId, TransactionId, UserId, WalletId},
+};
 use serde::{Deserialize, Serialize};
 
-use super::{
-    common::{Blockchain, CustodyType, FeeLevel, PageParams, TransactionFee},
-    wallet::Nft,
+use super::common::{
+    Blockchain, CustodyType, FeeLevel, Order, PageParams, TransactionFee, optional_setters,
 };
+use crate::Error;
 
 // ── State / type enums ────────────────────────────────────────────────────────
 
-/// Current on-chain state of a transaction.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransactionState {
-    /// Transaction was cancelled before broadcast.
-    Cancelled,
-    /// At least one confirmation received.
-    Confirmed,
-    /// Transaction is finalized.
-    Complete,
-    /// Transaction was denied by a compliance rule.
-    Denied,
-    /// Transaction failed on-chain.
-    Failed,
-    /// Submission initiated internally.
-    Initiated,
-    /// Mempool cleared.
-    Cleared,
-    /// Waiting in the internal queue.
-    Queued,
-    /// Broadcast to the network.
-    Sent,
-    /// Stuck in the mempool with insufficient gas.
-    Stuck,
-}
-
-/// Direction of a transaction relative to the wallet.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransactionType {
-    /// Incoming transaction.
-    Inbound,
-    /// Outgoing transaction.
-    Outbound,
-}
-
-/// High-level operation type for a transaction.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum Operation {
-    /// Simple token or coin transfer.
-    Transfer,
-    /// Call to an existing smart-contract function.
-    ContractExecution,
-    /// Deployment of a new smart contract.
-    ContractDeployment,
+api_enum! {
+    /// Current on-chain state of a transaction.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransactionState {
+        /// Transaction was cancelled before broadcast.
+        Cancelled,
+        /// At least one confirmation received.
+        Confirmed,
+        /// Transaction is finalized.
+        Complete,
+        /// Transaction was denied by a compliance rule.
+        Denied,
+        /// Transaction failed on-chain.
+        Failed,
+        /// Submission initiated internally.
+        Initiated,
+        /// Mempool cleared.
+        Cleared,
+        /// Waiting in the internal queue.
+        Queued,
+        /// Broadcast to the network.
+        Sent,
+        /// Stuck in the mempool with insufficient gas.
+        Stuck,
+    }
+}
+
+api_enum! {
+    /// Direction of a transaction relative to the wallet.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum TransactionType {
+        /// Incoming transaction.
+        Inbound,
+        /// Outgoing transaction.
+        Outbound,
+    }
+}
+
+api_enum! {
+    /// High-level operation type for a transaction.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum Operation {
+        /// Simple token or coin transfer.
+        Transfer,
+        /// Call to an existing smart-contract function.
+        ContractExecution,
+        /// Deployment of a new smart contract.
+        ContractDeployment,
+    }
 }
 
 // ── Risk / screening enums ────────────────────────────────────────────────────
 
-/// Recommended action from the compliance engine.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskAction {
-    /// Transaction may proceed.
-    Approve,
-    /// Transaction requires manual review.
-    Review,
-    /// Originating wallet should be frozen.
-    FreezeWallet,
-    /// Transaction must be denied.
-    Deny,
-}
-
-/// Risk severity level assigned by the screening engine.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskScore {
-    /// Risk could not be determined.
-    Unknown,
-    /// Low risk.
-    Low,
-    /// Moderate risk.
-    Medium,
-    /// High risk.
-    High,
-    /// Severe risk.
-    Severe,
-    /// Address is on a block-list.
-    Blocklist,
-}
-
-/// Category of risk flagged by the screening engine.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskCategory {
-    /// OFAC or other sanctions list.
-    Sanctions,
-    /// Entity that facilitates sanctioned activity.
-    SanctionsDesignatedFacilitator,
-    /// Administratively designated sanctions target.
-    SanctionsAdminDesignated,
-    /// Sector-specific sanctions.
-    SanctionsSector,
-    /// Entity providing financial services to sanctioned counterparties.
-    FinancialServiceProvider,
-    /// Coin mixer or privacy wallet.
-    MixerOrPrivacyWallet,
-    /// Ransomware-associated address.
-    Ransomware,
-    /// Child exploitation-related content or activity.
-    Child,
-    /// Terrorist financing activity.
-    TerroristFinancing,
-    /// Online fraud shop.
-    FraudShop,
-    /// Cryptocurrency exchange.
-    Exchange,
-    /// Unhosted (self-custodied) wallet.
-    Unhosted,
-    /// Darknet market or service.
-    Darknet,
-    /// Gambling platform.
-    Gambling,
-}
-
-/// Source type for a risk signal.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum RiskType {
-    /// Direct ownership risk.
-    Ownership,
-    /// Counterparty risk.
-    Counterparty,
-    /// Indirect exposure risk.
-    Indirect,
+api_enum! {
+    /// Recommended action from the compliance engine.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskAction {
+        /// Transaction may proceed.
+        Approve,
+        /// Transaction requires manual review.
+        Review,
+        /// Originating wallet should be frozen.
+        FreezeWallet,
+        /// Transaction must be denied.
+        Deny,
+    }
+}
+
+api_enum! {
+    /// Risk severity level assigned by the screening engine.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskScore {
+        /// Risk could not be determined (`UNKNOWN` on the wire).
+        #[serde(rename = "UNKNOWN")]
+        Undetermined,
+        /// Low risk.
+        Low,
+        /// Moderate risk.
+        Medium,
+        /// High risk.
+        High,
+        /// Severe risk.
+        Severe,
+        /// Address is on a block-list.
+        Blocklist,
+    }
+}
+
+api_enum! {
+    /// Category of risk flagged by the screening engine.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskCategory {
+        /// OFAC or other sanctions list.
+        Sanctions,
+        /// Child sexual abuse material.
+        Csam,
+        /// Illicit behavior.
+        IllicitBehavior,
+        /// Gambling platform.
+        Gambling,
+        /// Terrorist financing activity.
+        TerroristFinancing,
+        /// Unsupported.
+        Unsupported,
+        /// Frozen address.
+        Frozen,
+        /// Other risk.
+        Other,
+        /// High-risk industry.
+        HighRiskIndustry,
+        /// Politically exposed person.
+        Pep,
+        /// Trusted entity.
+        Trusted,
+        /// Hacking.
+        Hacking,
+        /// Human trafficking.
+        HumanTrafficking,
+        /// Special measures.
+        SpecialMeasures,
+    }
+}
+
+api_enum! {
+    /// Source type for a risk signal.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskType {
+        /// Direct ownership risk.
+        Ownership,
+        /// Counterparty risk.
+        Counterparty,
+        /// Indirect exposure risk.
+        Indirect,
+    }
+}
+
+api_enum! {
+    /// Kind of entity a risk signal was raised on.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum RiskSignalSource {
+        /// The counterparty address.
+        Address,
+        /// The blockchain.
+        Blockchain,
+        /// The transferred asset.
+        Asset,
+    }
 }
 
 // ── Screening structs ─────────────────────────────────────────────────────────
@@ -146,8 +182,8 @@ pub enum RiskType {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RiskSignal {
-    /// Source entity type (`ADDRESS`, `BLOCKCHAIN`, or `ASSET`).
-    pub source: String,
+    /// Kind of entity the signal was raised on.
+    pub source: RiskSignalSource,
     /// The actual source value (address string, chain name, etc.).
     pub source_value: String,
     /// Assigned risk score.
@@ -164,7 +200,7 @@ pub struct RiskSignal {
 #[serde(rename_all = "camelCase")]
 pub struct TransactionScreeningDecision {
     /// ISO 8601 timestamp when the screening ran.
-    pub screening_date: String,
+    pub screening_date: DateTime<Utc>,
     /// Name of the compliance rule that triggered, if any.
     pub rule_name: Option<String>,
     /// Actions recommended by the screening engine.
@@ -180,7 +216,7 @@ pub struct TransactionScreeningDecision {
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
     /// Circle-assigned transaction ID.
-    pub id: String,
+    pub id: TransactionId,
     /// Current on-chain state.
     pub state: TransactionState,
     /// Blockchain this transaction is on.
@@ -188,18 +224,18 @@ pub struct Transaction {
     /// Direction of the transaction.
     pub transaction_type: TransactionType,
     /// ISO 8601 creation timestamp.
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
     /// ISO 8601 last-updated timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
 
     /// ABI function signature for contract calls.
     pub abi_function_signature: Option<String>,
     /// ABI parameters for contract calls.
     pub abi_parameters: Option<Vec<serde_json::Value>>,
-    /// Token amounts transferred (decimal strings).
-    pub amounts: Option<Vec<String>>,
+    /// Token amounts transferred.
+    pub amounts: Option<Vec<TokenAmount>>,
     /// USD equivalent of the transferred amount.
-    pub amount_in_usd: Option<String>,
+    pub amount_in_usd: Option<TokenAmount>,
     /// Block hash for the confirmed transaction.
     pub block_hash: Option<String>,
     /// Block height for the confirmed transaction.
@@ -219,13 +255,13 @@ pub struct Transaction {
     /// Requested fee level.
     pub fee_level: Option<FeeLevel>,
     /// ISO 8601 timestamp of the first on-chain confirmation.
-    pub first_confirm_date: Option<String>,
-    /// Actual network fee paid (decimal string).
-    pub network_fee: Option<String>,
+    pub first_confirm_date: Option<DateTime<Utc>>,
+    /// Actual network fee paid.
+    pub network_fee: Option<TokenAmount>,
     /// Network fee expressed in USD.
-    pub network_fee_in_usd: Option<String>,
-    /// NFTs transferred in this transaction.
-    pub nfts: Option<Vec<Nft>>,
+    pub network_fee_in_usd: Option<TokenAmount>,
+    /// NFTs transferred in this transaction, each in JSON string format.
+    pub nfts: Option<Vec<String>>,
     /// High-level operation type.
     pub operation: Option<Operation>,
     /// Application-defined reference identifier.
@@ -233,15 +269,24 @@ pub struct Transaction {
     /// Source address.
     pub source_address: Option<String>,
     /// Circle token ID transferred.
-    pub token_id: Option<String>,
+    pub token_id: Option<TokenId>,
     /// On-chain transaction hash.
     pub tx_hash: Option<String>,
     /// ID of the end-user who initiated this transaction.
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
     /// ID of the source wallet.
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
     /// Compliance screening evaluation.
     pub transaction_screening_evaluation: Option<TransactionScreeningDecision>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<Transaction, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for Transaction {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
 }
 
 // ── Response wrappers ─────────────────────────────────────────────────────────
@@ -285,7 +330,7 @@ pub struct LowestNonceTransactionFeeInfo {
     /// Suggested high-end fee for the replacement transaction.
     pub new_high_estimated_fee: TransactionFee,
     /// Difference in fee between old and new.
-    pub fee_difference_amount: String,
+    pub fee_difference_amount: TokenAmount,
 }
 
 /// `data` payload for the lowest-nonce transaction endpoint.
@@ -355,13 +400,13 @@ pub struct ValidateAddressResponse {
 #[serde(rename_all = "camelCase")]
 pub struct CreateTransferTxRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Source wallet ID.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Destination address.
     pub destination_address: String,
-    /// Amounts to transfer (decimal strings).
-    pub amounts: Option<Vec<String>>,
+    /// Amounts to transfer.
+    pub amounts: Option<Vec<TokenAmount>>,
     /// Gas fee level preference.
     pub fee_level: Option<FeeLevel>,
     /// Custom gas limit override.
@@ -377,19 +422,33 @@ pub struct CreateTransferTxRequest {
     /// Application-defined reference identifier.
     pub ref_id: Option<String>,
     /// Circle token ID to transfer.
-    pub token_id: Option<String>,
+    pub token_id: Option<TokenId>,
     /// On-chain token contract address (alternative to token_id).
     pub token_address: Option<String>,
     /// Blockchain to use (for cross-chain transfers).
     pub blockchain: Option<Blockchain>,
 }
 
+impl CreateTransferTxRequest {
+    /// Checks `destination_address` offline against `blockchain`, when set,
+    /// so a malformed address fails before the request is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if the address is malformed.
+    pub fn validate(&self) -> Result<(), AddressError> {
+        self.blockchain
+            .as_ref()
+            .map_or(Ok(()), |chain| chain.check_address(&self.destination_address))
+    }
+}
+
 /// Request body for `accelerateTransaction`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccelerateTxRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
 }
 
 /// Request body for `cancelTransaction`.
@@ -397,7 +456,7 @@ pub struct AccelerateTxRequest {
 #[serde(rename_all = "camelCase")]
 pub struct CancelTxRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
 }
 
 /// Request body for `createContractExecutionTransaction`.
@@ -405,9 +464,9 @@ pub struct CancelTxRequest {
 #[serde(rename_all = "camelCase")]
 pub struct CreateContractExecutionTxRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Source wallet ID.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Target contract address.
     pub contract_address: String,
     /// ABI function signature to call.
@@ -417,7 +476,7 @@ pub struct CreateContractExecutionTxRequest {
     /// Raw ABI-encoded call data (alternative to abi_function_signature).
     pub call_data: Option<String>,
     /// Amount of native coin to send with the call.
-    pub amount: Option<String>,
+    pub amount: Option<TokenAmount>,
     /// Gas fee level preference.
     pub fee_level: Option<FeeLevel>,
     /// Custom gas limit override.
@@ -437,9 +496,9 @@ pub struct CreateContractExecutionTxRequest {
 #[serde(rename_all = "camelCase")]
 pub struct CreateWalletUpgradeTxRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Wallet to upgrade.
-    pub wallet_id: String,
+    pub wallet_id: WalletId,
     /// Target SCA core version (e.g. `"circle_6900_singleowner_v2"`).
     pub new_sca_core: String,
     /// Gas fee level preference.
@@ -460,8 +519,8 @@ pub struct CreateWalletUpgradeTxRequest {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EstimateTransferFeeRequest {
-    /// Amounts to transfer (decimal strings).
-    pub amounts: Vec<String>,
+    /// Amounts to transfer.
+    pub amounts: Vec<TokenAmount>,
     /// Destination address.
     pub destination_address: String,
     /// NFT token IDs to transfer.
@@ -469,13 +528,27 @@ pub struct EstimateTransferFeeRequest {
     /// Source address (used when wallet_id is not provided).
     pub source_address: Option<String>,
     /// Circle token ID.
-    pub token_id: Option<String>,
+    pub token_id: Option<TokenId>,
     /// On-chain token contract address.
     pub token_address: Option<String>,
     /// Blockchain for the transfer.
     pub blockchain: Option<Blockchain>,
     /// Source wallet ID.
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
+}
+
+impl EstimateTransferFeeRequest {
+    /// Checks `destination_address` and `source_address` offline against
+    /// `blockchain`, when set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AddressError::Invalid`] if an address is malformed.
+    pub fn validate(&self) -> Result<(), AddressError> {
+        let Some(chain) = &self.blockchain else { return Ok(()) };
+        chain.check_address(&self.destination_address)?;
+        self.source_address.as_deref().map_or(Ok(()), |address| chain.check_address(address))
+    }
 }
 
 /// Request body for `estimateContractExecutionFee`.
@@ -491,13 +564,13 @@ pub struct EstimateContractExecFeeRequest {
     /// Raw ABI-encoded call data.
     pub call_data: Option<String>,
     /// Amount of native coin to send.
-    pub amount: Option<String>,
+    pub amount: Option<TokenAmount>,
     /// Blockchain for the call.
     pub blockchain: Option<Blockchain>,
     /// Source address.
     pub source_address: Option<String>,
     /// Source wallet ID.
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
 }
 
 /// Request body for `validateAddress`.
@@ -529,18 +602,24 @@ pub struct ListTransactionsParams {
     /// Filter by transaction type (inbound/outbound).
     pub tx_type: Option<TransactionType>,
     /// Filter by user ID.
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
     /// Comma-separated list of wallet IDs to filter by.
     pub wallet_ids: Option<String>,
     /// Start of date range (ISO 8601).
-    pub from: Option<String>,
+    #[serde(serialize_with = "circle_core::timestamp::serialize_option")]
+    pub from: Option<DateTime<Utc>>,
     /// End of date range (ISO 8601).
-    pub to: Option<String>,
+    #[serde(serialize_with = "circle_core::timestamp::serialize_option")]
+    pub to: Option<DateTime<Utc>>,
+    /// Sort order by creation date.
+    pub order: Option<Order>,
     /// Pagination cursors.
     #[serde(flatten)]
     pub page: PageParams,
 }
 
+circle_core::paginated!(ListTransactionsParams => page);
+
 /// Query parameters for `getLowestNonceTransaction`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -550,7 +629,234 @@ pub struct GetLowestNonceTxParams {
     /// On-chain address to search for.
     pub address: Option<String>,
     /// Wallet ID to search for.
-    pub wallet_id: Option<String>,
+    pub wallet_id: Option<WalletId>,
+}
+
+// ── Request builders ──────────────────────────────────────────────────────────
+
+impl CreateTransferTxRequest {
+    /// Starts a builder for a transfer from `wallet_id` to
+    /// `destination_address`.
+    pub fn builder(
+        wallet_id: WalletId,
+        destination_address: impl Into<String>,
+    ) -> CreateTransferTxRequestBuilder {
+        CreateTransferTxRequestBuilder {
+            req: Self {
+                idempotency_key: IdempotencyKey::random(),
+                wallet_id,
+                destination_address: destination_address.into(),
+                amounts: None,
+                fee_level: None,
+                gas_limit: None,
+                gas_price: None,
+                max_fee: None,
+                priority_fee: None,
+                nft_token_ids: None,
+                ref_id: None,
+                token_id: None,
+                token_address: None,
+                blockchain: None,
+            },
+        }
+    }
+}
+
+/// Builds a [`CreateTransferTxRequest`], checking the field combinations
+/// Circle accepts:
+///
+/// - `amounts` or `nft_token_ids`, not both;
+/// - `token_id`, or `blockchain` (with a `token_address`, or alone for the native asset), not both;
+/// - `fee_level`, or `gas_limit` with either `gas_price` or `max_fee` and `priority_fee`.
+///
+/// The idempotency key defaults to a random UUID.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct CreateTransferTxRequestBuilder {
+    req: CreateTransferTxRequest,
+}
+
+impl CreateTransferTxRequestBuilder {
+    optional_setters! {
+        /// Blockchain of the token, for a `token_address` or the native asset.
+        blockchain: Blockchain,
+        /// Circle token ID to transfer.
+        token_id: TokenId,
+        /// On-chain token contract address, with `blockchain`.
+        token_address: String,
+        /// Application-defined reference identifier.
+        ref_id: String,
+        /// Gas fee level preference.
+        fee_level: FeeLevel,
+        /// Custom gas limit override.
+        gas_limit: String,
+        /// Custom gas price override (legacy).
+        gas_price: String,
+        /// EIP-1559 max fee per gas override.
+        max_fee: String,
+        /// EIP-1559 priority fee override.
+        priority_fee: String,
+    }
+
+    /// Idempotency key, instead of a random one — typically
+    /// [`IdempotencyKey::derive`]d from a business id, so that a retry after
+    /// a restart is deduplicated.
+    pub const fn idempotency_key(mut self, idempotency_key: IdempotencyKey) -> Self {
+        self.req.idempotency_key = idempotency_key;
+        self
+    }
+
+    /// Amounts to transfer.
+    pub fn amounts(mut self, amounts: impl IntoIterator<Item = TokenAmount>) -> Self {
+        self.req.amounts = Some(amounts.into_iter().collect());
+        self
+    }
+
+    /// NFT token IDs to transfer.
+    pub fn nft_token_ids(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.req.nft_token_ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] naming the fields of an invalid
+    /// combination, or for a destination address malformed for `blockchain`.
+    pub fn build(self) -> Result<CreateTransferTxRequest, Error> {
+        let req = &self.req;
+        fields::exclusive(
+            ("amounts", req.amounts.is_some()),
+            ("nft_token_ids", req.nft_token_ids.is_some()),
+            true,
+        )?;
+        fields::exclusive(
+            ("token_id", req.token_id.is_some()),
+            ("blockchain", req.blockchain.is_some()),
+            true,
+        )?;
+        fields::requires(
+            ("token_address", req.token_address.is_some()),
+            ("blockchain", req.blockchain.is_some()),
+        )?;
+        fields::fees(
+            req.fee_level.is_some(),
+            req.gas_limit.is_some(),
+            req.gas_price.is_some(),
+            req.max_fee.is_some(),
+            req.priority_fee.is_some(),
+        )?;
+        req.validate()?;
+        Ok(self.req)
+    }
+}
+
+impl CreateContractExecutionTxRequest {
+    /// Starts a builder for a call of `contract_address` from `wallet_id`.
+    pub fn builder(
+        wallet_id: WalletId,
+        contract_address: impl Into<String>,
+    ) -> CreateContractExecutionTxRequestBuilder {
+        CreateContractExecutionTxRequestBuilder {
+            req: Self {
+                idempotency_key: IdempotencyKey::random(),
+                wallet_id,
+                contract_address: contract_address.into(),
+                abi_function_signature: None,
+                abi_parameters: None,
+                call_data: None,
+                amount: None,
+                fee_level: None,
+                gas_limit: None,
+                gas_price: None,
+                max_fee: None,
+                priority_fee: None,
+                ref_id: None,
+            },
+        }
+    }
+}
+
+/// Builds a [`CreateContractExecutionTxRequest`], checking the field
+/// combinations Circle accepts:
+///
+/// - `abi_function_signature` (with optional `abi_parameters`) or `call_data`, not both;
+/// - `fee_level`, or `gas_limit` with either `gas_price` or `max_fee` and `priority_fee`.
+///
+/// The idempotency key defaults to a random UUID.
+#[derive(Debug, Clone)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct CreateContractExecutionTxRequestBuilder {
+    req: CreateContractExecutionTxRequest,
+}
+
+impl CreateContractExecutionTxRequestBuilder {
+    optional_setters! {
+        /// ABI function signature to call.
+        abi_function_signature: String,
+        /// Raw ABI-encoded call data.
+        call_data: String,
+        /// Amount of native coin to send with the call.
+        amount: TokenAmount,
+        /// Application-defined reference identifier.
+        ref_id: String,
+        /// Gas fee level preference.
+        fee_level: FeeLevel,
+        /// Custom gas limit override.
+        gas_limit: String,
+        /// Custom gas price override.
+        gas_price: String,
+        /// EIP-1559 max fee per gas override.
+        max_fee: String,
+        /// EIP-1559 priority fee override.
+        priority_fee: String,
+    }
+
+    /// Idempotency key, instead of a random one — typically
+    /// [`IdempotencyKey::derive`]d from a business id, so that a retry after
+    /// a restart is deduplicated.
+    pub const fn idempotency_key(mut self, idempotency_key: IdempotencyKey) -> Self {
+        self.req.idempotency_key = idempotency_key;
+        self
+    }
+
+    /// ABI parameters for the function call.
+    pub fn abi_parameters(
+        mut self,
+        parameters: impl IntoIterator<Item = serde_json::Value>,
+    ) -> Self {
+        self.req.abi_parameters = Some(parameters.into_iter().collect());
+        self
+    }
+
+    /// Checks the request and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] naming the fields of an invalid
+    /// combination.
+    pub fn build(self) -> Result<CreateContractExecutionTxRequest, Error> {
+        let req = &self.req;
+        let signature = req.abi_function_signature.is_some();
+        fields::exclusive(
+            ("abi_function_signature", signature),
+            ("call_data", req.call_data.is_some()),
+            true,
+        )?;
+        fields::requires(
+            ("abi_parameters", req.abi_parameters.is_some()),
+            ("abi_function_signature", signature),
+        )?;
+        fields::fees(
+            req.fee_level.is_some(),
+            req.gas_limit.is_some(),
+            req.gas_price.is_some(),
+            req.max_fee.is_some(),
+            req.priority_fee.is_some(),
+        )?;
+        Ok(self.req)
+    }
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -559,6 +865,15 @@ pub struct GetLowestNonceTxParams {
 mod tests {
     use super::*;
 
+    #[cfg(not(feature = "strict"))]
+    #[test]
+    fn unknown_transaction_state_roundtrips() -> Result<(), Box<dyn std::error::Error>> {
+        let parsed: TransactionState = serde_json::from_str("\"REORGED\"")?;
+        assert_eq!(parsed, TransactionState::Unknown("REORGED".to_string()));
+        assert_eq!(serde_json::to_string(&parsed)?, "\"REORGED\"");
+        Ok(())
+    }
+
     #[test]
     fn transaction_state_screaming() -> Result<(), Box<dyn std::error::Error>> {
         assert_eq!(serde_json::to_string(&TransactionState::Complete)?, "\"COMPLETE\"");
@@ -586,10 +901,10 @@ mod tests {
     #[test]
     fn transfer_tx_request_camel_case() -> Result<(), Box<dyn std::error::Error>> {
         let req = CreateTransferTxRequest {
-            idempotency_key: "key".to_string(),
-            wallet_id: "w1".to_string(),
+            idempotency_key: IdempotencyKey::random(),
+            wallet_id: "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?,
             destination_address: "0xdest".to_string(),
-            amounts: Some(vec!["1.0".to_string()]),
+            amounts: Some(vec!["1.0".parse()?]),
             fee_level: Some(FeeLevel::High),
             gas_limit: None,
             gas_price: None,
@@ -607,4 +922,56 @@ mod tests {
         assert!(s.contains("destinationAddress"), "{s}");
         Ok(())
     }
+
+    #[test]
+    fn transfer_builder_checks_token_and_fee_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let destination = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        let req = CreateTransferTxRequest::builder(wallet_id, destination)
+            .blockchain(Blockchain::EthSepolia)
+            .token_address("0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238")
+            .amounts(["2".parse()?])
+            .gas_limit("21000")
+            .gas_price("30")
+            .build()?;
+        assert_eq!(req.idempotency_key.as_uuid().get_version_num(), 4);
+
+        let cases = [
+            (
+                CreateTransferTxRequest::builder(wallet_id, destination)
+                    .token_address("0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238")
+                    .token_id("e2f3a4b5-c6d7-4e8f-9a0b-1c2d3e4f5a6b".parse::<TokenId>()?),
+                "`token_address` requires `blockchain`",
+            ),
+            (
+                CreateTransferTxRequest::builder(wallet_id, destination)
+                    .blockchain(Blockchain::EthSepolia)
+                    .gas_price("30")
+                    .max_fee("40"),
+                "`gas_price` and `max_fee` cannot both be set",
+            ),
+        ];
+        for (builder, expected) in cases {
+            let result = builder.amounts(["1".parse()?]).build();
+            assert!(matches!(&result, Err(Error::InvalidParam(m)) if m == expected), "{result:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn contract_execution_builder_needs_one_call() -> Result<(), Box<dyn std::error::Error>> {
+        let wallet_id: WalletId = "a7b1c2d3-e4f5-4a6b-8c7d-9e0f1a2b3c4d".parse()?;
+        let builder = CreateContractExecutionTxRequest::builder(
+            wallet_id,
+            "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+        )
+        .fee_level(FeeLevel::Medium);
+        assert!(builder.clone().call_data("0xa9059cbb").build().is_ok());
+        let result = builder.build();
+        assert!(
+            matches!(&result, Err(Error::InvalidParam(m)) if m == "one of `abi_function_signature` or `call_data` is required"),
+            "{result:?}"
+        );
+        Ok(())
+    }
 }
@@ -3,44 +3,52 @@
 //! Contains request parameters and response types for user registration and
 //! management endpoints.
 
+use chrono::{DateTime, Utc};
+use circle_core::{api_enum, ids::UserId};
 use serde::{Deserialize, Serialize};
 
-use super::common::PageParams;
+use super::common::{Order, PageParams};
 
 // ── Enums ─────────────────────────────────────────────────────────────────────
 
-/// PIN status for an end-user.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum PinStatus {
-    /// PIN has been set and is active.
-    Enabled,
-    /// PIN has not been configured yet.
-    Unset,
-    /// PIN is locked after too many failed attempts.
-    Locked,
-}
-
-/// Overall account status of an end-user.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum EndUserStatus {
-    /// Account is active.
-    Enabled,
-    /// Account has been disabled.
-    Disabled,
-}
-
-/// Status of security question recovery for an end-user.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum SecurityQuestionStatus {
-    /// Security questions are configured.
-    Enabled,
-    /// Security questions have not been set.
-    Unset,
-    /// Security question recovery is locked.
-    Locked,
+api_enum! {
+    /// PIN status for an end-user.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum PinStatus {
+        /// PIN has been set and is active.
+        Enabled,
+        /// PIN has not been configured yet.
+        Unset,
+        /// PIN is locked after too many failed attempts.
+        Locked,
+    }
+}
+
+api_enum! {
+    /// Overall account status of an end-user.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum EndUserStatus {
+        /// Account is active.
+        Enabled,
+        /// Account has been disabled.
+        Disabled,
+    }
+}
+
+api_enum! {
+    /// Status of security question recovery for an end-user.
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    pub enum SecurityQuestionStatus {
+        /// Security questions are configured.
+        Enabled,
+        /// Security questions have not been set.
+        Unset,
+        /// Security question recovery is locked.
+        Locked,
+    }
 }
 
 // ── Models ────────────────────────────────────────────────────────────────────
@@ -52,11 +60,11 @@ pub struct PinSecurityDetails {
     /// Number of failed PIN attempts since last successful authentication.
     pub failed_attempts: Option<i32>,
     /// Date the PIN was locked.
-    pub locked_date: Option<String>,
+    pub locked_date: Option<DateTime<Utc>>,
     /// Date the PIN lock expires.
-    pub locked_expiry_date: Option<String>,
+    pub locked_expiry_date: Option<DateTime<Utc>>,
     /// Date of the last lock override by an admin.
-    pub last_lock_override_date: Option<String>,
+    pub last_lock_override_date: Option<DateTime<Utc>>,
 }
 
 /// An end-user record returned by the Circle API.
@@ -64,9 +72,9 @@ pub struct PinSecurityDetails {
 #[serde(rename_all = "camelCase")]
 pub struct EndUser {
     /// Unique identifier for the end-user.
-    pub id: Option<String>,
+    pub id: Option<UserId>,
     /// ISO 8601 timestamp when the user was created.
-    pub create_date: Option<String>,
+    pub create_date: Option<DateTime<Utc>>,
     /// Current PIN status.
     pub pin_status: Option<PinStatus>,
     /// Current account status.
@@ -77,6 +85,15 @@ pub struct EndUser {
     pub pin_details: Option<serde_json::Value>,
     /// Extended security question details (opaque JSON).
     pub security_question_details: Option<serde_json::Value>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<EndUser, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for EndUser {
+    fn cursor(&self) -> Option<String> {
+        self.id.as_ref().map(ToString::to_string)
+    }
 }
 
 // ── Response wrappers ─────────────────────────────────────────────────────────
@@ -148,7 +165,7 @@ pub struct UserTokenResponse {
 #[serde(rename_all = "camelCase")]
 pub struct CreateUserRequest {
     /// Application-defined identifier for the user.
-    pub user_id: String,
+    pub user_id: UserId,
 }
 
 /// Request body for `getUserToken`.
@@ -156,7 +173,7 @@ pub struct CreateUserRequest {
 #[serde(rename_all = "camelCase")]
 pub struct GetUserTokenRequest {
     /// The application user ID whose token should be retrieved.
-    pub user_id: String,
+    pub user_id: UserId,
 }
 
 /// Query parameters for `listUsers`.
@@ -165,11 +182,17 @@ pub struct GetUserTokenRequest {
 pub struct ListUsersParams {
     /// Filter by PIN status.
     pub pin_status: Option<PinStatus>,
+    /// Filter by security question status.
+    pub security_question_status: Option<SecurityQuestionStatus>,
+    /// Sort order by creation date.
+    pub order: Option<Order>,
     /// Pagination cursors.
     #[serde(flatten)]
     pub page: PageParams,
 }
 
+circle_core::paginated!(ListUsersParams => page);
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -186,13 +209,14 @@ mod tests {
     #[test]
     fn end_user_round_trip() -> Result<(), Box<dyn std::error::Error>> {
         let user = EndUser {
-            id: Some("user-123".to_string()),
-            create_date: Some("2024-01-01T00:00:00Z".to_string()),
+            id: Some(UserId::from("user-123")),
+            create_date: Some("2024-01-01T00:00:00Z".parse()?),
             pin_status: Some(PinStatus::Enabled),
             status: Some(EndUserStatus::Enabled),
             security_question_status: Some(SecurityQuestionStatus::Unset),
             pin_details: None,
             security_question_details: None,
+            extra: serde_json::Map::new(),
         };
         let json = serde_json::to_string(&user)?;
         let decoded: EndUser = serde_json::from_str(&json)?;
@@ -201,9 +225,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn end_user_keeps_undeclared_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let json = r#"{"id":"user-123","pinStatus":"ENABLED","kycLevel":"BASIC"}"#;
+        let user: EndUser = serde_json::from_str(json)?;
+        assert_eq!(user.extra.get("kycLevel"), Some(&serde_json::Value::from("BASIC")));
+        assert!(serde_json::to_string(&user)?.contains("\"kycLevel\":\"BASIC\""));
+        Ok(())
+    }
+
     #[test]
     fn create_user_request_serializes() -> Result<(), Box<dyn std::error::Error>> {
-        let req = CreateUserRequest { user_id: "user-abc".to_string() };
+        let req = CreateUserRequest { user_id: UserId::from("user-abc") };
         let json = serde_json::to_string(&req)?;
         assert!(json.contains("userId"), "expected camelCase userId in {json}");
         Ok(())
@@ -3,10 +3,19 @@
 //! Contains request parameters and response types for wallet management
 //! endpoints, including balances, NFTs, and associated token data.
 
+use chrono::{DateTime, Utc};
+use circle_core::{
+    Error,
+    address::Address,
+    amount::{self, AmountError, BigUint, TokenAmount},
+    ids::{IdempotencyKey, TokenId, UserId, WalletId, WalletSetId},
+    network::Network,
+};
 use serde::{Deserialize, Serialize};
 
 use super::common::{
-    AccountType, Blockchain, CustodyType, PageParams, ScaCore, TokenStandard, WalletState,
+    AccountType, Blockchain, CursorParams, CustodyType, Order, PageParams, ScaCore, TokenStandard,
+    WalletState,
 };
 
 // ── Wallet metadata ───────────────────────────────────────────────────────────
@@ -28,7 +37,7 @@ pub struct WalletMetadata {
 #[serde(rename_all = "camelCase")]
 pub struct Token {
     /// Circle-assigned token ID.
-    pub id: String,
+    pub id: TokenId,
     /// Blockchain this token lives on.
     pub blockchain: Blockchain,
     /// `true` if this is the native coin of the chain (e.g. ETH, MATIC).
@@ -44,9 +53,24 @@ pub struct Token {
     /// On-chain contract address (absent for native coins).
     pub token_address: Option<String>,
     /// ISO 8601 last-updated timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// ISO 8601 creation timestamp.
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
+}
+
+impl Token {
+    /// The human amount of `units` base units of this token.
+    pub fn amount_from_base_units(
+        &self,
+        units: impl Into<BigUint>,
+    ) -> Result<TokenAmount, AmountError> {
+        Ok(TokenAmount::from_base_units(units, amount::decimals(self.decimals)?))
+    }
+
+    /// `amount` in base units of this token.
+    pub fn to_base_units(&self, amount: &TokenAmount) -> Result<BigUint, AmountError> {
+        amount.to_base_units(amount::decimals(self.decimals)?)
+    }
 }
 
 // ── Balance ───────────────────────────────────────────────────────────────────
@@ -55,12 +79,18 @@ pub struct Token {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Balance {
-    /// Decimal string representation of the amount.
-    pub amount: String,
+    /// Amount held.
+    pub amount: TokenAmount,
     /// Token descriptor.
     pub token: Token,
     /// ISO 8601 timestamp of the last balance update.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
+}
+
+impl circle_core::pagination::PageItem for Balance {
+    fn cursor(&self) -> Option<String> {
+        Some(self.token.id.to_string())
+    }
 }
 
 /// `data` payload wrapping a list of balances.
@@ -81,18 +111,6 @@ pub struct Balances {
 
 // ── NFT ───────────────────────────────────────────────────────────────────────
 
-/// NFT on-chain metadata.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct NftMetadata {
-    /// NFT display name.
-    pub name: Option<String>,
-    /// NFT description.
-    pub description: Option<String>,
-    /// URL of the NFT image.
-    pub image: Option<String>,
-}
-
 /// An NFT held by a wallet.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -102,11 +120,17 @@ pub struct Nft {
     /// Quantity held (usually "1" for ERC-721).
     pub amount: String,
     /// ISO 8601 last-updated timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// On-chain token ID within the collection.
     pub nft_token_id: Option<String>,
-    /// Off-chain metadata (name, description, image).
-    pub metadata: Option<NftMetadata>,
+    /// IPFS or HTTP URI of the NFT metadata.
+    pub metadata: Option<String>,
+}
+
+impl circle_core::pagination::PageItem for Nft {
+    fn cursor(&self) -> Option<String> {
+        Some(self.token.id.to_string())
+    }
 }
 
 /// `data` payload wrapping a list of NFTs.
@@ -132,15 +156,15 @@ pub struct Nfts {
 #[serde(rename_all = "camelCase")]
 pub struct Wallet {
     /// Circle-assigned wallet ID.
-    pub id: String,
+    pub id: WalletId,
     /// On-chain address for this wallet.
     pub address: String,
     /// Blockchain this wallet is deployed on.
     pub blockchain: Blockchain,
     /// ISO 8601 creation timestamp.
-    pub create_date: String,
+    pub create_date: DateTime<Utc>,
     /// ISO 8601 last-updated timestamp.
-    pub update_date: String,
+    pub update_date: DateTime<Utc>,
     /// Custody model (always `Enduser` for this API).
     pub custody_type: CustodyType,
     /// Optional display name.
@@ -150,15 +174,37 @@ pub struct Wallet {
     /// Current wallet state.
     pub state: WalletState,
     /// ID of the end-user who owns this wallet.
-    pub user_id: Option<String>,
+    pub user_id: Option<UserId>,
     /// ID of the wallet set this wallet belongs to.
-    pub wallet_set_id: String,
+    pub wallet_set_id: WalletSetId,
     /// Initial public key at wallet creation.
     pub initial_public_key: Option<String>,
     /// Account type (EOA or SCA).
     pub account_type: Option<AccountType>,
     /// SCA core version (for smart contract accounts).
     pub sca_core: Option<ScaCore>,
+    /// Response fields this SDK version does not model yet, kept verbatim.
+    #[serde(flatten, deserialize_with = "circle_core::unknown_fields::capture::<Wallet, _>")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl circle_core::pagination::PageItem for Wallet {
+    fn cursor(&self) -> Option<String> {
+        Some(self.id.to_string())
+    }
+}
+
+impl Wallet {
+    /// [`Self::address`] parsed for [`Self::blockchain`], in canonical form.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidParam`] if the chain is unknown to this SDK
+    /// version or the address is malformed.
+    pub fn parsed_address(&self) -> Result<Address, Error> {
+        let network = Network::try_from(self.blockchain.clone())?;
+        Ok(Address::parse(network, &self.address)?)
+    }
 }
 
 /// `data` payload wrapping a list of wallets.
@@ -234,9 +280,11 @@ pub struct ListWalletsParams {
     /// Filter by SCA core version.
     pub sca_core: Option<ScaCore>,
     /// Filter by wallet set ID.
-    pub wallet_set_id: Option<String>,
+    pub wallet_set_id: Option<WalletSetId>,
     /// Filter by application reference ID.
     pub ref_id: Option<String>,
+    /// Sort order by creation date.
+    pub order: Option<Order>,
     /// Pagination cursors.
     #[serde(flatten)]
     pub page: PageParams,
@@ -256,7 +304,7 @@ pub struct ListWalletBalancesParams {
     pub standard: Option<TokenStandard>,
     /// Pagination cursors.
     #[serde(flatten)]
-    pub page: PageParams,
+    pub page: CursorParams,
 }
 
 /// Query parameters for `listWalletNfts`.
@@ -273,15 +321,17 @@ pub struct ListWalletNftsParams {
     pub standard: Option<TokenStandard>,
     /// Pagination cursors.
     #[serde(flatten)]
-    pub page: PageParams,
+    pub page: CursorParams,
 }
 
+circle_core::paginated!(ListWalletsParams => page, ListWalletBalancesParams => page, ListWalletNftsParams => page);
+
 /// Request body for `createEndUserWallet`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateEndUserWalletRequest {
     /// Client-generated idempotency key (UUID).
-    pub idempotency_key: String,
+    pub idempotency_key: IdempotencyKey,
     /// Blockchains on which to create wallets.
     pub blockchains: Vec<Blockchain>,
     /// Account type for the new wallet.
@@ -314,7 +364,7 @@ mod tests {
     #[test]
     fn create_wallet_request_camel_case() -> Result<(), Box<dyn std::error::Error>> {
         let req = CreateEndUserWalletRequest {
-            idempotency_key: "abc-def".to_string(),
+            idempotency_key: IdempotencyKey::random(),
             blockchains: vec![Blockchain::Eth, Blockchain::EthSepolia],
             account_type: None,
             metadata: None,
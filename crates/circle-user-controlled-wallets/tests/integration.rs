@@ -1,4 +1,250 @@
 #![allow(missing_docs)]
 // Integration tests for circle-user-controlled-wallets.
-// Requires Prism mock server on port 4013.
+// Each test runs against its own in-process circle-mock server.
 // Run via: just integration-test
+
+use circle_core::{CassetteTransport, HpxTransport};
+use circle_mock::MockServer;
+use circle_user_controlled_wallets::{
+    Error, UserWalletsClient,
+    error::CircleErrorCode,
+    ids::{IdempotencyKey, TokenId, TransactionId, UserId},
+    models::{
+        challenge::{ChallengeStatus, ChallengeType, SetPinAndInitWalletRequest, SetPinRequest},
+        common::Blockchain,
+        transaction::{CreateTransferTxRequest, TransactionState},
+        user::{CreateUserRequest, GetUserTokenRequest, PinStatus},
+        wallet::ListWalletsParams,
+    },
+    pagination::{self, StreamOptions},
+};
+
+async fn start() -> Result<(MockServer, UserWalletsClient), Box<dyn std::error::Error>> {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let server = MockServer::start().await?;
+    let client = UserWalletsClient::with_base_url("TEST_API_KEY:id:secret", server.base_url());
+    Ok((server, client))
+}
+
+/// Creates `user_id`, approves its initialize challenge (sent with
+/// `idempotency_key`) with a wallet on `ETH-SEPOLIA` and returns a user token.
+async fn initialized_user(
+    server: &MockServer,
+    client: &UserWalletsClient,
+    user_id: &str,
+    idempotency_key: IdempotencyKey,
+) -> Result<String, Box<dyn std::error::Error>> {
+    client.create_user(&CreateUserRequest { user_id: UserId::from(user_id) }).await?;
+    let token = client
+        .get_user_token(&GetUserTokenRequest { user_id: UserId::from(user_id) })
+        .await?
+        .data
+        .user_token;
+    let challenge = client
+        .initialize_user(
+            &token,
+            &SetPinAndInitWalletRequest {
+                idempotency_key,
+                account_type: None,
+                blockchains: Some(vec![Blockchain::EthSepolia]),
+                metadata: None,
+            },
+        )
+        .await?;
+    server.state().complete_challenge(&challenge.data.challenge_id.to_string())?;
+    Ok(token)
+}
+
+#[tokio::test]
+async fn initialize_challenge_creates_wallets_once_approved()
+-> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    client.create_user(&CreateUserRequest { user_id: UserId::from("alice") }).await?;
+    let token = client
+        .get_user_token(&GetUserTokenRequest { user_id: UserId::from("alice") })
+        .await?
+        .data
+        .user_token;
+
+    let challenge_id = client
+        .initialize_user(
+            &token,
+            &SetPinAndInitWalletRequest {
+                idempotency_key: "6e5d4c3b-2a19-4f8e-8d7c-6b5a4f3e2d1c".parse()?,
+                account_type: None,
+                blockchains: Some(vec![Blockchain::EthSepolia]),
+                metadata: None,
+            },
+        )
+        .await?
+        .data
+        .challenge_id;
+
+    let pending = client.get_challenge(&token, &challenge_id).await?.data.challenge;
+    assert_eq!(pending.challenge_type, ChallengeType::Initialize);
+    assert_eq!(pending.status, ChallengeStatus::Pending);
+    let wallets = client.list_wallets(&token, &ListWalletsParams::default()).await?;
+    assert!(wallets.data.wallets.is_empty());
+
+    let created = server.state().complete_challenge(&challenge_id.to_string())?;
+
+    let completed = client.get_challenge(&token, &challenge_id).await?.data.challenge;
+    assert_eq!(completed.status, ChallengeStatus::Complete);
+    assert_eq!(completed.correlation_ids, Some(created.clone()));
+    let wallets = client.list_wallets(&token, &ListWalletsParams::default()).await?;
+    let listed: Vec<String> = wallets.data.wallets.iter().map(|w| w.id.to_string()).collect();
+    assert_eq!(listed, created);
+    let user = client.get_user(&UserId::from("alice")).await?.data.user;
+    assert_eq!(user.pin_status, Some(PinStatus::Enabled));
+    let challenges =
+        pagination::collect_all(client.list_challenges_stream(&token, StreamOptions::default()))
+            .await?;
+    assert_eq!(challenges.iter().map(|c| c.id).collect::<Vec<_>>(), [challenge_id]);
+    let wallets = client.list_wallets_stream(
+        &token,
+        &ListWalletsParams::default(),
+        StreamOptions { max_items: Some(1), ..StreamOptions::default() },
+    );
+    assert_eq!(pagination::collect_all(wallets).await?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn cassette_replays_requests_with_fresh_keys() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, _) = start().await?;
+    let cassette =
+        std::env::temp_dir().join(format!("circle-user-cassette-{}.json", std::process::id()));
+    let initialize = async |client: UserWalletsClient| -> Result<String, Error> {
+        client.create_user(&CreateUserRequest { user_id: UserId::from("carol") }).await?;
+        let token = client
+            .get_user_token(&GetUserTokenRequest { user_id: UserId::from("carol") })
+            .await?
+            .data
+            .user_token;
+        let request = SetPinAndInitWalletRequest {
+            idempotency_key: IdempotencyKey::random(),
+            account_type: None,
+            blockchains: Some(vec![Blockchain::EthSepolia]),
+            metadata: None,
+        };
+        Ok(client.initialize_user(&token, &request).await?.data.challenge_id.to_string())
+    };
+
+    let recorder = UserWalletsClient::builder("TEST_API_KEY:id:secret")
+        .base_url(server.base_url())
+        .transport(CassetteTransport::record(&cassette, HpxTransport::new(hpx::Client::new())))
+        .build()?;
+    let recorded = initialize(recorder).await?;
+    drop(server);
+
+    let player = UserWalletsClient::builder("TEST_API_KEY:id:secret")
+        .transport(CassetteTransport::replay(&cassette)?)
+        .build()?;
+    let replayed = initialize(player).await;
+    std::fs::remove_file(&cassette)?;
+    assert_eq!(replayed?, recorded);
+    Ok(())
+}
+
+#[tokio::test]
+async fn users_only_see_their_own_wallets() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let alice = initialized_user(
+        &server,
+        &client,
+        "alice",
+        "2f1e0d9c-8b7a-4f6e-9d5c-4b3a2f1e0d9c".parse()?,
+    )
+    .await?;
+    let bob =
+        initialized_user(&server, &client, "bob", "4d3c2b1a-0f9e-4d8c-b7a6-5f4e3d2c1b0a".parse()?)
+            .await?;
+
+    let alice_wallets = client.list_wallets(&alice, &ListWalletsParams::default()).await?;
+    let bob_wallets = client.list_wallets(&bob, &ListWalletsParams::default()).await?;
+    assert_eq!(alice_wallets.data.wallets.len(), 1);
+    assert_eq!(bob_wallets.data.wallets.len(), 1);
+
+    let result = client.get_wallet(&alice, &bob_wallets.data.wallets[0].id).await;
+    assert!(matches!(result, Err(Error::Api { .. })));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transfer_waits_for_its_challenge() -> Result<(), Box<dyn std::error::Error>> {
+    let (server, client) = start().await?;
+    let token = initialized_user(
+        &server,
+        &client,
+        "carol",
+        "8c7b6a5f-4e3d-4c2b-a190-8f7e6d5c4b3a".parse()?,
+    )
+    .await?;
+    let wallet = client.list_wallets(&token, &ListWalletsParams::default()).await?.data.wallets;
+    let wallet_id = wallet[0].id;
+    let usdc = server.state().token_id("ETH-SEPOLIA", "USDC").unwrap_or_default();
+    server.state().set_balance(&wallet_id.to_string(), &usdc, "10")?;
+    let destination = server.state().create_wallet("ETH-SEPOLIA")?;
+
+    let challenge_id = client
+        .create_transfer_transaction(
+            &token,
+            &CreateTransferTxRequest {
+                idempotency_key: "7a6b5c4d-3e2f-4a1b-9c8d-7e6f5a4b3c2d".parse()?,
+                wallet_id,
+                destination_address: destination["address"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                amounts: Some(vec!["2.5".parse()?]),
+                fee_level: None,
+                gas_limit: None,
+                gas_price: None,
+                max_fee: None,
+                priority_fee: None,
+                nft_token_ids: None,
+                ref_id: None,
+                token_id: Some(usdc.parse::<TokenId>()?),
+                token_address: None,
+                blockchain: None,
+            },
+        )
+        .await?
+        .data
+        .challenge_id;
+
+    let transaction_ids = server.state().complete_challenge(&challenge_id.to_string())?;
+    let transaction_id = transaction_ids.first().ok_or("challenge created no transaction")?;
+    server.state().settle_transaction(transaction_id)?;
+
+    let transaction_id: TransactionId = transaction_id.parse()?;
+    let transaction = client.get_transaction(&token, &transaction_id).await?.data.transaction;
+    assert_eq!(transaction.state, TransactionState::Complete);
+    assert_eq!(server.state().balance(&wallet_id.to_string(), &usdc).as_deref(), Some("7.5"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn pin_is_required_before_other_challenges() -> Result<(), Box<dyn std::error::Error>> {
+    let (_server, client) = start().await?;
+    client.create_user(&CreateUserRequest { user_id: UserId::from("dave") }).await?;
+    let token = client
+        .get_user_token(&GetUserTokenRequest { user_id: UserId::from("dave") })
+        .await?
+        .data
+        .user_token;
+
+    let result = client
+        .update_pin_challenge(
+            &token,
+            &SetPinRequest { idempotency_key: "1b2c3d4e-5f6a-4b7c-8d9e-0f1a2b3c4d5e".parse()? },
+        )
+        .await;
+    match result {
+        Err(Error::Api { code, .. }) => {
+            assert_eq!(code, CircleErrorCode::UserDoesNotSetPinYet.code());
+        }
+        other => return Err(format!("expected an API error, got {other:?}").into()),
+    }
+    Ok(())
+}